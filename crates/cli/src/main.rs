@@ -0,0 +1,107 @@
+//! Command-line tools over the core crate.
+//!
+//! `tidy-cli lint SCHEMAS` checks a schema JSON file (see `tidy_core::schema`) with
+//! `lint_schemas` against the generator's room names, printing one lint per line and
+//! failing if there is any.
+//!
+//! `tidy-cli render-episode EPISODE OUT` plays an episode file and writes it as an animated
+//! GIF (for a `.gif` OUT) or APNG. The episode file is JSON:
+//!
+//! ```json
+//! { "opts": { "seed": 7 }, "actions": ["MoveRight", { "Interact": { "dx": 0, "dy": 1 } }] }
+//! ```
+//!
+//! `opts` are the `GenOpts` of the world, missing fields taking their defaults, and `start`
+//! (`[x, y]`) places the agent, by default on the first room cell as the bindings do.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use serde::Deserialize;
+use tidy_core::gen::ROOM_NAME_POOL;
+use tidy_core::{generate, lint_schemas, Action, EpisodeFormat, EpisodeRenderOpts, GenOpts, ObjectSchema, Simulator};
+
+const USAGE: &str = "usage: tidy-cli render-episode EPISODE OUT [--scale N] [--frame-skip N] [--max-frames N] [--delay MS] [--trail]
+       tidy-cli lint SCHEMAS";
+
+/// An episode file, see the module docs
+#[derive(Deserialize)]
+struct Episode {
+    #[serde(default)]
+    opts: GenOpts,
+    start: Option<(usize, usize)>,
+    actions: Vec<Action>,
+}
+
+/// Value of a numeric flag
+fn number<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value.parse().map_err(|_| format!("{}: not a number: {}", flag, value))
+}
+
+/// Render options from the flags after EPISODE and OUT
+fn render_opts(flags: &[String], out: &Path) -> Result<EpisodeRenderOpts, String> {
+    let mut opts = EpisodeRenderOpts { format: EpisodeFormat::from_path(out), ..Default::default() };
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--scale" => opts.scale = number(flag, flags.next())?,
+            "--frame-skip" => opts.frame_skip = number(flag, flags.next())?,
+            "--max-frames" => opts.max_frames = Some(number(flag, flags.next())?),
+            "--delay" => opts.frame_delay_ms = number(flag, flags.next())?,
+            "--trail" => opts.trail = true,
+            other => return Err(format!("unknown option {}\n{}", other, USAGE)),
+        }
+    }
+    Ok(opts)
+}
+
+fn render_episode(episode: &Path, out: &Path, flags: &[String]) -> Result<(), String> {
+    let opts = render_opts(flags, out)?;
+    let json = std::fs::read_to_string(episode).map_err(|e| format!("cannot read {}: {}", episode.display(), e))?;
+    let episode: Episode = serde_json::from_str(&json).map_err(|e| format!("{}: {}", episode.display(), e))?;
+    let world = generate(&episode.opts).map_err(|e| e.to_string())?;
+    let (x, y) = match episode.start {
+        Some(start) => start,
+        None => {
+            let start = world.layout.cells.iter().position(|&c| c >= 0).ok_or("the world has no room cell")?;
+            (start % world.layout.width, start / world.layout.width)
+        }
+    };
+    let mut sim = Simulator::new(world, x, y)?;
+    tidy_core::export::save_episode(&mut sim, &episode.actions, &opts, out).map_err(|e| format!("cannot write {}: {}", out.display(), e))
+}
+
+/// Print the lints of a schema file; `Ok(false)` if there were any
+fn lint(path: &Path) -> Result<bool, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let schemas = ObjectSchema::from_json(&json).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let lints = lint_schemas(&schemas, ROOM_NAME_POOL);
+    for lint in &lints {
+        println!("{}: {}", lint.kind(), lint);
+    }
+    Ok(lints.is_empty())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.as_slice() {
+        [command, episode, out, flags @ ..] if command == "render-episode" => render_episode(Path::new(episode), Path::new(out), flags),
+        [command, schemas] if command == "lint" => match lint(Path::new(schemas)) {
+            Ok(true) => Ok(()),
+            Ok(false) => return ExitCode::FAILURE,
+            Err(e) => Err(e),
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("tidy-cli: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
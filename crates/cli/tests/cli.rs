@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use tidy_core::export::render_episode;
+use tidy_core::{generate, Action, EpisodeFormat, EpisodeRenderOpts, GenOpts, Simulator};
+
+/// Scratch directory for one test, emptied first
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tidy-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn cli() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_tidy-cli"))
+}
+
+#[test]
+fn render_episode_writes_the_core_animation() {
+    let dir = scratch("render");
+    let actions = [Action::MoveRight, Action::MoveDown, Action::Interact { dx: 0, dy: 1 }, Action::MoveLeft].repeat(3);
+    let episode = serde_json::json!({ "opts": { "seed": 3 }, "actions": actions });
+    std::fs::write(dir.join("episode.json"), episode.to_string()).unwrap();
+
+    let status = cli()
+        .arg("render-episode")
+        .args([dir.join("episode.json"), dir.join("out.gif")])
+        .args(["--frame-skip", "2", "--trail"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let world = generate(&GenOpts { seed: 3, ..Default::default() }).unwrap();
+    let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    let width = world.layout.width;
+    let mut sim = Simulator::new(world, start % width, start / width).unwrap();
+    let opts = EpisodeRenderOpts { format: EpisodeFormat::Gif, frame_skip: 2, trail: true, ..Default::default() };
+    let gif = std::fs::read(dir.join("out.gif")).unwrap();
+    assert!(gif.starts_with(b"GIF89a"));
+    assert_eq!(gif, render_episode(&mut sim, &actions, &opts));
+
+    // any other extension is an APNG
+    assert!(cli().arg("render-episode").args([dir.join("episode.json"), dir.join("out.png")]).status().unwrap().success());
+    assert!(std::fs::read(dir.join("out.png")).unwrap().starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn bad_arguments_fail_without_output() {
+    let dir = scratch("bad");
+    std::fs::write(dir.join("episode.json"), r#"{ "actions": ["Fly"] }"#).unwrap();
+    let out = dir.join("out.gif");
+    assert_eq!(cli().arg("render-episode").output().unwrap().status.code(), Some(2));
+    let unknown = cli().arg("render-episode").args([dir.join("episode.json"), out.clone()]).output().unwrap();
+    assert!(!unknown.status.success());
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("Fly"));
+    let flag = cli().arg("render-episode").args([dir.join("episode.json"), out.clone()]).arg("--scale").output().unwrap();
+    assert!(String::from_utf8_lossy(&flag.stderr).contains("--scale needs a value"));
+    assert!(!out.exists());
+}
+
+#[test]
+fn lint_reports_catalog_mistakes() {
+    let dir = scratch("lint");
+    let schemas = r#"[{ "name": "Cup", "icon": "cup" }, { "name": "Cup", "icon": "cup", "constraint": { "inside_of": ["Teapot"] } }]"#;
+    std::fs::write(dir.join("bad.json"), schemas).unwrap();
+    std::fs::write(dir.join("good.json"), r#"[{ "name": "Cup", "icon": "cup" }]"#).unwrap();
+
+    let bad = cli().args(["lint".into(), dir.join("bad.json")]).output().unwrap();
+    assert_eq!(bad.status.code(), Some(1));
+    let stdout = String::from_utf8(bad.stdout).unwrap();
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["duplicate_name: Cup: duplicate schema name", "unknown_object: Cup: references unknown object 'Teapot'"]);
+    let good = cli().args(["lint".into(), dir.join("good.json")]).output().unwrap();
+    assert!(good.status.success() && good.stdout.is_empty());
+}
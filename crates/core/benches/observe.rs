@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tidy_core::{generate, Action, GenOpts, LocalView, SimBatch, Simulator};
+
+const RADIUS: usize = 5;
+const ENVS: usize = 64;
+
+fn simulator(seed: u64) -> Simulator {
+    let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+    let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    let width = world.layout.width;
+    Simulator::new(world, start % width, start / width).unwrap()
+}
+
+fn local_view(c: &mut Criterion) {
+    let sim = simulator(0);
+    c.bench_function("local_view", |b| b.iter(|| black_box(sim.local_view(RADIUS, false))));
+    let mut view = LocalView::default();
+    c.bench_function("local_view_into", |b| {
+        b.iter(|| {
+            sim.local_view_into(RADIUS, false, &mut view);
+            black_box(&view);
+        })
+    });
+}
+
+fn step_all(c: &mut Criterion) {
+    let mut batch = SimBatch::new((0..ENVS as u64).map(simulator).collect());
+    let mut cells = vec![0; ENVS * SimBatch::observation_len(RADIUS)];
+    let (mut rewards, mut done) = (vec![0.0; ENVS], vec![false; ENVS]);
+    // turning in place keeps every environment stepping without ending the episode
+    let actions = [Action::TurnLeft; ENVS];
+    c.bench_function("step_all_64", |b| {
+        b.iter(|| batch.step_all(&actions, RADIUS, &mut cells, &mut rewards, &mut done).unwrap())
+    });
+}
+
+criterion_group!(benches, local_view, step_all);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+//! Time per-cell and per-id object lookups on a world with about 200 objects: the
+//! `World` object index against the linear scans it replaced.
+//!
+//! Run with `cargo run --release -p tidy-env-core --example object_lookup`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use tidy_core::{generate, GenOpts, Object, World};
+
+const ROUNDS: u32 = 200;
+
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    start.elapsed() / ROUNDS
+}
+
+fn scan_at(world: &World, x: usize, y: usize) -> Vec<&Object> {
+    let mut here: Vec<&Object> = world.objects.iter().filter(|o| o.x == x && o.y == y).collect();
+    here.sort_by_key(|o| o.id);
+    here
+}
+
+fn main() {
+    let opts = GenOpts { seed: 1, width: 48, height: 40, max_rooms: 10, max_objects: 200, ..Default::default() };
+    let world = generate(&opts).expect("generation succeeds");
+    let cells: Vec<(usize, usize)> =
+        (0..world.layout.height).flat_map(|y| (0..world.layout.width).map(move |x| (x, y))).collect();
+    let ids: Vec<_> = world.objects.iter().map(|o| o.id).collect();
+    println!("{} objects on {}x{} cells", ids.len(), world.layout.width, world.layout.height);
+
+    // the first lookup after a change made outside the `World` methods rebuilds the index
+    let mut fresh = world.clone();
+    fresh.clear_object_index();
+    let build = Instant::now();
+    black_box(fresh.objects_at(0, 0));
+    println!("index build: {:?}", build.elapsed());
+
+    let scan = time(|| cells.iter().for_each(|&(x, y)| {
+        black_box(scan_at(&world, x, y));
+    }));
+    let index = time(|| cells.iter().for_each(|&(x, y)| {
+        black_box(world.objects_at(x, y));
+    }));
+    println!("objects_at, every cell: scan {scan:?}, index {index:?} ({:.1}x)", scan.as_secs_f64() / index.as_secs_f64());
+
+    let scan = time(|| ids.iter().for_each(|&id| {
+        black_box(world.objects.iter().find(|o| o.id == id));
+    }));
+    let index = time(|| ids.iter().for_each(|&id| {
+        black_box(world.object(id));
+    }));
+    println!("object, every id: scan {scan:?}, index {index:?} ({:.1}x)", scan.as_secs_f64() / index.as_secs_f64());
+}
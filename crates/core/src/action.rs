@@ -0,0 +1,281 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::checkpoint::Record;
+use crate::hooks::HookDecision;
+use crate::object::ObjectId;
+use crate::sim::{InteractError, MoveError, SimEvent, Simulator};
+use crate::world::tidiness_share;
+
+/// A single agent action, dispatched through `Simulator::step`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PickUp,
+    Drop,
+    /// Open/close a door, pick up, drop or place into at the given offset from the agent
+    Interact { dx: i8, dy: i8 },
+    PlaceInto(ObjectId),
+    /// Put the held object in a satisfying container or floor cell on or next to the agent
+    AutoPlace,
+    /// Do nothing; always succeeds and takes no time
+    Noop,
+    /// Pick up this object from the agent's cell; `PickUp` takes the lowest id there
+    PickUpById(ObjectId),
+    /// Open or close this container; see `Simulator::closed_containers`
+    ToggleContainer(ObjectId),
+    /// Run this appliance, e.g. a dishwasher cleaning the dishes inside
+    RunAppliance(ObjectId),
+    /// Turn a quarter counter-clockwise in place; see `Agent::facing`
+    TurnLeft,
+    /// Turn a quarter clockwise in place
+    TurnRight,
+    /// Move one cell in the facing direction
+    MoveForward,
+    /// `Interact` with the cell in the facing direction
+    InteractAhead,
+}
+
+impl Action {
+    /// Fixed, parameterless action space for policies that emit discrete ids, with the names
+    /// the bindings list them by; an action's id is its index here
+    pub const DISCRETE: [(&'static str, Action); 16] = [
+        ("move_up", Action::MoveUp),
+        ("move_down", Action::MoveDown),
+        ("move_left", Action::MoveLeft),
+        ("move_right", Action::MoveRight),
+        ("pick_up", Action::PickUp),
+        ("drop", Action::Drop),
+        ("interact_up", Action::Interact { dx: 0, dy: -1 }),
+        ("interact_down", Action::Interact { dx: 0, dy: 1 }),
+        ("interact_left", Action::Interact { dx: -1, dy: 0 }),
+        ("interact_right", Action::Interact { dx: 1, dy: 0 }),
+        ("auto_place", Action::AutoPlace),
+        ("noop", Action::Noop),
+        ("turn_left", Action::TurnLeft),
+        ("turn_right", Action::TurnRight),
+        ("move_forward", Action::MoveForward),
+        ("interact_ahead", Action::InteractAhead),
+    ];
+
+    /// Action with the given discrete id
+    pub fn from_id(id: usize) -> Option<Action> {
+        Self::DISCRETE.get(id).map(|&(_, action)| action)
+    }
+
+    /// Discrete id of this action; `None` for actions outside `DISCRETE`
+    pub fn id(&self) -> Option<usize> {
+        Self::DISCRETE.iter().position(|(_, action)| action == self)
+    }
+
+    /// Name of this action in `DISCRETE`, e.g. "interact_left"
+    pub fn discrete_name(&self) -> Option<&'static str> {
+        self.id().map(|id| Self::DISCRETE[id].0)
+    }
+
+    /// Whether the action can change where objects are or what they are; moves, turns, `Noop`
+    /// and `ToggleContainer` cannot
+    fn moves_objects(&self) -> bool {
+        !matches!(
+            self,
+            Action::MoveUp
+                | Action::MoveDown
+                | Action::MoveLeft
+                | Action::MoveRight
+                | Action::Noop
+                | Action::ToggleContainer(_)
+                | Action::TurnLeft
+                | Action::TurnRight
+                | Action::MoveForward
+        )
+    }
+}
+
+/// Why `Simulator::step` did not perform an action
+#[derive(Debug, PartialEq)]
+pub enum ActionError {
+    /// A movement or object action failed
+    Move(MoveError),
+    /// `Action::Interact` failed
+    Interact(InteractError),
+    /// A hook refused the action
+    Denied(String),
+    /// `Simulator::step_agent` got an id past `num_agents`
+    UnknownAgent(usize),
+}
+
+impl ActionError {
+    /// Name of the underlying variant, as the bindings report it
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionError::Move(e) => e.name(),
+            ActionError::Interact(e) => e.name(),
+            ActionError::Denied(_) => "Denied",
+            ActionError::UnknownAgent(_) => "UnknownAgent",
+        }
+    }
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Move(e) => write!(f, "{}", e),
+            ActionError::Interact(e) => write!(f, "{}", e),
+            ActionError::Denied(reason) => write!(f, "Denied: {}", reason),
+            ActionError::UnknownAgent(id) => write!(f, "No agent with id {}", id),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl From<MoveError> for ActionError {
+    fn from(e: MoveError) -> Self {
+        ActionError::Move(e)
+    }
+}
+
+impl From<ActionError> for MoveError {
+    fn from(e: ActionError) -> Self {
+        match e {
+            ActionError::Move(e) => e,
+            ActionError::Interact(e) => MoveError::Denied(e.to_string()),
+            ActionError::Denied(reason) => MoveError::Denied(reason),
+            e @ ActionError::UnknownAgent(_) => MoveError::Denied(e.to_string()),
+        }
+    }
+}
+
+impl From<ActionError> for InteractError {
+    fn from(e: ActionError) -> Self {
+        match e {
+            ActionError::Interact(e) => e,
+            ActionError::Move(e) => InteractError::Move(e),
+            ActionError::Denied(reason) => InteractError::Denied(reason),
+            e @ ActionError::UnknownAgent(_) => InteractError::Denied(e.to_string()),
+        }
+    }
+}
+
+/// Result of a successful `Simulator::step`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepOutcome {
+    /// The action that was executed, after any hook replaced it
+    pub action: Action,
+    /// Successful actions taken so far, this one included
+    pub steps: u32,
+    /// Reward from `Simulator::reward_config`, if set, plus what the hooks returned
+    pub reward: f32,
+    /// Change in `Simulator::tidiness` caused by the action
+    pub tidiness_delta: f32,
+    /// Primitive actions this outcome covers: 1 for `step`, the route length for macros
+    pub primitive_steps: u32,
+    /// Objects the action disposed of, and events from the `tick`s a macro runs between its
+    /// primitive actions
+    pub events: Vec<SimEvent>,
+    /// Whether `Simulator::is_done` holds after the action
+    pub done: bool,
+}
+
+impl Simulator {
+    /// Perform an action, consulting the registered hooks before and after it.
+    ///
+    /// The direct methods (`up`, `pick_up`, `interact`, ...) all go through here, so hooks
+    /// see every action.
+    pub fn step(&mut self, action: Action) -> Result<StepOutcome, ActionError> {
+        // hooks get `&Simulator`, so take them out while they run
+        let mut hooks = std::mem::take(&mut self.hooks);
+        let result = self.step_with(&mut hooks, action);
+        self.hooks = hooks;
+        self.record_action(action, result.as_ref().map(|_| ()));
+        if let Ok(outcome) = &result {
+            self.invalidate_room_cache(&outcome.action);
+            // log what actually ran, so replaying does not depend on the hooks
+            self.log_record(match self.active {
+                0 => Record::Action(outcome.action),
+                agent => Record::AgentAction(agent, outcome.action),
+            });
+        }
+        result
+    }
+
+    /// `step` for the agent with the given id; agent 0 is the one `step` moves.
+    ///
+    /// Hooks see the acting agent as `Simulator::agent` and `holding`.
+    pub fn step_agent(&mut self, agent: usize, action: Action) -> Result<StepOutcome, ActionError> {
+        if agent == 0 {
+            return self.step(action);
+        }
+        if agent >= self.num_agents() {
+            return Err(ActionError::UnknownAgent(agent));
+        }
+        self.swap_agent(agent - 1, agent);
+        let result = self.step(action);
+        self.swap_agent(agent - 1, 0);
+        result
+    }
+
+    fn step_with(&mut self, hooks: &mut crate::hooks::Hooks, mut action: Action) -> Result<StepOutcome, ActionError> {
+        for hook in hooks.iter_mut() {
+            match hook.before_action(self, &action) {
+                HookDecision::Allow => {}
+                HookDecision::Deny(e) => return Err(e),
+                HookDecision::Replace(replacement) => action = replacement,
+            }
+        }
+        let counts_before = action.moves_objects().then(|| self.placement_counts());
+        let baseline = self.reward_baseline(action.moves_objects());
+        let disposed_before = self.world.disposed.len();
+        self.execute(action)?;
+        let (tidiness_delta, placed_delta) = match counts_before {
+            Some((placed, total)) => {
+                let (placed_after, total_after) = self.placement_counts();
+                let delta = tidiness_share(placed_after, total_after) - tidiness_share(placed, total);
+                (delta, placed_after as isize - placed as isize)
+            }
+            None => (0.0, 0),
+        };
+        let events =
+            self.world.disposed[disposed_before..].iter().map(|o| SimEvent::Disposed { id: o.id, name: o.name.clone() }).collect();
+        let reward = baseline.map_or(0.0, |baseline| self.shaped_reward(baseline, placed_delta));
+        let done = self.is_done();
+        let mut outcome = StepOutcome { action, steps: self.steps, reward, tidiness_delta, primitive_steps: 1, events, done };
+        for hook in hooks.iter_mut() {
+            outcome.reward += hook.after_action(self, &outcome);
+        }
+        Ok(outcome)
+    }
+
+    fn execute(&mut self, action: Action) -> Result<(), ActionError> {
+        match action {
+            Action::MoveUp => self.try_move(0, -1)?,
+            Action::MoveDown => self.try_move(0, 1)?,
+            Action::MoveLeft => self.try_move(-1, 0)?,
+            Action::MoveRight => self.try_move(1, 0)?,
+            Action::PickUp => self.do_pick_up()?,
+            Action::Drop => self.do_drop()?,
+            Action::Interact { dx, dy } => self.do_interact(dx as isize, dy as isize).map_err(ActionError::Interact)?,
+            Action::PlaceInto(id) => self.do_place_into(id)?,
+            Action::AutoPlace => self.do_auto_place()?,
+            Action::Noop => {}
+            Action::PickUpById(id) => self.do_pick_up_by_id(id)?,
+            Action::ToggleContainer(id) => self.do_toggle_container(id)?,
+            Action::RunAppliance(id) => self.do_run_appliance(id)?,
+            Action::TurnLeft => self.turn(self.agent.facing.left()),
+            Action::TurnRight => self.turn(self.agent.facing.right()),
+            Action::MoveForward => {
+                let (dx, dy) = self.agent.facing.offset();
+                self.try_move(dx, dy)?
+            }
+            Action::InteractAhead => {
+                let (dx, dy) = self.agent.facing.offset();
+                self.do_interact(dx, dy).map_err(ActionError::Interact)?
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,91 +1,111 @@
-use crate::gen::Layout;
-
-/// Possible errors when moving the agent
-#[derive(Debug, PartialEq)]
-pub enum MoveError {
-    /// Attempted to move outside the layout bounds
-    OutOfBounds,
-    /// Attempted to move into a wall or outside cell
-    HitObstacle,
-}
+use serde::{Deserialize, Serialize};
 
 /// Represents an agent within the layout
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Agent {
     pub x: usize,
     pub y: usize,
+    /// Direction `MoveForward` and `InteractAhead` use; successful absolute moves turn the agent too
+    #[serde(default)]
+    pub facing: Direction,
 }
 
 impl Agent {
-    /// Create a new agent at the given coordinates
+    /// Create a new agent at the given coordinates, facing north
     pub fn new(x: usize, y: usize) -> Self {
-        Agent { x, y }
+        Agent { x, y, facing: Direction::North }
     }
 }
 
-/// Simulator state pairing a layout with an agent
-#[derive(Debug)]
-pub struct Simulator {
-    pub layout: Layout,
-    pub agent: Agent,
+/// Compass direction on the grid; north is up (negative y)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Direction {
+    #[default]
+    North,
+    East,
+    South,
+    West,
 }
 
-impl Simulator {
-    /// Initialize the simulator with a layout and agent start position
-    pub fn new(layout: Layout, start_x: usize, start_y: usize) -> Result<Self, &'static str> {
-        // Check bounds
-        if start_x >= layout.width || start_y >= layout.height {
-            return Err("Start position out of bounds");
-        }
-        // Check starting cell is free (non-negative)
-        let idx = start_y * layout.width + start_x;
-        if layout.cells[idx] < 0 {
-            return Err("Start position is not navigable");
+impl Direction {
+    /// Lowercase name, as the bindings report it
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::East => "east",
+            Direction::South => "south",
+            Direction::West => "west",
         }
-        Ok(Simulator {
-            layout,
-            agent: Agent::new(start_x, start_y),
-        })
     }
 
-    /// Attempt to move the agent up (decreasing y)
-    pub fn up(&mut self) -> Result<(), MoveError> {
-        self.try_move(0, -1)
+    /// Direction after a quarter turn counter-clockwise
+    pub fn left(self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
     }
 
-    /// Move the agent down (increasing y)
-    pub fn down(&mut self) -> Result<(), MoveError> {
-        self.try_move(0, 1)
+    /// Direction after a quarter turn clockwise
+    pub fn right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
     }
 
-    /// Move the agent left (decreasing x)
-    pub fn left(&mut self) -> Result<(), MoveError> {
-        self.try_move(-1, 0)
+    /// Grid offset of one step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
     }
 
-    /// Move the agent right (increasing x)
-    pub fn right(&mut self) -> Result<(), MoveError> {
-        self.try_move(1, 0)
+    /// Direction of a single-axis offset such as a move; `None` for diagonals and zero
+    pub fn from_offset(dx: isize, dy: isize) -> Option<Direction> {
+        match (dx.signum(), dy.signum()) {
+            (0, -1) => Some(Direction::North),
+            (1, 0) => Some(Direction::East),
+            (0, 1) => Some(Direction::South),
+            (-1, 0) => Some(Direction::West),
+            _ => None,
+        }
     }
 
-    /// Internal helper: attempt to move by (dx, dy)
-    fn try_move(&mut self, dx: isize, dy: isize) -> Result<(), MoveError> {
-        let new_x = self.agent.x as isize + dx;
-        let new_y = self.agent.y as isize + dy;
-        // Bounds check
-        if new_x < 0 || new_x >= self.layout.width as isize || new_y < 0 || new_y >= self.layout.height as isize {
-            return Err(MoveError::OutOfBounds);
+    /// A grid offset in the frame of an agent facing this way, where ahead is `(0, -1)` and
+    /// to the right is `(1, 0)`
+    pub fn to_agent_frame(self, dx: isize, dy: isize) -> (isize, isize) {
+        match self {
+            Direction::North => (dx, dy),
+            Direction::East => (dy, -dx),
+            Direction::South => (-dx, -dy),
+            Direction::West => (-dy, dx),
         }
-        let new_x = new_x as usize;
-        let new_y = new_y as usize;
-        let idx = new_y * self.layout.width + new_x;
-        // Obstacle check (walls and outside are negative)
-        if self.layout.cells[idx] < 0 {
-            return Err(MoveError::HitObstacle);
+    }
+
+    /// Inverse of `to_agent_frame`
+    pub fn from_agent_frame(self, dx: isize, dy: isize) -> (isize, isize) {
+        match self {
+            Direction::North => (dx, dy),
+            Direction::East => (-dy, dx),
+            Direction::South => (-dx, -dy),
+            Direction::West => (dy, -dx),
         }
-        // Apply move
-        self.agent.x = new_x;
-        self.agent.y = new_y;
-        Ok(())
     }
 }
+
+/// Former layout-only simulator; movement now lives in `sim::Simulator`, which takes a
+/// `World` (wrap a bare layout as `World::new(layout, Vec::new())`)
+#[deprecated(since = "0.1.0", note = "use `tidy_core::Simulator` (sim::Simulator)")]
+pub type Simulator = crate::sim::Simulator;
+
+/// Former layout-only move error; `sim::MoveError` is the single shared enum
+#[deprecated(since = "0.1.0", note = "use `tidy_core::MoveError` (sim::MoveError)")]
+pub type MoveError = crate::sim::MoveError;
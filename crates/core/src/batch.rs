@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::action::Action;
+use crate::world::Cell;
+use crate::sim::Simulator;
+use crate::view::LocalView;
+
+/// Why a `SimBatch` call refused its buffers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchError {
+    /// One action per simulator is needed
+    ActionCount { expected: usize, found: usize },
+    /// An output buffer has the wrong length; `buffer` names it
+    BufferSize { buffer: &'static str, expected: usize, found: usize },
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::ActionCount { expected, found } => write!(f, "expected {} actions, got {}", expected, found),
+            BatchError::BufferSize { buffer, expected, found } => {
+                write!(f, "expected a {} buffer of {} elements, got {}", buffer, expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// Simulators stepped in lockstep, e.g. the environments of a vectorized RL loop.
+///
+/// Observations are the `Simulator::local_view` cells of each simulator, written row-major
+/// into one caller-owned slab with `observation_len(radius)` cells per simulator, so a host
+/// can allocate its arrays once and have every step fill them in place. One scratch view is
+/// shared by all simulators; once it and the simulators' bounded event logs have grown,
+/// steps that do not move objects allocate nothing.
+pub struct SimBatch {
+    pub sims: Vec<Simulator>,
+    view: LocalView,
+}
+
+impl SimBatch {
+    pub fn new(sims: Vec<Simulator>) -> SimBatch {
+        SimBatch { sims, view: LocalView::default() }
+    }
+
+    /// Number of simulators
+    pub fn len(&self) -> usize {
+        self.sims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sims.is_empty()
+    }
+
+    /// Cells in one simulator's observation: `(2 * radius + 1)²`
+    pub fn observation_len(radius: usize) -> usize {
+        (2 * radius + 1) * (2 * radius + 1)
+    }
+
+    /// Step simulator `i` with `actions[i]`, then write its observation into
+    /// `cells[i * n..(i + 1) * n]` (n = `observation_len(radius)`), its reward into
+    /// `rewards[i]` and `Simulator::is_done` into `done[i]`.
+    ///
+    /// A failed action leaves its simulator unchanged and scores 0; the other simulators
+    /// still step. Nothing steps when a buffer has the wrong length.
+    pub fn step_all(&mut self, actions: &[Action], radius: usize, cells: &mut [Cell], rewards: &mut [f32], done: &mut [bool]) -> Result<(), BatchError> {
+        let n = self.len();
+        if actions.len() != n {
+            return Err(BatchError::ActionCount { expected: n, found: actions.len() });
+        }
+        self.check_cells(radius, cells)?;
+        check_len("rewards", n, rewards.len())?;
+        check_len("done", n, done.len())?;
+        for (i, sim) in self.sims.iter_mut().enumerate() {
+            rewards[i] = sim.step(actions[i]).map_or(0.0, |outcome| outcome.reward);
+            done[i] = sim.is_done();
+        }
+        self.observe_all(radius, cells)
+    }
+
+    /// Write every simulator's observation into `cells` as `step_all` does, without stepping
+    pub fn observe_all(&mut self, radius: usize, cells: &mut [Cell]) -> Result<(), BatchError> {
+        self.check_cells(radius, cells)?;
+        let len = Self::observation_len(radius);
+        for (sim, out) in self.sims.iter().zip(cells.chunks_exact_mut(len)) {
+            sim.local_view_into(radius, false, &mut self.view);
+            self.view.pad_to(radius);
+            out.copy_from_slice(&self.view.cells);
+        }
+        Ok(())
+    }
+
+    fn check_cells(&self, radius: usize, cells: &[Cell]) -> Result<(), BatchError> {
+        check_len("cells", self.len() * Self::observation_len(radius), cells.len())
+    }
+}
+
+fn check_len(buffer: &'static str, expected: usize, found: usize) -> Result<(), BatchError> {
+    if expected != found {
+        return Err(BatchError::BufferSize { buffer, expected, found });
+    }
+    Ok(())
+}
@@ -0,0 +1,176 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::world::{Cell, Layout};
+use crate::{CLOSED_DOOR, LOCKED_DOOR, OPEN_DOOR, OUTSIDE, STAIRS_BASE, WALL, WINDOW};
+
+/// Typed view of a grid cell value.
+///
+/// Layouts keep storing plain `i8`s (that is also the FFI format); every `i8` maps to exactly
+/// one kind and back, so `CellKind::from_i8(c).to_i8() == c` for all values:
+///
+/// | value          | kind                   |
+/// |----------------|------------------------|
+/// | `0..=127`      | `Room(id)`             |
+/// | `-1`           | `Wall`                 |
+/// | `-2`           | `Outside`              |
+/// | `-3`           | `ClosedDoor`           |
+/// | `-4`           | `OpenDoor`             |
+/// | `-5`           | `LockedDoor`           |
+/// | `-6`           | `Window`               |
+/// | `-7..=-128`    | `Stairs(0..=121)`      |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CellKind {
+    Room(u8),
+    Wall,
+    Outside,
+    ClosedDoor,
+    OpenDoor,
+    /// Door that cannot be opened by interacting with it
+    LockedDoor,
+    /// Part of the outer wall the agent can see through but not pass
+    Window,
+    /// Staircase; the payload identifies which staircase (e.g. the level it leads to)
+    Stairs(u8),
+}
+
+impl CellKind {
+    /// Kind of a raw cell value
+    pub fn from_i8(cell: Cell) -> Self {
+        match cell {
+            c if c >= 0 => CellKind::Room(c as u8),
+            WALL => CellKind::Wall,
+            OUTSIDE => CellKind::Outside,
+            CLOSED_DOOR => CellKind::ClosedDoor,
+            OPEN_DOOR => CellKind::OpenDoor,
+            LOCKED_DOOR => CellKind::LockedDoor,
+            WINDOW => CellKind::Window,
+            c => CellKind::Stairs((STAIRS_BASE - c) as u8),
+        }
+    }
+
+    /// Raw cell value of this kind.
+    ///
+    /// Panics when the payload is out of range: room ids above 127 or staircases above 121.
+    pub fn to_i8(self) -> Cell {
+        match self {
+            CellKind::Room(id) => i8::try_from(id).expect("room id out of range"),
+            CellKind::Wall => WALL,
+            CellKind::Outside => OUTSIDE,
+            CellKind::ClosedDoor => CLOSED_DOOR,
+            CellKind::OpenDoor => OPEN_DOOR,
+            CellKind::LockedDoor => LOCKED_DOOR,
+            CellKind::Window => WINDOW,
+            CellKind::Stairs(n) => {
+                assert!(n <= (STAIRS_BASE - i8::MIN) as u8, "staircase id out of range");
+                STAIRS_BASE - n as i8
+            }
+        }
+    }
+
+    /// Snake-case name of the kind without its payload, e.g. `closed_door` or `room`
+    pub fn name(self) -> &'static str {
+        match self {
+            CellKind::Room(_) => "room",
+            CellKind::Wall => "wall",
+            CellKind::Outside => "outside",
+            CellKind::ClosedDoor => "closed_door",
+            CellKind::OpenDoor => "open_door",
+            CellKind::LockedDoor => "locked_door",
+            CellKind::Window => "window",
+            CellKind::Stairs(_) => "stairs",
+        }
+    }
+
+    /// Room id of a room cell
+    pub fn room(self) -> Option<usize> {
+        match self {
+            CellKind::Room(id) => Some(id as usize),
+            _ => None,
+        }
+    }
+
+    pub fn is_door(self) -> bool {
+        matches!(self, CellKind::ClosedDoor | CellKind::OpenDoor | CellKind::LockedDoor)
+    }
+
+    /// Whether the agent can step onto the cell right now
+    pub fn is_passable(self) -> bool {
+        matches!(self, CellKind::Room(_) | CellKind::OpenDoor | CellKind::Stairs(_))
+    }
+
+    /// Whether a route may cross the cell, opening it first if it is a closed door
+    pub fn is_traversable(self) -> bool {
+        self.is_passable() || self == CellKind::ClosedDoor
+    }
+}
+
+impl From<Cell> for CellKind {
+    fn from(cell: Cell) -> Self {
+        CellKind::from_i8(cell)
+    }
+}
+
+impl From<CellKind> for Cell {
+    fn from(kind: CellKind) -> Self {
+        kind.to_i8()
+    }
+}
+
+impl Layout {
+    /// Kind of the cell at (x, y)
+    pub fn kind_at(&self, x: usize, y: usize) -> CellKind {
+        CellKind::from_i8(self.cells[y * self.width + x])
+    }
+
+    /// Kind of the cell at (x, y); `None` off the grid
+    pub fn kind(&self, x: usize, y: usize) -> Option<CellKind> {
+        (x < self.width && y < self.height).then(|| self.kind_at(x, y))
+    }
+
+    /// Kinds of all cells in row-major order
+    pub fn kinds(&self) -> impl Iterator<Item = CellKind> + '_ {
+        self.cells.iter().map(|&c| CellKind::from_i8(c))
+    }
+
+    /// `(x, y, kind)` for all cells in row-major order
+    pub fn kinds_with_coords(&self) -> impl Iterator<Item = (usize, usize, CellKind)> + '_ {
+        let w = self.width;
+        self.cells.iter().enumerate().map(move |(i, &c)| (i % w, i / w, CellKind::from_i8(c)))
+    }
+
+    /// Build a layout from ASCII rows: `#` wall, space outside, `D`/`d` closed/open door,
+    /// `L` locked door, `=` window and digits `0`-`9` for room ids indexing `room_names`.
+    ///
+    /// All rows must have the same length.
+    pub fn from_ascii(rows: &[&str], room_names: &[&'static str]) -> Result<Layout, String> {
+        let width = rows.first().map_or(0, |r| r.chars().count());
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("Row {} has {} columns, expected {}", y, row.chars().count(), width));
+            }
+            for (x, c) in row.chars().enumerate() {
+                let kind = match c {
+                    '#' => CellKind::Wall,
+                    ' ' => CellKind::Outside,
+                    'D' => CellKind::ClosedDoor,
+                    'd' => CellKind::OpenDoor,
+                    'L' => CellKind::LockedDoor,
+                    '=' => CellKind::Window,
+                    '0'..='9' => {
+                        let room = c as u8 - b'0';
+                        if room as usize >= room_names.len() {
+                            return Err(format!("Room {} at ({}, {}) has no name", room, x, y));
+                        }
+                        CellKind::Room(room)
+                    }
+                    _ => return Err(format!("Unknown cell '{}' at ({}, {})", c, x, y)),
+                };
+                cells.push(kind.to_i8());
+            }
+        }
+        Ok(Layout::new(width, rows.len(), cells, room_names.to_vec()))
+    }
+}
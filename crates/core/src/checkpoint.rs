@@ -0,0 +1,494 @@
+//! Crash-safe episode checkpoints.
+//!
+//! A checkpoint file is a sequence of records, each a little-endian `u32` length followed by
+//! a msgpack-encoded `Record`. It starts with a format header and a snapshot (files from
+//! before format headers start with the snapshot); afterwards every state-changing
+//! call is logged and a fresh snapshot is appended periodically. Resuming restores the last
+//! complete snapshot and replays the records after it, ignoring a truncated final record.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::agent::{Agent, Direction};
+use crate::format::{CompatError, FormatHeader, FORMAT_VERSION};
+use crate::world::{Cell, World};
+use crate::object::{Object, ObjectId, ObjectSchema};
+use crate::sim::{ActionCosts, Simulator};
+
+/// Why a checkpoint could not be written or resumed
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(std::io::Error),
+    Encode(String),
+    /// A complete record could not be decoded
+    Decode(String),
+    /// The file contains no complete snapshot
+    NoSnapshot,
+    /// The snapshot belongs to a world with a different layout
+    FingerprintMismatch { expected: u64, found: u64 },
+    /// The snapshot names an object schema this build does not know
+    UnknownObject(String),
+    /// Replaying the logged record with this index failed
+    Diverged { record: usize, error: String },
+    /// The file was written in a format this build cannot read
+    Compat(CompatError),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(e) => write!(f, "checkpoint I/O error: {}", e),
+            CheckpointError::Encode(e) => write!(f, "could not encode checkpoint: {}", e),
+            CheckpointError::Decode(e) => write!(f, "could not decode checkpoint: {}", e),
+            CheckpointError::NoSnapshot => write!(f, "checkpoint contains no complete snapshot"),
+            CheckpointError::FingerprintMismatch { expected, found } => {
+                write!(f, "checkpoint is for world {:016x}, not {:016x}", found, expected)
+            }
+            CheckpointError::UnknownObject(name) => write!(f, "checkpoint references unknown object '{}'", name),
+            CheckpointError::Diverged { record, error } => write!(f, "replay diverged at record {}: {}", record, error),
+            CheckpointError::Compat(e) => write!(f, "cannot load checkpoint: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(e: std::io::Error) -> Self {
+        CheckpointError::Io(e)
+    }
+}
+
+impl From<CompatError> for CheckpointError {
+    fn from(e: CompatError) -> Self {
+        CheckpointError::Compat(e)
+    }
+}
+
+/// Mutable state of one object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectState {
+    pub id: ObjectId,
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+    pub contents: Vec<ObjectId>,
+    pub label: Option<String>,
+    /// Container holding the object (format 2); informational, `restore` goes by `contents`
+    #[serde(default)]
+    pub parent: Option<ObjectId>,
+    /// Whether the container is open; states saved before containers could close have it open
+    #[serde(default = "open_by_default")]
+    pub open: bool,
+}
+
+fn open_by_default() -> bool {
+    true
+}
+
+impl From<&Object> for ObjectState {
+    /// State without `parent`, which only the whole world knows; `Simulator::state` fills it in
+    fn from(o: &Object) -> Self {
+        ObjectState {
+            id: o.id,
+            name: o.name.to_string(),
+            x: o.x,
+            y: o.y,
+            contents: o.contents.clone(),
+            label: o.label.clone(),
+            parent: None,
+            open: o.open,
+        }
+    }
+}
+
+/// Everything about a simulator that can change after it was created
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimState {
+    /// `World::fingerprint` of the world the state belongs to
+    pub fingerprint: u64,
+    /// Cells including door states
+    pub cells: Vec<Cell>,
+    pub objects: Vec<ObjectState>,
+    pub agent: (usize, usize),
+    pub holding: Option<ObjectState>,
+    /// What the held object contains, see `Simulator::carried`; states saved before carried
+    /// contents left the world keep them in `objects`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carried: Vec<ObjectState>,
+    pub known_targets: Vec<ObjectId>,
+    pub decay_enabled: bool,
+    pub decay_remaining: Vec<(ObjectId, usize)>,
+    pub costs: ActionCosts,
+    pub steps: u32,
+    pub time: u32,
+    pub max_time: Option<u32>,
+    /// Objects thrown away into a disposing container, see `World::disposed`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disposed: Vec<ObjectState>,
+    /// `Agent::facing`; states saved before agents had a facing face north
+    #[serde(default)]
+    pub facing: Direction,
+    /// Agents after the first, see `Simulator::new_multi`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub other_agents: Vec<AgentState>,
+}
+
+/// Position, facing and held object of one agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentState {
+    pub x: usize,
+    pub y: usize,
+    pub facing: Direction,
+    pub holding: Option<ObjectState>,
+    /// What the held object contains, like `SimState::carried`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub carried: Vec<ObjectState>,
+}
+
+impl SimState {
+    /// Set every object's `parent` from the containers' `contents`
+    pub fn backfill_parents(&mut self) {
+        let parents: Vec<(ObjectId, ObjectId)> =
+            self.objects.iter().flat_map(|o| o.contents.iter().map(move |&c| (c, o.id))).collect();
+        for o in &mut self.objects {
+            o.parent = parents.iter().find(|&&(child, _)| child == o.id).map(|&(_, p)| p);
+        }
+    }
+
+    /// Msgpack of the state with a `format` header, for `from_msgpack`
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        crate::format::to_msgpack(self)
+    }
+
+    /// Load a state written by `to_msgpack`, by this or an older build
+    pub fn from_msgpack(bytes: &[u8]) -> Result<SimState, CompatError> {
+        crate::format::from_msgpack(bytes)
+    }
+}
+
+/// A whole simulator as written by `Simulator::to_json`
+#[derive(Serialize, Deserialize)]
+struct SavedSimulator {
+    world: World,
+    state: SimState,
+}
+
+/// One entry of a checkpoint file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Record {
+    Snapshot(Box<SimState>),
+    Action(Action),
+    Tick,
+    Inspect(ObjectId),
+    Label(ObjectId, Option<String>),
+    Door { x: isize, y: isize, open: bool },
+    /// Format of the file; the first record of files written since format 2
+    Header(FormatHeader),
+    /// An action of the agent with this id, see `Simulator::step_agent`; agent 0 logs `Action`
+    AgentAction(usize, Action),
+}
+
+/// Open checkpoint file plus the autosave schedule
+#[derive(Debug)]
+pub(crate) struct Checkpointer {
+    file: File,
+    every: u32,
+    since_snapshot: u32,
+    /// First write error; checkpointing stops once one occurs
+    error: Option<CheckpointError>,
+}
+
+fn write_record(file: &mut File, record: &Record) -> Result<(), CheckpointError> {
+    // named fields, since positional ones would shift when a skipped field is absent;
+    // records from older, positional files still decode
+    let bytes = rmp_serde::to_vec_named(record).map_err(|e| CheckpointError::Encode(e.to_string()))?;
+    let mut buf = Vec::with_capacity(bytes.len() + 4);
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&bytes);
+    // a single write keeps a crash from splitting the length prefix from its payload
+    file.write_all(&buf)?;
+    Ok(())
+}
+
+/// Decode every complete record; a truncated final record is dropped.
+///
+/// The format header, if any, is checked before anything else is decoded, and snapshots
+/// from older formats are upgraded.
+pub fn read_records(bytes: &[u8]) -> Result<Vec<Record>, CheckpointError> {
+    let mut records = Vec::new();
+    let mut rest = bytes;
+    let mut version = None;
+    while rest.len() >= 4 {
+        let len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let Some(payload) = rest.get(4..4 + len) else { break };
+        let record = rmp_serde::from_slice(payload);
+        if records.is_empty() {
+            // the header comes before any record a newer format may have changed
+            let header = match &record {
+                Ok(Record::Header(header)) => header.clone(),
+                _ => FormatHeader::legacy(),
+            };
+            header.check()?;
+            version = Some(header.format_version);
+        }
+        records.push(record.map_err(|e| CheckpointError::Decode(e.to_string()))?);
+        rest = &rest[4 + len..];
+    }
+    if version.is_some_and(|v| v < FORMAT_VERSION) {
+        for record in &mut records {
+            if let Record::Snapshot(state) = record {
+                state.backfill_parents();
+            }
+        }
+    }
+    Ok(records)
+}
+
+impl Simulator {
+    /// Capture the simulator's mutable state
+    pub fn state(&self) -> SimState {
+        let mut decay_remaining: Vec<(ObjectId, usize)> = self.decay_remaining.iter().map(|(&id, &n)| (id, n)).collect();
+        decay_remaining.sort_unstable();
+        let mut known_targets: Vec<ObjectId> = self.known_targets.iter().copied().collect();
+        known_targets.sort_unstable();
+        // agent 0 is not in `agent` while `step_agent` runs another one
+        let agent = *self.agent_by_id(0).expect("agent 0 always exists");
+        let other_agents = (1..self.num_agents())
+            .filter_map(|id| {
+                let a = self.agent_by_id(id)?;
+                let holding = self.holding_by_id(id).map(ObjectState::from);
+                let carried = self.carried_by_id(id).iter().map(ObjectState::from).collect();
+                Some(AgentState { x: a.x, y: a.y, facing: a.facing, holding, carried })
+            })
+            .collect();
+        let mut state = SimState {
+            fingerprint: self.world.fingerprint(),
+            cells: self.world.layout.cells.clone(),
+            objects: self.world.objects.iter().map(ObjectState::from).collect(),
+            agent: (agent.x, agent.y),
+            holding: self.holding_by_id(0).map(ObjectState::from),
+            carried: self.carried_by_id(0).iter().map(ObjectState::from).collect(),
+            known_targets,
+            decay_enabled: self.decay_enabled,
+            decay_remaining,
+            costs: self.costs,
+            steps: self.steps,
+            time: self.time,
+            max_time: self.max_time,
+            disposed: self.world.disposed.iter().map(ObjectState::from).collect(),
+            facing: agent.facing,
+            other_agents,
+        };
+        state.backfill_parents();
+        state
+    }
+
+    /// The world and episode state as JSON with a `format` header, for `from_json`.
+    ///
+    /// Unlike `state`, this needs no simulator to restore into. Settings outside `SimState`
+    /// (hooks, checkpointing, `strict_reach`) are not saved.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        crate::format::to_json(&SavedSimulator { world: self.world.clone(), state: self.state() })
+    }
+
+    /// Rebuild a simulator from `to_json` output
+    pub fn from_json(json: &str) -> Result<Simulator, CheckpointError> {
+        let SavedSimulator { world, state } = crate::format::from_json(json)?;
+        world.validate().map_err(CheckpointError::Decode)?;
+        let mut sim = Simulator::new(world, state.agent.0, state.agent.1).map_err(|e| CheckpointError::Decode(e.into()))?;
+        sim.restore(&state)?;
+        // a loaded episode resets to where it was saved, not to how its world was generated
+        sim.initial = Some(Box::new(state));
+        Ok(sim)
+    }
+
+    /// Restore a state captured from a simulator over the same layout.
+    ///
+    /// Hooks and checkpointing settings are kept; everything in `SimState` is replaced.
+    pub fn restore(&mut self, state: &SimState) -> Result<(), CheckpointError> {
+        let expected = self.world.fingerprint();
+        if state.fingerprint != expected || state.cells.len() != self.world.layout.cells.len() {
+            return Err(CheckpointError::FingerprintMismatch { expected, found: state.fingerprint });
+        }
+        let objects = state.objects.iter().map(|o| self.object_from_state(o)).collect::<Result<Vec<_>, _>>()?;
+        let holding = state.holding.as_ref().map(|o| self.object_from_state(o)).transpose()?;
+        let carried = state.carried.iter().map(|o| self.object_from_state(o)).collect::<Result<Vec<_>, _>>()?;
+        let disposed = state.disposed.iter().map(|o| self.object_from_state(o)).collect::<Result<Vec<_>, _>>()?;
+        let other_agents = state
+            .other_agents
+            .iter()
+            .map(|a| {
+                let holding = a.holding.as_ref().map(|o| self.object_from_state(o)).transpose()?;
+                let carried = a.carried.iter().map(|o| self.object_from_state(o)).collect::<Result<Vec<_>, _>>()?;
+                Ok((Agent { x: a.x, y: a.y, facing: a.facing }, holding, carried))
+            })
+            .collect::<Result<Vec<_>, CheckpointError>>()?;
+        self.world.layout.cells = state.cells.clone();
+        self.world.layout.sync_doors();
+        self.world.objects = objects;
+        self.world.disposed = disposed;
+        self.world.clear_object_index();
+        self.holding = holding;
+        self.carried = carried;
+        self.agent.x = state.agent.0;
+        self.agent.y = state.agent.1;
+        self.agent.facing = state.facing;
+        self.other_agents = other_agents;
+        self.active = 0;
+        // states saved before carried contents left the world keep them there
+        self.carry_contents();
+        for slot in 0..self.other_agents.len() {
+            self.swap_agent(slot, slot + 1);
+            self.carry_contents();
+            self.swap_agent(slot, 0);
+        }
+        self.known_targets = state.known_targets.iter().copied().collect();
+        self.events.clear();
+        self.decay_enabled = state.decay_enabled;
+        self.decay_remaining = state.decay_remaining.iter().copied().collect();
+        self.costs = state.costs;
+        self.steps = state.steps;
+        self.time = state.time;
+        self.max_time = state.max_time;
+        self.invalidate_caches();
+        Ok(())
+    }
+
+    /// Return to the state captured when the simulator was created: the world as generated,
+    /// the agent at its start, nothing held and the clock at zero.
+    ///
+    /// Simulators from `from_json` return to the loaded state. Like `restore`, this keeps hooks
+    /// and rules, and it also keeps the current `costs`, `decay_enabled` and `max_time`. An
+    /// active checkpoint gets a fresh snapshot so that resuming it starts after the reset, and
+    /// an active recording starts over.
+    pub fn reset(&mut self) -> Result<(), CheckpointError> {
+        let Some(mut state) = self.initial.as_deref().cloned() else { return Ok(()) };
+        state.costs = self.costs;
+        state.decay_enabled = self.decay_enabled;
+        state.max_time = self.max_time;
+        self.restore(&state)?;
+        self.log_snapshot();
+        if self.recording.is_some() {
+            self.enable_recording();
+        }
+        Ok(())
+    }
+
+    /// Rebuild an object, resolving its name against the objects already present or the catalog
+    fn object_from_state(&self, state: &ObjectState) -> Result<Object, CheckpointError> {
+        let known = self.world.objects.iter().chain(self.held_objects()).chain(&self.world.disposed);
+        let known = known.flat_map(Object::decay_chain).find(|o| o.name == state.name);
+        let mut obj = match (known, ObjectSchema::find(&state.name)) {
+            // objects from custom schemas are only known through the current world, including
+            // what its objects decay into
+            (Some(o), _) => o.clone(),
+            (None, Some(schema)) => Object::from_schema(state.id, schema, state.x, state.y),
+            (None, None) => return Err(CheckpointError::UnknownObject(state.name.clone())),
+        };
+        obj.id = state.id;
+        obj.x = state.x;
+        obj.y = state.y;
+        obj.contents = state.contents.clone();
+        obj.label = state.label.clone();
+        obj.open = state.open;
+        Ok(obj)
+    }
+
+    /// Start writing checkpoints to `path`, replacing the file.
+    ///
+    /// Writes a format header and a snapshot immediately, then logs every state change and appends a fresh
+    /// snapshot after every `every` logged changes.
+    pub fn checkpoint_to(&mut self, path: impl AsRef<Path>, every: u32) -> Result<(), CheckpointError> {
+        let mut file = File::create(path)?;
+        write_record(&mut file, &Record::Header(FormatHeader::current()))?;
+        write_record(&mut file, &Record::Snapshot(Box::new(self.state())))?;
+        self.checkpoint = Some(Checkpointer { file, every: every.max(1), since_snapshot: 0, error: None });
+        Ok(())
+    }
+
+    /// Stop checkpointing; returns the first write error that occurred while it was active
+    pub fn stop_checkpointing(&mut self) -> Result<(), CheckpointError> {
+        match self.checkpoint.take() {
+            Some(Checkpointer { error: Some(e), .. }) => Err(e),
+            Some(mut cp) => Ok(cp.file.flush()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Log a state change to the active checkpoint, if any
+    pub(crate) fn log_record(&mut self, record: Record) {
+        let Some(mut cp) = self.checkpoint.take() else { return };
+        if cp.error.is_none() {
+            cp.since_snapshot += 1;
+            let mut result = write_record(&mut cp.file, &record);
+            if result.is_ok() && cp.since_snapshot >= cp.every {
+                cp.since_snapshot = 0;
+                result = write_record(&mut cp.file, &Record::Snapshot(Box::new(self.state())));
+            }
+            cp.error = result.err();
+        }
+        self.checkpoint = Some(cp);
+    }
+
+    /// Append a snapshot to the active checkpoint, if any, and restart its autosave count
+    fn log_snapshot(&mut self) {
+        let Some(mut cp) = self.checkpoint.take() else { return };
+        if cp.error.is_none() {
+            cp.since_snapshot = 0;
+            cp.error = write_record(&mut cp.file, &Record::Snapshot(Box::new(self.state()))).err();
+        }
+        self.checkpoint = Some(cp);
+    }
+
+    /// Resume from a checkpoint file written for a simulator over the same layout.
+    ///
+    /// Restores the last complete snapshot and replays the changes logged after it;
+    /// returns the number of replayed records. Register hooks only after resuming, since
+    /// they are consulted during the replay.
+    pub fn resume_from(&mut self, path: impl AsRef<Path>) -> Result<usize, CheckpointError> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        self.resume_records(read_records(&bytes)?)
+    }
+
+    /// Restore the last snapshot among `records` and replay the records after it
+    pub fn resume_records(&mut self, records: Vec<Record>) -> Result<usize, CheckpointError> {
+        let start = records
+            .iter()
+            .rposition(|r| matches!(r, Record::Snapshot(_)))
+            .ok_or(CheckpointError::NoSnapshot)?;
+        let Record::Snapshot(state) = &records[start] else { unreachable!() };
+        self.restore(state)?;
+        // replaying must not append to a checkpoint that may be the file being read
+        let checkpoint = self.checkpoint.take();
+        let mut replayed = 0;
+        for (i, record) in records.iter().enumerate().skip(start + 1) {
+            let diverged = |error: String| CheckpointError::Diverged { record: i, error };
+            match record {
+                Record::Snapshot(_) => unreachable!("records after the last snapshot"),
+                Record::Action(action) => self.step(*action).map(|_| ()).map_err(|e| diverged(e.to_string()))?,
+                Record::AgentAction(agent, action) => {
+                    self.step_agent(*agent, *action).map(|_| ()).map_err(|e| diverged(e.to_string()))?
+                }
+                Record::Tick => {
+                    self.tick();
+                }
+                Record::Inspect(id) => self.inspect(*id).map(|_| ()).map_err(|e| diverged(e.to_string()))?,
+                Record::Label(id, label) => self.set_label(*id, label.clone()).map_err(|e| diverged(e.to_string()))?,
+                Record::Door { x, y, open } => self.use_door(*x, *y, *open).map_err(|e| diverged(e.to_string()))?,
+                Record::Header(_) => return Err(diverged("format header after the first snapshot".into())),
+            }
+            replayed += 1;
+        }
+        // the replayed changes are history, like everything before the snapshot
+        self.events.clear();
+        self.checkpoint = checkpoint;
+        Ok(replayed)
+    }
+}
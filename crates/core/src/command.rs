@@ -0,0 +1,311 @@
+use std::fmt;
+
+use crate::action::Action;
+use crate::agent::{Agent, Direction};
+use crate::cell::CellKind;
+use crate::language::labelled_name;
+use crate::object::Object;
+use crate::search::object_score;
+use crate::world::World;
+
+/// Why `parse_command` could not turn text into an action
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The text has no words
+    Empty,
+    /// No verb in the table starts the text
+    UnknownVerb(String),
+    /// The verb needs an argument the text leaves out: "direction", "object" or "container"
+    MissingArgument(&'static str),
+    /// A move or turn names no direction the parser knows
+    UnknownDirection(String),
+    /// Nothing at or next to the agent matches the reference
+    UnknownObject(String),
+    /// Several differently named things match equally well, described as the parser accepts them
+    AmbiguousObject { query: String, candidates: Vec<String> },
+}
+
+impl ParseError {
+    /// Name of the variant, as the bindings report it
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParseError::Empty => "Empty",
+            ParseError::UnknownVerb(_) => "UnknownVerb",
+            ParseError::MissingArgument(_) => "MissingArgument",
+            ParseError::UnknownDirection(_) => "UnknownDirection",
+            ParseError::UnknownObject(_) => "UnknownObject",
+            ParseError::AmbiguousObject { .. } => "AmbiguousObject",
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => f.write_str("empty command"),
+            ParseError::UnknownVerb(verb) => write!(f, "unknown verb '{}'", verb),
+            ParseError::MissingArgument(what) => write!(f, "missing {}", what),
+            ParseError::UnknownDirection(text) => write!(f, "unknown direction '{}'", text),
+            ParseError::UnknownObject(query) => write!(f, "no '{}' at or next to the agent", query),
+            ParseError::AmbiguousObject { query, candidates } => {
+                write!(f, "'{}' could be the {}", query, candidates.join(" or the "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What a verb does with the rest of the command
+#[derive(Debug, Clone, Copy)]
+enum Verb {
+    Move,
+    Turn,
+    PickUp,
+    Drop,
+    Put,
+    Open,
+    Close,
+    Run,
+    Wait,
+}
+
+/// Verb phrases; the longest one starting the command wins, so "put down" beats "put"
+const VERBS: &[(&str, Verb)] = &[
+    ("go", Verb::Move),
+    ("move", Verb::Move),
+    ("walk", Verb::Move),
+    ("step", Verb::Move),
+    ("turn", Verb::Turn),
+    ("rotate", Verb::Turn),
+    ("pick up", Verb::PickUp),
+    ("pick", Verb::PickUp),
+    ("grab", Verb::PickUp),
+    ("take", Verb::PickUp),
+    ("get", Verb::PickUp),
+    ("drop", Verb::Drop),
+    ("put down", Verb::Drop),
+    ("set down", Verb::Drop),
+    ("put", Verb::Put),
+    ("place", Verb::Put),
+    ("store", Verb::Put),
+    ("insert", Verb::Put),
+    ("open", Verb::Open),
+    ("close", Verb::Close),
+    ("shut", Verb::Close),
+    ("run", Verb::Run),
+    ("start", Verb::Run),
+    ("turn on", Verb::Run),
+    ("switch on", Verb::Run),
+    ("wait", Verb::Wait),
+    ("do nothing", Verb::Wait),
+];
+
+/// Words that carry no meaning for the parser; "it" and "this" stand for the held object,
+/// which no verb needs named
+const FILLERS: &[&str] = &["the", "a", "an", "please", "now", "then", "just", "it", "this", "that"];
+
+/// Words between a verb and the object or direction they refer to
+const LINKS: &[&str] = &["to", "of", "at", "side", "one", "your", "my", "towards", "toward"];
+
+/// Prepositions before the container in "put it in the dishwasher"
+const INTO: &[&str] = &["in", "into", "inside", "on", "onto"];
+
+/// Direction word in the agent's frame; "forward" and "back" follow its facing
+fn direction(word: &str, agent: &Agent) -> Option<Direction> {
+    Some(match word {
+        "north" | "up" | "upwards" => Direction::North,
+        "south" | "down" | "downwards" => Direction::South,
+        "east" | "right" => Direction::East,
+        "west" | "left" => Direction::West,
+        "forward" | "forwards" | "ahead" | "front" => agent.facing,
+        "back" | "backward" | "backwards" => agent.facing.left().left(),
+        _ => return None,
+    })
+}
+
+/// Parse a command such as "go north", "pick up the apple", "open the door to the east" or
+/// "put it in the dishwasher" into an action for an agent in `world`.
+///
+/// Verbs come from a table matched on whole words. Objects are looked up at and next to the
+/// agent with `search::fuzzy_score` against schema names and labels: the best score of at
+/// least 0.5 wins, a direction word ("the drawer to the left", "the door ahead") narrows the
+/// search to one cell, and equally good matches with the same name resolve to the one under
+/// the agent, then the lowest id. Opening an open door or container gives `Action::Noop`.
+/// The action is not checked against the simulator; stepping it reports why it fails.
+pub fn parse_command(text: &str, world: &World, agent: &Agent) -> Result<Action, ParseError> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !FILLERS.contains(w))
+        .collect();
+    let Some(first) = words.first() else {
+        return Err(ParseError::Empty);
+    };
+    let (phrase, verb) = VERBS
+        .iter()
+        .filter(|(phrase, _)| phrase.split(' ').enumerate().all(|(i, w)| words.get(i) == Some(&w)))
+        .max_by_key(|(phrase, _)| phrase.len())
+        .ok_or_else(|| ParseError::UnknownVerb(first.to_string()))?;
+    let args = &words[phrase.split(' ').count()..];
+    match verb {
+        Verb::Move => {
+            let args: Vec<&str> = args.iter().copied().filter(|w| !LINKS.contains(w)).collect();
+            let Some(word) = args.first() else {
+                return Err(ParseError::MissingArgument("direction"));
+            };
+            match (*word, direction(word, agent)) {
+                ("forward" | "forwards" | "ahead", _) => Ok(Action::MoveForward),
+                (_, Some(dir)) => Ok(move_toward(dir)),
+                _ => Err(ParseError::UnknownDirection(args.join(" "))),
+            }
+        }
+        Verb::Turn => match args {
+            ["left"] => Ok(Action::TurnLeft),
+            ["right"] => Ok(Action::TurnRight),
+            [] => Err(ParseError::MissingArgument("direction")),
+            _ => Err(ParseError::UnknownDirection(args.join(" "))),
+        },
+        Verb::PickUp => {
+            // "pick the apple up"
+            let args = args.strip_suffix(&["up"]).unwrap_or(args);
+            if args.is_empty() {
+                return Ok(Action::PickUp);
+            }
+            resolve_object(world, agent, args, |o| o.pickable).map(|o| Action::PickUpById(o.id))
+        }
+        Verb::Drop | Verb::Put => match args.iter().position(|w| INTO.contains(w)) {
+            Some(at) => {
+                let target = &args[at + 1..];
+                if target.is_empty() {
+                    return Err(ParseError::MissingArgument("container"));
+                }
+                resolve_object(world, agent, target, |o| o.has_room()).map(|o| Action::PlaceInto(o.id))
+            }
+            None if matches!(verb, Verb::Drop) || args.contains(&"down") => Ok(Action::Drop),
+            None if args.contains(&"away") => Ok(Action::AutoPlace),
+            None => Err(ParseError::MissingArgument("container")),
+        },
+        Verb::Open | Verb::Close => {
+            let open = matches!(verb, Verb::Open);
+            if args.iter().any(|w| matches!(*w, "door" | "doorway")) {
+                return door_action(world, agent, args, open);
+            }
+            let obj = resolve_object(world, agent, args, |o| o.capacity > 0)?;
+            Ok(if open && obj.open { Action::Noop } else { Action::ToggleContainer(obj.id) })
+        }
+        Verb::Run => resolve_object(world, agent, args, |_| true).map(|o| Action::RunAppliance(o.id)),
+        Verb::Wait => Ok(Action::Noop),
+    }
+}
+
+/// The absolute move in a direction
+fn move_toward(dir: Direction) -> Action {
+    match dir {
+        Direction::North => Action::MoveUp,
+        Direction::South => Action::MoveDown,
+        Direction::West => Action::MoveLeft,
+        Direction::East => Action::MoveRight,
+    }
+}
+
+/// Where a reference points: "here" is the agent's cell, a direction the neighbour that way.
+/// Returns the cell offset, if any, and the remaining name words
+fn split_reference<'a>(words: &[&'a str], agent: &Agent) -> (Option<(isize, isize)>, Vec<&'a str>) {
+    let mut offset = None;
+    let mut name = Vec::new();
+    for &word in words {
+        if word == "here" {
+            offset = Some((0, 0));
+        } else if let Some(dir) = direction(word, agent) {
+            offset = Some(dir.offset());
+        } else if !LINKS.contains(&word) {
+            name.push(word);
+        }
+    }
+    (offset, name)
+}
+
+/// How a candidate at offset (dx, dy) from the agent reads in an `AmbiguousObject` list
+fn where_phrase(dx: isize, dy: isize) -> String {
+    match Direction::from_offset(dx, dy) {
+        Some(dir) => format!("to the {}", dir.name()),
+        None => "here".into(),
+    }
+}
+
+/// Best fuzzy match for the reference among objects at or next to the agent; `prefer` breaks
+/// ties between differently named matches when it singles out one name
+fn resolve_object<'a>(
+    world: &'a World,
+    agent: &Agent,
+    words: &[&str],
+    prefer: impl Fn(&Object) -> bool,
+) -> Result<&'a Object, ParseError> {
+    let (offset, name) = split_reference(words, agent);
+    if name.is_empty() {
+        return Err(ParseError::MissingArgument("object"));
+    }
+    let query = name.join(" ");
+    let offset_of = |o: &Object| (o.x as isize - agent.x as isize, o.y as isize - agent.y as isize);
+    let scored: Vec<(&Object, f32)> = world
+        .objects
+        .iter()
+        .filter(|o| {
+            let (dx, dy) = offset_of(o);
+            offset.map_or(dx.abs() + dy.abs() <= 1, |off| off == (dx, dy))
+        })
+        .map(|o| (o, object_score(&query, o)))
+        .collect();
+    let best = scored.iter().map(|&(_, s)| s).fold(0.0, f32::max);
+    if best < 0.5 {
+        return Err(ParseError::UnknownObject(query));
+    }
+    let mut matches: Vec<&Object> = scored.iter().filter(|&&(_, s)| s == best).map(|&(o, _)| o).collect();
+    if matches.iter().any(|o| prefer(o)) {
+        matches.retain(|o| prefer(o));
+    }
+    let same = |a: &Object, b: &Object| a.name == b.name && a.label == b.label;
+    if matches.iter().any(|o| !same(o, matches[0])) {
+        let candidates = matches
+            .iter()
+            .map(|o| {
+                let (dx, dy) = offset_of(o);
+                format!("{} {}", labelled_name(o), where_phrase(dx, dy))
+            })
+            .collect();
+        return Err(ParseError::AmbiguousObject { query, candidates });
+    }
+    Ok(matches.into_iter().min_by_key(|o| (offset_of(o) != (0, 0), o.id)).unwrap())
+}
+
+/// `Interact` toward the neighbouring door to open or close; `Noop` when every door in reach
+/// already is that way
+fn door_action(world: &World, agent: &Agent, words: &[&str], open: bool) -> Result<Action, ParseError> {
+    let (offset, _) = split_reference(words, agent);
+    let layout = &world.layout;
+    let doors: Vec<((isize, isize), CellKind)> = [Direction::North, Direction::East, Direction::South, Direction::West]
+        .into_iter()
+        .map(Direction::offset)
+        .filter(|&off| offset.is_none_or(|o| o == off))
+        .filter_map(|(dx, dy)| {
+            let x = agent.x.checked_add_signed(dx)?;
+            let y = agent.y.checked_add_signed(dy)?;
+            let kind = layout.kind(x, y)?;
+            matches!(kind, CellKind::ClosedDoor | CellKind::OpenDoor | CellKind::LockedDoor).then_some(((dx, dy), kind))
+        })
+        .collect();
+    if doors.is_empty() {
+        return Err(ParseError::UnknownObject("door".into()));
+    }
+    let to_change: Vec<(isize, isize)> =
+        doors.iter().filter(|(_, kind)| (*kind == CellKind::OpenDoor) != open).map(|&(off, _)| off).collect();
+    match to_change.as_slice() {
+        [] => Ok(Action::Noop),
+        [(dx, dy)] => Ok(Action::Interact { dx: *dx as i8, dy: *dy as i8 }),
+        _ => Err(ParseError::AmbiguousObject {
+            query: "door".into(),
+            candidates: to_change.iter().map(|&(dx, dy)| format!("door {}", where_phrase(dx, dy))).collect(),
+        }),
+    }
+}
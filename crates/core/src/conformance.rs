@@ -0,0 +1,380 @@
+//! Conformance spec shared by the core crate and the language bindings.
+//!
+//! The spec lists the simulator operations every binding must expose, under the name each
+//! binding uses, and scripted cases: a tutorial scenario plus a sequence of calls with the
+//! outcome, agent cell and held object expected after each one. Tutorial worlds are drawn
+//! by hand, so the expectations do not move when the generator changes.
+//!
+//! `run` plays the spec through a `Driver`; `Simulator` is the driver for the core crate.
+//! The bindings export the spec as JSON (`conformance_spec()` in Python,
+//! `conformance_spec_json()` in WASM) and their drivers (`crates/ffi_py/conformance.py`,
+//! `crates/wasm/conformance.mjs`) call each operation by its binding name. A missing
+//! operation or a different result fails with the step and operation name.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::object::ObjectId;
+use crate::scenarios;
+use crate::sim::Simulator;
+
+/// How a driver turns an operation's return value into an `Outcome`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Returns {
+    /// `Ok` on success, whatever the value
+    Nothing,
+    Bool,
+    /// An object or nothing, as `Id`
+    Object,
+    /// A list of objects or ids, as `Ids` in the order returned
+    Objects,
+    Number,
+}
+
+/// A simulator operation and its name in each binding
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Operation {
+    /// Name in the core crate and in the spec's steps
+    pub name: &'static str,
+    /// Integer parameters, in call order
+    pub params: &'static [&'static str],
+    pub returns: Returns,
+    /// Method or property name on `PySimulator`
+    pub python: &'static str,
+    /// Method or getter name on `ApartmentSimulator`
+    pub wasm: &'static str,
+}
+
+/// Observable result of one call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "value")]
+pub enum Outcome {
+    Ok,
+    /// The call raised, threw or returned an error; messages are not compared
+    Failed,
+    Bool(bool),
+    Id(Option<ObjectId>),
+    Ids(Vec<ObjectId>),
+    Number(u32),
+}
+
+/// One scripted call and the state expected after it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Step {
+    pub op: &'static str,
+    pub args: Vec<i64>,
+    pub expect: Outcome,
+    pub agent: (usize, usize),
+    pub holding: Option<ObjectId>,
+}
+
+/// Steps played on a fresh simulator for a tutorial scenario
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Case {
+    /// Name of the `scenarios::tutorial` scenario to start from
+    pub scenario: &'static str,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Spec {
+    pub operations: Vec<Operation>,
+    pub cases: Vec<Case>,
+}
+
+/// Runs operations on a simulator under test
+pub trait Driver {
+    /// Call an operation and normalize its result; `Err` when the operation is missing
+    fn call(&mut self, op: &Operation, args: &[i64]) -> Result<Outcome, String>;
+    fn agent(&self) -> (usize, usize);
+    fn holding(&self) -> Option<ObjectId>;
+}
+
+/// A step whose result differed from the spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub scenario: &'static str,
+    /// Index of the step within its case
+    pub step: usize,
+    pub op: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} step {} ({}): {}", self.scenario, self.step, self.op, self.message)
+    }
+}
+
+fn op(name: &'static str, params: &'static [&'static str], returns: Returns) -> Operation {
+    Operation { name, params, returns, python: name, wasm: name }
+}
+
+fn step(op: &'static str, args: &[i64], expect: Outcome, agent: (usize, usize), holding: Option<ObjectId>) -> Step {
+    Step { op, args: args.to_vec(), expect, agent, holding }
+}
+
+/// The operations and cases every binding must pass
+pub fn spec() -> Spec {
+    use Outcome::*;
+    let operations = vec![
+        Operation { wasm: "up", ..op("move_up", &[], Returns::Nothing) },
+        Operation { wasm: "down", ..op("move_down", &[], Returns::Nothing) },
+        Operation { wasm: "left", ..op("move_left", &[], Returns::Nothing) },
+        Operation { wasm: "right", ..op("move_right", &[], Returns::Nothing) },
+        op("turn_left", &[], Returns::Nothing),
+        op("turn_right", &[], Returns::Nothing),
+        op("move_forward", &[], Returns::Nothing),
+        op("interact_ahead", &[], Returns::Nothing),
+        op("interact", &["dx", "dy"], Returns::Nothing),
+        op("pick_up", &[], Returns::Nothing),
+        op("pick_up_by_id", &["object_id"], Returns::Nothing),
+        op("drop", &[], Returns::Nothing),
+        op("place_into", &["container_id"], Returns::Nothing),
+        op("auto_place", &[], Returns::Nothing),
+        op("toggle_container", &["container_id"], Returns::Nothing),
+        op("run_appliance", &["appliance_id"], Returns::Nothing),
+        op("go_to_object", &["object_id"], Returns::Nothing),
+        op("go_to_room", &["room"], Returns::Nothing),
+        op("inspect", &["object_id"], Returns::Nothing),
+        op("reset", &[], Returns::Nothing),
+        op("is_target_known", &["object_id"], Returns::Bool),
+        op("check_placement", &["object_id"], Returns::Bool),
+        op("get_holding", &[], Returns::Object),
+        op("get_object_by_id", &["object_id"], Returns::Object),
+        op("get_objects_at", &["x", "y"], Returns::Objects),
+        op("get_contents", &["container_id"], Returns::Objects),
+        op("steps", &[], Returns::Number),
+        op("time", &[], Returns::Number),
+        op("distance_to", &["x", "y"], Returns::Number),
+    ];
+    let cases = vec![
+        // FruitBowl 0 at (6, 1), Apple 1 at (3, 3)
+        Case {
+            scenario: "first_apple",
+            steps: vec![
+                step("drop", &[], Failed, (1, 4), None),
+                step("move_left", &[], Failed, (1, 4), None),
+                step("move_right", &[], Ok, (2, 4), None),
+                step("move_right", &[], Ok, (3, 4), None),
+                step("move_up", &[], Ok, (3, 3), None),
+                step("get_objects_at", &[3, 3], Ids(vec![1]), (3, 3), None),
+                step("check_placement", &[1], Bool(false), (3, 3), None),
+                step("pick_up_by_id", &[0], Failed, (3, 3), None),
+                step("pick_up", &[], Ok, (3, 3), Some(1)),
+                step("get_holding", &[], Id(Some(1)), (3, 3), Some(1)),
+                step("get_objects_at", &[3, 3], Ids(vec![]), (3, 3), Some(1)),
+                step("pick_up", &[], Failed, (3, 3), Some(1)),
+                step("drop", &[], Ok, (3, 3), None),
+                step("pick_up_by_id", &[1], Ok, (3, 3), Some(1)),
+                // the bowl is out of reach from here
+                step("place_into", &[0], Failed, (3, 3), Some(1)),
+                step("go_to_object", &[0], Ok, (6, 1), Some(1)),
+                // a bowl has no lid
+                step("toggle_container", &[0], Failed, (6, 1), Some(1)),
+                step("place_into", &[0], Ok, (6, 1), None),
+                step("get_contents", &[0], Ids(vec![1]), (6, 1), None),
+                step("check_placement", &[1], Bool(true), (6, 1), None),
+                step("get_holding", &[], Id(None), (6, 1), None),
+                step("steps", &[], Number(12), (6, 1), None),
+                step("is_target_known", &[1], Bool(false), (6, 1), None),
+                step("inspect", &[1], Ok, (6, 1), None),
+                step("is_target_known", &[1], Bool(true), (6, 1), None),
+                // taking it out again must also clear the bowl's contents
+                step("pick_up", &[], Ok, (6, 1), Some(1)),
+                step("get_contents", &[0], Ids(vec![]), (6, 1), Some(1)),
+                // and it can be put back from the next cell
+                step("move_down", &[], Ok, (6, 2), Some(1)),
+                step("place_into", &[0], Ok, (6, 2), None),
+                step("get_contents", &[0], Ids(vec![1]), (6, 2), None),
+            ],
+        },
+        // closed door at (6, 3) and (6, 4) between two rooms, Apple 1 at (10, 2)
+        Case {
+            scenario: "closed_door",
+            steps: vec![
+                // the only route to the apple runs through the door
+                step("distance_to", &[10, 2], Number(9), (3, 4), None),
+                step("move_right", &[], Ok, (4, 4), None),
+                step("move_right", &[], Ok, (5, 4), None),
+                step("move_right", &[], Failed, (5, 4), None),
+                step("interact", &[1, 0], Ok, (5, 4), None),
+                step("move_right", &[], Ok, (6, 4), None),
+                step("move_right", &[], Ok, (7, 4), None),
+                step("interact", &[-1, 0], Ok, (7, 4), None),
+                step("move_left", &[], Failed, (7, 4), None),
+                step("auto_place", &[], Failed, (7, 4), None),
+                step("go_to_object", &[1], Ok, (10, 2), None),
+                step("pick_up", &[], Ok, (10, 2), Some(1)),
+                step("go_to_room", &[0], Ok, (5, 3), Some(1)),
+                step("time", &[], Number(19), (5, 3), Some(1)),
+            ],
+        },
+        // the same door with egocentric actions; the agent starts facing north
+        Case {
+            scenario: "closed_door",
+            steps: vec![
+                step("turn_right", &[], Ok, (3, 4), None),
+                step("move_forward", &[], Ok, (4, 4), None),
+                step("move_forward", &[], Ok, (5, 4), None),
+                step("move_forward", &[], Failed, (5, 4), None),
+                step("interact_ahead", &[], Ok, (5, 4), None),
+                step("move_forward", &[], Ok, (6, 4), None),
+                // four quarter turns face east again
+                step("turn_right", &[], Ok, (6, 4), None),
+                step("turn_right", &[], Ok, (6, 4), None),
+                step("turn_right", &[], Ok, (6, 4), None),
+                step("turn_right", &[], Ok, (6, 4), None),
+                step("move_forward", &[], Ok, (7, 4), None),
+                step("turn_left", &[], Ok, (7, 4), None),
+                step("turn_left", &[], Ok, (7, 4), None),
+                step("interact_ahead", &[], Ok, (7, 4), None),
+                step("move_forward", &[], Failed, (7, 4), None),
+                step("steps", &[], Number(13), (7, 4), None),
+            ],
+        },
+        // Dishwasher 0 at (1, 4), DiningTable 1 at (10, 2) holding DirtyPlate 2
+        Case {
+            scenario: "dishwasher",
+            steps: vec![
+                // a table is no appliance, and the dishwasher is out of reach
+                step("run_appliance", &[1], Failed, (3, 2), None),
+                step("run_appliance", &[0], Failed, (3, 2), None),
+                step("go_to_object", &[2], Ok, (10, 2), None),
+                step("pick_up_by_id", &[2], Ok, (10, 2), Some(2)),
+                step("go_to_object", &[0], Ok, (1, 4), Some(2)),
+                step("place_into", &[0], Ok, (1, 4), None),
+                step("check_placement", &[2], Bool(true), (1, 4), None),
+                // the clean plate belongs in a cupboard
+                step("run_appliance", &[0], Ok, (1, 4), None),
+                step("check_placement", &[2], Bool(false), (1, 4), None),
+                step("get_contents", &[0], Ids(vec![2]), (1, 4), None),
+            ],
+        },
+        // TrashCan 0 at (1, 1), RottenBanana 1 at (6, 3)
+        Case {
+            scenario: "rotten_fruit",
+            steps: vec![
+                step("go_to_object", &[1], Ok, (6, 3), None),
+                step("pick_up", &[], Ok, (6, 3), Some(1)),
+                step("go_to_object", &[0], Ok, (1, 1), Some(1)),
+                step("place_into", &[0], Ok, (1, 1), None),
+                // thrown away: gone from the world and taking no room in the can
+                step("get_contents", &[0], Ids(vec![]), (1, 1), None),
+                step("get_object_by_id", &[1], Id(None), (1, 1), None),
+                step("get_objects_at", &[1, 1], Ids(vec![0]), (1, 1), None),
+                step("check_placement", &[1], Bool(true), (1, 1), None),
+                // resetting brings the banana back where the episode started
+                step("reset", &[], Ok, (4, 2), None),
+                step("get_object_by_id", &[1], Id(Some(1)), (4, 2), None),
+                step("get_objects_at", &[6, 3], Ids(vec![1]), (4, 2), None),
+                step("steps", &[], Number(0), (4, 2), None),
+            ],
+        },
+    ];
+    Spec { operations, cases }
+}
+
+/// Play every case of `spec` through the drivers `start` creates for each scenario
+pub fn run<D: Driver>(spec: &Spec, mut start: impl FnMut(&str) -> Result<D, String>) -> Vec<Failure> {
+    let mut failures = Vec::new();
+    for case in &spec.cases {
+        let mut driver = match start(case.scenario) {
+            Ok(driver) => driver,
+            Err(message) => {
+                failures.push(Failure { scenario: case.scenario, step: 0, op: "start", message });
+                continue;
+            }
+        };
+        for (i, step) in case.steps.iter().enumerate() {
+            let fail = |message| Failure { scenario: case.scenario, step: i, op: step.op, message };
+            let Some(op) = spec.operations.iter().find(|o| o.name == step.op) else {
+                failures.push(fail("not in the spec's operations".into()));
+                break;
+            };
+            let outcome = match driver.call(op, &step.args) {
+                Ok(outcome) => outcome,
+                Err(message) => {
+                    failures.push(fail(message));
+                    break;
+                }
+            };
+            let found = (outcome, driver.agent(), driver.holding());
+            let expected = (step.expect.clone(), step.agent, step.holding);
+            if found != expected {
+                failures.push(fail(format!("expected {:?}, got {:?}", expected, found)));
+                // later steps build on this one
+                break;
+            }
+        }
+    }
+    failures
+}
+
+/// Run the spec against the core simulator
+pub fn run_core(spec: &Spec) -> Vec<Failure> {
+    run(spec, |name| {
+        scenarios::tutorial()
+            .into_iter()
+            .find(|sc| sc.name == name)
+            .map(|sc| sc.simulator())
+            .ok_or_else(|| format!("unknown tutorial scenario '{}'", name))
+    })
+}
+
+impl Driver for Simulator {
+    fn call(&mut self, op: &Operation, args: &[i64]) -> Result<Outcome, String> {
+        let arg = |i: usize| args.get(i).copied().ok_or_else(|| format!("missing argument '{}'", op.params[i]));
+        let id = |i: usize| arg(i).map(|a| a as usize);
+        let done = |ok: bool| if ok { Outcome::Ok } else { Outcome::Failed };
+        Ok(match op.name {
+            "move_up" => done(self.up().is_ok()),
+            "move_down" => done(self.down().is_ok()),
+            "move_left" => done(self.left().is_ok()),
+            "move_right" => done(self.right().is_ok()),
+            "turn_left" => done(self.turn_left().is_ok()),
+            "turn_right" => done(self.turn_right().is_ok()),
+            "move_forward" => done(self.move_forward().is_ok()),
+            "interact_ahead" => done(self.interact_ahead().is_ok()),
+            "interact" => done(self.interact(arg(0)? as isize, arg(1)? as isize).is_ok()),
+            "pick_up" => done(self.pick_up().is_ok()),
+            "pick_up_by_id" => done(self.pick_up_by_id(id(0)?).is_ok()),
+            "drop" => done(self.drop().is_ok()),
+            "place_into" => done(self.place_into(id(0)?).is_ok()),
+            "auto_place" => done(self.auto_place().is_ok()),
+            "toggle_container" => done(self.toggle_container(id(0)?).is_ok()),
+            "run_appliance" => done(self.run_appliance(id(0)?).is_ok()),
+            "go_to_object" => done(self.go_to_object(id(0)?).is_ok()),
+            "go_to_room" => done(self.go_to_room(id(0)?).is_ok()),
+            "inspect" => done(self.inspect(id(0)?).is_ok()),
+            "reset" => done(self.reset().is_ok()),
+            "is_target_known" => Outcome::Bool(self.is_target_known(id(0)?)),
+            "check_placement" => {
+                let id = id(0)?;
+                Outcome::Bool(self.world.is_in_place(id))
+            }
+            "get_holding" => Outcome::Id(self.holding.as_ref().map(|o| o.id)),
+            "get_object_by_id" => Outcome::Id(self.world.object(id(0)?).map(|o| o.id)),
+            "get_objects_at" => Outcome::Ids(self.world.objects_at(id(0)?, id(1)?).iter().map(|o| o.id).collect()),
+            "get_contents" => {
+                let id = id(0)?;
+                let open = self.world.object(id).filter(|o| self.is_open(o));
+                Outcome::Ids(open.map_or(Vec::new(), |o| o.contents.clone()))
+            }
+            "steps" => Outcome::Number(self.steps),
+            "time" => Outcome::Number(self.time),
+            "distance_to" => self.distance_to(id(0)?, id(1)?).map_or(Outcome::Failed, |d| Outcome::Number(d as u32)),
+            other => return Err(format!("core driver does not implement '{}'", other)),
+        })
+    }
+
+    fn agent(&self) -> (usize, usize) {
+        (self.agent.x, self.agent.y)
+    }
+
+    fn holding(&self) -> Option<ObjectId> {
+        self.holding.as_ref().map(|o| o.id)
+    }
+}
@@ -0,0 +1,74 @@
+//! Coordinate conventions.
+//!
+//! Every flat grid in this crate (`Layout::cells`, `Layout::zones`, `WorldView::cells`)
+//! is row-major: the cell at column `x`, row `y` lives at index `y * width + x`. This is
+//! the layout numpy expects for an array of shape `(height, width)`, indexed `[y, x]`.
+
+use crate::world::{Cell, Layout};
+
+/// A grid position; `x` is the column, `y` the row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub fn new(x: usize, y: usize) -> Self {
+        Coord { x, y }
+    }
+
+    /// Row-major index of this position in a grid of the given width
+    pub fn index(self, width: usize) -> usize {
+        flat_index(self.x, self.y, width)
+    }
+
+    /// Position of a row-major index in a grid of the given width
+    pub fn from_index(i: usize, width: usize) -> Self {
+        coords_from_index(i, width)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Self {
+        Coord { x, y }
+    }
+}
+
+/// Row-major index of column `x`, row `y`
+pub fn flat_index(x: usize, y: usize, width: usize) -> usize {
+    y * width + x
+}
+
+/// Column and row of a row-major index
+pub fn coords_from_index(i: usize, width: usize) -> Coord {
+    Coord { x: i % width, y: i / width }
+}
+
+/// Convert a row-major grid into column-major (x-major) order, where column `x`,
+/// row `y` lives at index `x * height + y`
+pub fn transpose_cells<T: Copy>(cells: &[T], width: usize, height: usize) -> Vec<T> {
+    assert_eq!(cells.len(), width * height, "grid length does not match dimensions");
+    let mut out = Vec::with_capacity(cells.len());
+    for x in 0..width {
+        for y in 0..height {
+            out.push(cells[y * width + x]);
+        }
+    }
+    out
+}
+
+impl Layout {
+    /// Cell at a position
+    pub fn cell(&self, at: Coord) -> Cell {
+        self.cells[at.index(self.width)]
+    }
+
+    /// Shape and data for building a numpy-style array: `((height, width), cells)`.
+    ///
+    /// The data is the row-major `cells` buffer unchanged, so `np.array(data).reshape(shape)`
+    /// is indexed as `[y, x]`.
+    pub fn to_ndarray_order(&self) -> ((usize, usize), &[Cell]) {
+        ((self.height, self.width), &self.cells)
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::gen::{generate_with_report, GenOpts, GenReport, World};
+use crate::nav;
+use crate::object::ObjectConstraint;
+use crate::cell::CellKind;
+
+/// Estimated steps at which the difficulty score reaches `1 - 1/e`
+const DIFFICULTY_SCALE: f32 = 400.0;
+
+/// Measurable proxies for how hard a world is to tidy
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DifficultyScore {
+    /// Pickable objects not in their target placement
+    pub misplaced: usize,
+    /// Mean walking distance from a misplaced object to its nearest valid target
+    pub mean_distance: f32,
+    /// Closed doors crossed on those shortest routes, summed over objects
+    pub closed_doors: usize,
+    /// Misplaced objects that can only be tidied by putting them into a container
+    pub container_targets: usize,
+    /// Misplaced objects with no reachable target
+    pub unreachable: usize,
+    /// Rough oracle episode length: route lengths plus door, pick-up and drop actions
+    pub estimated_steps: usize,
+    /// `estimated_steps` squashed into [0, 1)
+    pub score: f32,
+}
+
+/// Estimate the difficulty of tidying a world from its initial state
+pub fn estimate(world: &World) -> DifficultyScore {
+    let layout = &world.layout;
+    let w = layout.width;
+    let mut out = DifficultyScore::default();
+    let mut total_distance = 0;
+    // objects of one schema share a target, so cache the distance field per name
+    let mut fields: HashMap<&str, Vec<Option<usize>>> = HashMap::new();
+    let (mut goals, mut queue) = (Vec::new(), VecDeque::new());
+    for o in world.objects.iter().filter(|o| o.pickable) {
+        let Some(target) = o.target() else { continue };
+        if target.check(world, o.x, o.y) {
+            continue;
+        }
+        out.misplaced += 1;
+        if requires_container(target) {
+            out.container_targets += 1;
+        }
+        let field = fields.entry(&o.name).or_insert_with(|| {
+            goals.clear();
+            goals.extend((0..layout.cells.len()).filter(|&i| layout.cells[i] >= 0 && target.check(world, i % w, i / w)));
+            let mut dist = Vec::new();
+            nav::distance_field_into(layout, &goals, &mut dist, &mut queue);
+            dist
+        });
+        let start = o.y * w + o.x;
+        let Some(d) = field[start] else {
+            out.unreachable += 1;
+            continue;
+        };
+        total_distance += d;
+        // walk down the distance field to count closed doors on one shortest route
+        let (mut i, mut doors) = (start, 0);
+        while let Some(next) = neighbors(i, w, layout.height).find(|&n| field[n].is_some_and(|nd| nd + 1 == field[i].unwrap())) {
+            let closed = |j: usize| CellKind::from_i8(layout.cells[j]) == CellKind::ClosedDoor;
+            if closed(next) && !closed(i) {
+                doors += 1;
+            }
+            i = next;
+        }
+        out.closed_doors += doors;
+        out.estimated_steps += d + doors + 2;
+    }
+    let reachable = out.misplaced - out.unreachable;
+    if reachable > 0 {
+        out.mean_distance = total_distance as f32 / reachable as f32;
+    }
+    out.score = 1.0 - (-(out.estimated_steps as f32) / DIFFICULTY_SCALE).exp();
+    out
+}
+
+fn neighbors(i: usize, w: usize, h: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (i % w, i / w);
+    [
+        (x > 0).then(|| i - 1),
+        (x + 1 < w).then(|| i + 1),
+        (y > 0).then(|| i - w),
+        (y + 1 < h).then(|| i + w),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Whether every way of satisfying the constraint puts the object inside a container
+fn requires_container(c: &ObjectConstraint) -> bool {
+    match c {
+        ObjectConstraint::InsideOf(_) => true,
+        ObjectConstraint::And(cs) => cs.iter().any(requires_container),
+        ObjectConstraint::Or(cs) => !cs.is_empty() && cs.iter().all(requires_container),
+        _ => false,
+    }
+}
+
+/// Searches seeds for worlds whose difficulty score falls in a band
+#[derive(Debug, Clone)]
+pub struct CurriculumSampler {
+    template: GenOpts,
+    range: (f32, f32),
+    next_seed: u64,
+    /// Seeds tried per `sample` call before giving up
+    pub max_attempts: usize,
+}
+
+impl CurriculumSampler {
+    /// Sample worlds generated from `template` with a score in `[range.0, range.1]`,
+    /// trying seeds upwards from `template.seed`
+    pub fn new(template: GenOpts, range: (f32, f32)) -> Self {
+        CurriculumSampler { next_seed: template.seed, template, range, max_attempts: 1000 }
+    }
+
+    /// Generate worlds until one falls in the band; returns its seed, world and report.
+    ///
+    /// Consecutive calls continue from the last seed tried, so they never return the same world twice.
+    pub fn sample(&mut self) -> Option<(u64, World, GenReport)> {
+        for _ in 0..self.max_attempts {
+            let seed = self.next_seed;
+            self.next_seed = self.next_seed.wrapping_add(1);
+            let Ok((world, report)) = generate_with_report(&GenOpts { seed, ..self.template.clone() }) else {
+                continue;
+            };
+            if (self.range.0..=self.range.1).contains(&report.difficulty.score) {
+                return Some((seed, world, report));
+            }
+        }
+        None
+    }
+}
+
+impl Iterator for CurriculumSampler {
+    type Item = (u64, World, GenReport);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sample()
+    }
+}
@@ -0,0 +1,133 @@
+//! Episode export: an action sequence played on a simulator, as an animated PNG or GIF.
+//!
+//! Frames are `render_rgba` images of the simulator after every `frame_skip`-th action,
+//! between the starting state and the final one. APNGs use the stored-block PNG encoder
+//! `render_png` uses and keep every color; GIFs are LZW-compressed and much smaller, with
+//! one shared palette of 256 colors. Files grow with the frame count, so long episodes want
+//! a larger `frame_skip` or a `max_frames` cap.
+
+use std::path::Path;
+
+use crate::action::Action;
+use crate::render::{self, RenderOpts, Theme};
+use crate::sim::Simulator;
+
+/// File format of `render_episode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EpisodeFormat {
+    #[default]
+    Apng,
+    Gif,
+}
+
+impl EpisodeFormat {
+    /// Format for a file name: `.gif` gives `Gif`, anything else `Apng`
+    pub fn from_path(path: &Path) -> EpisodeFormat {
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("gif") => EpisodeFormat::Gif,
+            _ => EpisodeFormat::Apng,
+        }
+    }
+}
+
+/// How `render_episode` draws and paces an episode
+#[derive(Debug, Clone)]
+pub struct EpisodeRenderOpts {
+    pub format: EpisodeFormat,
+    pub theme: Theme,
+    /// Pixels per cell
+    pub scale: usize,
+    pub render: RenderOpts,
+    /// Draw a frame after every this many actions; 0 counts as 1
+    pub frame_skip: usize,
+    /// Most frames to keep, raising `frame_skip` as needed; at least 2 (first and last)
+    pub max_frames: Option<usize>,
+    /// How long each frame shows
+    pub frame_delay_ms: u16,
+    /// Mark the cells the agent has walked through with a small dot in the agent color
+    pub trail: bool,
+}
+
+impl Default for EpisodeRenderOpts {
+    fn default() -> Self {
+        EpisodeRenderOpts {
+            format: EpisodeFormat::default(),
+            theme: Theme::default(),
+            scale: 8,
+            render: RenderOpts::default(),
+            frame_skip: 1,
+            max_frames: None,
+            frame_delay_ms: 100,
+            trail: false,
+        }
+    }
+}
+
+impl EpisodeRenderOpts {
+    /// Actions between frames for an episode of `actions` actions
+    fn stride(&self, actions: usize) -> usize {
+        let skip = self.frame_skip.max(1);
+        match self.max_frames {
+            Some(max) => skip.max(actions.div_ceil(max.max(2) - 1)),
+            None => skip,
+        }
+    }
+}
+
+/// RGBA frames of `actions` played on `sim`: the starting state, the state after every
+/// `frame_skip`-th action and the final state, each once.
+///
+/// `sim` is stepped as by `Simulator::step`; failed actions leave the state as it was and
+/// the episode goes on. Build `sim` fresh from a seed or a world to render a whole episode.
+pub fn episode_frames(sim: &mut Simulator, actions: &[Action], opts: &EpisodeRenderOpts) -> Vec<Vec<u8>> {
+    let stride = opts.stride(actions.len());
+    let mut trail = vec![(sim.agent.x, sim.agent.y)];
+    let mut frames = vec![frame(sim, &trail, opts)];
+    for (i, &action) in actions.iter().enumerate() {
+        let _ = sim.step(action);
+        if opts.trail && trail.last() != Some(&(sim.agent.x, sim.agent.y)) {
+            trail.push((sim.agent.x, sim.agent.y));
+        }
+        if (i + 1) % stride == 0 || i + 1 == actions.len() {
+            frames.push(frame(sim, &trail, opts));
+        }
+    }
+    frames
+}
+
+/// Animated PNG or GIF, per `opts.format`, of `episode_frames`, looping forever
+pub fn render_episode(sim: &mut Simulator, actions: &[Action], opts: &EpisodeRenderOpts) -> Vec<u8> {
+    let frames = episode_frames(sim, actions, opts);
+    let layout = &sim.world.layout;
+    let scale = opts.scale.max(1);
+    let (width, height) = (layout.width * scale, layout.height * scale);
+    match opts.format {
+        EpisodeFormat::Apng => render::encode_apng(width, height, &frames, opts.frame_delay_ms),
+        EpisodeFormat::Gif => render::encode_gif(width, height, &frames, opts.frame_delay_ms),
+    }
+}
+
+/// `render_episode` written to `path`, conventionally a `.png`, `.apng` or `.gif` file to
+/// match `opts.format` (see `EpisodeFormat::from_path`)
+pub fn save_episode(sim: &mut Simulator, actions: &[Action], opts: &EpisodeRenderOpts, path: &Path) -> std::io::Result<()> {
+    std::fs::write(path, render_episode(sim, actions, opts))
+}
+
+/// `render_rgba` of the current state with the trail cells behind the agent dotted
+fn frame(sim: &Simulator, trail: &[(usize, usize)], opts: &EpisodeRenderOpts) -> Vec<u8> {
+    let scale = opts.scale.max(1);
+    let mut pixels = render::render_rgba(sim, &opts.theme, scale, opts.render);
+    if !opts.trail {
+        return pixels;
+    }
+    let width = sim.world.layout.width * scale;
+    let margin = scale * 3 / 8;
+    for &(x, y) in trail.iter().filter(|&&cell| cell != (sim.agent.x, sim.agent.y)) {
+        for py in y * scale + margin..(y + 1) * scale - margin {
+            for px in x * scale + margin..(x + 1) * scale - margin {
+                pixels[(py * width + px) * 4..][..3].copy_from_slice(&opts.theme.agent.color);
+            }
+        }
+    }
+    pixels
+}
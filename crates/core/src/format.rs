@@ -0,0 +1,167 @@
+//! Version headers for serialized artifacts.
+//!
+//! World JSON/msgpack, `SimState` snapshots, checkpoint files and scenario lists carry a
+//! `FormatHeader`, and loaders check it before decoding anything else.
+//!
+//! Compatibility policy:
+//! - `FORMAT_VERSION` is bumped when a serialized type changes so that an older reader would
+//!   fail on it or misread it: a field removed, renamed or given a new meaning, or enum
+//!   variants reordered. Adding a field with a serde default, or a new enum variant at the
+//!   end, is not breaking and does not bump it. Crate releases do not bump it either.
+//! - Every bump adds a step to `migrations::upgrade`. Loaders accept every version from
+//!   `MIN_FORMAT_VERSION` up to `FORMAT_VERSION` and upgrade it first. Newer artifacts are
+//!   refused with `CompatError::TooNew`, never guessed at.
+//! - Artifacts written before headers existed count as version 1.
+//! - `features` lists the cargo features the artifact depends on, such as the built-in
+//!   catalog its object names refer to. A build without one of them refuses the artifact
+//!   with `CompatError::MissingFeature`.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::migrations;
+
+/// Format written by this build; see the module docs for when it changes
+pub const FORMAT_VERSION: u32 = 2;
+/// Oldest format this build can upgrade; headerless artifacts count as version 1
+pub const MIN_FORMAT_VERSION: u32 = 1;
+
+/// Version information embedded in serialized artifacts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatHeader {
+    /// Version of the crate that wrote the artifact, for diagnostics only
+    pub crate_version: String,
+    pub format_version: u32,
+    /// Cargo features the artifact depends on
+    pub features: Vec<String>,
+}
+
+impl FormatHeader {
+    /// Header describing what this build writes
+    pub fn current() -> Self {
+        let mut features = Vec::new();
+        if cfg!(feature = "default-schemas") {
+            features.push("default-schemas".to_string());
+        }
+        FormatHeader { crate_version: env!("CARGO_PKG_VERSION").to_string(), format_version: FORMAT_VERSION, features }
+    }
+
+    /// Header assumed for artifacts written before headers existed
+    pub(crate) fn legacy() -> Self {
+        FormatHeader { format_version: MIN_FORMAT_VERSION, crate_version: String::new(), ..Self::current() }
+    }
+
+    /// Check that this build can read, possibly after migrations, an artifact with this header
+    pub fn check(&self) -> Result<(), CompatError> {
+        self.check_version()?;
+        let ours = FormatHeader::current().features;
+        match self.features.iter().find(|f| !ours.contains(f)) {
+            Some(missing) => Err(CompatError::MissingFeature(missing.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Check only that `format_version` is within the range migrations can handle
+    pub(crate) fn check_version(&self) -> Result<(), CompatError> {
+        if self.format_version > FORMAT_VERSION {
+            return Err(CompatError::TooNew { found: self.format_version, supported: FORMAT_VERSION, writer: self.crate_version.clone() });
+        }
+        if self.format_version < MIN_FORMAT_VERSION {
+            return Err(CompatError::TooOld { found: self.format_version, oldest: MIN_FORMAT_VERSION });
+        }
+        Ok(())
+    }
+}
+
+/// Why an artifact cannot be loaded by this build
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompatError {
+    /// Written in a newer format; `writer` is the crate version that wrote it
+    TooNew { found: u32, supported: u32, writer: String },
+    /// Written in a format older than any migration reaches
+    TooOld { found: u32, oldest: u32 },
+    /// Depends on a cargo feature this build lacks
+    MissingFeature(String),
+    /// Not a valid artifact of the expected kind
+    Malformed(String),
+}
+
+impl CompatError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            CompatError::TooNew { .. } => "format_too_new",
+            CompatError::TooOld { .. } => "format_too_old",
+            CompatError::MissingFeature(_) => "missing_feature",
+            CompatError::Malformed(_) => "malformed",
+        }
+    }
+}
+
+impl fmt::Display for CompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatError::TooNew { found, supported, writer } => write!(
+                f,
+                "artifact uses format {} (written by tidy-env {}) but this build reads up to format {}; upgrade tidy-env to load it",
+                found, writer, supported
+            ),
+            CompatError::TooOld { found, oldest } => write!(
+                f,
+                "artifact uses format {} but the oldest supported format is {}; re-export it with an older tidy-env release",
+                found, oldest
+            ),
+            CompatError::MissingFeature(feature) => {
+                write!(f, "artifact needs the '{}' feature; rebuild tidy-env with it enabled", feature)
+            }
+            CompatError::Malformed(e) => write!(f, "malformed artifact: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CompatError {}
+
+/// A value serialized with a `format` header next to its own fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub format: FormatHeader,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wrap a value with the current header
+    pub fn new(data: T) -> Self {
+        Versioned { format: FormatHeader::current(), data }
+    }
+}
+
+/// JSON of `value` with the current header added as a `format` field; `value` must serialize as a map
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&Versioned::new(value))
+}
+
+/// Load JSON written by `to_json`, by this or an older build: upgrade it, check the header, decode it
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, CompatError> {
+    let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| CompatError::Malformed(e.to_string()))?;
+    migrations::upgrade_value(&mut value)?;
+    let versioned: Versioned<T> = serde_json::from_value(value).map_err(|e| CompatError::Malformed(e.to_string()))?;
+    versioned.format.check()?;
+    Ok(versioned.data)
+}
+
+/// Msgpack of `value` with the current header added as a `format` field; `value` must serialize as a map
+pub fn to_msgpack<T: Serialize>(value: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    rmp_serde::to_vec_named(&Versioned::new(value))
+}
+
+/// Load msgpack written by `to_msgpack`, by this or an older build, the same way as `from_json`
+pub fn from_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CompatError> {
+    let mut value: serde_json::Value = rmp_serde::from_slice(bytes).map_err(|e| CompatError::Malformed(e.to_string()))?;
+    migrations::upgrade_value(&mut value)?;
+    let versioned: Versioned<T> = serde_json::from_value(value).map_err(|e| CompatError::Malformed(e.to_string()))?;
+    versioned.format.check()?;
+    Ok(versioned.data)
+}
@@ -1,54 +1,260 @@
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::{Rng, SeedableRng};
 use rand::seq::{IteratorRandom, SliceRandom};
 use rand::rngs::StdRng;
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
 
-use crate::object::{Object, ObjectSchema};
+use crate::difficulty::{self, DifficultyScore};
+use crate::nav;
+use crate::object::{Object, ObjectConstraint, ObjectId, ObjectSchema, RoomKit};
+use crate::task::{self, DeliveryTask, TaskKind, GROCERY_BAG};
+pub use crate::world::{Cell, ComposeError, Door, ComposeMap, Layout, RoomSign, World, WorldDiff, COMPOUND_NAME_SEPARATOR, NO_ZONE, room_name_parts};
 
 use crate::{
     OUTSIDE, WALL, CLOSED_DOOR
 };
 
-pub type Cell = i8;
-
-#[derive(Clone, Copy)]
+/// Generation options; deserializing fills missing fields from `GenOpts::default()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GenOpts {
     pub seed: u64,
     pub max_rooms: usize,
     pub width: usize,    // number of columns
     pub height: usize,   // number of rows
     pub max_objects: usize, // maximum number of objects to place
+    pub zone_min_area: usize, // rooms at least this large are split into zones (0 disables zoning)
+    pub open_plan_prob: f32, // probability of merging two connected rooms into one open-plan room
+    pub strict: bool, // make `generate` fail when a room kit fixture cannot be placed
+    pub task: TaskKind, // delivery kinds add a filled grocery bag near the agent start
+    pub placement_temperature: f32, // softmax temperature over schema preferences; infinity samples spawn cells uniformly
+    pub min_room_area: usize, // regions smaller than twice this are not split further
+    pub min_room_thickness: usize, // regions thinner than twice this on either axis are not split further
+    pub min_aspect_ratio: f64, // a split is rejected if a side's length-to-thickness ratio falls outside these bounds
+    pub max_aspect_ratio: f64,
+    pub door_min: usize, // door width range in cells, capped by the length of the shared wall
+    pub door_max: usize,
+    pub required_rooms: Vec<String>, // names from the room name pool given to the largest regions if not drawn
+    pub room_name_pool: Option<Vec<String>>, // room names to draw from instead of ROOM_NAME_POOL; numbered ("Bedroom 2") once exhausted
+    pub layout_seed: Option<u64>, // seeds the shell, rooms, doors, room names and zones instead of `seed`
+    pub object_seed: Option<u64>, // seeds object placement and the delivery task instead of a seed derived from `seed`
+    pub misplaced_ratio: Option<f32>, // share of the pickable objects with off-target spawn cells that start off target; None leaves it to the spawn constraint
 }
 
-#[derive(Debug)]
-pub struct Layout {
-    pub width: usize,
-    pub height: usize,
-    pub cells: Vec<Cell>,
-    pub room_names: Vec<&'static str>,
+impl Default for GenOpts {
+    fn default() -> Self {
+        GenOpts {
+            seed: 0,
+            max_rooms: 5,
+            width: 20,
+            height: 20,
+            max_objects: 15,
+            zone_min_area: 0,
+            open_plan_prob: 0.0,
+            strict: false,
+            task: TaskKind::Tidy,
+            placement_temperature: 0.5,
+            min_room_area: 24,
+            min_room_thickness: 3,
+            min_aspect_ratio: 1.2,
+            max_aspect_ratio: 4.0,
+            door_min: 2,
+            door_max: 4,
+            required_rooms: Vec::new(),
+            room_name_pool: None,
+            layout_seed: None,
+            object_seed: None,
+            misplaced_ratio: None,
+        }
+    }
+}
+
+/// Most rooms a layout can have, since room ids are non-negative cells
+pub const MAX_ROOMS: usize = Cell::MAX as usize + 1;
+/// Smallest shell side; thinner shells are walled off entirely
+pub const MIN_SHELL_SIDE: usize = 3;
+/// Required length of the longer shell side, so the concave corner cannot swallow the shell
+pub const MIN_SHELL_SPAN: usize = 8;
+/// Largest shell side, so a bad width or height cannot exhaust memory
+pub const MAX_SHELL_SIDE: usize = 1024;
+
+/// Mixed into `seed` to derive the object placement seed
+const OBJECT_STREAM: u64 = 0x6f62_6a65_6374_7321;
+
+/// SplitMix64 finalizer, so that neighbouring seeds give unrelated streams
+fn split_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = (seed ^ stream).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl GenOpts {
+    /// Seed of the floor plan: `layout_seed`, or `seed` itself
+    pub fn effective_layout_seed(&self) -> u64 {
+        self.layout_seed.unwrap_or(self.seed)
+    }
+
+    /// Seed of object placement: `object_seed`, or a seed derived from `seed` and independent of the layout
+    pub fn effective_object_seed(&self) -> u64 {
+        self.object_seed.unwrap_or_else(|| split_seed(self.seed, OBJECT_STREAM))
+    }
+
+    /// Room names drawn for regions: `room_name_pool` without repeats, or `ROOM_NAME_POOL` if unset or empty
+    pub fn effective_room_name_pool(&self) -> Vec<Cow<'static, str>> {
+        match &self.room_name_pool {
+            Some(pool) if !pool.is_empty() => distinct_rooms(pool).into_iter().map(|room| Cow::Owned(room.to_string())).collect(),
+            _ => ROOM_NAME_POOL.iter().map(|&room| Cow::Borrowed(room)).collect(),
+        }
+    }
+
+    /// Check that these options can produce a world before running the generator
+    pub fn validate(&self) -> Result<(), GenError> {
+        if self.width.min(self.height) < MIN_SHELL_SIDE || self.width.max(self.height) < MIN_SHELL_SPAN {
+            return Err(GenError::ShellTooSmall { width: self.width, height: self.height });
+        }
+        if self.width.max(self.height) > MAX_SHELL_SIDE {
+            return Err(GenError::ShellTooLarge { width: self.width, height: self.height });
+        }
+        if self.max_rooms > MAX_ROOMS {
+            return Err(GenError::TooManyRooms { requested: self.max_rooms, max: MAX_ROOMS });
+        }
+        if !(0.0..=1.0).contains(&self.open_plan_prob) {
+            return Err(GenError::InvalidProbability(self.open_plan_prob));
+        }
+        if self.placement_temperature.is_nan() || self.placement_temperature <= 0.0 {
+            return Err(GenError::InvalidTemperature(self.placement_temperature));
+        }
+        if let Some(ratio) = self.misplaced_ratio.filter(|r| !(0.0..=1.0).contains(r)) {
+            return Err(GenError::InvalidMisplacedRatio(ratio));
+        }
+        // the shell interior bounds any room; the concave corner takes more, but varies by seed
+        let interior = (self.width - 2) * (self.height - 2);
+        if self.min_room_area == 0 || self.min_room_thickness == 0 || self.min_room_area > interior {
+            return Err(GenError::InvalidRoomSize {
+                min_area: self.min_room_area,
+                min_thickness: self.min_room_thickness,
+                interior,
+            });
+        }
+        let (min_ar, max_ar) = (self.min_aspect_ratio, self.max_aspect_ratio);
+        if min_ar.is_nan() || max_ar.is_nan() || min_ar <= 0.0 || min_ar > max_ar {
+            return Err(GenError::InvalidAspectRatio { min: min_ar, max: max_ar });
+        }
+        if self.door_min == 0 || self.door_min > self.door_max {
+            return Err(GenError::InvalidDoorWidth { min: self.door_min, max: self.door_max });
+        }
+        let pool = self.effective_room_name_pool();
+        if let Some(room) = self.required_rooms.iter().find(|&room| !pool.iter().any(|name| name == room)) {
+            return Err(GenError::UnknownRoom(room.clone()));
+        }
+        let required = distinct_rooms(&self.required_rooms).len();
+        if required > self.max_rooms {
+            return Err(GenError::TooFewRooms { rooms: self.max_rooms, required });
+        }
+        Ok(())
+    }
 }
 
-impl Layout {
-    pub fn new(
-        width: usize,
-        height: usize,
-        cells: Vec<Cell>,
-        room_names: Vec<&'static str>,
-    ) -> Self {
-        Self { width, height, cells, room_names }
+/// Why generation was refused or produced an unusable world
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenError {
+    /// The shell is too small to hold any room
+    ShellTooSmall { width: usize, height: usize },
+    /// A shell side exceeds `MAX_SHELL_SIDE`
+    ShellTooLarge { width: usize, height: usize },
+    /// `max_rooms` exceeds the number of room ids a cell can hold
+    TooManyRooms { requested: usize, max: usize },
+    /// `open_plan_prob` lies outside [0, 1]
+    InvalidProbability(f32),
+    /// `placement_temperature` is not positive
+    InvalidTemperature(f32),
+    /// `misplaced_ratio` lies outside [0, 1]
+    InvalidMisplacedRatio(f32),
+    /// `min_room_area` or `min_room_thickness` is zero, or `min_room_area` exceeds the shell's interior cells
+    InvalidRoomSize { min_area: usize, min_thickness: usize, interior: usize },
+    /// The aspect-ratio bounds are NaN, not positive or out of order
+    InvalidAspectRatio { min: f64, max: f64 },
+    /// `door_min` is zero or exceeds `door_max`
+    InvalidDoorWidth { min: usize, max: usize },
+    /// `required_rooms` names a room outside the effective room name pool
+    UnknownRoom(String),
+    /// `max_rooms`, or the regions the layout ended up with, cannot hold every required room
+    TooFewRooms { rooms: usize, required: usize },
+    /// A BSP split left one of its sides without cells
+    RoomSplitFailed,
+    /// The finished layout contains no room cell
+    NoRooms,
+    /// Room kit fixtures could not be placed while `strict` was set; (room, fixture) pairs
+    KitFailed(Vec<(String, String)>),
+}
+
+impl GenError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            GenError::ShellTooSmall { .. } => "shell_too_small",
+            GenError::ShellTooLarge { .. } => "shell_too_large",
+            GenError::TooManyRooms { .. } => "too_many_rooms",
+            GenError::InvalidProbability(_) => "invalid_probability",
+            GenError::InvalidTemperature(_) => "invalid_temperature",
+            GenError::InvalidMisplacedRatio(_) => "invalid_misplaced_ratio",
+            GenError::InvalidRoomSize { .. } => "invalid_room_size",
+            GenError::InvalidAspectRatio { .. } => "invalid_aspect_ratio",
+            GenError::InvalidDoorWidth { .. } => "invalid_door_width",
+            GenError::UnknownRoom(_) => "unknown_room",
+            GenError::TooFewRooms { .. } => "too_few_rooms",
+            GenError::RoomSplitFailed => "room_split_failed",
+            GenError::NoRooms => "no_rooms",
+            GenError::KitFailed(_) => "kit_failed",
+        }
+    }
+}
+
+impl std::fmt::Display for GenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenError::ShellTooSmall { width, height } => write!(
+                f,
+                "{}x{} shell is too small; both sides must be at least {} and one at least {}",
+                width, height, MIN_SHELL_SIDE, MIN_SHELL_SPAN
+            ),
+            GenError::ShellTooLarge { width, height } => {
+                write!(f, "{}x{} shell is too large; neither side may exceed {}", width, height, MAX_SHELL_SIDE)
+            }
+            GenError::TooManyRooms { requested, max } => write!(f, "max_rooms {} exceeds the limit of {} rooms", requested, max),
+            GenError::InvalidProbability(p) => write!(f, "open_plan_prob {} is outside [0, 1]", p),
+            GenError::InvalidTemperature(t) => write!(f, "placement_temperature {} must be positive", t),
+            GenError::InvalidMisplacedRatio(r) => write!(f, "misplaced_ratio {} is outside [0, 1]", r),
+            GenError::InvalidRoomSize { min_area, min_thickness, interior } => write!(
+                f,
+                "min_room_area {} must lie in 1..={} for this shell and min_room_thickness {} must be positive",
+                min_area, interior, min_thickness
+            ),
+            GenError::InvalidAspectRatio { min, max } => {
+                write!(f, "aspect ratio bounds {}..={} must be positive and in order", min, max)
+            }
+            GenError::InvalidDoorWidth { min, max } => write!(f, "door width {}..={} must be positive and in order", min, max),
+            GenError::UnknownRoom(room) => write!(f, "required room '{}' is not in the room name pool", room),
+            GenError::TooFewRooms { rooms, required } => write!(f, "{} rooms cannot hold the {} required rooms", rooms, required),
+            GenError::RoomSplitFailed => write!(f, "a room split left one side empty"),
+            GenError::NoRooms => write!(f, "generated layout contains no rooms"),
+            GenError::KitFailed(failures) => {
+                let missing: Vec<String> = failures.iter().map(|(room, fixture)| format!("{} in the {}", fixture, room)).collect();
+                write!(f, "could not place room kit fixtures: {}", missing.join(", "))
+            }
+        }
     }
 }
 
-const MIN_THICK_CELLS: usize = 3;
-const MIN_ROOM_AREA_CELLS: usize = 24;
+impl std::error::Error for GenError {}
 
 const MIN_SIZE_RATIO: f64 = 0.2;
-const MIN_AR: f64 = 1.2;
-const MAX_AR: f64 = 4.0;
 
 const SAMPLE_C: usize = 10;
-const DOOR_MIN_CELLS: usize = 2;
-const DOOR_MAX_CELLS: usize = 4;
 
 
 
@@ -73,7 +279,7 @@ fn thinnest_segment_length(mask: &[bool], width: usize, height: usize) -> (usize
         if find_runs(row) > 0 {
             let mut start = None;
             for x in 0..=width {
-                if x < width && row[x] {
+                if row.get(x).copied().unwrap_or(false) {
                     start.get_or_insert(x);
                 } else if let Some(s) = start {
                     min_w = min_w.min(x - s);
@@ -107,8 +313,8 @@ struct Region {
 }
 
 impl Region {
-    /// Build region from mask with given width/height
-    fn new(mask: Vec<bool>, width: usize, height: usize) -> Self {
+    /// Build region from mask with given width/height; `None` if the mask is empty
+    fn new(mask: Vec<bool>, width: usize, height: usize) -> Option<Self> {
         let mut ys = Vec::new();
         let mut xs = Vec::new();
         for y in 0..height {
@@ -119,12 +325,12 @@ impl Region {
                 }
             }
         }
-        let miny = *ys.iter().min().unwrap();
-        let maxy = *ys.iter().max().unwrap();
-        let minx = *xs.iter().min().unwrap();
-        let maxx = *xs.iter().max().unwrap();
+        let miny = *ys.iter().min()?;
+        let maxy = *ys.iter().max()?;
+        let minx = *xs.iter().min()?;
+        let maxx = *xs.iter().max()?;
         let area = mask.iter().filter(|&&b| b).count();
-        Self { mask, area, bbox: (miny, maxy, minx, maxx) }
+        Some(Self { mask, area, bbox: (miny, maxy, minx, maxx) })
     }
 }
 
@@ -150,33 +356,67 @@ fn make_concave_shell(width: usize, height: usize, seed: u64) -> Vec<bool> {
     shell
 }
 
-fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize, height: usize) -> (Vec<Region>, Vec<bool>) {
-    let mut rng = StdRng::seed_from_u64(seed);
-    let mut wall_mask = vec![false; width * height];
-    let mut regions = vec![Region::new(shell.to_vec(), width, height)];
+/// Resumable BSP state; each `split_once` call applies at most one split
+struct Bsp {
+    rng: StdRng,
+    wall_mask: Vec<bool>,
+    regions: Vec<Region>,
+    target_rooms: usize,
+    min_area: usize,
+    min_thickness: usize,
+    aspect_ratio: RangeInclusive<f64>,
+    done: bool,
+}
 
-    while regions.len() < target_rooms {
+impl Bsp {
+    fn new(shell: &[bool], opts: &GenOpts) -> Result<Self, GenError> {
+        let (width, height) = (opts.width, opts.height);
+        let region = Region::new(shell.to_vec(), width, height).ok_or(GenError::ShellTooSmall { width, height })?;
+        Ok(Bsp {
+            rng: StdRng::seed_from_u64(opts.effective_layout_seed()),
+            wall_mask: vec![false; width * height],
+            regions: vec![region],
+            target_rooms: opts.max_rooms,
+            min_area: opts.min_room_area,
+            min_thickness: opts.min_room_thickness,
+            aspect_ratio: opts.min_aspect_ratio..=opts.max_aspect_ratio,
+            done: false,
+        })
+    }
+
+    /// Split the largest region once; returns false when splitting has finished
+    fn split_once(&mut self, width: usize, height: usize, mut trace: Option<&mut GenTrace>) -> Result<bool, GenError> {
+        if self.done || self.regions.len() >= self.target_rooms {
+            self.done = true;
+            return Ok(false);
+        }
+        let Bsp { rng, wall_mask, regions, min_area, min_thickness, aspect_ratio, done, .. } = self;
         regions.sort_unstable_by_key(|r| usize::MAX - r.area);
         let region = regions.remove(0);
-        if region.area < MIN_ROOM_AREA_CELLS * 2 { regions.push(region); break; }
+        if region.area < *min_area * 2 { regions.push(region); *done = true; return Ok(false); }
         let (miny, maxy, minx, maxx) = region.bbox;
-        if (maxy - miny + 1) < 2 * MIN_THICK_CELLS || (maxx - minx + 1) < 2 * MIN_THICK_CELLS {
+        if (maxy - miny + 1) < 2 * *min_thickness || (maxx - minx + 1) < 2 * *min_thickness {
             regions.push(region);
-            break;
+            *done = true;
+            return Ok(false);
         }
         let mut candidates = Vec::new();
         // Vertical splits
         let mut xs: Vec<usize> = (minx + 1..maxx).collect();
         if xs.len() > SAMPLE_C {
-            xs = xs.into_iter().choose_multiple(&mut rng, SAMPLE_C);
+            xs = xs.into_iter().choose_multiple(rng, SAMPLE_C);
         }
         for &x in &xs {
             let col: Vec<bool> = (miny..=maxy).map(|y| region.mask[y * width + x]).collect();
-            if !col.iter().all(|&b| b) || find_runs(&col) != 1 { continue; }
+            if !col.iter().all(|&b| b) || find_runs(&col) != 1 {
+                if let Some(t) = trace.as_deref_mut() { t.reject(region.bbox, 'v', x, SplitRejection::NotContiguous); }
+                continue;
+            }
             // Area ratio
             let left = region.mask.iter().enumerate().filter(|&(i, &b)| b && (i % width) < x).count();
             let right = region.mask.iter().enumerate().filter(|&(i, &b)| b && (i % width) > x).count();
             if (left as f64) < MIN_SIZE_RATIO * region.area as f64 || (right as f64) < MIN_SIZE_RATIO * region.area as f64 {
+                if let Some(t) = trace.as_deref_mut() { t.reject(region.bbox, 'v', x, SplitRejection::AreaRatio); }
                 continue;
             }
             // Aspect ratio checks on both sides
@@ -190,7 +430,7 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
                 let bw = xs2.iter().max().unwrap() - xs2.iter().min().unwrap() + 1;
                 let bh = ys2.iter().max().unwrap() - ys2.iter().min().unwrap() + 1;
                 let ar = if bw >= bh { bw as f64 / mh.max(1) as f64 } else { bh as f64 / mw.max(1) as f64 };
-                if ar < MIN_AR || ar > MAX_AR { ok = false; }
+                if !aspect_ratio.contains(&ar) { ok = false; }
             }
             // Right side
             if ok {
@@ -201,22 +441,30 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
                 let bw = xs2.iter().max().unwrap() - xs2.iter().min().unwrap() + 1;
                 let bh = ys2.iter().max().unwrap() - ys2.iter().min().unwrap() + 1;
                 let ar = if bw >= bh { bw as f64 / mh.max(1) as f64 } else { bh as f64 / mw.max(1) as f64 };
-                if ar < MIN_AR || ar > MAX_AR { ok = false; }
+                if !aspect_ratio.contains(&ar) { ok = false; }
+            }
+            if ok {
+                candidates.push(('v', x));
+            } else if let Some(t) = trace.as_deref_mut() {
+                t.reject(region.bbox, 'v', x, SplitRejection::AspectRatio);
             }
-            if ok { candidates.push(('v', x)); }
         }
         // Horizontal splits
         let mut ys: Vec<usize> = (miny + 1..maxy).collect();
         if ys.len() > SAMPLE_C {
-            ys = ys.into_iter().choose_multiple(&mut rng, SAMPLE_C);
+            ys = ys.into_iter().choose_multiple(rng, SAMPLE_C);
         }
         for &y in &ys {
             let row: Vec<bool> = (minx..=maxx).map(|x| region.mask[y * width + x]).collect();
-            if !row.iter().all(|&b| b) || find_runs(&row) != 1 { continue; }
+            if !row.iter().all(|&b| b) || find_runs(&row) != 1 {
+                if let Some(t) = trace.as_deref_mut() { t.reject(region.bbox, 'h', y, SplitRejection::NotContiguous); }
+                continue;
+            }
             // Area ratio
             let top = region.mask.iter().enumerate().filter(|&(i, &b)| b && (i / width) < y).count();
             let bot = region.mask.iter().enumerate().filter(|&(i, &b)| b && (i / width) > y).count();
             if (top as f64) < MIN_SIZE_RATIO * region.area as f64 || (bot as f64) < MIN_SIZE_RATIO * region.area as f64 {
+                if let Some(t) = trace.as_deref_mut() { t.reject(region.bbox, 'h', y, SplitRejection::AreaRatio); }
                 continue;
             }
             // Aspect ratio checks on both sides
@@ -230,7 +478,7 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
                 let bw = xs2.iter().max().unwrap() - xs2.iter().min().unwrap() + 1;
                 let bh = ys2.iter().max().unwrap() - ys2.iter().min().unwrap() + 1;
                 let ar = if bw >= bh { bw as f64 / mh.max(1) as f64 } else { bh as f64 / mw.max(1) as f64 };
-                if ar < MIN_AR || ar > MAX_AR { ok = false; }
+                if !aspect_ratio.contains(&ar) { ok = false; }
             }
             // Bottom side
             if ok {
@@ -241,15 +489,23 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
                 let bw = xs2.iter().max().unwrap() - xs2.iter().min().unwrap() + 1;
                 let bh = ys2.iter().max().unwrap() - ys2.iter().min().unwrap() + 1;
                 let ar = if bw >= bh { bw as f64 / mh.max(1) as f64 } else { bh as f64 / mw.max(1) as f64 };
-                if ar < MIN_AR || ar > MAX_AR { ok = false; }
+                if !aspect_ratio.contains(&ar) { ok = false; }
+            }
+            if ok {
+                candidates.push(('h', y));
+            } else if let Some(t) = trace.as_deref_mut() {
+                t.reject(region.bbox, 'h', y, SplitRejection::AspectRatio);
             }
-            if ok { candidates.push(('h', y)); }
         }
         if candidates.is_empty() {
             regions.push(region);
-            break;
+            *done = true;
+            return Ok(false);
+        }
+        let &(orient, coord) = candidates.iter().choose(rng).unwrap();
+        if let Some(t) = trace {
+            t.splits.push(TraceSplit { bbox: region.bbox, orientation: orient, coord, candidates: candidates.len() });
         }
-        let &(orient, coord) = candidates.iter().choose(&mut rng).unwrap();
         let mut mask_a = region.mask.clone();
         let mut mask_b = region.mask.clone();
         if orient == 'v' {
@@ -265,15 +521,25 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
                 if idx / width <= coord { mask_b[idx] = false; }
             }
         }
-        if mask_a.iter().filter(|&&b| b).count() >= mask_b.iter().filter(|&&b| b).count() {
-            regions.push(Region::new(mask_a, width, height));
-            regions.push(Region::new(mask_b, width, height));
+        let a = Region::new(mask_a, width, height).ok_or(GenError::RoomSplitFailed)?;
+        let b = Region::new(mask_b, width, height).ok_or(GenError::RoomSplitFailed)?;
+        if a.area >= b.area {
+            regions.extend([a, b]);
         } else {
-            regions.push(Region::new(mask_b, width, height));
-            regions.push(Region::new(mask_a, width, height));
+            regions.extend([b, a]);
         }
+        Ok(true)
     }
-    // Outer walls (including diagonal)
+
+    /// Add the outer walls and hand out the final regions and wall mask
+    fn finish(mut self, shell: &[bool], width: usize, height: usize) -> (Vec<Region>, Vec<bool>) {
+        add_outer_walls(shell, &mut self.wall_mask, width, height);
+        (self.regions, self.wall_mask)
+    }
+}
+
+/// Mark every shell cell touching the outside (including diagonally) as wall
+fn add_outer_walls(shell: &[bool], wall_mask: &mut [bool], width: usize, height: usize) {
     for y in 0..height {
         for x in 0..width {
             if !shell[y * width + x] { continue; }
@@ -290,7 +556,42 @@ fn bsp_with_walls(shell: &[bool], target_rooms: usize, seed: u64, width: usize,
             }
         }
     }
-    (regions, wall_mask)
+}
+
+/// Required room names once each, in order
+fn distinct_rooms(required: &[String]) -> Vec<&str> {
+    let mut rooms = Vec::new();
+    for room in required.iter().map(String::as_str) {
+        if !rooms.contains(&room) {
+            rooms.push(room);
+        }
+    }
+    rooms
+}
+
+/// `count` names from `pool` in order, then again numbered from 2 ("Bedroom 2") until there are enough
+fn room_names_from(pool: &[Cow<'static, str>], count: usize) -> Vec<Cow<'static, str>> {
+    (0..count)
+        .map(|i| match i / pool.len() {
+            0 => pool[i % pool.len()].clone(),
+            round => Cow::Owned(format!("{} {}", pool[i % pool.len()], round + 1)),
+        })
+        .collect()
+}
+
+/// Rename regions so every required room exists, taking the largest regions whose drawn name is not required
+fn require_rooms(room_names: &mut [Cow<'static, str>], regions: &[Region], required: &[String]) -> Result<(), GenError> {
+    let required = distinct_rooms(required);
+    if required.len() > room_names.len() {
+        return Err(GenError::TooFewRooms { rooms: room_names.len(), required: required.len() });
+    }
+    let mut free: Vec<usize> = (0..room_names.len()).filter(|&r| !required.contains(&&*room_names[r])).collect();
+    free.sort_by_key(|&r| std::cmp::Reverse(regions[r].area));
+    let missing = required.iter().filter(|&&room| !room_names.iter().any(|n| n == room)).copied().collect::<Vec<_>>();
+    for (room, region) in missing.into_iter().zip(free) {
+        room_names[region] = Cow::Owned(room.to_string());
+    }
+    Ok(())
 }
 
 fn build_labels(regions: &[Region], width: usize, height: usize) -> Vec<Cell> {
@@ -303,32 +604,81 @@ fn build_labels(regions: &[Region], width: usize, height: usize) -> Vec<Cell> {
     labels
 }
 
-fn carve_doors(labels: &[Cell], wall_mask: &mut [bool], shell: &[bool], seed: u64, width: usize, height: usize) -> Vec<bool> {
-    let mut rng = StdRng::seed_from_u64(seed);
+/// Whether floor cell `room` has a neighbor in the same room besides `from`, so a door
+/// at `from` does not open into a one-cell dead end
+fn opens_into(floor: &[Cell], room: usize, from: usize, width: usize, height: usize) -> bool {
+    let (x, y) = (room % width, room / width);
+    [
+        (x > 0).then(|| room - 1),
+        (x + 1 < width).then(|| room + 1),
+        (y > 0).then(|| room - width),
+        (y + 1 < height).then(|| room + width),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|n| n != from && floor[n] == floor[room])
+}
+
+/// Split the wall cells shared by two rooms into straight contiguous runs; `vertical`
+/// marks cells of walls with the rooms to their left and right
+fn wall_runs(cells: &[usize], vertical: &BTreeSet<usize>, width: usize) -> Vec<Vec<usize>> {
+    let (mut columns, mut rows): (Vec<usize>, Vec<usize>) = cells.iter().partition(|i| vertical.contains(i));
+    columns.sort_unstable_by_key(|&i| (i % width, i / width));
+    rows.sort_unstable();
+    let mut runs: Vec<Vec<usize>> = Vec::new();
+    for (cells, step) in [(columns, width), (rows, 1)] {
+        let mut run: Vec<usize> = Vec::new();
+        for i in cells {
+            if run.last().is_some_and(|&last| last + step != i) {
+                runs.push(std::mem::take(&mut run));
+            }
+            run.push(i);
+        }
+        if !run.is_empty() {
+            runs.push(run);
+        }
+    }
+    runs
+}
+
+/// Carve one door per spanning-tree edge of the room adjacency; returns the door mask and
+/// the door segments, all closed
+fn carve_doors(labels: &[Cell], wall_mask: &mut [bool], shell: &[bool], opts: &GenOpts, mut trace: Option<&mut GenTrace>) -> (Vec<bool>, Vec<Door>) {
+    let (width, height) = (opts.width, opts.height);
+    let mut rng = StdRng::seed_from_u64(opts.effective_layout_seed());
     let mut adjacency: BTreeMap<(Cell, Cell), Vec<usize>> = BTreeMap::new();
+    let mut vertical = BTreeSet::new();
+    // region labels cover their own walls; only floor cells count as rooms here
+    let floor: Vec<Cell> = labels.iter().zip(wall_mask.iter()).map(|(&l, &wall)| if wall { WALL } else { l }).collect();
     for y in 1..height-1 {
         for x in 1..width-1 {
             let idx = y * width + x;
             if !wall_mask[idx] { continue; }
             if !(shell[idx - width] && shell[idx + width] && shell[idx - 1] && shell[idx + 1]) { continue; }
-            let left = labels[idx - 1]; let right = labels[idx + 1];
-            if left >= 0 && right >= 0 && left != right {
+            // both sides must lead somewhere, not into a sliver against the notch or a corner
+            let opens = |a: usize, b: usize| opens_into(&floor, a, idx, width, height) && opens_into(&floor, b, idx, width, height);
+            let left = floor[idx - 1]; let right = floor[idx + 1];
+            if left >= 0 && right >= 0 && left != right && opens(idx - 1, idx + 1) {
                 let key = if left < right { (left, right) } else { (right, left) };
                 adjacency.entry(key).or_default().push(idx);
+                vertical.insert(idx);
                 continue;
             }
-            let up = labels[idx - width]; let down = labels[idx + width];
-            if up >= 0 && down >= 0 && up != down {
+            let up = floor[idx - width]; let down = floor[idx + width];
+            if up >= 0 && down >= 0 && up != down && opens(idx - width, idx + width) {
                 let key = if up < down { (up, down) } else { (down, up) };
                 adjacency.entry(key).or_default().push(idx);
             }
         }
     }
+    if let Some(t) = trace.as_deref_mut() {
+        t.adjacency = adjacency.iter().map(|(&rooms, cells)| TraceAdjacency { rooms, cells: cells.clone() }).collect();
+    }
     if adjacency.is_empty() {
-        return vec![false; width * height];
+        return (vec![false; width * height], Vec::new());
     }
     let mut graph: BTreeMap<Cell, BTreeSet<Cell>> = BTreeMap::new();
-    for (&(a, b), _) in &adjacency {
+    for &(a, b) in adjacency.keys() {
         graph.entry(a).or_default().insert(b);
         graph.entry(b).or_default().insert(a);
     }
@@ -348,60 +698,589 @@ fn carve_doors(labels: &[Cell], wall_mask: &mut [bool], shell: &[bool], seed: u6
         }
     }
     let mut door_mask = vec![false; width * height];
+    let mut doors = Vec::new();
     // for each edge ...
     for edge in tree_edges.into_iter() {
-        if let Some(mut cells) = adjacency.get(&edge).cloned() {
-            let coords: Vec<(usize, usize)> = cells.iter().map(|&i| (i / width, i % width)).collect();
-            let all_x_equal = coords.iter().all(|&(_, x)| x == coords[0].1);
-            if all_x_equal {
-                cells.sort_unstable_by_key(|&i| i / width);
-            } else {
-                cells.sort_unstable_by_key(|&i| i % width);
-            }
+        if let Some(shared) = adjacency.get(&edge) {
+            // the longest straight run of the shared wall, so every door cell faces both rooms
+            let cells = wall_runs(shared, &vertical, width)
+                .into_iter()
+                .rev()
+                .max_by_key(|run| run.len())
+                .expect("adjacent rooms share at least one wall cell");
             let total = cells.len();
-            let width = rng.gen_range(DOOR_MIN_CELLS..=DOOR_MAX_CELLS).min(total);
-            let start = rng.gen_range(0..=total - width);
+            let width = rng.gen_range(opts.door_min..=opts.door_max).min(total);
+            // keep the door towards the middle of the wall instead of hugging its ends
+            let slack = total - width;
+            let start = rng.gen_range(slack / 4..=slack - slack / 4);
             for &idx in &cells[start..start + width] {
                 wall_mask[idx] = false;
                 door_mask[idx] = true;
             }
+            if let Some(t) = trace.as_deref_mut() {
+                t.doors.push(TraceAdjacency { rooms: edge, cells: cells[start..start + width].to_vec() });
+            }
+            let mut segment = cells[start..start + width].to_vec();
+            segment.sort_unstable();
+            doors.push(Door { id: 0, cells: segment, rooms: edge, open: false });
         }
     }
-    door_mask
+    // ids in row-major order of the first cell, as `Layout::new` numbers detected doors
+    doors.sort_by_key(|d| d.cells[0]);
+    for (id, door) in doors.iter_mut().enumerate() {
+        door.id = id;
+    }
+    (door_mask, doors)
 }
 
-/// Simulation world bundling layout and objects
-#[derive(Debug)]
-pub struct World {
-    pub layout: Layout,
-    pub objects: Vec<Object>,
+/// Follow `merge_open_plan` folding room `b` into `a`: doors between them are gone, and
+/// the others get the new room ids and fresh ids
+fn merge_doors(doors: &mut Vec<Door>, (a, b): (Cell, Cell)) {
+    let renumber = |r: Cell| if r == b { a } else if r > b { r - 1 } else { r };
+    doors.retain(|d| d.rooms != (a, b));
+    for (id, door) in doors.iter_mut().enumerate() {
+        let (p, q) = (renumber(door.rooms.0), renumber(door.rooms.1));
+        door.id = id;
+        door.rooms = (p.min(q), p.max(q));
+    }
 }
 
-fn place_objects(world: &mut World, schemas: &[ObjectSchema], seed: u64, max_objects: usize) {
-    use rand::seq::SliceRandom;
-    // list all floor cells
-    let w = world.layout.width;
-    let h = world.layout.height;
-    let cells = &world.layout.cells;
-    let mut floor = Vec::new();
+/// "A / B" name for a merged room
+fn compound_room_name(a: &str, b: &str) -> String {
+    format!("{}{}{}", a, COMPOUND_NAME_SEPARATOR, b)
+}
+
+/// Merge two rooms connected by a door into one open-plan room.
+///
+/// The separating wall (including the door) becomes floor of the lower room id, the
+/// higher id is folded into it, later ids shift down by one, and the names are joined
+/// into a compound name. Returns the merged pair (a, b), or `None` if no two rooms share a door.
+fn merge_open_plan(cells: &mut [Cell], room_names: &mut Vec<Cow<'static, str>>, width: usize, height: usize, rng: &mut StdRng) -> Option<(Cell, Cell)> {
+    // rooms on opposite sides of a wall or door cell
+    let opposite = |cells: &[Cell], idx: usize| -> Option<(Cell, Cell)> {
+        let (x, y) = (idx % width, idx / width);
+        if x == 0 || y == 0 || x + 1 == width || y + 1 == height {
+            return None;
+        }
+        for (p, q) in [(cells[idx - 1], cells[idx + 1]), (cells[idx - width], cells[idx + width])] {
+            if p >= 0 && q >= 0 && p != q {
+                return Some((p.min(q), p.max(q)));
+            }
+        }
+        None
+    };
+    let edges: BTreeSet<(Cell, Cell)> = (0..cells.len())
+        .filter(|&i| cells[i] == CLOSED_DOOR)
+        .filter_map(|i| opposite(cells, i))
+        .collect();
+    let &(a, b) = edges.iter().choose(rng)?;
+    let shared: Vec<usize> = (0..cells.len())
+        .filter(|&i| matches!(cells[i], WALL | CLOSED_DOOR) && opposite(cells, i) == Some((a, b)))
+        .collect();
+    for i in shared {
+        cells[i] = a;
+    }
+    for c in cells.iter_mut() {
+        if *c == b {
+            *c = a;
+        } else if *c > b {
+            *c -= 1;
+        }
+    }
+    // remove wall stubs left dangling inside the merged room
+    loop {
+        let stubs: Vec<usize> = (0..cells.len())
+            .filter(|&i| cells[i] == WALL)
+            .filter(|&i| {
+                let (x, y) = (i % width, i / width);
+                let neighbors = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < width).then(|| i + 1),
+                    (y > 0).then(|| i - width),
+                    (y + 1 < height).then(|| i + width),
+                ];
+                let blocked = neighbors.iter().filter(|n| n.is_none_or(|n| cells[n] < 0)).count();
+                blocked <= 1 && neighbors.iter().flatten().all(|&n| cells[n] < 0 || cells[n] == a)
+            })
+            .collect();
+        if stubs.is_empty() {
+            break;
+        }
+        for i in stubs {
+            cells[i] = a;
+        }
+    }
+    let removed = room_names.remove(b as usize);
+    room_names[a as usize] = Cow::Owned(compound_room_name(&room_names[a as usize], &removed));
+    Some((a, b))
+}
+
+/// Split every room of at least `min_area` cells into two named zones along its longer axis
+fn assign_zones(layout: &mut Layout, min_area: usize, rng: &mut StdRng) {
+    let w = layout.width;
+    for rid in 0..layout.room_names.len() {
+        let room: Vec<usize> = (0..layout.cells.len())
+            .filter(|&i| layout.cells[i] == rid as Cell)
+            .collect();
+        if room.is_empty() || room.len() < min_area {
+            continue;
+        }
+        let minx = room.iter().map(|&i| i % w).min().unwrap();
+        let maxx = room.iter().map(|&i| i % w).max().unwrap();
+        let miny = room.iter().map(|&i| i / w).min().unwrap();
+        let maxy = room.iter().map(|&i| i / w).max().unwrap();
+        let mut names = ZONE_NAME_POOL.to_vec();
+        names.shuffle(rng);
+        let first = layout.zone_names.len() as Cell;
+        layout.zone_names.extend(names[..2].iter().map(|&name| Cow::Borrowed(name)));
+        for &i in &room {
+            let second_half = if maxx - minx >= maxy - miny {
+                i % w > (minx + maxx) / 2
+            } else {
+                i / w > (miny + maxy) / 2
+            };
+            layout.zones[i] = if second_half { first + 1 } else { first };
+        }
+    }
+}
+
+/// Summary of what happened during generation
+#[derive(Debug, Clone, Default)]
+pub struct GenReport {
+    /// Number of objects requested through `GenOpts::max_objects`
+    pub requested_objects: usize,
+    /// Number of objects actually placed
+    pub placed_objects: usize,
+    /// Schemas that found no valid candidate cell at their last attempt
+    pub exhausted_schemas: Vec<&'static str>,
+    /// Why each schema in `exhausted_schemas` ran out, in the same order
+    pub exhausted_reasons: Vec<SkipReason>,
+    /// Number of passes made over the schema list
+    pub passes: usize,
+    /// Human-readable warnings (e.g. far fewer objects than requested)
+    pub warnings: Vec<String>,
+    /// How each object came to be placed, in placement order
+    pub provenance: Vec<PlacementProvenance>,
+    /// (room name, fixture) pairs of room kits that could not be placed
+    pub kit_failures: Vec<(String, String)>,
+    /// Estimated difficulty of tidying the generated world
+    pub difficulty: DifficultyScore,
+    /// The grocery bag and its items, for delivery task kinds
+    pub delivery: Option<DeliveryTask>,
+    /// Pickable objects that had spawn cells off their target, which `GenOpts::misplaced_ratio`
+    /// is a share of; empty without a ratio
+    pub misplaceable_objects: Vec<ObjectId>,
+}
+
+impl GenReport {
+    /// Placement record of the object with the given id
+    pub fn provenance(&self, id: ObjectId) -> Option<&PlacementProvenance> {
+        self.provenance.iter().find(|p| p.object_id == id)
+    }
+}
+
+/// Why object placement gave up on a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SkipReason {
+    /// No free cell or container satisfies the schema's target
+    NoTargetCell,
+    /// No free cell or container satisfies the schema's spawn constraint
+    NoSpawnCell,
+}
+
+/// Why an object was placed where it was
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacementProvenance {
+    pub object_id: ObjectId,
+    pub schema_name: &'static str,
+    pub chosen_cell: (usize, usize),
+    /// Container the object was put into, if any
+    pub inside_parent: Option<ObjectId>,
+    /// The part of the spawn constraint that admitted the cell, e.g. `InRoomNamed(["Kitchen"])`
+    pub satisfied_branch: String,
+    /// Number of valid candidate placements before the floor/inside coin flip
+    pub candidates_considered: usize,
+}
+
+/// Why a candidate BSP split was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SplitRejection {
+    /// The split line does not run through the region in one contiguous piece
+    NotContiguous,
+    /// One side would be smaller than the minimum area ratio
+    AreaRatio,
+    /// One side would violate the aspect-ratio bounds
+    AspectRatio,
+}
+
+/// An accepted BSP split; `orientation` is 'v' (wall at column `coord`) or 'h' (row `coord`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSplit {
+    pub bbox: (usize, usize, usize, usize), // (miny, maxy, minx, maxx) of the split region
+    pub orientation: char,
+    pub coord: usize,
+    /// Number of valid split lines the accepted one was drawn from
+    #[serde(default)]
+    pub candidates: usize,
+}
+
+/// A rejected BSP split candidate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceRejection {
+    pub bbox: (usize, usize, usize, usize),
+    pub orientation: char,
+    pub coord: usize,
+    pub reason: SplitRejection,
+}
+
+/// Wall cells shared by a pair of rooms (or carved into a door between them)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceAdjacency {
+    pub rooms: (Cell, Cell),
+    pub cells: Vec<usize>,
+}
+
+/// Step-by-step record of how a layout was built, collected by `generate_with_trace`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenTrace {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major mask of cells inside the apartment shell
+    pub shell: Vec<bool>,
+    /// Accepted splits in the order they were applied
+    pub splits: Vec<TraceSplit>,
+    pub rejected: Vec<TraceRejection>,
+    /// Candidate door cells for every pair of adjacent rooms
+    pub adjacency: Vec<TraceAdjacency>,
+    /// Door segments actually carved, one per spanning-tree edge
+    pub doors: Vec<TraceAdjacency>,
+}
+
+impl GenTrace {
+    fn reject(&mut self, bbox: (usize, usize, usize, usize), orientation: char, coord: usize, reason: SplitRejection) {
+        self.rejected.push(TraceRejection { bbox, orientation, coord, reason });
+    }
+
+    /// Rebuild the final wall mask by replaying the recorded splits, outer walls and doors
+    pub fn replay_walls(&self) -> Vec<bool> {
+        let (width, height) = (self.width, self.height);
+        let mut wall_mask = vec![false; width * height];
+        for split in &self.splits {
+            let (miny, maxy, minx, maxx) = split.bbox;
+            if split.orientation == 'v' {
+                for y in miny..=maxy { wall_mask[y * width + split.coord] = true; }
+            } else {
+                for x in minx..=maxx { wall_mask[split.coord * width + x] = true; }
+            }
+        }
+        add_outer_walls(&self.shell, &mut wall_mask, width, height);
+        for door in &self.doors {
+            for &idx in &door.cells {
+                wall_mask[idx] = false;
+            }
+        }
+        wall_mask
+    }
+}
+
+/// Safety net on the total number of schema placement attempts
+const MAX_PLACEMENT_ATTEMPTS: usize = 100_000;
+/// Rooms at most this many cells wide (hallways) get a cap on floor objects
+pub const NARROW_ROOM_WIDTH: usize = 3;
+/// Narrow rooms hold at most one floor object per this many cells
+pub const NARROW_ROOM_CELLS_PER_OBJECT: usize = 6;
+
+/// Width of a room: the largest, over its cells, of the shorter of the horizontal and
+/// vertical runs of room cells through the cell, so an L of two 2-wide arms is 2 wide
+fn room_width(layout: &Layout, room: Cell) -> usize {
+    let (w, h) = (layout.width, layout.height);
+    let in_room = |x: usize, y: usize| layout.cells[y * w + x] == room;
+    let mut width = 0;
     for y in 0..h {
-        for x in 0..w {
-            if cells[y*w + x] >= 0 {
-                floor.push((x,y));
+        for x in (0..w).filter(|&x| in_room(x, y)) {
+            let run = |cells: &mut dyn Iterator<Item = bool>| cells.take_while(|&c| c).count();
+            let across = 1 + run(&mut (0..x).rev().map(|x| in_room(x, y))) + run(&mut (x + 1..w).map(|x| in_room(x, y)));
+            let down = 1 + run(&mut (0..y).rev().map(|y| in_room(x, y))) + run(&mut (y + 1..h).map(|y| in_room(x, y)));
+            width = width.max(across.min(down));
+        }
+    }
+    width
+}
+
+/// Cells furniture (non-pickable or movement-blocking objects) must stay off so every room
+/// stays traversable: the articulation cells of each room given the furniture already placed
+fn furniture_cut_cells(world: &World) -> Vec<bool> {
+    let layout = &world.layout;
+    let mut blocked = vec![false; layout.cells.len()];
+    for o in world.objects.iter().filter(|o| !o.pickable || o.blocks_movement) {
+        blocked[o.y * layout.width + o.x] = true;
+    }
+    let mut cut = vec![false; layout.cells.len()];
+    for room in 0..layout.room_names.len() {
+        for i in nav::articulation_cells(layout, room as Cell, &blocked) {
+            cut[i] = true;
+        }
+    }
+    cut
+}
+
+/// Cells of movement-blocking objects, row-major
+fn blocking_cells(world: &World) -> Vec<bool> {
+    let mut blocked = vec![false; world.layout.cells.len()];
+    for o in world.objects.iter().filter(|o| o.blocks_movement) {
+        blocked[o.y * world.layout.width + o.x] = true;
+    }
+    blocked
+}
+
+/// Room cells out of reach when `blocked` cells cannot be entered: free cells outside the
+/// largest walkable area, and blocked cells with no neighbour in it to work on them from
+fn sealed_cells(layout: &Layout, blocked: &[bool]) -> Vec<usize> {
+    let free = |i: usize| nav::walkable(layout, i) && !blocked[i];
+    let mut area = vec![None; layout.cells.len()];
+    let mut sizes = Vec::new();
+    for start in 0..layout.cells.len() {
+        if area[start].is_some() || layout.cells[start] < 0 || !free(start) {
+            continue;
+        }
+        let dist = nav::distance_field_over(layout, &[start], free);
+        for i in (0..layout.cells.len()).filter(|&i| dist[i].is_some()) {
+            area[i] = Some(sizes.len());
+        }
+        sizes.push((0..layout.cells.len()).filter(|&i| dist[i].is_some() && layout.cells[i] >= 0).count());
+    }
+    // ties go to the area found first
+    let largest = (0..sizes.len()).max_by_key(|&a| (sizes[a], std::cmp::Reverse(a)));
+    let reached = |i: usize| area[i].is_some() && area[i] == largest;
+    (0..layout.cells.len())
+        .filter(|&i| layout.cells[i] >= 0 && !reached(i))
+        .filter(|&i| !blocked[i] || !nav::neighbors(layout, i).any(reached))
+        .collect()
+}
+
+/// Move blocking objects that wall off part of the apartment, so the agent can reach every
+/// room cell with `Simulator::blocking_objects` on.
+///
+/// Placement already keeps furniture off each room's cut cells; this catches what slips
+/// through. The blocker whose removal frees the most cells moves, with its contents, to the
+/// nearest free cell of its room that leaves fewer cells sealed off. When it has nowhere to
+/// go, or no single blocker frees anything, a blocker is dropped from the world instead and
+/// its contents stay on the floor; the report then no longer counts it as placed.
+fn unseal(world: &mut World, report: &mut GenReport) {
+    let w = world.layout.width;
+    // cells the layout itself keeps apart are not the furniture's doing
+    let walled_off = sealed_cells(&world.layout, &vec![false; world.layout.cells.len()]);
+    loop {
+        let blocked = blocking_cells(world);
+        let mut sealed = sealed_cells(&world.layout, &blocked);
+        sealed.retain(|i| !walled_off.contains(i));
+        if sealed.is_empty() {
+            return;
+        }
+        let blockers: Vec<(ObjectId, usize)> =
+            world.objects.iter().filter(|o| o.blocks_movement).map(|o| (o.id, o.y * w + o.x)).collect();
+        let sealed_with = |moves: &[(usize, bool)]| {
+            let mut blocked = blocked.clone();
+            for &(cell, b) in moves {
+                blocked[cell] = b;
+            }
+            sealed_cells(&world.layout, &blocked).iter().filter(|i| !walled_off.contains(i)).count()
+        };
+        let shared = |cell: usize| blockers.iter().filter(|&&(_, c)| c == cell).count() > 1;
+        let best = blockers.iter().map(|&(id, cell)| (sealed_with(&[(cell, shared(cell))]), id, cell)).min();
+        let dropped = match best {
+            Some((freed, id, cell)) if freed < sealed.len() => {
+                let room = world.layout.cells[cell];
+                let mut free: Vec<usize> = (0..world.layout.cells.len())
+                    .filter(|&i| world.layout.cells[i] == room && world.objects_at(i % w, i / w).is_empty())
+                    .collect();
+                free.sort_by_key(|&i| ((i % w).abs_diff(cell % w) + (i / w).abs_diff(cell / w), i));
+                if let Some(to) = free.into_iter().find(|&to| sealed_with(&[(cell, shared(cell)), (to, true)]) < sealed.len()) {
+                    let name = world.object(id).map_or("object", |o| &o.name);
+                    report.warnings.push(format!("moved {} {} so every room stays reachable", name, id));
+                    world.move_object(id, to % w, to / w);
+                    continue;
+                }
+                id
+            }
+            // no single blocker frees anything (e.g. a wall of furniture two deep): drop one that borders the sealed cells
+            _ => match blockers.iter().find(|&&(_, cell)| nav::neighbors(&world.layout, cell).any(|n| sealed.contains(&n))) {
+                Some(&(id, _)) => id,
+                None => return,
+            },
+        };
+        // contents keep their cell and, with the container gone, lie on the floor
+        if let Some(object) = world.detach_object(dropped) {
+            report.warnings.push(format!("dropped {} {} so every room stays reachable", object.name, dropped));
+            report.placed_objects = report.placed_objects.saturating_sub(1);
+            report.provenance.retain(|p| p.object_id != dropped);
+            for p in report.provenance.iter_mut().filter(|p| p.inside_parent == Some(dropped)) {
+                p.inside_parent = None;
             }
         }
     }
-    // randomize schema order
-    let mut rng = StdRng::seed_from_u64(seed);
-    let mut order: Vec<&ObjectSchema> = schemas.iter().collect();
-    order.shuffle(&mut rng);
-    let mut id = 0;
-    for schema in order {
-        if id >= max_objects { break; }
+}
+
+/// Resumable object placement; each `step` makes at most one schema attempt
+struct Placement {
+    rng: StdRng,
+    floor: Vec<(usize, usize)>,
+    exhausted: Vec<Option<SkipReason>>,
+    attempts: usize,
+    id: usize,
+    max_objects: usize,
+    order: Vec<usize>,
+    cursor: usize,
+    in_pass: bool,
+    placed_before: usize,
+    done: bool,
+    /// Floor object cap per room id, set for narrow rooms
+    floor_caps: Vec<Option<usize>>,
+    /// Softmax temperature over schema preferences
+    temperature: f32,
+    /// Probability of spawning a pickable object off its target, if controlled
+    misplaced_ratio: Option<f32>,
+    /// Off-target draws not yet honoured, negative after objects forced off target
+    misplaced_owed: i64,
+}
+
+impl Placement {
+    fn new(world: &World, schema_count: usize, opts: &GenOpts, report: &mut GenReport) -> Self {
+        let max_objects = opts.max_objects;
+        // list all floor cells
+        let w = world.layout.width;
+        let h = world.layout.height;
+        let cells = &world.layout.cells;
+        let mut floor = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                if world.layout.kind_at(x, y).room().is_some() {
+                    floor.push((x,y));
+                }
+            }
+        }
+        report.requested_objects = max_objects;
+        let floor_caps = (0..world.layout.room_names.len() as Cell)
+            .map(|room| {
+                let narrow = room_width(&world.layout, room) <= NARROW_ROOM_WIDTH;
+                let area = cells.iter().filter(|&&c| c == room).count();
+                narrow.then(|| (area / NARROW_ROOM_CELLS_PER_OBJECT).max(1))
+            })
+            .collect();
+        Placement {
+            rng: StdRng::seed_from_u64(opts.effective_object_seed()),
+            floor,
+            exhausted: vec![None; schema_count],
+            attempts: 0,
+            id: 0,
+            max_objects,
+            order: Vec::new(),
+            cursor: 0,
+            in_pass: false,
+            placed_before: 0,
+            done: false,
+            floor_caps,
+            temperature: opts.placement_temperature,
+            misplaced_ratio: opts.misplaced_ratio,
+            misplaced_owed: 0,
+        }
+    }
+
+    /// Pick a candidate by softmax over `schema.preference` scores; uniform without preferences
+    fn choose_preferred<'c>(
+        &mut self,
+        world: &World,
+        schema: &ObjectSchema,
+        candidates: &'c [(usize, usize, Option<usize>)],
+    ) -> Option<&'c (usize, usize, Option<usize>)> {
+        if schema.preference.is_empty() {
+            return candidates.choose(&mut self.rng);
+        }
+        let scores: Vec<f32> = candidates
+            .iter()
+            .map(|&(x, y, _)| schema.preference.iter().filter(|(c, _)| c.check(world, x, y)).map(|&(_, w)| w).sum())
+            .collect();
+        let best = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        // shifted by the best score so the weights stay finite; the best candidates weigh 1
+        let weights: Vec<f32> = scores.iter().map(|s| ((s - best) / self.temperature).exp()).collect();
+        let index = WeightedIndex::new(&weights).ok()?.sample(&mut self.rng);
+        candidates.get(index)
+    }
+
+    /// Cells off-limits to floor placements of `schema`: articulation cells for furniture,
+    /// and every cell of a narrow room that has reached its cap
+    fn off_limits(&self, world: &World, schema: &ObjectSchema) -> Vec<bool> {
+        let layout = &world.layout;
+        let furniture = !schema.pickable || schema.blocks_movement;
+        let mut off = if furniture { furniture_cut_cells(world) } else { vec![false; layout.cells.len()] };
+        let mut occupied = vec![false; layout.cells.len()];
+        for o in &world.objects {
+            occupied[o.y * layout.width + o.x] = true;
+        }
+        let mut load = vec![0; self.floor_caps.len()];
+        for (i, _) in occupied.iter().enumerate().filter(|(_, &o)| o) {
+            if let Some(l) = usize::try_from(layout.cells[i]).ok().and_then(|room| load.get_mut(room)) {
+                *l += 1;
+            }
+        }
+        for (i, off) in off.iter_mut().enumerate() {
+            let Ok(room) = usize::try_from(layout.cells[i]) else { continue };
+            if self.floor_caps.get(room).copied().flatten().is_some_and(|cap| load[room] >= cap) {
+                *off = true;
+            }
+        }
+        off
+    }
+
+    /// Start a pass or make the next schema attempt; returns false once placement has finished.
+    ///
+    /// Passes keep cycling over the schemas (one instance per schema per pass) until the
+    /// requested count is reached or a whole pass places nothing. Every pass retries the
+    /// schemas that found no cell before, since objects placed since, such as the container an
+    /// `InsideOf` target needs, may give them one.
+    fn step(&mut self, world: &mut World, schemas: &[ObjectSchema], report: &mut GenReport) -> bool {
+        if self.done {
+            return false;
+        }
+        if !self.in_pass {
+            if self.id >= self.max_objects || self.exhausted.iter().all(Option::is_some) {
+                self.finish(schemas, report);
+                return false;
+            }
+            report.passes += 1;
+            self.placed_before = self.id;
+            // randomize schema order
+            self.order = (0..schemas.len()).collect();
+            self.order.shuffle(&mut self.rng);
+            self.cursor = 0;
+            self.in_pass = true;
+            return true;
+        }
+        if self.cursor < self.order.len() && self.id < self.max_objects && self.attempts < MAX_PLACEMENT_ATTEMPTS {
+            let si = self.order[self.cursor];
+            self.cursor += 1;
+            self.attempts += 1;
+            self.place_one(world, schemas, si, report);
+            return true;
+        }
+        self.cursor = self.order.len();
+        self.in_pass = false;
+        if self.id == self.placed_before || self.attempts >= MAX_PLACEMENT_ATTEMPTS {
+            self.finish(schemas, report);
+            return false;
+        }
+        true
+    }
+
+    /// Try to place one instance of `schemas[si]`, marking the schema exhausted if impossible
+    /// and clearing the mark once it places again
+    fn place_one(&mut self, world: &mut World, schemas: &[ObjectSchema], si: usize, report: &mut GenReport) {
+        let schema = &schemas[si];
+        let floor = &self.floor;
         // first, we check if the schema's target constraints *can* be satisfied
         // if not, skip this schema
         let mut target_candidates = Vec::new();
-        for &(x,y) in &floor {
+        for &(x,y) in floor {
             if schema.target.check(world, x, y) {
                 // if there is no other object in the cell, add it to candidates
                 if world.objects.iter().all(|o| o.x != x || o.y != y) {
@@ -410,104 +1289,505 @@ fn place_objects(world: &mut World, schemas: &[ObjectSchema], seed: u64, max_obj
             }
         }
         for parent in world.objects.iter() {
-            if parent.capacity > 0 && schema.target.check(world, parent.x, parent.y) {
+            if parent.has_room() && schema.target.check(world, parent.x, parent.y) {
                 target_candidates.push((parent.x, parent.y));
             }
         }
         // if there are no candidates, skip this schema
         if target_candidates.is_empty() {
-            continue;
+            self.exhausted[si] = Some(SkipReason::NoTargetCell);
+            return;
         }
 
         // gather placements: floor + weighted inside
-        let mut candidates = Vec::new();
-        for &(x,y) in &floor {
-            if schema.constraint.check(world, x, y) {
-                // if there is no other object in the cell, add it to candidates
-                if world.objects.iter().all(|o| o.x != x || o.y != y) {
-                    candidates.push((x,y,false));
+        let off_limits = self.off_limits(world, schema);
+        // with a misplaced ratio, pickable objects go on target cells or on spawn cells off target;
+        // only objects with both kinds of cell are drawn for, and an off-target draw that cannot
+        // be honoured is owed to the next of them, so the ratio holds over misplaceable objects
+        let mut misplaceable = false;
+        let mut candidates = match self.misplaced_ratio {
+            Some(ratio) if schema.pickable => {
+                let off = self.spawn_candidates(world, schema, &off_limits, Some(false));
+                let on = self.spawn_candidates(world, schema, &off_limits, Some(true));
+                misplaceable = !off.is_empty();
+                if misplaceable {
+                    self.misplaced_owed += self.rng.gen_bool(ratio as f64) as i64;
+                }
+                if misplaceable && (self.misplaced_owed > 0 || on.is_empty()) {
+                    self.misplaced_owed -= 1;
+                    off
+                } else {
+                    on
                 }
             }
-        }
-        for parent in world.objects.iter() {
-            if parent.capacity > 0 && schema.constraint.check(world, parent.x, parent.y) && schema.pickable{
-                candidates.push((parent.x, parent.y, true));
-            }
-        }
+            _ => self.spawn_candidates(world, schema, &off_limits, None),
+        };
         // fallback to allow placement when no valid domain
         if candidates.is_empty() {
-            continue;
+            self.exhausted[si] = Some(SkipReason::NoSpawnCell);
+            return;
         }
+        let candidates_considered = candidates.len();
         // if there is both inside and non-inside choose 50/50 between those first and then filter candidates
         // first, find out if there are both some inside and outside candidates
         let mut inside = false;
         let mut outside = false;
-        for &(_, _, is_inside) in &candidates {
-            if is_inside { inside = true; } else { outside = true; }
+        for &(_, _, parent) in &candidates {
+            if parent.is_some() { inside = true; } else { outside = true; }
         }
 
         // if both, pick either inside or outside 50/50
         // if only one, pick that one
         if inside && outside {
-            let pick_inside = rng.gen_bool(0.5);
-            candidates.retain(|&(_, _, is_inside)| is_inside == pick_inside);
+            let pick_inside = self.rng.gen_bool(0.5);
+            candidates.retain(|&(_, _, parent)| parent.is_some() == pick_inside);
         } else if inside {
-            candidates.retain(|&(_, _, is_inside)| is_inside);
+            candidates.retain(|&(_, _, parent)| parent.is_some());
         } else if outside {
-            candidates.retain(|&(_, _, is_inside)| !is_inside);
-        }
-
-        if let Some(&(x,y,inside)) = candidates.choose(&mut rng) {
-            if inside {
-                let pi = world.objects.iter().position(|o| o.x==x && o.y==y).unwrap();
-                world.objects[pi].contents.push(id);
-            }
-            let obj = Object { id,
-                              name: schema.name,
-                              capacity: schema.capacity,
-                              pickable: schema.pickable,
-                              description: schema.description,
-                              x,y,
-                              contents: Vec::new() };
-            world.objects.push(obj);
-            id += 1;
-        }
-    }
-}
-
-pub fn generate(opts: &GenOpts) -> World {
-    let width = opts.width;
-    let height = opts.height;
-    let mut rng = StdRng::seed_from_u64(opts.seed);
-    let shell = make_concave_shell(width, height, opts.seed);
-    let (regions, mut wall_mask) = bsp_with_walls(&shell, opts.max_rooms, opts.seed, width, height);
-    let labels = build_labels(&regions, width, height);
-    let door_mask = carve_doors(&labels, &mut wall_mask, &shell, opts.seed, width, height);
-    let mut cells = Vec::with_capacity(width * height);
-    for i in 0..width * height {
-        if !shell[i] {
-            cells.push(OUTSIDE);
-        } else if wall_mask[i] {
-            cells.push(WALL);
-        } else if door_mask[i] {
-            cells.push(CLOSED_DOOR);
+            candidates.retain(|&(_, _, parent)| parent.is_none());
+        }
+
+        if let Some(&(x,y,parent)) = self.choose_preferred(world, schema, &candidates) {
+            report.provenance.push(PlacementProvenance {
+                object_id: self.id,
+                schema_name: schema.name,
+                chosen_cell: (x, y),
+                inside_parent: None,
+                satisfied_branch: schema.constraint.satisfied_branch(world, x, y).unwrap_or_default(),
+                candidates_considered,
+            });
+            // the chosen parent itself, not whichever object comes first on its cell
+            if let Some(pi) = parent {
+                world.objects[pi].contents.push(self.id);
+                report.provenance.last_mut().unwrap().inside_parent = Some(world.objects[pi].id);
+            }
+            self.exhausted[si] = None;
+            if misplaceable {
+                report.misplaceable_objects.push(self.id);
+            }
+            self.spawn(world, schemas, schema, x, y);
+        }
+    }
+
+    /// Free floor cells and containers for `schema`, as (x, y, parent index).
+    ///
+    /// `on_target` None admits cells satisfying the spawn constraint; Some(true) cells
+    /// satisfying the target instead, Some(false) spawn cells that miss the target.
+    fn spawn_candidates(&self, world: &World, schema: &ObjectSchema, off_limits: &[bool], on_target: Option<bool>) -> Vec<(usize, usize, Option<usize>)> {
+        let admits = |x, y| match on_target {
+            None => schema.constraint.check(world, x, y),
+            Some(true) => schema.target.check(world, x, y),
+            Some(false) => schema.constraint.check(world, x, y) && !schema.target.check(world, x, y),
+        };
+        let mut candidates = Vec::new();
+        for &(x,y) in &self.floor {
+            if !off_limits[y * world.layout.width + x] && admits(x, y) {
+                // if there is no other object in the cell, add it to candidates
+                if world.objects.iter().all(|o| o.x != x || o.y != y) {
+                    candidates.push((x,y,None));
+                }
+            }
+        }
+        for (pi, parent) in world.objects.iter().enumerate() {
+            if parent.has_room() && admits(parent.x, parent.y) && schema.pickable{
+                candidates.push((parent.x, parent.y, Some(pi)));
+            }
+        }
+        candidates
+    }
+
+    /// Add a new instance of `schema` at (x, y) under the next free id.
+    ///
+    /// Ids count up from 0 in placement order; `World::canonicalize` relies on this order.
+    fn spawn(&mut self, world: &mut World, schemas: &[ObjectSchema], schema: &ObjectSchema, x: usize, y: usize) {
+        world.add_object(Object::from_schema_among(self.id, schema, schemas, x, y));
+        self.id += 1;
+    }
+
+    /// Place the fixtures of every kit matching a room's name (or a component of a merged name).
+    ///
+    /// Candidates are free cells of the room satisfying the fixture's spawn constraint,
+    /// relaxed to any free wall-adjacent cell of the room when none do. Fixtures that cannot
+    /// be picked up never go on cells that would cut the room apart; the narrow-room cap does
+    /// not apply to kits.
+    fn place_kits(&mut self, world: &mut World, schemas: &[ObjectSchema], kits: &[RoomKit], report: &mut GenReport) {
+        if self.max_objects == 0 {
+            return;
+        }
+        let w = world.layout.width;
+        for room in 0..world.layout.room_names.len() {
+            let name = world.layout.room_names[room].clone();
+            for kit in kits.iter().filter(|k| name.split(COMPOUND_NAME_SEPARATOR).any(|part| part == k.room)) {
+                for fixture in &kit.fixtures {
+                    let Some(schema) = schemas.iter().find(|s| s.name == fixture) else {
+                        report.kit_failures.push((name.to_string(), fixture.clone()));
+                        continue;
+                    };
+                    let furniture = !schema.pickable || schema.blocks_movement;
+                    let cut = if furniture { furniture_cut_cells(world) } else { vec![false; world.layout.cells.len()] };
+                    let free: Vec<(usize, usize)> = self
+                        .floor
+                        .iter()
+                        .copied()
+                        .filter(|&(x, y)| world.layout.cells[y * w + x] == room as Cell && !cut[y * w + x])
+                        .filter(|&(x, y)| world.objects.iter().all(|o| o.x != x || o.y != y))
+                        .collect();
+                    let mut candidates: Vec<(usize, usize)> =
+                        free.iter().copied().filter(|&(x, y)| schema.constraint.check(world, x, y)).collect();
+                    if candidates.is_empty() {
+                        candidates = free
+                            .iter()
+                            .copied()
+                            .filter(|&(x, y)| ObjectConstraint::AdjacentObstacle.check(world, x, y))
+                            .collect();
+                    }
+                    let Some(&(x, y)) = candidates.choose(&mut self.rng) else {
+                        report.kit_failures.push((name.to_string(), fixture.clone()));
+                        continue;
+                    };
+                    report.provenance.push(PlacementProvenance {
+                        object_id: self.id,
+                        schema_name: schema.name,
+                        chosen_cell: (x, y),
+                        inside_parent: None,
+                        satisfied_branch: format!("RoomKit({})", kit.room),
+                        candidates_considered: candidates.len(),
+                    });
+                    self.spawn(world, schemas, schema, x, y);
+                }
+            }
+        }
+        for (room, fixture) in &report.kit_failures {
+            report.warnings.push(format!("could not place kit fixture {} in the {}", fixture, room));
+        }
+    }
+
+    fn finish(&mut self, schemas: &[ObjectSchema], report: &mut GenReport) {
+        self.done = true;
+        let (id, max_objects, attempts) = (self.id, self.max_objects, self.attempts);
+        report.placed_objects = id;
+        (report.exhausted_schemas, report.exhausted_reasons) =
+            schemas.iter().zip(&self.exhausted).filter_map(|(s, &e)| Some((s.name, e?))).unzip();
+        if attempts >= MAX_PLACEMENT_ATTEMPTS {
+            report.warnings.push(format!("placement stopped after {} attempts", attempts));
+        }
+        if id * 2 < max_objects {
+            report.warnings.push(format!(
+                "placed only {} of {} requested objects; constraints allow no more",
+                id, max_objects
+            ));
+        }
+    }
+}
+
+/// Generate a world after validating `opts`.
+///
+/// Fails if the options are invalid, the layout ends up without rooms, or, in strict
+/// mode, any room kit fixture could not be placed.
+pub fn generate(opts: &GenOpts) -> Result<World, GenError> {
+    generate_with_schemas(opts, &scattered_schemas())
+}
+
+/// `generate` with `schemas` instead of the built-in catalog, e.g. a workshop with only tools.
+///
+/// Room kit fixtures without a schema in `schemas` are left out rather than reported, so
+/// only the given objects appear. Names in the schemas can be built at runtime with
+/// `world::intern`, and constraint name lists as `Names::Owned`.
+pub fn generate_with_schemas(opts: &GenOpts, schemas: &[ObjectSchema]) -> Result<World, GenError> {
+    let kits = RoomKit::defaults()
+        .into_iter()
+        .map(|mut kit| {
+            kit.fixtures.retain(|f| schemas.iter().any(|s| s.name == f));
+            kit
+        })
+        .collect();
+    let mut job = GenJob::new(opts).with_schemas(schemas.to_vec()).with_room_kits(kits);
+    while job.step() {}
+    job.finish().map(|(world, _, _)| world)
+}
+
+/// Generate a world together with a report describing the object placement and difficulty
+pub fn generate_with_report(opts: &GenOpts) -> Result<(World, GenReport), GenError> {
+    let (world, mut report, _) = generate_inner(opts, None)?;
+    report.difficulty = difficulty::estimate(&world);
+    Ok((world, report))
+}
+
+/// Generate a world while recording every step of the layout construction
+pub fn generate_with_trace(opts: &GenOpts) -> Result<(World, GenTrace), GenError> {
+    let trace = GenTrace { width: opts.width, height: opts.height, ..Default::default() };
+    let (world, _, trace) = generate_inner(opts, Some(trace))?;
+    Ok((world, trace.expect("trace was enabled")))
+}
+
+/// Generate with seeds `opts.seed`, `opts.seed + 1`, ... until a world comes out, at most
+/// `attempts` times (at least once); returns the world and the number of retries it took.
+///
+/// Invalid options fail immediately, since no seed can fix them; otherwise the error of the
+/// last attempt is returned. Explicit `layout_seed` and `object_seed` overrides are kept.
+pub fn generate_or_retry(opts: &GenOpts, attempts: u32) -> Result<(World, u32), GenError> {
+    opts.validate()?;
+    let mut last = GenError::NoRooms;
+    for retry in 0..attempts.max(1) {
+        match generate(&GenOpts { seed: opts.seed.wrapping_add(retry as u64), ..opts.clone() }) {
+            Ok(world) => return Ok((world, retry)),
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+/// The built-in catalog minus task props, which are added by their task, and appliance
+/// products such as clean plates, which only come out of `Simulator::run_appliance`
+fn scattered_schemas() -> Vec<ObjectSchema> {
+    let catalog = ObjectSchema::catalog();
+    let produced = |name: &str| catalog.iter().any(|s| s.transforms.iter().any(|&(_, to)| to == name));
+    ObjectSchema::default_schemas().into_iter().filter(|s| s.name != GROCERY_BAG && !produced(s.name)).collect()
+}
+
+pub(crate) fn generate_inner(opts: &GenOpts, trace: Option<GenTrace>) -> Result<(World, GenReport, Option<GenTrace>), GenError> {
+    let mut job = GenJob::new(opts);
+    job.trace = trace;
+    while job.step() {}
+    job.finish()
+}
+
+/// Outcome of `GenJob::run_for`
+#[derive(Debug)]
+pub enum GenProgress {
+    /// Generation is still running; the value is the completed fraction in [0, 1)
+    InProgress(f32),
+    /// Generation has finished
+    Done(World),
+    /// Generation stopped with an error; the job must not be advanced afterwards
+    Failed(GenError),
+}
+
+enum GenStage {
+    Shell,
+    Split,
+    Doors,
+    Kits,
+    Place,
+    Done,
+}
+
+/// Resumable generator that can be advanced in small slices of work.
+///
+/// The work is checkpointed between the shell, every BSP split, door carving and
+/// every object placement attempt. The finished world is identical to the one
+/// produced by `generate` for the same options.
+pub struct GenJob {
+    opts: GenOpts,
+    stage: GenStage,
+    rng: StdRng,
+    shell: Vec<bool>,
+    bsp: Option<Bsp>,
+    world: Option<World>,
+    placement: Option<Placement>,
+    schemas: Vec<ObjectSchema>,
+    kits: Vec<RoomKit>,
+    report: GenReport,
+    trace: Option<GenTrace>,
+    error: Option<GenError>,
+}
+
+impl GenJob {
+    /// Start a job; invalid options end it before any work, with `run_for` returning `Failed`
+    pub fn new(opts: &GenOpts) -> Self {
+        let mut job = GenJob {
+            opts: opts.clone(),
+            stage: GenStage::Shell,
+            rng: StdRng::seed_from_u64(opts.effective_layout_seed()),
+            shell: Vec::new(),
+            bsp: None,
+            world: None,
+            placement: None,
+            schemas: scattered_schemas(),
+            kits: RoomKit::defaults(),
+            report: GenReport::default(),
+            trace: None,
+            error: None,
+        };
+        if let Err(e) = opts.validate() {
+            job.fail(e);
+        }
+        job
+    }
+
+    fn fail(&mut self, error: GenError) {
+        self.error = Some(error);
+        self.stage = GenStage::Done;
+    }
+
+    /// Replace the object schemas scattered over the rooms and used by room kits
+    pub fn with_schemas(mut self, schemas: Vec<ObjectSchema>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Replace the room kits placed before the general placement loop
+    pub fn with_room_kits(mut self, kits: Vec<RoomKit>) -> Self {
+        self.kits = kits;
+        self
+    }
+
+    /// Perform one unit of work; returns false once generation has finished or failed
+    pub fn step(&mut self) -> bool {
+        let opts = self.opts.clone();
+        let (width, height) = (opts.width, opts.height);
+        match self.stage {
+            GenStage::Shell => {
+                self.shell = make_concave_shell(width, height, opts.effective_layout_seed());
+                if let Some(t) = self.trace.as_mut() {
+                    t.shell = self.shell.clone();
+                }
+                match Bsp::new(&self.shell, &opts) {
+                    Ok(bsp) => {
+                        self.bsp = Some(bsp);
+                        self.stage = GenStage::Split;
+                    }
+                    Err(e) => self.fail(e),
+                }
+            }
+            GenStage::Split => {
+                let bsp = self.bsp.as_mut().expect("BSP state exists while splitting");
+                match bsp.split_once(width, height, self.trace.as_mut()) {
+                    Ok(true) => {}
+                    Ok(false) => self.stage = GenStage::Doors,
+                    Err(e) => self.fail(e),
+                }
+            }
+            GenStage::Doors => {
+                let bsp = self.bsp.take().expect("BSP state exists before carving doors");
+                let (regions, mut wall_mask) = bsp.finish(&self.shell, width, height);
+                let shell = &self.shell;
+                let labels = build_labels(&regions, width, height);
+                let (door_mask, mut doors) = carve_doors(&labels, &mut wall_mask, shell, &opts, self.trace.as_mut());
+                let mut cells = Vec::with_capacity(width * height);
+                for i in 0..width * height {
+                    if !shell[i] {
+                        cells.push(OUTSIDE);
+                    } else if wall_mask[i] {
+                        cells.push(WALL);
+                    } else if door_mask[i] {
+                        cells.push(CLOSED_DOOR);
+                    } else {
+                        cells.push(labels[i]);
+                    }
+                }
+                // assign randomized names to each room region
+                let mut pool = opts.effective_room_name_pool();
+                pool.shuffle(&mut self.rng);
+                let mut room_names = room_names_from(&pool, regions.len());
+                if let Err(e) = require_rooms(&mut room_names, &regions, &opts.required_rooms) {
+                    self.fail(e);
+                    return false;
+                }
+                if opts.open_plan_prob > 0.0 && self.rng.gen::<f32>() < opts.open_plan_prob {
+                    if let Some(merged) = merge_open_plan(&mut cells, &mut room_names, width, height, &mut self.rng) {
+                        merge_doors(&mut doors, merged);
+                    }
+                }
+                if !cells.iter().any(|&c| c >= 0) {
+                    self.fail(GenError::NoRooms);
+                    return false;
+                }
+                let mut layout = Layout::new(width, height, cells, room_names);
+                layout.doors = doors;
+                if opts.zone_min_area > 0 {
+                    assign_zones(&mut layout, opts.zone_min_area, &mut self.rng);
+                }
+                let world = World::new(layout, Vec::new());
+                self.placement = Some(Placement::new(&world, self.schemas.len(), &opts, &mut self.report));
+                self.world = Some(world);
+                self.stage = GenStage::Kits;
+            }
+            GenStage::Kits => {
+                let placement = self.placement.as_mut().expect("placement state exists while placing");
+                let world = self.world.as_mut().expect("world exists while placing");
+                placement.place_kits(world, &self.schemas, &self.kits, &mut self.report);
+                self.stage = GenStage::Place;
+                if opts.strict && !self.report.kit_failures.is_empty() {
+                    self.fail(GenError::KitFailed(self.report.kit_failures.clone()));
+                }
+            }
+            GenStage::Place => {
+                let placement = self.placement.as_mut().expect("placement state exists while placing");
+                let world = self.world.as_mut().expect("world exists while placing");
+                if !placement.step(world, &self.schemas, &mut self.report) {
+                    if opts.task.has_delivery() {
+                        match task::add_delivery(world, &mut placement.rng) {
+                            Ok(delivery) => self.report.delivery = Some(delivery),
+                            Err(e) => self.report.warnings.push(format!("no delivery task: {}", e)),
+                        }
+                    }
+                    unseal(world, &mut self.report);
+                    self.stage = GenStage::Done;
+                }
+            }
+            GenStage::Done => return false,
+        }
+        true
+    }
+
+    /// Completed fraction of the work in [0, 1]
+    pub fn progress(&self) -> f32 {
+        match self.stage {
+            GenStage::Shell => 0.0,
+            GenStage::Split => {
+                let bsp = self.bsp.as_ref().expect("BSP state exists while splitting");
+                0.05 + 0.45 * bsp.regions.len() as f32 / bsp.target_rooms.max(1) as f32
+            }
+            GenStage::Doors => 0.5,
+            GenStage::Kits => 0.55,
+            GenStage::Place => {
+                let placement = self.placement.as_ref().expect("placement state exists while placing");
+                0.6 + 0.4 * placement.id as f32 / placement.max_objects.max(1) as f32
+            }
+            GenStage::Done => 1.0,
+        }
+    }
+
+    /// Perform up to `budget_ops` units of work.
+    ///
+    /// Returns `Done` with the world once finished; the job must not be advanced afterwards.
+    pub fn run_for(&mut self, budget_ops: usize) -> GenProgress {
+        for _ in 0..budget_ops {
+            if !self.step() {
+                break;
+            }
+        }
+        if let Some(e) = &self.error {
+            GenProgress::Failed(e.clone())
+        } else if self.is_done() {
+            GenProgress::Done(self.world.take().expect("GenJob already returned its world"))
         } else {
-            cells.push(labels[i]);
+            GenProgress::InProgress(self.progress().min(0.99))
+        }
+    }
+
+    /// Whether all stages have completed
+    pub fn is_done(&self) -> bool {
+        matches!(self.stage, GenStage::Done)
+    }
+
+    fn finish(self) -> Result<(World, GenReport, Option<GenTrace>), GenError> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok((self.world.expect("generation finished"), self.report, self.trace)),
         }
     }
-    // assign randomized names to each room region
-    let mut pool = ROOM_NAME_POOL.to_vec();
-    pool.shuffle(&mut rng);
-    let region_count = regions.len();
-    let room_names = pool.into_iter().take(region_count).collect();
-    let layout = Layout::new(width, height, cells, room_names);
-    let mut world = World { layout, objects: Vec::new() };
-    place_objects(&mut world, &ObjectSchema::default_schemas(), opts.seed, opts.max_objects);
-    world
 }
 
 /// Pool of possible room names; randomized per world
 pub const ROOM_NAME_POOL: &[&str] = &[
     "Living Room", "Kitchen", "Bedroom", "Bathroom", "Dining Room",
     "Study", "Guest Room", "Office", "Hallway", "Playroom",
-];
\ No newline at end of file
+];
+
+/// Pool of zone names used when subdividing large rooms
+pub const ZONE_NAME_POOL: &[&str] = &[
+    "seating area", "window side", "reading corner", "work area",
+    "storage corner", "entry area",
+];
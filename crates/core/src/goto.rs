@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::action::{Action, ActionError, StepOutcome};
+use crate::nav;
+use crate::object::ObjectId;
+use crate::search::object_score;
+use crate::sim::Simulator;
+use crate::world::room_name_parts;
+use crate::cell::CellKind;
+
+/// Why a `go_to_*` macro did not reach its target
+#[derive(Debug, PartialEq)]
+pub enum GoToError {
+    /// No room with this id has any cells
+    UnknownRoom(usize),
+    /// No object with this id is in the world or held
+    UnknownObject(ObjectId),
+    /// No walking route to the target exists
+    Unreachable,
+    /// A primitive action failed mid-route; `progress` covers the actions executed before it
+    Interrupted { progress: StepOutcome, error: ActionError },
+    /// An action left the agent off the planned route, e.g. because a hook replaced it
+    OffRoute { progress: StepOutcome },
+}
+
+impl fmt::Display for GoToError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoToError::UnknownRoom(room) => write!(f, "unknown room {}", room),
+            GoToError::UnknownObject(id) => write!(f, "unknown object {}", id),
+            GoToError::Unreachable => f.write_str("target is unreachable"),
+            GoToError::Interrupted { progress, error } => {
+                write!(f, "interrupted after {} steps: {}", progress.primitive_steps, error)
+            }
+            GoToError::OffRoute { progress } => write!(f, "left the planned route after {} steps", progress.primitive_steps),
+        }
+    }
+}
+
+impl std::error::Error for GoToError {}
+
+/// A high-level command understood by `Simulator::parse_command`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    GoToRoom(usize),
+    GoToObject(ObjectId),
+}
+
+/// The move onto the neighbouring cell at offset (dx, dy)
+fn move_toward(dx: i8, dy: i8) -> Action {
+    match (dx, dy) {
+        (0, -1) => Action::MoveUp,
+        (0, 1) => Action::MoveDown,
+        (-1, 0) => Action::MoveLeft,
+        _ => Action::MoveRight,
+    }
+}
+
+impl Simulator {
+    /// Walk to the nearest cell of a room, opening closed doors on the way.
+    ///
+    /// Every primitive action goes through `step` (so hooks apply) and is followed by a
+    /// `tick`; don't tick again for the returned outcome.
+    pub fn go_to_room(&mut self, room: usize) -> Result<StepOutcome, GoToError> {
+        let layout = &self.world.layout;
+        let Ok(cell) = i8::try_from(room) else { return Err(GoToError::UnknownRoom(room)) };
+        let goals: Vec<usize> = (0..layout.cells.len()).filter(|&i| layout.cells[i] == cell).collect();
+        if goals.is_empty() {
+            return Err(GoToError::UnknownRoom(room));
+        }
+        self.follow_route(&goals)
+    }
+
+    /// Walk onto an object's cell (its container's, if stored), opening closed doors on the way.
+    /// A cell that `blocking_object_at` keeps the agent off is reached by walking next to it.
+    ///
+    /// Holding the object counts as already being there. See `go_to_room` for how steps are taken.
+    pub fn go_to_object(&mut self, object_id: ObjectId) -> Result<StepOutcome, GoToError> {
+        let layout = &self.world.layout;
+        if self.holding.as_ref().is_some_and(|o| o.id == object_id) {
+            return self.follow_route(&[self.agent.y * layout.width + self.agent.x]);
+        }
+        let obj = self.world.object(object_id).ok_or(GoToError::UnknownObject(object_id))?;
+        let (x, y) = (obj.x, obj.y);
+        if self.blocking_object_at(x, y).is_none() {
+            return self.follow_route(&[y * layout.width + x]);
+        }
+        let goals: Vec<usize> = nav::neighbors(layout, y * layout.width + x).collect();
+        self.follow_route(&goals)
+    }
+
+    /// Walk to the cell (x, y), opening closed doors on the way. See `go_to_room` for how steps are taken.
+    pub fn go_to(&mut self, x: usize, y: usize) -> Result<StepOutcome, GoToError> {
+        let layout = &self.world.layout;
+        if x >= layout.width || y >= layout.height {
+            return Err(GoToError::Unreachable);
+        }
+        self.follow_route(&[y * layout.width + x])
+    }
+
+    /// Actions that walk the agent to (x, y): moves, each closed door on the way preceded by
+    /// the `Interact` that opens it. `None` when (x, y) is off the grid or unreachable; without
+    /// `through_closed_doors` only room cells and open doors are walked over. Cells of blocking
+    /// objects are avoided when `blocking_objects` is on.
+    ///
+    /// The plan is computed without acting, so it goes stale if doors change before it runs.
+    pub fn path_to(&self, x: usize, y: usize, through_closed_doors: bool) -> Option<Vec<Action>> {
+        let layout = &self.world.layout;
+        let w = layout.width;
+        if x >= w || y >= layout.height {
+            return None;
+        }
+        let from = self.agent.y * w + self.agent.x;
+        let route = if through_closed_doors {
+            self.walking_route(from, &[y * w + x])?
+        } else {
+            let blocked = self.blocked_cells();
+            let passable = |i: usize| layout.kind_at(i % w, i / w).is_passable() && (i == from || !blocked[i]);
+            nav::find_path_over(layout, from, &[y * w + x], passable)?
+        };
+        let mut actions = Vec::with_capacity(route.len());
+        let mut at = from;
+        for next in route {
+            let (dx, dy) = ((next % w) as i8 - (at % w) as i8, (next / w) as i8 - (at / w) as i8);
+            if layout.kind_at(next % w, next / w) == CellKind::ClosedDoor {
+                actions.push(Action::Interact { dx, dy });
+            }
+            actions.push(move_toward(dx, dy));
+            at = next;
+        }
+        Some(actions)
+    }
+
+    /// Moves needed to walk to (x, y), through closed doors and around blocking objects as
+    /// `path_to` does; opening doors is not counted. `None` when off the grid or unreachable.
+    pub fn distance_to(&self, x: usize, y: usize) -> Option<usize> {
+        let layout = &self.world.layout;
+        if x >= layout.width || y >= layout.height {
+            return None;
+        }
+        let w = layout.width;
+        self.walking_route(self.agent.y * w + self.agent.x, &[y * w + x]).map(|route| route.len())
+    }
+
+    /// `nav::find_path` that also keeps off cells of blocking objects, except the start
+    pub(crate) fn walking_route(&self, from: usize, goals: &[usize]) -> Option<Vec<usize>> {
+        let layout = &self.world.layout;
+        let blocked = self.blocked_cells();
+        nav::find_path_over(layout, from, goals, |i| nav::walkable(layout, i) && (i == from || !blocked[i]))
+    }
+
+    /// Walk to the nearest of the given row-major cells
+    pub(crate) fn follow_route(&mut self, goals: &[usize]) -> Result<StepOutcome, GoToError> {
+        let w = self.world.layout.width;
+        let route = self.walking_route(self.agent.y * w + self.agent.x, goals).ok_or(GoToError::Unreachable)?;
+        let mut progress = StepOutcome {
+            action: Action::Noop,
+            steps: self.steps,
+            reward: 0.0,
+            tidiness_delta: 0.0,
+            primitive_steps: 0,
+            events: Vec::new(),
+            done: self.is_done(),
+        };
+        for next in route {
+            let (dx, dy) = ((next % w) as i8 - self.agent.x as i8, (next / w) as i8 - self.agent.y as i8);
+            // doors are checked when reached, since they may have changed since planning
+            if self.world.layout.kind_at(next % w, next / w) == CellKind::ClosedDoor {
+                self.run_primitive(Action::Interact { dx, dy }, &mut progress)?;
+            }
+            self.run_primitive(move_toward(dx, dy), &mut progress)?;
+            if self.agent.y * w + self.agent.x != next {
+                return Err(GoToError::OffRoute { progress });
+            }
+        }
+        Ok(progress)
+    }
+
+    fn run_primitive(&mut self, action: Action, progress: &mut StepOutcome) -> Result<(), GoToError> {
+        match self.step(action) {
+            Ok(outcome) => {
+                progress.action = outcome.action;
+                progress.steps = outcome.steps;
+                progress.reward += outcome.reward;
+                progress.tidiness_delta += outcome.tidiness_delta;
+                progress.primitive_steps += 1;
+                progress.events.extend(self.tick());
+                progress.done = self.is_done();
+                Ok(())
+            }
+            Err(error) => Err(GoToError::Interrupted { progress: progress.clone(), error }),
+        }
+    }
+
+    /// Parse "go to the kitchen" or "go to the fruit bowl" style commands.
+    ///
+    /// Rooms match by name (or one part of a merged room's name). Objects match by schema name
+    /// or custom label with `search::fuzzy_score`, so "dish washer" finds the Dishwasher; the
+    /// best score of at least 0.5 wins, and among equally good matches the nearest by walking distance.
+    pub fn parse_command(&self, text: &str) -> Option<Command> {
+        let text = text.trim().trim_end_matches(['.', '!']).to_lowercase();
+        let rest = text.strip_prefix("go to ").or_else(|| text.strip_prefix("goto "))?.trim();
+        let target = rest.strip_prefix("the ").unwrap_or(rest).trim();
+        let layout = &self.world.layout;
+        let room = layout.room_names.iter().position(|name| {
+            room_name_parts(name).chain([&**name]).any(|part| part.to_lowercase() == target)
+        });
+        if let Some(room) = room.filter(|&r| layout.cells.contains(&(r as i8))) {
+            return Some(Command::GoToRoom(room));
+        }
+        let dist = nav::distance_field(layout, &[self.agent.y * layout.width + self.agent.x]);
+        let scored: Vec<_> = self.world.objects.iter().map(|o| (o, object_score(target, o))).collect();
+        let best = scored.iter().map(|&(_, s)| s).fold(0.0, f32::max);
+        if best < 0.5 {
+            return None;
+        }
+        scored
+            .iter()
+            .filter(|&&(_, s)| s == best)
+            .min_by_key(|(o, _)| (dist[o.y * layout.width + o.x].unwrap_or(usize::MAX), o.id))
+            .map(|(o, _)| Command::GoToObject(o.id))
+    }
+
+    /// Execute a parsed command
+    pub fn run_command(&mut self, command: Command) -> Result<StepOutcome, GoToError> {
+        match command {
+            Command::GoToRoom(room) => self.go_to_room(room),
+            Command::GoToObject(id) => self.go_to_object(id),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::action::{Action, ActionError, StepOutcome};
+use crate::world::room_name_parts;
+use crate::sim::Simulator;
+
+/// What a hook wants `Simulator::step` to do with an action
+#[derive(Debug, PartialEq)]
+pub enum HookDecision {
+    Allow,
+    /// Refuse the action with the given error
+    Deny(ActionError),
+    /// Execute a different action instead; later hooks see the replacement
+    Replace(Action),
+}
+
+/// Custom simulator rule, consulted by `Simulator::step` in registration order
+pub trait SimHook: Send {
+    /// Called before an action runs
+    fn before_action(&mut self, _sim: &Simulator, _action: &Action) -> HookDecision {
+        HookDecision::Allow
+    }
+
+    /// Called after an action succeeded; the returned value is added to the step's reward
+    fn after_action(&mut self, _sim: &Simulator, _outcome: &StepOutcome) -> f32 {
+        0.0
+    }
+}
+
+/// Registered hooks of a simulator
+#[derive(Default)]
+pub(crate) struct Hooks(Vec<Box<dyn SimHook>>);
+
+impl Hooks {
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn SimHook>> {
+        self.0.iter_mut()
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hooks({})", self.0.len())
+    }
+}
+
+impl Simulator {
+    /// Register a hook; it runs after all previously added hooks
+    pub fn add_hook(&mut self, hook: Box<dyn SimHook>) {
+        self.hooks.0.push(hook);
+    }
+
+    /// Remove every registered hook
+    pub fn clear_hooks(&mut self) {
+        self.hooks.0.clear();
+    }
+
+    /// Number of registered hooks
+    pub fn hook_count(&self) -> usize {
+        self.hooks.0.len()
+    }
+}
+
+/// Forbids moving into the named room from outside it
+#[derive(Debug, Clone)]
+pub struct RoomBan {
+    pub room: String,
+}
+
+impl RoomBan {
+    fn in_room(&self, sim: &Simulator, x: usize, y: usize) -> bool {
+        let layout = &sim.world.layout;
+        let cell = layout.cells[y * layout.width + x];
+        // merged open-plan rooms are banned when any component matches
+        cell >= 0 && room_name_parts(&layout.room_names[cell as usize]).any(|part| part == self.room)
+    }
+}
+
+impl SimHook for RoomBan {
+    fn before_action(&mut self, sim: &Simulator, action: &Action) -> HookDecision {
+        let (dx, dy) = match action {
+            Action::MoveUp => (0, -1),
+            Action::MoveDown => (0, 1),
+            Action::MoveLeft => (-1, 0),
+            Action::MoveRight => (1, 0),
+            Action::MoveForward => sim.agent.facing.offset(),
+            _ => return HookDecision::Allow,
+        };
+        let layout = &sim.world.layout;
+        let (x, y) = (sim.agent.x as isize + dx, sim.agent.y as isize + dy);
+        if x < 0 || y < 0 || x >= layout.width as isize || y >= layout.height as isize {
+            return HookDecision::Allow;
+        }
+        if self.in_room(sim, x as usize, y as usize) && !self.in_room(sim, sim.agent.x, sim.agent.y) {
+            return HookDecision::Deny(ActionError::Denied(format!("entering the {} is not allowed", self.room)));
+        }
+        HookDecision::Allow
+    }
+}
+
+/// Charges a fixed reward penalty for every pick-up
+#[derive(Debug, Clone, Copy)]
+pub struct PickupTax {
+    pub amount: f32,
+    was_holding: bool,
+}
+
+impl PickupTax {
+    pub fn new(amount: f32) -> Self {
+        PickupTax { amount, was_holding: false }
+    }
+}
+
+impl SimHook for PickupTax {
+    fn before_action(&mut self, sim: &Simulator, _action: &Action) -> HookDecision {
+        self.was_holding = sim.holding.is_some();
+        HookDecision::Allow
+    }
+
+    fn after_action(&mut self, sim: &Simulator, _outcome: &StepOutcome) -> f32 {
+        // covers both `PickUp` and an `Interact` that picked something up
+        if !self.was_holding && sim.holding.is_some() {
+            -self.amount
+        } else {
+            0.0
+        }
+    }
+}
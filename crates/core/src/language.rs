@@ -1,28 +1,478 @@
-use crate::object::Object;
-use crate::gen::World;
+use std::collections::HashSet;
+
+#[cfg(feature = "sim")]
+use crate::agent::Direction;
+#[cfg(feature = "sim")]
+use crate::cell::CellKind;
+#[cfg(feature = "sim")]
+use crate::object::ContainerKind;
+use crate::object::{Names, Object, ObjectConstraint, ObjectId, ObjectSchema};
+#[cfg(feature = "sim")]
+use crate::sim::Simulator;
+use crate::world::{room_name_parts, World};
+
+pub use crate::object::humanize;
+#[cfg(feature = "sim")]
+pub use crate::command::{parse_command, ParseError};
+
+/// Describe where a cell lies, e.g. "in the seating area of the Living Room"
+pub fn describe_location(world: &World, x: usize, y: usize) -> String {
+    let layout = &world.layout;
+    let cell = layout.cells[y * layout.width + x];
+    if cell < 0 {
+        return "outside any room".into();
+    }
+    let room = layout.room_names.get(cell as usize).map_or("unnamed room", |name| name);
+    match layout.zone_at(x, y) {
+        Some(zone) => format!("in the {} of the {}", layout.zone_names[zone], room),
+        None => format!("in the {}", room),
+    }
+}
+
+/// Display name followed by the custom label, e.g. "drawer labelled 'office supplies'"
+pub(crate) fn labelled_name(obj: &Object) -> String {
+    match &obj.label {
+        Some(label) => format!("{} labelled '{}'", obj.display_name, label),
+        None => obj.display_name.to_string(),
+    }
+}
+
+/// Display name of a schema named in a constraint; unknown schemas are humanized
+fn schema_display_name(name: &str) -> String {
+    ObjectSchema::find(name).map_or_else(|| humanize(name), |s| s.resolved_display_name().to_string())
+}
+
+/// Names with the article of the first, e.g. "an oven or cupboard"
+fn with_article(names: &[String], join: fn(&[String]) -> String) -> String {
+    let list = join(names);
+    format!("{} {}", article(&list), list)
+}
 
 /// Describe an object and its contents in natural language
 pub fn describe_object(obj: &Object, world: &World) -> String {
-    let base = format!("The {} at ({}, {})", obj.name, obj.x, obj.y);
+    let mut base = format!("The {} at ({}, {})", labelled_name(obj), obj.x, obj.y);
+    if world.layout.zone_at(obj.x, obj.y).is_some() {
+        base = format!("{} {}", base, describe_location(world, obj.x, obj.y));
+    }
     if obj.contents.is_empty() {
         format!("{} contains nothing.", base)
     } else {
         let items: Vec<String> = obj
             .contents
             .iter()
-            .filter_map(|id| world.objects.iter().find(|o| o.id == *id))
-            .map(|o| format!("a {}", o.name))
+            .filter_map(|&id| world.object(id))
+            .map(|o| {
+                let name = labelled_name(o);
+                format!("{} {}", article(&name), name)
+            })
             .collect();
-        let list = match items.len() {
-            1 => items[0].clone(),
-            _ => {
-                let last = items.last().unwrap().clone();
-                let mut rest = items[..items.len()-1].join(", ");
-                rest.push_str(" and ");
-                rest.push_str(&last);
-                rest
+        format!("{} contains {}.", base, join_all(&items))
+    }
+}
+
+/// Join names as "a, b and c"
+fn join_all(items: &[String]) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items[0].clone(),
+        _ => format!("{} and {}", items[..items.len() - 1].join(", "), items[items.len() - 1]),
+    }
+}
+
+/// "a Chair" or "3 Chairs" for each distinct name, in order of first appearance
+fn counted_names<'a>(objects: impl Iterator<Item = &'a Object>) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for obj in objects {
+        let name = labelled_name(obj);
+        match counts.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(name, count)| match count {
+            1 => format!("{} {}", article(&name), name),
+            _ => format!("{} {}", count, plural(&name)),
+        })
+        .collect()
+}
+
+/// "an" before a vowel, "a" otherwise
+fn article(name: &str) -> &'static str {
+    match name.chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some('a' | 'e' | 'i' | 'o' | 'u') => "an",
+        _ => "a",
+    }
+}
+
+/// English plural of a schema name; labelled names pluralize the name before the label
+fn plural(name: &str) -> String {
+    let (head, tail) = name.split_once(" labelled ").map_or((name, None), |(h, t)| (h, Some(t)));
+    let es = ["s", "x", "z", "ch", "sh"].iter().any(|end| head.ends_with(end));
+    let head = if es { format!("{}es", head) } else { format!("{}s", head) };
+    match tail {
+        Some(tail) => format!("{} labelled {}", head, tail),
+        None => head,
+    }
+}
+
+/// Room sentence from the top-level objects in the room
+fn room_sentence(name: &str, objects: &[&Object], contained: &HashSet<ObjectId>) -> String {
+    let top_level = || objects.iter().copied().filter(|o| !contained.contains(&o.id));
+    let furniture = counted_names(top_level().filter(|o| !o.pickable));
+    let loose = counted_names(top_level().filter(|o| o.pickable));
+    match (furniture.is_empty(), loose.is_empty()) {
+        (true, true) => format!("The {} is empty.", name),
+        (false, true) => format!("The {} contains {}.", name, join_all(&furniture)),
+        (true, false) => format!("The {} has {} on the floor.", name, join_all(&loose)),
+        (false, false) => {
+            format!("The {} contains {}, with {} on the floor.", name, join_all(&furniture), join_all(&loose))
+        }
+    }
+}
+
+/// Ids of objects inside another object
+fn contained_ids(world: &World) -> HashSet<ObjectId> {
+    world.objects.iter().flat_map(|o| o.contents.iter().copied()).collect()
+}
+
+/// Describe a room's furniture and the loose items on its floor, e.g. "The Kitchen contains a
+/// refrigerator and a dishwasher, with a dirty plate on the floor." Objects inside containers
+/// are left out.
+pub fn describe_room(world: &World, room: usize) -> String {
+    let layout = &world.layout;
+    let Some((x0, y0, x1, y1)) = layout.room_bbox(room) else {
+        return format!("There is no room {}.", room);
+    };
+    let name = layout.room_names.get(room).map_or("unnamed room", |name| name);
+    let objects: Vec<&Object> = world
+        .objects
+        .iter()
+        .filter(|o| (x0..=x1).contains(&o.x) && (y0..=y1).contains(&o.y))
+        .filter(|o| layout.cells[o.y * layout.width + o.x] == room as i8)
+        .collect();
+    room_sentence(name, &objects, &contained_ids(world))
+}
+
+/// Summarize the apartment: its rooms, what each contains and how many objects are out of
+/// place, e.g. "The apartment has 2 rooms: the Kitchen and the Bathroom. The Kitchen contains
+/// ... 1 object is out of place." Objects a simulator's agents hold are not in the world and
+/// are not counted.
+pub fn describe_world(world: &World) -> String {
+    let layout = &world.layout;
+    let rooms: Vec<usize> = (0..layout.room_names.len()).filter(|&r| layout.room_area(r) > 0).collect();
+    let mut by_room: Vec<Vec<&Object>> = vec![Vec::new(); layout.room_names.len()];
+    for obj in &world.objects {
+        let cell = layout.cells[obj.y * layout.width + obj.x];
+        if let Some(objects) = usize::try_from(cell).ok().and_then(|room| by_room.get_mut(room)) {
+            objects.push(obj);
+        }
+    }
+    let names: Vec<String> = rooms.iter().map(|&r| format!("the {}", layout.room_names[r])).collect();
+    let mut parts = vec![match rooms.len() {
+        0 => "The apartment has no rooms.".to_string(),
+        1 => format!("The apartment has 1 room: {}.", names[0]),
+        n => format!("The apartment has {} rooms: {}.", n, join_all(&names)),
+    }];
+    let contained = contained_ids(world);
+    parts.extend(rooms.iter().map(|&r| room_sentence(&layout.room_names[r], &by_room[r], &contained)));
+    parts.push(match world.misplaced_objects().len() {
+        0 => "Everything is in its place.".to_string(),
+        1 => "1 object is out of place.".to_string(),
+        n => format!("{} objects are out of place.", n),
+    });
+    parts.join(" ")
+}
+
+/// Join names as "a, b or c"
+fn join_alternatives(items: &[String]) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items[0].clone(),
+        _ => format!("{} or {}", items[..items.len() - 1].join(", "), items[items.len() - 1]),
+    }
+}
+
+/// Describe a placement constraint, e.g. "inside a drawer or cupboard"
+pub fn describe_constraint(constraint: &ObjectConstraint) -> String {
+    let display = |names: &Names| names.iter().map(schema_display_name).collect::<Vec<String>>();
+    match constraint {
+        ObjectConstraint::InRoom => "anywhere in a room".into(),
+        ObjectConstraint::AdjacentObstacle => "against a wall".into(),
+        ObjectConstraint::CloseToObstacle => "near a wall".into(),
+        ObjectConstraint::InsideOf(names) => format!("inside {}", with_article(&display(names), join_alternatives)),
+        ObjectConstraint::InRoomNamed(names) => {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            format!("in the {}", join_alternatives(&names))
+        }
+        ObjectConstraint::InZoneWith(names) => format!("next to the {}", join_alternatives(&display(names))),
+        ObjectConstraint::WorldHas(names) => {
+            format!("anywhere once there is {}", with_article(&display(names), join_alternatives))
+        }
+        ObjectConstraint::And(cs) => cs.iter().map(describe_constraint).collect::<Vec<_>>().join(" and "),
+        ObjectConstraint::Or(cs) => {
+            let parts: Vec<String> = cs.iter().map(describe_constraint).collect();
+            join_alternatives(&parts)
+        }
+    }
+}
+
+/// Describe where an object belongs, e.g. "The spatula belongs inside a drawer or cupboard."
+pub fn describe_target(obj: &Object) -> String {
+    match obj.target() {
+        Some(target) => format!("The {} belongs {}.", obj.display_name, describe_constraint(target)),
+        None => format!("The {} has an unknown destination.", obj.display_name),
+    }
+}
+
+/// Where an instruction sends an object
+enum Destination<'a> {
+    Container(&'a Object),
+    NextTo(&'a Object),
+    Room(usize),
+}
+
+/// Manhattan distance between two cells
+fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// Nearest destination for `obj` under one constraint of its `target`; containers must have
+/// room and satisfy the whole target where they stand
+fn resolve<'a>(
+    world: &'a World,
+    obj: &Object,
+    target: &ObjectConstraint,
+    constraint: &ObjectConstraint,
+) -> Option<(Destination<'a>, usize)> {
+    let from = (obj.x, obj.y);
+    match constraint {
+        ObjectConstraint::InsideOf(names) => world
+            .objects
+            .iter()
+            .filter(|c| c.id != obj.id && !obj.contents.contains(&c.id))
+            .filter(|c| names.contains(&c.name) && c.has_room() && target.check(world, c.x, c.y))
+            .min_by_key(|c| (distance(from, (c.x, c.y)), c.id))
+            .map(|c| (Destination::Container(c), distance(from, (c.x, c.y)))),
+        ObjectConstraint::InZoneWith(names) => world
+            .objects
+            .iter()
+            .filter(|o| o.id != obj.id && names.contains(&o.name))
+            .min_by_key(|o| (distance(from, (o.x, o.y)), o.id))
+            .map(|o| (Destination::NextTo(o), distance(from, (o.x, o.y)))),
+        ObjectConstraint::InRoomNamed(names) => {
+            let layout = &world.layout;
+            (0..layout.room_names.len())
+                .filter(|&room| room_name_parts(&layout.room_names[room]).any(|part| names.contains(part)))
+                .filter_map(|room| {
+                    let (x0, y0, x1, y1) = layout.room_bbox(room)?;
+                    Some((room, distance(from, (obj.x.clamp(x0, x1), obj.y.clamp(y0, y1)))))
+                })
+                .min_by_key(|&(room, d)| (d, room))
+                .map(|(room, d)| (Destination::Room(room), d))
+        }
+        // a container or anchor object beats a whole room, whatever the distance
+        ObjectConstraint::And(constraints) => constraints
+            .iter()
+            .filter_map(|c| resolve(world, obj, target, c))
+            .min_by_key(|(dest, _)| matches!(dest, Destination::Room(_))),
+        ObjectConstraint::Or(constraints) => constraints
+            .iter()
+            .filter_map(|c| resolve(world, obj, target, c))
+            .min_by_key(|&(_, d)| d),
+        _ => None,
+    }
+}
+
+/// Wall conditions in a target, as a phrase to add to a room instruction
+fn wall_phrase(constraint: &ObjectConstraint) -> Option<&'static str> {
+    match constraint {
+        ObjectConstraint::AdjacentObstacle => Some("against a wall"),
+        ObjectConstraint::CloseToObstacle => Some("near a wall"),
+        ObjectConstraint::And(constraints) => constraints.iter().find_map(wall_phrase),
+        _ => None,
+    }
+}
+
+/// Instruction to put away a misplaced object, e.g. "Put the moldy bread into the trash can in
+/// the Kitchen."
+///
+/// `InsideOf` targets name the nearest container with room, `InZoneWith` targets the nearest
+/// anchor object and `InRoomNamed` targets the nearest matching room; when no fitting container
+/// was generated the instruction falls back to the target room, and then to
+/// `describe_constraint`. `None` for unknown ids, objects without a target and objects already
+/// in place.
+pub fn instruction_for(world: &World, id: ObjectId) -> Option<String> {
+    let obj = world.object(id)?;
+    let target = obj.target()?;
+    if obj.check_placement(world) {
+        return None;
+    }
+    let name = labelled_name(obj);
+    Some(match resolve(world, obj, target, target) {
+        Some((Destination::Container(c), _)) => {
+            format!("Put the {} into the {} {}.", name, labelled_name(c), describe_location(world, c.x, c.y))
+        }
+        Some((Destination::NextTo(o), _)) => {
+            format!("Put the {} next to the {} {}.", name, labelled_name(o), describe_location(world, o.x, o.y))
+        }
+        Some((Destination::Room(room), _)) => {
+            let room = &world.layout.room_names[room];
+            match wall_phrase(target) {
+                Some(wall) => format!("Put the {} {} in the {}.", name, wall, room),
+                None => format!("Put the {} in the {}.", name, room),
             }
-        };
-        format!("{} contains {}.", base, list)
+        }
+        None => format!("Put the {} {}.", name, describe_constraint(target)),
+    })
+}
+
+/// Instructions for every misplaced object, by ascending id; see `instruction_for`
+pub fn task_instructions(world: &World) -> Vec<String> {
+    world.misplaced_objects().into_iter().filter_map(|id| instruction_for(world, id)).collect()
+}
+
+/// "one cell", "two cells", ... "10 cells"
+#[cfg(feature = "sim")]
+fn cells(n: usize) -> String {
+    const WORDS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    let count = WORDS.get(n).map_or_else(|| n.to_string(), |w| w.to_string());
+    format!("{} {}", count, if n == 1 { "cell" } else { "cells" })
+}
+
+/// Where an offset from the agent lies, e.g. "here", "two cells to the north" or "one cell
+/// south and three cells east"
+#[cfg(feature = "sim")]
+fn relative(dx: isize, dy: isize) -> String {
+    let (ns, ew) = (if dy < 0 { "north" } else { "south" }, if dx < 0 { "west" } else { "east" });
+    match (dx, dy) {
+        (0, 0) => "here".into(),
+        (0, _) => format!("{} to the {}", cells(dy.unsigned_abs()), ns),
+        (_, 0) => format!("{} to the {}", cells(dx.unsigned_abs()), ew),
+        _ => format!("{} {} and {} {}", cells(dy.unsigned_abs()), ns, cells(dx.unsigned_abs()), ew),
+    }
+}
+
+/// What a neighbouring cell is, seen from a cell of room `here`
+#[cfg(feature = "sim")]
+fn cell_phrase(world: &World, kind: CellKind, here: Option<usize>) -> String {
+    match kind {
+        CellKind::Room(room) if here == Some(room as usize) => "floor".into(),
+        CellKind::Room(room) => {
+            format!("the {}", world.layout.room_names.get(room as usize).map_or("unnamed room", |name| name))
+        }
+        CellKind::Wall => "a wall".into(),
+        CellKind::Outside => "the outside".into(),
+        CellKind::ClosedDoor => "a closed door".into(),
+        CellKind::OpenDoor => "an open door".into(),
+        CellKind::LockedDoor => "a locked door".into(),
+        CellKind::Window => "a window".into(),
+        CellKind::Stairs(_) => "stairs".into(),
+    }
+}
+
+/// A visible object with its state and what can be seen in or on it, e.g. "a closed
+/// cupboard" or "a dining table with a dirty plate on it"
+#[cfg(feature = "sim")]
+fn visible_object(sim: &Simulator, obj: &Object) -> String {
+    let name = labelled_name(obj);
+    if obj.closable() && !sim.is_open(obj) {
+        return format!("a closed {}", name);
+    }
+    let contents: Vec<&Object> = obj.contents.iter().filter_map(|&id| sim.world.object(id)).collect();
+    if contents.is_empty() {
+        return format!("{} {}", article(&name), name);
+    }
+    let on = if obj.container_kind == Some(ContainerKind::Surface) { "on" } else { "in" };
+    format!("{} {} with {} {} it", article(&name), name, join_all(&counted_names(contents.into_iter())), on)
+}
+
+/// Text observation of the agent's surroundings: where it stands and faces, what it holds,
+/// the four neighbouring cells, closed doors further away and the objects and other agents
+/// within `radius` (Chebyshev, as in `Simulator::local_view`), nearest first. Objects inside
+/// containers show up with their container. The text only depends on the simulator state.
+#[cfg(feature = "sim")]
+pub fn describe_surroundings(sim: &Simulator, radius: usize) -> String {
+    let world = &sim.world;
+    let (ax, ay) = (sim.agent.x, sim.agent.y);
+    let view = sim.local_view(radius, false);
+    let side = view.side() as isize;
+    let r = radius as isize;
+    let kind_at = |dx: isize, dy: isize| CellKind::from_i8(view.cells[((dy + r) * side + dx + r) as usize]);
+    let here = kind_at(0, 0).room();
+    let mut parts = Vec::new();
+
+    let location = match kind_at(0, 0) {
+        CellKind::Room(_) => describe_location(world, ax, ay),
+        _ => "in a doorway".into(),
+    };
+    parts.push(format!("You are {} at ({}, {}), facing {}.", location, ax, ay, sim.agent.facing.name()));
+    parts.push(match &sim.holding {
+        Some(obj) => {
+            let name = labelled_name(obj);
+            format!("You are holding {} {}.", article(&name), name)
+        }
+        None => "Your hands are empty.".into(),
+    });
+
+    // neighbours come from the layout, so even a radius of 0 describes them
+    let around: Vec<String> = [Direction::North, Direction::East, Direction::South, Direction::West]
+        .iter()
+        .map(|dir| {
+            let (dx, dy) = dir.offset();
+            let kind = ax
+                .checked_add_signed(dx)
+                .zip(ay.checked_add_signed(dy))
+                .and_then(|(x, y)| world.layout.kind(x, y))
+                .unwrap_or(CellKind::Outside);
+            format!("{} to the {}", cell_phrase(world, kind, here), dir.name())
+        })
+        .collect();
+    parts.push(format!("Around you: {}.", join_all(&around)));
+
+    // closed doors past the neighbouring cells, once per door at its nearest cell
+    // cells scanned nearest first, so the first cell seen of each door is its nearest
+    let mut offsets: Vec<(isize, isize)> = (-r..=r).flat_map(|dy| (-r..=r).map(move |dx| (dx, dy))).collect();
+    offsets.sort_by_key(|&(dx, dy)| (dx.abs() + dy.abs(), dy, dx));
+    let mut seen: Vec<usize> = Vec::new();
+    let mut doors: Vec<String> = Vec::new();
+    for (dx, dy) in offsets {
+        let kind = kind_at(dx, dy);
+        if !matches!(kind, CellKind::ClosedDoor | CellKind::LockedDoor) {
+            continue;
+        }
+        let (x, y) = ((ax as isize + dx) as usize, (ay as isize + dy) as usize);
+        if let Some(door) = world.layout.door_at(y * world.layout.width + x) {
+            if seen.contains(&door.id) {
+                continue;
+            }
+            seen.push(door.id);
+        }
+        if dx.abs() + dy.abs() > 1 {
+            doors.push(format!("{} {}", cell_phrase(world, kind, here), relative(dx, dy)));
+        }
+    }
+    if !doors.is_empty() {
+        parts.push(format!("You see {}.", join_all(&doors)));
+    }
+
+    let mut objects: Vec<(isize, isize, &Object)> =
+        view.objects.iter().filter_map(|&(id, dx, dy)| world.object(id).map(|o| (dx, dy, o))).collect();
+    objects.sort_by_key(|&(dx, dy, o)| (dx.abs() + dy.abs(), dy, dx, o.id));
+    parts.push(if objects.is_empty() {
+        "You see no objects nearby.".into()
+    } else {
+        let objects: Vec<String> =
+            objects.iter().map(|&(dx, dy, o)| format!("{} {}", visible_object(sim, o), relative(dx, dy))).collect();
+        format!("Nearby you see {}.", join_all(&objects))
+    });
+
+    for (id, agent, _) in sim.other_agents() {
+        let (dx, dy) = (agent.x as isize - ax as isize, agent.y as isize - ay as isize);
+        if dx.abs() <= r && dy.abs() <= r {
+            parts.push(format!("Agent {} is {}.", id, relative(dx, dy)));
+        }
     }
-}
\ No newline at end of file
+    parts.join(" ")
+}
@@ -1,17 +1,185 @@
+//! Core of tidy-env: layouts, object catalog, generation and simulation.
+//!
+//! Cargo features, all on by default, slim the crate for size-sensitive hosts such as a
+//! wasm viewer:
+//! - `gen`: BSP layouts, object placement, perturbations and curricula; pulls in `rand`
+//! - `sim`: simulator, actions, macros, checkpoints, recordings and the scripted solver; needs `serde` and `language`
+//! - `language`: natural-language descriptions and instructions
+//! - `render`: RGBA, SVG and ASCII drawing of worlds and simulator states; with `sim`, animated
+//!   episode export
+//! - `serde`: serde derives, JSON/msgpack artifacts and their format headers
+//! - `default-schemas`: the built-in object catalog and room kits
+//!
+//! Layouts, worlds, cells, the object model, navigation and stats are always built.
+
 pub mod object;
+pub mod cell;
+pub mod world;
+#[cfg(feature = "gen")]
 pub mod gen;
+#[cfg(feature = "sim")]
 pub mod agent;
+#[cfg(feature = "sim")]
 pub mod sim;
+#[cfg(feature = "language")]
 pub mod language;
+#[cfg(feature = "sim")]
+pub mod view;
+#[cfg(feature = "sim")]
+pub mod batch;
+#[cfg(feature = "gen")]
+pub mod perturb;
+#[cfg(feature = "gen")]
+pub mod lint;
+#[cfg(feature = "gen")]
+pub mod schema;
+pub mod coords;
+pub mod nav;
+#[cfg(feature = "sim")]
+pub mod reward;
+pub mod stats;
+#[cfg(feature = "gen")]
+pub mod difficulty;
+#[cfg(feature = "sim")]
+pub mod action;
+#[cfg(feature = "sim")]
+pub mod hooks;
+#[cfg(feature = "sim")]
+pub mod checkpoint;
+#[cfg(feature = "sim")]
+pub mod recording;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(all(feature = "sim", feature = "render"))]
+pub mod export;
+#[cfg(feature = "sim")]
+pub mod goto;
+#[cfg(feature = "sim")]
+pub mod command;
+#[cfg(feature = "sim")]
+pub mod solver;
+pub mod search;
+pub mod transaction;
+#[cfg(feature = "gen")]
+pub mod telemetry;
+#[cfg(feature = "sim")]
+pub mod room_graph;
+pub mod task;
+#[cfg(feature = "serde")]
+pub mod format;
+#[cfg(feature = "serde")]
+pub mod migrations;
+#[cfg(all(feature = "sim", feature = "default-schemas"))]
+pub mod scenarios;
+#[cfg(all(feature = "sim", feature = "default-schemas"))]
+pub mod conformance;
+
+pub use object::{ContainerKind, Object, ObjectSchema, ObjectId, RoomKit};
+pub use cell::CellKind;
+pub use world::{ComposeError, ComposeMap, Door, Layout, RoomSign, World, WorldDiff};
+#[cfg(feature = "gen")]
+pub use gen::{GenError, GenJob, GenOpts, GenProgress, GenReport, PlacementProvenance, GenTrace, generate, generate_or_retry, generate_with_schemas, generate_with_report, generate_with_trace};
+#[cfg(feature = "sim")]
+pub use agent::{Agent, Direction};
+#[cfg(feature = "sim")]
+pub use sim::{ActionCosts, ContainerPreview, DoneCriteria, DropPreview, InteractError, Simulator, MoveError, SimEvent};
+#[cfg(feature = "sim")]
+pub use view::{LocalView, WorldView, ObjectLite};
+#[cfg(feature = "sim")]
+pub use batch::{BatchError, SimBatch};
+#[cfg(feature = "gen")]
+pub use perturb::{Perturbation, PerturbError};
+pub use transaction::{TxError, TxOpError, WorldTx};
+#[cfg(feature = "gen")]
+pub use telemetry::{GenTelemetry, generate_batch};
+#[cfg(feature = "sim")]
+pub use room_graph::RoomGraphObservation;
+pub use task::{DeliveryTask, TaskKind};
+#[cfg(feature = "sim")]
+pub use task::{Goal, TidyTask};
+#[cfg(feature = "serde")]
+pub use format::{CompatError, FormatHeader, Versioned};
+#[cfg(feature = "gen")]
+pub use lint::{SchemaLint, lint_schemas};
+#[cfg(feature = "gen")]
+pub use schema::SchemaError;
+#[cfg(feature = "sim")]
+pub use reward::{RewardConfig, ShapingConfig};
+pub use stats::{AggregateStats, Summary, WorldStats};
+#[cfg(feature = "gen")]
+pub use difficulty::{CurriculumSampler, DifficultyScore};
+#[cfg(feature = "sim")]
+pub use action::{Action, ActionError, StepOutcome};
+#[cfg(feature = "sim")]
+pub use hooks::{HookDecision, PickupTax, RoomBan, SimHook};
+#[cfg(feature = "sim")]
+pub use checkpoint::{AgentState, CheckpointError, ObjectState, Record, SimState};
+#[cfg(feature = "sim")]
+pub use recording::{RecordedAction, ReplayError};
+#[cfg(feature = "render")]
+pub use render::{CellStyle, RenderOpts, Theme};
+#[cfg(all(feature = "sim", feature = "render"))]
+pub use export::{EpisodeFormat, EpisodeRenderOpts};
+#[cfg(feature = "sim")]
+pub use goto::{Command, GoToError};
+#[cfg(feature = "sim")]
+pub use solver::SolveError;
+#[cfg(all(feature = "sim", feature = "default-schemas"))]
+pub use scenarios::Scenario;
+pub use coords::{Coord, coords_from_index, flat_index, transpose_cells};
+
+/// Entry points the bindings used while the package was named `core` and imported as
+/// `apartment_core`. Forks can swap `use core as apartment_core;` for `use
+/// tidy_core::apartment_core;` and get a deprecation warning on each old path; a deprecated
+/// glob re-export would not warn, so every item is an alias or a forwarding function
+pub mod apartment_core {
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::World`")]
+    pub type World = crate::World;
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::Layout`")]
+    pub type Layout = crate::Layout;
+    #[cfg(feature = "gen")]
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::GenOpts`")]
+    pub type GenOpts = crate::GenOpts;
+    #[cfg(feature = "sim")]
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::Simulator`")]
+    pub type Simulator = crate::Simulator;
+
+    #[cfg(feature = "gen")]
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::generate`")]
+    pub fn generate(opts: &crate::GenOpts) -> Result<crate::World, crate::GenError> {
+        crate::generate(opts)
+    }
+
+    #[cfg(feature = "gen")]
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::generate_with_trace`")]
+    pub fn generate_with_trace(opts: &crate::GenOpts) -> Result<(crate::World, crate::GenTrace), crate::GenError> {
+        crate::generate_with_trace(opts)
+    }
+
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::flat_index`")]
+    pub fn flat_index(x: usize, y: usize, width: usize) -> usize {
+        crate::flat_index(x, y, width)
+    }
+
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::coords_from_index`")]
+    pub fn coords_from_index(i: usize, width: usize) -> crate::Coord {
+        crate::coords_from_index(i, width)
+    }
 
-pub use object::{Object, ObjectSchema, ObjectId};
-pub use gen::{GenOpts, Layout, World, generate};
-pub use agent::Agent;
-pub use sim::{Simulator, MoveError};
+    #[deprecated(since = "0.1.0", note = "use `tidy_core::transpose_cells`")]
+    pub fn transpose_cells<T: Copy>(cells: &[T], width: usize, height: usize) -> Vec<T> {
+        crate::transpose_cells(cells, width, height)
+    }
+}
 
 // Cell type constants
 pub const WALL: i8 = -1;
 pub const OUTSIDE: i8 = -2;
 pub const CLOSED_DOOR: i8 = -3;
 pub const OPEN_DOOR: i8 = -4;
+pub const LOCKED_DOOR: i8 = -5;
+pub const WINDOW: i8 = -6;
+/// Value of staircase 0; staircase `n` is stored as `STAIRS_BASE - n`
+pub const STAIRS_BASE: i8 = -7;
+/// Original impassable cells; prefer `CellKind::is_passable`, which also covers locked doors and windows
 pub const OBSTACLES: [i8; 3] = [WALL, OUTSIDE, CLOSED_DOOR];
\ No newline at end of file
@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::gen::ROOM_NAME_POOL;
+use crate::object::{ContainerKind, ObjectConstraint, ObjectSchema};
+
+/// A problem found in a schema catalog by `lint_schemas`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaLint {
+    /// More than one schema uses this name
+    DuplicateName { schema: &'static str },
+    /// `InsideOf`, `WorldHas`, `InZoneWith`, `decays_into` or `transforms` references a name no
+    /// schema defines
+    UnknownObject { schema: &'static str, name: String },
+    /// `InRoomNamed` references a room the generator never produces
+    UnknownRoom { schema: &'static str, room: String },
+    /// `InsideOf` names a schema that cannot hold anything
+    ZeroCapacityContainer { schema: &'static str, container: String },
+    /// The target constraint can never be satisfied
+    UnsatisfiableTarget { schema: &'static str },
+    /// Every valid spawn position already satisfies the target, so the item always starts tidy
+    AlwaysTidy { schema: &'static str },
+    /// The icon is empty or not a snake_case slug
+    InvalidIcon { schema: &'static str, icon: &'static str },
+    /// `container_kind` is set with capacity 0, missing with a positive capacity, or not
+    /// `Enclosed` while `starts_closed` is set
+    ContainerKindMismatch { schema: &'static str },
+}
+
+impl SchemaLint {
+    /// Short machine-readable name of the lint
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchemaLint::DuplicateName { .. } => "duplicate_name",
+            SchemaLint::UnknownObject { .. } => "unknown_object",
+            SchemaLint::UnknownRoom { .. } => "unknown_room",
+            SchemaLint::ZeroCapacityContainer { .. } => "zero_capacity_container",
+            SchemaLint::UnsatisfiableTarget { .. } => "unsatisfiable_target",
+            SchemaLint::AlwaysTidy { .. } => "always_tidy",
+            SchemaLint::InvalidIcon { .. } => "invalid_icon",
+            SchemaLint::ContainerKindMismatch { .. } => "container_kind_mismatch",
+        }
+    }
+
+    /// Name of the schema the lint was raised for
+    pub fn schema(&self) -> &'static str {
+        match self {
+            SchemaLint::DuplicateName { schema }
+            | SchemaLint::UnknownObject { schema, .. }
+            | SchemaLint::UnknownRoom { schema, .. }
+            | SchemaLint::ZeroCapacityContainer { schema, .. }
+            | SchemaLint::UnsatisfiableTarget { schema }
+            | SchemaLint::AlwaysTidy { schema }
+            | SchemaLint::InvalidIcon { schema, .. }
+            | SchemaLint::ContainerKindMismatch { schema } => schema,
+        }
+    }
+}
+
+impl fmt::Display for SchemaLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaLint::DuplicateName { schema } => write!(f, "{}: duplicate schema name", schema),
+            SchemaLint::UnknownObject { schema, name } => write!(f, "{}: references unknown object '{}'", schema, name),
+            SchemaLint::UnknownRoom { schema, room } => write!(f, "{}: references unknown room '{}'", schema, room),
+            SchemaLint::ZeroCapacityContainer { schema, container } => {
+                write!(f, "{}: container '{}' has capacity 0", schema, container)
+            }
+            SchemaLint::UnsatisfiableTarget { schema } => write!(f, "{}: target can never be satisfied", schema),
+            SchemaLint::AlwaysTidy { schema } => write!(f, "{}: always spawns in its target placement", schema),
+            SchemaLint::InvalidIcon { schema, icon } => write!(f, "{}: icon '{}' is not a snake_case slug", schema, icon),
+            SchemaLint::ContainerKindMismatch { schema } => {
+                write!(f, "{}: container_kind does not match capacity or starts_closed", schema)
+            }
+        }
+    }
+}
+
+/// Check a schema catalog for dangling references, impossible or trivial targets, bad icons
+/// and container kinds that disagree with the capacity.
+///
+/// `room_pool` is the set of room names the generator can assign.
+pub fn lint_schemas(schemas: &[ObjectSchema], room_pool: &[&str]) -> Vec<SchemaLint> {
+    let mut lints = Vec::new();
+    let mut seen = HashSet::new();
+    for s in schemas {
+        if !seen.insert(s.name) && !lints.contains(&SchemaLint::DuplicateName { schema: s.name }) {
+            lints.push(SchemaLint::DuplicateName { schema: s.name });
+        }
+    }
+    for s in schemas {
+        let mut found = Vec::new();
+        check_references(&s.constraint, s.name, schemas, room_pool, &mut found);
+        check_references(&s.target, s.name, schemas, room_pool, &mut found);
+        for (preference, _) in &s.preference {
+            check_references(preference, s.name, schemas, room_pool, &mut found);
+        }
+        let decays = s.decays_into.iter().map(|&(into, _)| into);
+        for name in decays.chain(s.transforms.iter().flat_map(|&(from, to)| [from, to])) {
+            if !schemas.iter().any(|other| other.name == name) {
+                found.push(SchemaLint::UnknownObject { schema: s.name, name: name.to_string() });
+            }
+        }
+        let slug = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_';
+        if s.icon.is_empty() || !s.icon.chars().all(slug) {
+            found.push(SchemaLint::InvalidIcon { schema: s.name, icon: s.icon });
+        }
+        let enclosed = s.container_kind == Some(ContainerKind::Enclosed);
+        if s.container_kind.is_some() != (s.capacity > 0) || (s.starts_closed && !enclosed) {
+            found.push(SchemaLint::ContainerKindMismatch { schema: s.name });
+        }
+        for lint in found {
+            if !lints.contains(&lint) {
+                lints.push(lint);
+            }
+        }
+        if !satisfiable(&s.target, schemas, room_pool) {
+            lints.push(SchemaLint::UnsatisfiableTarget { schema: s.name });
+        } else if s.pickable && implies(&s.constraint, &s.target) {
+            lints.push(SchemaLint::AlwaysTidy { schema: s.name });
+        }
+    }
+    lints
+}
+
+impl ObjectSchema {
+    /// Lint the default catalog against the generator's room name pool
+    pub fn lint_defaults() -> Vec<SchemaLint> {
+        lint_schemas(Self::catalog(), ROOM_NAME_POOL)
+    }
+}
+
+fn check_references(
+    c: &ObjectConstraint,
+    schema: &'static str,
+    schemas: &[ObjectSchema],
+    room_pool: &[&str],
+    out: &mut Vec<SchemaLint>,
+) {
+    match c {
+        ObjectConstraint::And(cs) | ObjectConstraint::Or(cs) => {
+            for c in cs {
+                check_references(c, schema, schemas, room_pool, out);
+            }
+        }
+        ObjectConstraint::InsideOf(names) => {
+            for name in names.iter() {
+                match schemas.iter().find(|s| s.name == name) {
+                    None => out.push(SchemaLint::UnknownObject { schema, name: name.to_string() }),
+                    Some(s) if s.capacity == 0 => {
+                        out.push(SchemaLint::ZeroCapacityContainer { schema, container: name.to_string() })
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        ObjectConstraint::WorldHas(names) | ObjectConstraint::InZoneWith(names) => {
+            for name in names.iter() {
+                if !schemas.iter().any(|s| s.name == name) {
+                    out.push(SchemaLint::UnknownObject { schema, name: name.to_string() });
+                }
+            }
+        }
+        ObjectConstraint::InRoomNamed(rooms) => {
+            for room in rooms.iter() {
+                if !room_pool.contains(&room) {
+                    out.push(SchemaLint::UnknownRoom { schema, room: room.to_string() });
+                }
+            }
+        }
+        ObjectConstraint::InRoom | ObjectConstraint::AdjacentObstacle | ObjectConstraint::CloseToObstacle => {}
+    }
+}
+
+/// Conservative check: false only when no world can ever satisfy the constraint
+fn satisfiable(c: &ObjectConstraint, schemas: &[ObjectSchema], room_pool: &[&str]) -> bool {
+    match c {
+        ObjectConstraint::And(cs) => cs.iter().all(|c| satisfiable(c, schemas, room_pool)),
+        ObjectConstraint::Or(cs) => cs.iter().any(|c| satisfiable(c, schemas, room_pool)),
+        ObjectConstraint::InsideOf(names) => schemas.iter().any(|s| names.contains(s.name) && s.capacity > 0),
+        ObjectConstraint::WorldHas(names) | ObjectConstraint::InZoneWith(names) => {
+            schemas.iter().any(|s| names.contains(s.name))
+        }
+        ObjectConstraint::InRoomNamed(rooms) => rooms.iter().any(|r| room_pool.contains(&r)),
+        ObjectConstraint::InRoom | ObjectConstraint::AdjacentObstacle | ObjectConstraint::CloseToObstacle => true,
+    }
+}
+
+/// Conservative check: true only when every cell satisfying `c` also satisfies `t`
+fn implies(c: &ObjectConstraint, t: &ObjectConstraint) -> bool {
+    use ObjectConstraint::*;
+    match (c, t) {
+        // every valid placement lies on a room cell
+        (_, InRoom) => true,
+        (_, And(ts)) => ts.iter().all(|t| implies(c, t)),
+        (Or(cs), _) => cs.iter().all(|c| implies(c, t)),
+        (And(cs), _) => cs.iter().any(|c| implies(c, t)),
+        (_, Or(ts)) => ts.iter().any(|t| implies(c, t)),
+        (InsideOf(a), InsideOf(b)) | (InRoomNamed(a), InRoomNamed(b)) => a.iter().all(|n| b.contains(n)),
+        (AdjacentObstacle, AdjacentObstacle) | (CloseToObstacle, CloseToObstacle) => true,
+        _ => false,
+    }
+}
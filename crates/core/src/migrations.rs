@@ -0,0 +1,61 @@
+//! Upgrades of serialized artifacts to the current `FORMAT_VERSION`.
+//!
+//! Each step rewrites a JSON document from one format version to the next. A document
+//! without a `format` header is treated as version 1.
+
+use serde_json::Value;
+
+use crate::format::{CompatError, FormatHeader, FORMAT_VERSION, MIN_FORMAT_VERSION};
+
+/// Upgrade steps; entry `i` takes format `MIN_FORMAT_VERSION + i` to the next version
+const STEPS: [fn(&mut Value); (FORMAT_VERSION - MIN_FORMAT_VERSION) as usize] = [backfill_parents];
+
+/// Upgrade a JSON artifact to the current format, returning the upgraded JSON
+pub fn upgrade(json: &str) -> Result<String, CompatError> {
+    let mut value: Value = serde_json::from_str(json).map_err(|e| CompatError::Malformed(e.to_string()))?;
+    upgrade_value(&mut value)?;
+    Ok(value.to_string())
+}
+
+/// Upgrade a parsed JSON artifact in place and stamp it with the current format version.
+///
+/// Fails on artifacts newer than this build or older than `MIN_FORMAT_VERSION`; features
+/// are left for `FormatHeader::check`.
+pub fn upgrade_value(value: &mut Value) -> Result<(), CompatError> {
+    let Some(map) = value.as_object() else {
+        return Err(CompatError::Malformed("expected a JSON object".into()));
+    };
+    let mut header = match map.get("format") {
+        Some(format) => serde_json::from_value(format.clone()).map_err(|e| CompatError::Malformed(format!("bad format header: {}", e)))?,
+        None => FormatHeader::legacy(),
+    };
+    header.check_version()?;
+    for step in &STEPS[(header.format_version - MIN_FORMAT_VERSION) as usize..] {
+        step(value);
+    }
+    header.format_version = FORMAT_VERSION;
+    value["format"] = serde_json::to_value(&header).expect("headers always serialize");
+    Ok(())
+}
+
+/// 1 -> 2: `SimState` object states record the container holding them as `parent`
+fn backfill_parents(value: &mut Value) {
+    // worlds and scenarios keep their objects as they were
+    if value.get("fingerprint").is_none() {
+        return;
+    }
+    let Some(Value::Array(objects)) = value.get_mut("objects") else { return };
+    let mut parents = Vec::new();
+    for o in objects.iter() {
+        let (Some(id), Some(contents)) = (o["id"].as_u64(), o["contents"].as_array()) else { continue };
+        parents.extend(contents.iter().filter_map(Value::as_u64).map(|child| (child, id)));
+    }
+    for o in objects.iter_mut().filter_map(Value::as_object_mut) {
+        let id = o.get("id").and_then(Value::as_u64);
+        let parent = parents.iter().find(|&&(child, _)| Some(child) == id).map(|&(_, p)| p);
+        o.entry("parent").or_insert(parent.map_or(Value::Null, Value::from));
+    }
+    if let Some(holding) = value.get_mut("holding").and_then(Value::as_object_mut) {
+        holding.entry("parent").or_insert(Value::Null);
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+
+use crate::world::Layout;
+use crate::cell::CellKind;
+
+/// Whether the agent can stand on (or, for closed doors, open and walk through) a cell
+pub fn walkable(layout: &Layout, idx: usize) -> bool {
+    CellKind::from_i8(layout.cells[idx]).is_traversable()
+}
+
+/// The cells above, below, left and right of row-major cell `i` that lie on the grid
+pub fn neighbors(layout: &Layout, i: usize) -> impl Iterator<Item = usize> {
+    let (x, y, w) = (i % layout.width, i / layout.width, layout.width);
+    [(y > 0).then(|| i - w), (y + 1 < layout.height).then(|| i + w), (x > 0).then(|| i - 1), (x + 1 < w).then(|| i + 1)]
+        .into_iter()
+        .flatten()
+}
+
+/// Breadth-first step distance from every cell to the nearest of `sources` (row-major indices).
+///
+/// Closed doors count as walkable, so the field does not change when doors are toggled.
+/// Unreachable and non-walkable cells are `None`.
+pub fn distance_field(layout: &Layout, sources: &[usize]) -> Vec<Option<usize>> {
+    let mut dist = Vec::new();
+    distance_field_into(layout, sources, &mut dist, &mut VecDeque::new());
+    dist
+}
+
+/// `distance_field` writing into caller-owned buffers, so repeated queries reuse their allocations
+pub fn distance_field_into(
+    layout: &Layout,
+    sources: &[usize],
+    dist: &mut Vec<Option<usize>>,
+    queue: &mut VecDeque<usize>,
+) {
+    flood(layout, sources, dist, queue, |i| walkable(layout, i));
+}
+
+/// `distance_field` over the cells `passable` accepts instead of every walkable cell
+pub fn distance_field_over(layout: &Layout, sources: &[usize], passable: impl Fn(usize) -> bool) -> Vec<Option<usize>> {
+    let mut dist = Vec::new();
+    flood(layout, sources, &mut dist, &mut VecDeque::new(), passable);
+    dist
+}
+
+/// Breadth-first distances from `sources` over the cells `passable` accepts
+fn flood(
+    layout: &Layout,
+    sources: &[usize],
+    dist: &mut Vec<Option<usize>>,
+    queue: &mut VecDeque<usize>,
+    passable: impl Fn(usize) -> bool,
+) {
+    let (w, h) = (layout.width, layout.height);
+    dist.clear();
+    dist.resize(w * h, None);
+    queue.clear();
+    for &s in sources {
+        if passable(s) && dist[s].is_none() {
+            dist[s] = Some(0);
+            queue.push_back(s);
+        }
+    }
+    while let Some(i) = queue.pop_front() {
+        let d = dist[i].unwrap() + 1;
+        let (x, y) = (i % w, i / w);
+        let neighbors = [
+            (x > 0).then(|| i - 1),
+            (x + 1 < w).then(|| i + 1),
+            (y > 0).then(|| i - w),
+            (y + 1 < h).then(|| i + w),
+        ];
+        for n in neighbors.into_iter().flatten() {
+            if dist[n].is_none() && passable(n) {
+                dist[n] = Some(d);
+                queue.push_back(n);
+            }
+        }
+    }
+}
+
+/// Shortest walking route from `from` to the nearest of `goals` (row-major indices).
+///
+/// Returns the cells to step onto in order, excluding `from`; empty when `from` is a goal.
+/// Closed doors count as walkable, as in `distance_field`, so the caller opens them en route.
+pub fn find_path(layout: &Layout, from: usize, goals: &[usize]) -> Option<Vec<usize>> {
+    find_path_over(layout, from, goals, |i| walkable(layout, i))
+}
+
+/// `find_path` over the cells `passable` accepts instead of every walkable cell, e.g. only
+/// cells passable right now (`CellKind::is_passable`)
+pub fn find_path_over(layout: &Layout, from: usize, goals: &[usize], passable: impl Fn(usize) -> bool) -> Option<Vec<usize>> {
+    let mut dist = Vec::new();
+    flood(layout, goals, &mut dist, &mut VecDeque::new(), passable);
+    let w = layout.width;
+    let mut path = Vec::with_capacity(dist[from]?);
+    let mut i = from;
+    while let Some(d) = dist[i].filter(|&d| d > 0) {
+        let (x, y) = (i % w, i / w);
+        // fixed neighbour order keeps routes deterministic
+        i = [
+            (y > 0).then(|| i - w),
+            (y + 1 < layout.height).then(|| i + w),
+            (x > 0).then(|| i - 1),
+            (x + 1 < w).then(|| i + 1),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|&n| dist[n] == Some(d - 1))?;
+        path.push(i);
+    }
+    Some(path)
+}
+
+/// Cells of `room` whose occupation would split the room's free cells apart (cut vertices).
+///
+/// The graph is the room's cells not marked in `blocked`, plus the doors of the room, so
+/// the only cell in front of a door is always one: filling it cuts the door off. Returns
+/// row-major indices in order.
+pub fn articulation_cells(layout: &Layout, room: i8, blocked: &[bool]) -> Vec<usize> {
+    let (w, h) = (layout.width, layout.height);
+    let neighbors = |i: usize| {
+        let (x, y) = (i % w, i / w);
+        [(y > 0).then(|| i - w), (y + 1 < h).then(|| i + w), (x > 0).then(|| i - 1), (x + 1 < w).then(|| i + 1)]
+    };
+    let free: Vec<bool> = (0..w * h).map(|i| layout.cells[i] == room && !blocked[i]).collect();
+    let node: Vec<bool> = (0..w * h)
+        .map(|i| free[i] || (CellKind::from_i8(layout.cells[i]).is_door() && neighbors(i).iter().flatten().any(|&n| layout.cells[n] == room)))
+        .collect();
+
+    // iterative Tarjan: discovery times (0 = unvisited) and low-links
+    let mut disc = vec![0usize; w * h];
+    let mut low = vec![0usize; w * h];
+    let mut cut = vec![false; w * h];
+    let mut time = 0;
+    let mut stack: Vec<(usize, usize, usize)> = Vec::new(); // (cell, parent, next neighbour)
+    for root in (0..w * h).filter(|&i| node[i]) {
+        if disc[root] != 0 {
+            continue;
+        }
+        time += 1;
+        (disc[root], low[root]) = (time, time);
+        let mut root_children = 0;
+        stack.push((root, usize::MAX, 0));
+        while let Some(&(v, parent, k)) = stack.last() {
+            if k < 4 {
+                stack.last_mut().unwrap().2 += 1;
+                let Some(n) = neighbors(v)[k].filter(|&n| node[n] && n != parent) else { continue };
+                if disc[n] == 0 {
+                    time += 1;
+                    (disc[n], low[n]) = (time, time);
+                    root_children += usize::from(v == root);
+                    stack.push((n, v, 0));
+                } else {
+                    low[v] = low[v].min(disc[n]);
+                }
+            } else {
+                stack.pop();
+                if parent != usize::MAX {
+                    low[parent] = low[parent].min(low[v]);
+                    if parent != root && low[v] >= disc[parent] {
+                        cut[parent] = true;
+                    }
+                }
+            }
+        }
+        cut[root] = root_children > 1;
+    }
+    (0..w * h).filter(|&i| cut[i] && free[i]).collect()
+}
@@ -1,35 +1,288 @@
-use crate::gen::World;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::world::World;
 
 pub type ObjectId = usize;
 
 #[derive(Debug, Clone)]
 pub struct Object {
     pub id: ObjectId,
-    pub name: &'static str,
+    /// Schema name; borrowed from the schema for built-in objects, owned for loaded ones
+    pub name: Cow<'static, str>,
+    /// Name used in text, copied from the schema, e.g. "dirty plate". Only serialized when it
+    /// differs from what loading falls back to: the display name of the built-in schema of
+    /// the same name, or the humanized name
+    pub display_name: Cow<'static, str>,
+    /// Sprite key copied from the schema
+    pub icon: Cow<'static, str>,
     pub capacity: usize,
+    /// How contained objects are held, copied from the schema; `None` for non-containers
+    pub container_kind: Option<ContainerKind>,
     pub pickable: bool,
+    /// Whether the agent cannot walk onto the object's cell; only enforced with
+    /// `Simulator::blocking_objects`
+    pub blocks_movement: bool,
     pub x: usize,
     pub y: usize,
     pub contents: Vec<ObjectId>,
-    pub description: &'static str,
+    /// Whether the container is open; objects that cannot be closed are always open.
+    /// Only `Simulator::closed_containers` makes a closed container hide its contents
+    pub open: bool,
+    pub description: Cow<'static, str>,
+    /// Custom label shown on the object; `None` shows the humanized schema name
+    pub label: Option<String>,
+    /// Target constraint copied from the schema, so checks work for custom schemas too;
+    /// `None` for objects that have no tidy place. Worlds saved without it take it from the
+    /// built-in schema of the same name on loading
+    pub target: Option<ObjectConstraint>,
+    /// Whether objects placed into it are thrown away, copied from the schema
+    pub disposes: bool,
+    /// What the object decays into, resolved from the schema set it was made from so
+    /// custom schemas decay too; see `Simulator::enable_decay`
+    pub decay: Option<Decay>,
+    /// Appliance table resolved from the schema set it was made from, so custom appliances
+    /// work too; empty for objects that are not appliances, see `Simulator::run_appliance`
+    pub transforms: Vec<Transform>,
+}
+
+/// Decay of an object: after `steps` steps outside its target it turns into `into`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Decay {
+    pub steps: usize,
+    /// Fresh instance of the schema it turns into, with that schema's own decay; only the
+    /// fields `Object::decay_now` copies are used
+    pub into: Box<Object>,
+}
+
+impl Decay {
+    /// Decay of `schema`, resolving names among `schemas` first and then the built-in
+    /// catalog; a chain that comes back to a schema it already passed ends there
+    pub fn of(schema: &ObjectSchema, schemas: &[ObjectSchema]) -> Option<Decay> {
+        Self::resolve(schema, schemas, &mut vec![schema.name])
+    }
+
+    fn resolve(schema: &ObjectSchema, schemas: &[ObjectSchema], seen: &mut Vec<&'static str>) -> Option<Decay> {
+        let (name, steps) = schema.decays_into?;
+        let into = schemas.iter().find(|s| s.name == name).or_else(|| ObjectSchema::find(name))?;
+        let mut obj = Object { transforms: Transform::of(into, schemas), ..Object::instance(0, into, 0, 0) };
+        obj.decay = if seen.contains(&into.name) {
+            None
+        } else {
+            seen.push(into.name);
+            Self::resolve(into, schemas, seen)
+        };
+        Some(Decay { steps, into: Box::new(obj) })
+    }
+}
+
+/// Entry of an appliance table: running the appliance turns contained objects named `from`
+/// into `into`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transform {
+    pub from: Cow<'static, str>,
+    /// Fresh instance of the schema it turns into, with that schema's decay but no appliance
+    /// table of its own; only the fields `Object::decay_now` copies are used
+    pub into: Box<Object>,
+}
+
+impl Transform {
+    /// Appliance table of `schema`, resolving names among `schemas` first and then the
+    /// built-in catalog; pairs whose `to` names no schema are left out
+    pub fn of(schema: &ObjectSchema, schemas: &[ObjectSchema]) -> Vec<Transform> {
+        let resolve = |&(from, to): &(&'static str, &'static str)| {
+            let into = schemas.iter().find(|s| s.name == to).or_else(|| ObjectSchema::find(to))?;
+            let into = Object { decay: Decay::of(into, schemas), ..Object::instance(0, into, 0, 0) };
+            Some(Transform { from: Cow::Borrowed(from), into: Box::new(into) })
+        };
+        schema.transforms.iter().filter_map(resolve).collect()
+    }
+}
+
+/// `Object` as read back from JSON, before its fields are filled in from the schema
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ObjectRepr {
+    id: ObjectId,
+    name: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    icon: String,
+    capacity: usize,
+    /// Missing in worlds saved before objects carried it; taken from the built-in schema,
+    /// or enclosed for other containers, as in schema files
+    #[serde(default, deserialize_with = "present")]
+    container_kind: Option<Option<ContainerKind>>,
+    pickable: bool,
+    /// Missing from worlds saved before objects could block; taken from the schema then
+    #[serde(default)]
+    blocks_movement: Option<bool>,
+    x: usize,
+    y: usize,
+    contents: Vec<ObjectId>,
+    #[serde(default = "open_by_default")]
+    open: bool,
+    description: String,
+    label: Option<String>,
+    /// Missing (as opposed to `null`) in worlds saved before targets were stored; taken
+    /// from the built-in schema then
+    #[serde(default, deserialize_with = "present")]
+    target: Option<Option<ObjectConstraint>>,
+    /// Missing in worlds saved before objects carried them; taken from the built-in schema
+    #[serde(default)]
+    disposes: Option<bool>,
+    #[serde(default, deserialize_with = "present")]
+    decay: Option<Option<Decay>>,
+    /// Missing in worlds saved before objects carried their table; taken from the built-in
+    /// schema then
+    #[serde(default)]
+    transforms: Option<Vec<Transform>>,
+}
+
+// a field that is present, even as `null`, is `Some`; `serde(default)` makes a missing one `None`
+#[cfg(feature = "serde")]
+fn present<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(d: D) -> Result<Option<T>, D::Error> {
+    T::deserialize(d).map(Some)
+}
+
+// worlds saved before containers could be closed have every container open
+#[cfg(feature = "serde")]
+fn open_by_default() -> bool {
+    true
+}
+
+/// Display name of objects saved without one, see `Object::display_name`
+#[cfg(feature = "serde")]
+fn default_display_name(name: &str) -> Cow<'static, str> {
+    ObjectSchema::find(name).map_or_else(|| Cow::Owned(humanize(name)), |s| s.resolved_display_name())
+}
+
+// by hand, to leave out a display name that loading gives back anyway
+#[cfg(feature = "serde")]
+impl Serialize for Object {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let custom_name = self.display_name != default_display_name(&self.name);
+        let mut st = s.serialize_struct("Object", 17 + usize::from(custom_name))?;
+        st.serialize_field("id", &self.id)?;
+        st.serialize_field("name", &self.name)?;
+        if custom_name {
+            st.serialize_field("display_name", &self.display_name)?;
+        } else {
+            st.skip_field("display_name")?;
+        }
+        st.serialize_field("icon", &self.icon)?;
+        st.serialize_field("capacity", &self.capacity)?;
+        st.serialize_field("container_kind", &self.container_kind)?;
+        st.serialize_field("pickable", &self.pickable)?;
+        st.serialize_field("blocks_movement", &self.blocks_movement)?;
+        st.serialize_field("x", &self.x)?;
+        st.serialize_field("y", &self.y)?;
+        st.serialize_field("contents", &self.contents)?;
+        st.serialize_field("open", &self.open)?;
+        st.serialize_field("description", &self.description)?;
+        st.serialize_field("label", &self.label)?;
+        st.serialize_field("target", &self.target)?;
+        st.serialize_field("disposes", &self.disposes)?;
+        st.serialize_field("decay", &self.decay)?;
+        st.serialize_field("transforms", &self.transforms)?;
+        st.end()
+    }
+}
+
+// by hand, to fill in fields that older worlds lack from the schema of the same name
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Object {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let o = ObjectRepr::deserialize(d)?;
+        // text equal to the catalog's borrows it instead of keeping a copy
+        let schema = ObjectSchema::find(&o.name);
+        let resolve = |text: String, catalog: Option<&'static str>| match catalog.filter(|c| *c == text) {
+            Some(c) => Cow::Borrowed(c),
+            None => Cow::Owned(text),
+        };
+        let display_name = o.display_name.map_or_else(|| default_display_name(&o.name), Cow::Owned);
+        let name = resolve(o.name, schema.map(|s| s.name));
+        let icon = resolve(o.icon, schema.map(|s| s.icon));
+        let description = resolve(o.description, schema.map(|s| s.description));
+        Ok(Object {
+            id: o.id,
+            display_name,
+            icon,
+            container_kind: o.container_kind.unwrap_or_else(|| match schema {
+                Some(s) => s.container_kind,
+                None => (o.capacity > 0).then_some(ContainerKind::Enclosed),
+            }),
+            capacity: o.capacity,
+            pickable: o.pickable,
+            blocks_movement: o.blocks_movement.unwrap_or_else(|| schema.is_some_and(|s| s.blocks_movement)),
+            x: o.x,
+            y: o.y,
+            contents: o.contents,
+            open: o.open,
+            description,
+            label: o.label,
+            target: o.target.unwrap_or_else(|| schema.map(|s| s.target.clone())),
+            disposes: o.disposes.unwrap_or_else(|| schema.is_some_and(|s| s.disposes)),
+            decay: o.decay.unwrap_or_else(|| schema.and_then(|s| Decay::of(s, &[]))),
+            transforms: o.transforms.unwrap_or_else(|| schema.map_or_else(Vec::new, |s| Transform::of(s, &[]))),
+            name,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ObjectSchema {
     pub capacity: usize,
+    /// How contained objects are held; `None` exactly when `capacity` is 0
+    pub container_kind: Option<ContainerKind>,
+    /// Whether new instances start closed; only meaningful for enclosed containers
+    pub starts_closed: bool,
+    /// Whether objects placed into it are thrown away: removed from the world into
+    /// `World::disposed` instead of taking up capacity, e.g. a trash can
+    pub disposes: bool,
     pub name: &'static str,
+    /// Name used in text, e.g. `"TV stand"`; `None` uses `humanize(name)`
+    pub display_name: Option<&'static str>,
+    /// Stable snake_case sprite key for renderers, e.g. `"trash_can"`
+    pub icon: &'static str,
     pub pickable: bool,
+    /// Whether instances keep the agent off their cell (large furniture); see
+    /// `Simulator::blocking_objects`
+    pub blocks_movement: bool,
     pub constraint: ObjectConstraint,
     pub description: &'static str,
     pub target: ObjectConstraint,
+    /// Schema this object turns into after the given number of steps spent outside its target
+    pub decays_into: Option<(&'static str, usize)>,
+    /// Appliance table of `(from, to)` schema names: running the appliance turns every
+    /// contained `from` object into a `to`, see `Simulator::run_appliance`
+    pub transforms: Vec<(&'static str, &'static str)>,
+    /// Soft placement preferences: spawn cells where a constraint holds gain its weight,
+    /// and the generator samples cells by softmax over the summed weights
+    pub preference: Vec<(ObjectConstraint, f32)>,
 }
 
 impl Default for ObjectSchema {
     fn default() -> Self {
         ObjectSchema {
             capacity: 0,
+            container_kind: None,
+            starts_closed: false,
+            disposes: false,
             name: "",
+            display_name: None,
+            icon: "",
             pickable: false,
+            blocks_movement: false,
+            decays_into: None,
+            transforms: Vec::new(),
+            preference: Vec::new(),
             constraint: ObjectConstraint::InRoom,
             description: "",
             target: ObjectConstraint::InRoom,
@@ -37,6 +290,79 @@ impl Default for ObjectSchema {
     }
 }
 
+/// How a container holds other objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContainerKind {
+    /// Objects rest on top, e.g. a table or shelf
+    Surface,
+    /// Objects go inside, e.g. a cupboard or drawer
+    Enclosed,
+}
+
+/// Object or room names of an `ObjectConstraint`: borrowed for schemas written in code,
+/// owned for constraints read from JSON
+#[derive(Clone)]
+pub enum Names {
+    Static(&'static [&'static str]),
+    Owned(Vec<String>),
+}
+
+impl Names {
+    pub fn len(&self) -> usize {
+        match self {
+            Names::Static(names) => names.len(),
+            Names::Owned(names) => names.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The name at `i`; panics when out of range
+    pub fn get(&self, i: usize) -> &str {
+        match self {
+            Names::Static(names) => names[i],
+            Names::Owned(names) => &names[i],
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.iter().any(|n| n == name)
+    }
+}
+
+// a plain list either way, so the variant does not show in constraint dumps
+impl std::fmt::Debug for Names {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Names {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Names {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.iter())
+    }
+}
+
+impl From<Vec<String>> for Names {
+    fn from(names: Vec<String>) -> Self {
+        Names::Owned(names)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ObjectConstraint {
     InRoom,
@@ -44,63 +370,132 @@ pub enum ObjectConstraint {
     CloseToObstacle,
     And(Vec<ObjectConstraint>),
     Or(Vec<ObjectConstraint>),
-    InsideOf(&'static [&'static str]),
-    WorldHas(&'static [&'static str]),
-    InRoomNamed(&'static [&'static str]),
+    InsideOf(Names),
+    WorldHas(Names),
+    InRoomNamed(Names),
+    /// Same room and zone as one of the named objects (same room when unzoned)
+    InZoneWith(Names),
+}
+
+/// `ObjectConstraint` in JSON, in the form schema files use: a name such as `"in_room"` or
+/// an object with one key such as `{"inside_of": ["Sink"]}`
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ConstraintRepr {
+    InRoom,
+    AdjacentObstacle,
+    CloseToObstacle,
+    And(Vec<ConstraintRepr>),
+    Or(Vec<ConstraintRepr>),
+    InsideOf(Vec<String>),
+    WorldHas(Vec<String>),
+    InRoomNamed(Vec<String>),
+    InZoneWith(Vec<String>),
+}
+
+#[cfg(feature = "serde")]
+impl From<&ObjectConstraint> for ConstraintRepr {
+    fn from(c: &ObjectConstraint) -> Self {
+        let names = |names: &Names| names.iter().map(str::to_string).collect();
+        match c {
+            ObjectConstraint::InRoom => ConstraintRepr::InRoom,
+            ObjectConstraint::AdjacentObstacle => ConstraintRepr::AdjacentObstacle,
+            ObjectConstraint::CloseToObstacle => ConstraintRepr::CloseToObstacle,
+            ObjectConstraint::And(cs) => ConstraintRepr::And(cs.iter().map(Into::into).collect()),
+            ObjectConstraint::Or(cs) => ConstraintRepr::Or(cs.iter().map(Into::into).collect()),
+            ObjectConstraint::InsideOf(n) => ConstraintRepr::InsideOf(names(n)),
+            ObjectConstraint::WorldHas(n) => ConstraintRepr::WorldHas(names(n)),
+            ObjectConstraint::InRoomNamed(n) => ConstraintRepr::InRoomNamed(names(n)),
+            ObjectConstraint::InZoneWith(n) => ConstraintRepr::InZoneWith(names(n)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ConstraintRepr> for ObjectConstraint {
+    type Error = String;
+
+    fn try_from(c: ConstraintRepr) -> Result<Self, String> {
+        let names = |names: Vec<String>| -> Result<Names, String> { Ok(Names::Owned(names)) };
+        Ok(match c {
+            ConstraintRepr::InRoom => ObjectConstraint::InRoom,
+            ConstraintRepr::AdjacentObstacle => ObjectConstraint::AdjacentObstacle,
+            ConstraintRepr::CloseToObstacle => ObjectConstraint::CloseToObstacle,
+            ConstraintRepr::And(cs) => ObjectConstraint::And(cs.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?),
+            ConstraintRepr::Or(cs) => ObjectConstraint::Or(cs.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?),
+            ConstraintRepr::InsideOf(n) => ObjectConstraint::InsideOf(names(n)?),
+            ConstraintRepr::WorldHas(n) => ObjectConstraint::WorldHas(names(n)?),
+            ConstraintRepr::InRoomNamed(n) => ObjectConstraint::InRoomNamed(names(n)?),
+            ConstraintRepr::InZoneWith(n) => ObjectConstraint::InZoneWith(names(n)?),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ObjectConstraint {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        ConstraintRepr::from(self).serialize(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ObjectConstraint {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        ConstraintRepr::deserialize(d)?.try_into().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Whether any on-grid cell at the given offsets from (x, y) is not a room cell: a wall,
+/// door, window, staircase or outside
+fn non_room_at(world: &World, x: usize, y: usize, offsets: [(isize, isize); 4]) -> bool {
+    offsets.iter().any(|&(dx, dy)| {
+        let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+            return false;
+        };
+        world.layout.kind(nx, ny).is_some_and(|kind| kind.room().is_none())
+    })
 }
 
 impl ObjectConstraint {
     /// Check constraint against current layout and placed objects
     pub fn check(&self, world: &World, x: usize, y: usize) -> bool {
         match self {
-            ObjectConstraint::InRoom => world.layout.cells[y * world.layout.width + x] >= 0,
+            ObjectConstraint::InRoom => world.layout.kind_at(x, y).room().is_some(),
             ObjectConstraint::AdjacentObstacle => {
-                if !ObjectConstraint::InRoom.check(world, x, y) {
-                    return false;
-                }
-                let w = world.layout.width;
-                let h = world.layout.height;
-                for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                    let nx = x as isize + dx;
-                    let ny = y as isize + dy;
-                    if nx >= 0 && ny >= 0 && nx < w as isize && ny < h as isize {
-                        let nidx = ny as usize * w + nx as usize;
-                        if world.layout.cells[nidx] < 0 {
-                            return true;
-                        }
-                    }
-                }
-                false
+                ObjectConstraint::InRoom.check(world, x, y)
+                    && non_room_at(world, x, y, [(-1, 0), (1, 0), (0, -1), (0, 1)])
             }
             ObjectConstraint::CloseToObstacle => {
-                if !ObjectConstraint::InRoom.check(world, x, y) {
-                    return false;
-                }
-                let w = world.layout.width;
-                let h = world.layout.height;
-                for (dx, dy) in [(-2, 0), (2, 0), (0, -2), (0, 2)] {
-                    let nx = x as isize + dx;
-                    let ny = y as isize + dy;
-                    if nx >= 0 && ny >= 0 && nx < w as isize && ny < h as isize {
-                        let nidx = ny as usize * w + nx as usize;
-                        if world.layout.cells[nidx] < 0 {
-                            return true;
-                        }
-                    }
-                }
-                false
+                ObjectConstraint::InRoom.check(world, x, y)
+                    && non_room_at(world, x, y, [(-2, 0), (2, 0), (0, -2), (0, 2)])
             }
             ObjectConstraint::And(constraints) => constraints.iter().all(|c| c.check(world, x, y)),
             ObjectConstraint::Or(constraints) => constraints.iter().any(|c| c.check(world, x, y)),
             ObjectConstraint::InsideOf(names) => {
                 // allow placement only at parent coordinates with free capacity
-                world.objects.iter().any(|o| {
-                    names.contains(&o.name) && o.x == x && o.y == y && o.contents.len() < o.capacity
-                })
+                world.objects_at_iter(x, y).any(|o| names.contains(&o.name) && o.has_room())
             }
             ObjectConstraint::InRoomNamed(names) => {
-                let idx = world.layout.cells[y * world.layout.width + x] as usize;
-                names.contains(&world.layout.room_names[idx])
+                // walls, doors and outside cells belong to no room
+                let Some(room) = world.layout.kind_at(x, y).room() else {
+                    return false;
+                };
+                // merged open-plan rooms match any of their component names
+                world.layout.room_names.get(room).is_some_and(|name| {
+                    crate::world::room_name_parts(name).any(|part| names.contains(part))
+                })
+            }
+            ObjectConstraint::InZoneWith(names) => {
+                let Some(room) = world.layout.kind_at(x, y).room() else {
+                    return false;
+                };
+                let zone = world.layout.zone_at(x, y);
+                world.objects.iter().any(|o| {
+                    names.contains(&o.name)
+                        && world.layout.kind_at(o.x, o.y).room() == Some(room)
+                        && world.layout.zone_at(o.x, o.y) == zone
+                })
             }
             ObjectConstraint::WorldHas(names) => {
                 // require at least one of named objects already placed
@@ -110,32 +505,138 @@ impl ObjectConstraint {
     }
 }
 
+/// Fixtures that generation places in every room with the given name before any other object
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoomKit {
+    pub room: String,
+    /// Schema names, placed in order
+    pub fixtures: Vec<String>,
+}
+
+impl RoomKit {
+    /// Built-in kits; empty when the `default-schemas` feature is disabled
+    pub fn defaults() -> Vec<Self> {
+        if !cfg!(feature = "default-schemas") {
+            return Vec::new();
+        }
+        let kit = |room: &str, fixtures: &[&str]| RoomKit {
+            room: room.to_string(),
+            fixtures: fixtures.iter().map(|f| f.to_string()).collect(),
+        };
+        vec![
+            kit("Kitchen", &["TrashCan", "Refrigerator", "Cupboard"]),
+            kit("Bathroom", &["BathroomCabinet", "ToothbrushHolder"]),
+            kit("Bedroom", &["Bed", "Wardrobe"]),
+        ]
+    }
+
+    #[cfg(feature = "serde")]
+    /// Parse a JSON array of `{ "room": ..., "fixtures": [...] }` objects
+    pub fn from_json(json: &str) -> Result<Vec<Self>, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
+
+impl ObjectConstraint {
+    /// Describe the branch of this constraint that holds at a cell, or `None` if it does not hold.
+    ///
+    /// `Or` reports its first satisfied alternative; leaves are rendered in debug form.
+    pub fn satisfied_branch(&self, world: &World, x: usize, y: usize) -> Option<String> {
+        match self {
+            ObjectConstraint::And(constraints) => {
+                let parts = constraints
+                    .iter()
+                    .map(|c| c.satisfied_branch(world, x, y))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("And({})", parts.join(", ")))
+            }
+            ObjectConstraint::Or(constraints) => constraints.iter().find_map(|c| c.satisfied_branch(world, x, y)),
+            leaf => leaf.check(world, x, y).then(|| format!("{:?}", leaf)),
+        }
+    }
+}
+
 impl ObjectSchema {
+    /// Look up a default schema by object name
+    pub fn find(name: &str) -> Option<&'static Self> {
+        Self::catalog().iter().find(|s| s.name == name)
+    }
+
+    /// Built-in object catalog, built once and shared; empty when the `default-schemas` feature is disabled
+    pub fn catalog() -> &'static [Self] {
+        static CATALOG: OnceLock<Vec<ObjectSchema>> = OnceLock::new();
+        CATALOG.get_or_init(Self::builtin_schemas)
+    }
+
+    /// `display_name`, or the humanized `name` when it has none
+    pub fn resolved_display_name(&self) -> Cow<'static, str> {
+        self.display_name.map_or_else(|| Cow::Owned(humanize(self.name)), Cow::Borrowed)
+    }
+
+    /// Owned copy of the built-in catalog, for callers that extend or edit it
     pub fn default_schemas() -> Vec<Self> {
+        Self::catalog().to_vec()
+    }
+
+    #[cfg(not(feature = "default-schemas"))]
+    fn builtin_schemas() -> Vec<Self> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "default-schemas")]
+    fn builtin_schemas() -> Vec<Self> {
         vec![
             // Fixtures & Furniture
             ObjectSchema {
                 name: "TrashCan",
+                display_name: None,
+                icon: "trash_can",
                 capacity: 20,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: true,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::InRoom,
                 description: "A trash can.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Cupboard",
+                display_name: None,
+                icon: "cupboard",
                 capacity: 20,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "A kitchen cupboard.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "KitchenCabinet",
+                display_name: None,
+                icon: "kitchen_cabinet",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A kitchen cabinet.",
@@ -143,10 +644,19 @@ impl ObjectSchema {
             },
             ObjectSchema {
                 name: "Dishwasher",
+                display_name: None,
+                icon: "dishwasher",
                 capacity: 20,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
+                blocks_movement: true,
+                decays_into: None,
+                transforms: vec![("DirtyPlate", "Plate"), ("DirtyMug", "Mug"), ("DirtyGlass", "Glass")],
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A built-in dishwasher.",
@@ -154,10 +664,19 @@ impl ObjectSchema {
             },
             ObjectSchema {
                 name: "Refrigerator",
+                display_name: None,
+                icon: "refrigerator",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A refrigerator.",
@@ -165,30 +684,57 @@ impl ObjectSchema {
             },
             ObjectSchema {
                 name: "FruitBowl",
+                display_name: None,
+                icon: "fruit_bowl",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&[
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&[
                     "Kitchen",
                     "Dining Room",
                     "Living Room",
-                ]),
+                ])),
                 description: "A bowl for holding fruit.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Drawer",
+                display_name: None,
+                icon: "drawer",
                 capacity: 15,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bedroom", "Office", "Study"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom", "Office", "Study"])),
                 description: "A sliding drawer unit.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "StorageBox",
+                display_name: None,
+                icon: "storage_box",
                 capacity: 30,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InRoomNamed(&["Hallway", "Living Room"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Hallway", "Living Room"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A box for loose items.",
@@ -196,1186 +742,2311 @@ impl ObjectSchema {
             },
             ObjectSchema {
                 name: "DiningTable",
+                display_name: None,
+                icon: "dining_table",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Dining Room", "Kitchen"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Dining Room", "Kitchen"])),
                 description: "A dining table.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "CoffeeTable",
+                display_name: None,
+                icon: "coffee_table",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Living Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room"])),
                 description: "A coffee table.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Bookshelf",
+                display_name: None,
+                icon: "bookshelf",
                 capacity: 30,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&[
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&[
                     "Living Room",
                     "Study",
                     "Bedroom",
                     "Office",
-                ]),
+                ])),
                 description: "A bookshelf.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "TVStand",
+                display_name: None,
+                icon: "tv_stand",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Living Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room"])),
                 description: "A TV stand.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Sofa",
+                display_name: None,
+                icon: "sofa",
                 capacity: 3,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Living Room", "Guest Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::And(vec![
+                        ObjectConstraint::InRoomNamed(Names::Static(&["Living Room"])),
+                        ObjectConstraint::InZoneWith(Names::Static(&["TVStand"])),
+                    ]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Guest Room"])),
+                ]),
                 description: "A sofa.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Armchair",
+                display_name: None,
+                icon: "armchair",
                 capacity: 1,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Living Room", "Study"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Study"])),
                 description: "An armchair.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Bed",
+                display_name: None,
+                icon: "bed",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bedroom", "Guest Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom", "Guest Room"])),
                 description: "A bed.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Wardrobe",
+                display_name: None,
+                icon: "wardrobe",
                 capacity: 20,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bedroom", "Guest Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom", "Guest Room"])),
                 description: "A wardrobe.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Dresser",
+                display_name: None,
+                icon: "dresser",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bedroom", "Guest Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom", "Guest Room"])),
                 description: "A dresser.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Desk",
+                display_name: None,
+                icon: "desk",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Study", "Office", "Bedroom"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Study", "Office", "Bedroom"])),
                 description: "A desk.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Nightstand",
+                display_name: None,
+                icon: "nightstand",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bedroom", "Guest Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::And(vec![
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom", "Guest Room"])),
+                    ObjectConstraint::InZoneWith(Names::Static(&["Bed"])),
+                ]),
                 description: "A nightstand.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "ToyBox",
+                display_name: None,
+                icon: "toy_box",
                 capacity: 50,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Playroom", "Living Room"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Playroom", "Living Room"])),
                 description: "A toy box.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "BathroomCabinet",
+                display_name: None,
+                icon: "bathroom_cabinet",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 description: "A bathroom cabinet.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "KeyHolder",
+                display_name: None,
+                icon: "key_holder",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Hallway", "Office"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Hallway", "Office"])),
                 description: "A wall-mounted key holder.",
                 target: ObjectConstraint::InRoom,
             },
             // Kitchen Utensils
             ObjectSchema {
                 name: "Spatula",
+                display_name: None,
+                icon: "spatula",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&[
+                    ObjectConstraint::InsideOf(Names::Static(&[
                         "Drawer",
                         "Cupboard",
                         "Dishwasher",
                         "DiningTable",
-                    ]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A spatula for flipping food.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "Cupboard", "Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "Cupboard", "Dishwasher"])),
             },
             ObjectSchema {
                 name: "Whisk",
+                display_name: None,
+                icon: "whisk",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer", "Cupboard", "Dishwasher"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer", "Cupboard", "Dishwasher"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A whisk for mixing ingredients.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "Cupboard", "Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "Cupboard", "Dishwasher"])),
             },
             ObjectSchema {
                 name: "CookingPot",
+                display_name: None,
+                icon: "cooking_pot",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "Dishwasher", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Dishwasher", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A large cooking pot.",
-                target: ObjectConstraint::InsideOf(&["Cupboard", "Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Dishwasher"])),
             },
             ObjectSchema {
                 name: "FryingPan",
+                display_name: None,
+                icon: "frying_pan",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "Dishwasher", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Dishwasher", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A frying pan.",
-                target: ObjectConstraint::InsideOf(&["Cupboard", "Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Dishwasher"])),
             },
             ObjectSchema {
                 name: "CuttingBoard",
+                display_name: None,
+                icon: "cutting_board",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer", "Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer", "Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A wooden cutting board.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "Cupboard"])),
             },
             ObjectSchema {
                 name: "Dirty CuttingBoard",
+                display_name: None,
+                icon: "dirty_cutting_board",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A wooden cutting board.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "Kettle",
+                display_name: None,
+                icon: "kettle",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "An electric kettle.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             ObjectSchema {
                 name: "Blender",
+                display_name: None,
+                icon: "blender",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A countertop blender.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             ObjectSchema {
                 name: "Toaster",
+                display_name: None,
+                icon: "toaster",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A two-slice toaster.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             ObjectSchema {
                 name: "Microwave",
+                display_name: None,
+                icon: "microwave",
                 capacity: 1,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 description: "A microwave oven.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "MixingBowl",
+                display_name: None,
+                icon: "mixing_bowl",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A ceramic mixing bowl.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             // Food & Pantry
             ObjectSchema {
                 name: "Apple",
+                display_name: None,
+                icon: "apple",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: Some(("RottenApple", 300)),
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A fresh red apple.",
                 target: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl"]),
-                    ObjectConstraint::InsideOf(&["Refrigerator"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
                 ]),
             },
             ObjectSchema {
                 name: "Orange",
+                display_name: None,
+                icon: "orange",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A juicy orange.",
-                target: ObjectConstraint::InsideOf(&["FruitBowl"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
             },
             ObjectSchema {
                 name: "MilkCarton",
+                display_name: None,
+                icon: "milk_carton",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A carton of milk.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             ObjectSchema {
                 name: "Egg",
+                display_name: None,
+                icon: "egg",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A chicken egg.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             ObjectSchema {
                 name: "CerealBox",
+                display_name: None,
+                icon: "cereal_box",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A box of cereal.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             ObjectSchema {
                 name: "BreadLoaf",
+                display_name: None,
+                icon: "bread_loaf",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: Some(("MoldyBread", 400)),
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A loaf of bread.",
-                target: ObjectConstraint::InsideOf(&["Cupboard"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             ObjectSchema {
                 name: "CheeseBlock",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                display_name: None,
+                icon: "cheese_block",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
                 description: "A block of cheese.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             ObjectSchema {
                 name: "YogurtCup",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                display_name: None,
+                icon: "yogurt_cup",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
                 description: "A cup of yogurt.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             ObjectSchema {
                 name: "JuiceBottle",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                display_name: None,
+                icon: "juice_bottle",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
                 description: "A bottle of juice.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             ObjectSchema {
                 name: "WaterBottle",
+                display_name: None,
+                icon: "water_bottle",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator"]),
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A bottle of water.",
-                target: ObjectConstraint::InsideOf(&["Refrigerator"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
             },
             // Bathroom Essentials
             ObjectSchema {
                 name: "ShampooBottle",
+                display_name: None,
+                icon: "shampoo_bottle",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A bottle of shampoo.",
-                target: ObjectConstraint::InsideOf(&["BathroomCabinet"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
             },
             ObjectSchema {
                 name: "SoapBar",
+                display_name: None,
+                icon: "soap_bar",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A bar of soap.",
-                target: ObjectConstraint::InsideOf(&["BathroomCabinet"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
             },
             ObjectSchema {
                 name: "Hairbrush",
+                display_name: None,
+                icon: "hairbrush",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A hairbrush.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Razor",
+                display_name: None,
+                icon: "razor",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A shaving razor.",
-                target: ObjectConstraint::InsideOf(&["BathroomCabinet"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
             },
             ObjectSchema {
                 name: "Towel",
+                display_name: None,
+                icon: "towel",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])), 2.0),
+                    (ObjectConstraint::AdjacentObstacle, 1.0),
+                ],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A bath towel.",
-                target: ObjectConstraint::InsideOf(&["BathroomCabinet"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
             },
             ObjectSchema {
                 name: "Toothpaste",
+                display_name: None,
+                icon: "toothpaste",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A tube of toothpaste.",
-                target: ObjectConstraint::InsideOf(&["BathroomCabinet"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["BathroomCabinet"])),
             },
             ObjectSchema {
                 name: "ToothbrushHolder",
+                display_name: None,
+                icon: "toothbrush_holder",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 description: "A stand for toothbrushes.",
-                target: ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
             },
             ObjectSchema {
                 name: "Toothbrush",
+                display_name: None,
+                icon: "toothbrush",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["ToothbrushHolder", "BathroomCabinet"]),
-                    ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["ToothbrushHolder", "BathroomCabinet"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 ]),
                 description: "A toothbrush.",
-                target: ObjectConstraint::InsideOf(&["ToothbrushHolder"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["ToothbrushHolder"])),
             },
             ObjectSchema {
                 name: "BathMat",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                display_name: None,
+                icon: "bath_mat",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 description: "A mat outside the tub.",
-                target: ObjectConstraint::InRoomNamed(&["Bathroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
             },
             // Office Supplies
             ObjectSchema {
                 name: "Stapler",
+                display_name: None,
+                icon: "stapler",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A paper stapler.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "PaperStack",
+                display_name: None,
+                icon: "paper_stack",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Desk"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A stack of loose papers.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Envelope",
+                display_name: None,
+                icon: "envelope",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Desk"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A paper envelope.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Calculator",
+                display_name: None,
+                icon: "calculator",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Desk"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A desk calculator.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Mouse",
+                display_name: None,
+                icon: "mouse",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Desk"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A computer mouse.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Keyboard",
+                display_name: None,
+                icon: "keyboard",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Desk"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 ]),
                 description: "A computer keyboard.",
-                target: ObjectConstraint::InsideOf(&["Desk"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Desk"])),
             },
             ObjectSchema {
                 name: "Monitor",
+                display_name: None,
+                icon: "monitor",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
                 description: "A computer monitor.",
-                target: ObjectConstraint::InRoomNamed(&["Office", "Study"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Study"])),
             },
             // Cleaning Supplies
             ObjectSchema {
                 name: "Broom",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                display_name: None,
+                icon: "broom",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::AdjacentObstacle, 2.0),
+                    (ObjectConstraint::InZoneWith(Names::Static(&["StorageBox"])), 1.5),
+                ],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A broom for sweeping.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "Mop",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                display_name: None,
+                icon: "mop",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::AdjacentObstacle, 2.0),
+                    (ObjectConstraint::InZoneWith(Names::Static(&["StorageBox"])), 1.5),
+                ],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A mop for mopping floors.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "VacuumCleaner",
+                display_name: None,
+                icon: "vacuum_cleaner",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "An upright vacuum cleaner.",
                 target: ObjectConstraint::AdjacentObstacle,
             },
             ObjectSchema {
                 name: "Bucket",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                display_name: None,
+                icon: "bucket",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::AdjacentObstacle, 2.0),
+                    (ObjectConstraint::InZoneWith(Names::Static(&["StorageBox"])), 1.5),
+                ],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A cleaning bucket.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "SprayBottle",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                display_name: None,
+                icon: "spray_bottle",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::AdjacentObstacle, 2.0),
+                    (ObjectConstraint::InZoneWith(Names::Static(&["StorageBox"])), 1.5),
+                ],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A bottle of cleaning spray.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "Sponge",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                display_name: None,
+                icon: "sponge",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![
+                    (ObjectConstraint::AdjacentObstacle, 2.0),
+                    (ObjectConstraint::InZoneWith(Names::Static(&["StorageBox"])), 1.5),
+                ],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A cleaning sponge.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             // Personal Items
             ObjectSchema {
                 name: "Wallet",
+                display_name: None,
+                icon: "wallet",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A personal wallet.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
             },
             ObjectSchema {
                 name: "Sunglasses",
+                display_name: None,
+                icon: "sunglasses",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer"]),
-                    ObjectConstraint::InRoomNamed(&["Hallway", "Living Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Hallway", "Living Room"])),
                 ]),
                 description: "A pair of sunglasses.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Watch",
+                display_name: None,
+                icon: "watch",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A wristwatch.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Backpack",
+                display_name: None,
+                icon: "backpack",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A shoulder backpack.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "Umbrella",
+                display_name: None,
+                icon: "umbrella",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A closed umbrella.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             // Children’s Toys
             ObjectSchema {
                 name: "PuzzlePiece",
+                display_name: None,
+                icon: "puzzle_piece",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["ToyBox"]),
-                    ObjectConstraint::InRoomNamed(&["Playroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Playroom"])),
                 ]),
                 description: "A piece of a jigsaw puzzle.",
-                target: ObjectConstraint::InsideOf(&["ToyBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
             },
             ObjectSchema {
                 name: "LegoBrick",
+                display_name: None,
+                icon: "lego_brick",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["ToyBox"]),
-                    ObjectConstraint::InRoomNamed(&["Playroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Playroom"])),
                 ]),
                 description: "A single lego brick.",
-                target: ObjectConstraint::InsideOf(&["ToyBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
             },
             ObjectSchema {
                 name: "Ball",
+                display_name: None,
+                icon: "ball",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["ToyBox"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A small ball.",
-                target: ObjectConstraint::InsideOf(&["ToyBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
             },
             ObjectSchema {
                 name: "Doll",
+                display_name: None,
+                icon: "doll",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["ToyBox", "Sofa", "Armchair"]),
-                    ObjectConstraint::InRoomNamed(&["Playroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["ToyBox", "Sofa", "Armchair"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Playroom"])),
                 ]),
                 description: "A child’s doll.",
-                target: ObjectConstraint::InsideOf(&["ToyBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["ToyBox"])),
             },
             ObjectSchema {
                 name: "BoardGame",
+                display_name: None,
+                icon: "board_game",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
-                    ObjectConstraint::InsideOf(&["DiningTable", "CoffeeTable"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable", "CoffeeTable"])),
                 ]),
                 description: "A board game set.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "Crayon",
+                display_name: None,
+                icon: "crayon",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
-                    ObjectConstraint::InRoomNamed(&["Playroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Playroom"])),
                 ]),
                 description: "A colored crayon.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "PaintBrush",
+                display_name: None,
+                icon: "paint_brush",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
-                    ObjectConstraint::InRoomNamed(&["Playroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Playroom"])),
                 ]),
                 description: "A paint brush.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             // Decor & Electronics
             ObjectSchema {
                 name: "Vase",
+                display_name: None,
+                icon: "vase",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["CoffeeTable", "DiningTable"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "DiningTable"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A decorative vase.",
-                target: ObjectConstraint::InsideOf(&["CoffeeTable", "DiningTable"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "DiningTable"])),
             },
             ObjectSchema {
                 name: "PictureFrame",
+                display_name: None,
+                icon: "picture_frame",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["CoffeeTable", "DiningTable"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "DiningTable"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A photo in a frame.",
-                target: ObjectConstraint::InsideOf(&["CoffeeTable", "DiningTable"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "DiningTable"])),
             },
             ObjectSchema {
                 name: "Lamp",
+                display_name: None,
+                icon: "lamp",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "A floor lamp.",
                 target: ObjectConstraint::AdjacentObstacle,
             },
             ObjectSchema {
                 name: "Rug",
+                display_name: None,
+                icon: "rug",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::InRoom,
                 description: "A small rug.",
                 target: ObjectConstraint::InRoom,
             },
             ObjectSchema {
                 name: "Cushion",
+                display_name: None,
+                icon: "cushion",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Sofa", "Bed"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Sofa", "Bed"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A throw cushion.",
-                target: ObjectConstraint::InsideOf(&["Sofa", "Bed"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Sofa", "Bed"])),
             },
             ObjectSchema {
                 name: "GameController",
+                display_name: None,
+                icon: "game_controller",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["StorageBox"]),
-                    ObjectConstraint::InRoomNamed(&["Living Room", "Office"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Office"])),
                 ]),
                 description: "A video game controller.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "Headphones",
+                display_name: None,
+                icon: "headphones",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
-                    ObjectConstraint::InRoomNamed(&["Office", "Living Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Living Room"])),
                 ]),
                 description: "A pair of headphones.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
             },
             ObjectSchema {
                 name: "Speaker",
+                display_name: None,
+                icon: "speaker",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "A Bluetooth speaker.",
                 target: ObjectConstraint::AdjacentObstacle,
             },
             ObjectSchema {
                 name: "ChargingCable",
+                display_name: None,
+                icon: "charging_cable",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
                     ObjectConstraint::InRoom,
                 ]),
                 description: "A USB charging cable.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
             },
             // ——— Dirty kitchen items for the Dishwasher ———
             ObjectSchema {
                 name: "DirtyPlate",
+                display_name: None,
+                icon: "dirty_plate",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["DiningTable", "Cupboard"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable", "Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A ceramic plate with leftover food scraps.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyBowl",
+                display_name: None,
+                icon: "dirty_bowl",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["DiningTable", "Cupboard"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable", "Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A bowl stained with sauce or soup.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyCup",
+                display_name: None,
+                icon: "dirty_cup",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["DiningTable", "Cupboard"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable", "Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A cup with tea or coffee stains.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtySilverware",
+                display_name: None,
+                icon: "dirty_silverware",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A spoon, fork, or knife covered in food residue.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             // ——— More Dirty kitchen items for the Dishwasher ———
             ObjectSchema {
                 name: "DirtyGlass",
+                display_name: None,
+                icon: "dirty_glass",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A drinking glass with lipstick or juice stains.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyWineGlass",
+                display_name: None,
+                icon: "dirty_wine_glass",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A wine glass with dried wine residue.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyMug",
+                display_name: None,
+                icon: "dirty_mug",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Breakfast Nook"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A coffee mug with grounds and stains.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtySaucepan",
+                display_name: None,
+                icon: "dirty_saucepan",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "StoveTop"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A saucepan caked with burnt-on sauce.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyBakingTray",
+                display_name: None,
+                icon: "dirty_baking_tray",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "Oven"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A baking tray with hardened batter or crumbs.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
             },
             ObjectSchema {
                 name: "DirtyColander",
+                display_name: None,
+                icon: "dirty_colander",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "Sink Area"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A colander with stuck-on vegetable bits.",
-                target: ObjectConstraint::InsideOf(&["Dishwasher"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
+            },
+            // ——— Clean dishes, only produced by running the Dishwasher ———
+            ObjectSchema {
+                name: "Plate",
+                display_name: None,
+                icon: "plate",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
+                description: "A clean ceramic plate.",
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+            },
+            ObjectSchema {
+                name: "Mug",
+                display_name: None,
+                icon: "mug",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
+                description: "A clean coffee mug.",
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+            },
+            ObjectSchema {
+                name: "Glass",
+                display_name: None,
+                icon: "glass",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Dishwasher"])),
+                description: "A clean drinking glass.",
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
             },
             // ——— Spoiled fruits & vegetables for the TrashCan ———
             ObjectSchema {
                 name: "RottenTomato",
+                display_name: None,
+                icon: "rotten_tomato",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 ]),
                 description: "A tomato that has gone mushy and moldy.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
+            },
+            ObjectSchema {
+                name: "RottenApple",
+                display_name: None,
+                icon: "rotten_apple",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
+                constraint: ObjectConstraint::Or(vec![
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
+                ]),
+                description: "A bruised apple that has gone soft and brown.",
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "MoldyBread",
+                display_name: None,
+                icon: "moldy_bread",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A loaf of bread covered in green or white mold.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "SpoiledLettuce",
+                display_name: None,
+                icon: "spoiled_lettuce",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator", "FruitBowl"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator", "FruitBowl"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A head of lettuce that’s wilted and slimy.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "RottenBanana",
+                display_name: None,
+                icon: "rotten_banana",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A banana blackened with overripeness and rot.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             // ——— Even more spoiled fruits & vegetables for the TrashCan ———
             ObjectSchema {
                 name: "RottenStrawberry",
+                display_name: None,
+                icon: "rotten_strawberry",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A strawberry covered in mold and leaking juices.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "RottenGrapes",
+                display_name: None,
+                icon: "rotten_grapes",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A bunch of grapes that have shriveled and rotten.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "SpoiledCucumber",
+                display_name: None,
+                icon: "spoiled_cucumber",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator", "DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A cucumber that’s gone soft and slimy.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "SpoiledCarrot",
+                display_name: None,
+                icon: "spoiled_carrot",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Refrigerator", "VegetableCrisper"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Refrigerator"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A carrot that’s limp and discolored.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "RottenPotato",
+                display_name: None,
+                icon: "rotten_potato",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Pantry", "Cupboard"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A potato speckled with soft spots and mold.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "RottenOnion",
+                display_name: None,
+                icon: "rotten_onion",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Pantry", "Cupboard"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "An onion with a slimy, foul-smelling rot core.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "MoldyBreadSlice",
+                display_name: None,
+                icon: "moldy_bread_slice",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["BreadLoaf", "DiningTable"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["DiningTable"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "A single slice of bread covered in fuzzy mold.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             ObjectSchema {
                 name: "RottenBlueberries",
+                display_name: None,
+                icon: "rotten_blueberries",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["FruitBowl"]),
-                    ObjectConstraint::InRoomNamed(&["Kitchen"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["FruitBowl"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"])),
                 ]),
                 description: "Blueberries that have burst and fermented.",
-                target: ObjectConstraint::InsideOf(&["TrashCan"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["TrashCan"])),
             },
             // ——— Laundry & Clothing ———
             ObjectSchema {
                 name: "LaundryBasket",
+                display_name: None,
+                icon: "laundry_basket",
                 capacity: 50,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Laundry Room", "Bathroom"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
                 description: "A basket for holding dirty laundry.",
-                target: ObjectConstraint::InRoomNamed(&["Laundry Room", "Bathroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom"])),
             },
             ObjectSchema {
                 name: "DirtyClothes",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&[
+                display_name: None,
+                icon: "dirty_clothes",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&[
                     "LaundryBasket",
                     "Wardrobe",
                     "StorageBox",
-                ]),
+                ])),
                 description: "A pile of worn garments needing washing.",
-                target: ObjectConstraint::InsideOf(&["LaundryBasket"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["LaundryBasket"])),
             },
             ObjectSchema {
                 name: "IroningBoard",
+                display_name: None,
+                icon: "ironing_board",
                 capacity: 10,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Laundry Room", "Bedroom"]),
+                blocks_movement: true,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom"])),
                 description: "A fold-out ironing board.",
-                target: ObjectConstraint::InRoomNamed(&["Laundry Room", "Bedroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom"])),
             },
             ObjectSchema {
                 name: "Iron",
+                display_name: None,
+                icon: "iron",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Cupboard", "Drawer"]),
-                    ObjectConstraint::InRoomNamed(&["Laundry Room", "Bedroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Drawer"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Bedroom"])),
                 ]),
                 description: "An electric clothes iron.",
-                target: ObjectConstraint::InsideOf(&["Cupboard", "Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Drawer"])),
             },
             // ——— Safety & First Aid ———
             ObjectSchema {
                 name: "FireExtinguisher",
+                display_name: None,
+                icon: "fire_extinguisher",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "A wall-mounted fire extinguisher.",
                 target: ObjectConstraint::AdjacentObstacle,
             },
             ObjectSchema {
                 name: "FirstAidKit",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InRoomNamed(&["Bathroom", "Hallway", "Office"]),
+                display_name: None,
+                icon: "first_aid_kit",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom", "Hallway", "Office"])),
                 description: "A box of first-aid supplies.",
-                target: ObjectConstraint::InRoomNamed(&["Bathroom", "Hallway", "Office"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Bathroom", "Hallway", "Office"])),
             },
             // ——— Pet Supplies ———
             ObjectSchema {
                 name: "PetFoodBowl",
+                display_name: None,
+                icon: "pet_food_bowl",
                 capacity: 10,
-                pickable: true,
-                constraint: ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
                 description: "A bowl for pet food or water.",
-                target: ObjectConstraint::InRoomNamed(&["Kitchen", "Dining Room"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen", "Dining Room"])),
             },
             ObjectSchema {
                 name: "PetBed",
+                display_name: None,
+                icon: "pet_bed",
                 capacity: 5,
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom"])),
                 description: "A cushioned pet bed.",
-                target: ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom"])),
             },
             ObjectSchema {
                 name: "DogLeash",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                display_name: None,
+                icon: "dog_leash",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
                 description: "A leash for walking a dog.",
-                target: ObjectConstraint::InsideOf(&["Drawer", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer", "StorageBox"])),
             },
             // ——— Tools & Maintenance ———
             ObjectSchema {
                 name: "Hammer",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["GarageShelf", "StorageBox"]),
+                display_name: None,
+                icon: "hammer",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
                 description: "A standard claw hammer.",
-                target: ObjectConstraint::InsideOf(&["GarageShelf", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "ScrewdriverSet",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["GarageShelf", "Drawer"]),
+                display_name: None,
+                icon: "screwdriver_set",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
                 description: "A set of screwdrivers in a pouch.",
-                target: ObjectConstraint::InsideOf(&["GarageShelf", "Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Toolbox",
+                display_name: None,
+                icon: "toolbox",
                 capacity: 30,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: true,
+                disposes: false,
                 pickable: false,
-                constraint: ObjectConstraint::InRoomNamed(&["Garage", "Basement"]),
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoomNamed(Names::Static(&["Hallway", "Office"])),
                 description: "A portable metal toolbox.",
-                target: ObjectConstraint::InRoomNamed(&["Garage", "Basement"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Hallway", "Office"])),
             },
             // ——— Decor Items (wall-adjacent) ———
             ObjectSchema {
                 name: "WallMirror",
+                display_name: None,
+                icon: "wall_mirror",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom", "Hallway"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom", "Hallway"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A decorative wall mirror with an ornate frame.",
-                target: ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom", "Hallway"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom", "Hallway"])),
             },
             ObjectSchema {
                 name: "WallClock",
+                display_name: None,
+                icon: "wall_clock",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
                     ObjectConstraint::InRoom,
                     ObjectConstraint::AdjacentObstacle,
@@ -1385,114 +3056,344 @@ impl ObjectSchema {
             },
             ObjectSchema {
                 name: "Chandelier",
+                display_name: None,
+                icon: "chandelier",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::AdjacentObstacle,
                 description: "An elegant ceiling chandelier.",
                 target: ObjectConstraint::AdjacentObstacle,
             },
             ObjectSchema {
                 name: "Curtains",
+                display_name: None,
+                icon: "curtains",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A pair of window curtains.",
-                target: ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom"])),
             },
             ObjectSchema {
                 name: "Blinds",
+                display_name: None,
+                icon: "blinds",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Office", "Living Room"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Living Room"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A set of horizontal window blinds.",
-                target: ObjectConstraint::InRoomNamed(&["Office", "Living Room"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Office", "Living Room"])),
             },
             ObjectSchema {
                 name: "Tapestry",
+                display_name: None,
+                icon: "tapestry",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
                 constraint: ObjectConstraint::And(vec![
-                    ObjectConstraint::InRoomNamed(&["Living Room", "Dining Room"]),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Dining Room"])),
                     ObjectConstraint::AdjacentObstacle,
                 ]),
                 description: "A decorative woven wall tapestry.",
-                target: ObjectConstraint::InRoomNamed(&["Living Room", "Dining Room"]),
+                target: ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Dining Room"])),
             },
             // ——— Revised Pickable Decor Items ———
             ObjectSchema {
                 name: "ThrowBlanket",
+                display_name: None,
+                icon: "throw_blanket",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::Or(vec![
-                    ObjectConstraint::InsideOf(&["Sofa", "Armchair", "Bed"]),
-                    ObjectConstraint::InRoomNamed(&["Living Room", "Bedroom"]),
+                    ObjectConstraint::InsideOf(Names::Static(&["Sofa", "Armchair", "Bed"])),
+                    ObjectConstraint::InRoomNamed(Names::Static(&["Living Room", "Bedroom"])),
                 ]),
                 description: "A cozy knit throw blanket.",
-                target: ObjectConstraint::InsideOf(&["Wardrobe", "Dresser", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Wardrobe", "Dresser", "StorageBox"])),
             },
             ObjectSchema {
                 name: "DecorativeBowl",
+                display_name: None,
+                icon: "decorative_bowl",
                 capacity: 5,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["CoffeeTable", "DiningTable", "Shelf"]),
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "DiningTable", "Bookshelf"])),
                 description: "A ceramic bowl used purely for decoration.",
-                target: ObjectConstraint::InsideOf(&["Cupboard", "Drawer", "StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard", "Drawer", "StorageBox"])),
             },
             ObjectSchema {
                 name: "CoasterSet",
+                display_name: None,
+                icon: "coaster_set",
                 capacity: 4,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["CoffeeTable", "SideTable"]),
+                container_kind: Some(ContainerKind::Surface),
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable"])),
                 description: "A set of drink coasters.",
-                target: ObjectConstraint::InsideOf(&["Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Drawer"])),
             },
             ObjectSchema {
                 name: "Sculpture",
-                capacity: 0,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["Shelf", "CoffeeTable", "Mantel"]),
+                display_name: None,
+                icon: "sculpture",
+                capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["Bookshelf", "CoffeeTable"])),
                 description: "A small decorative sculpture or figurine.",
-                target: ObjectConstraint::InsideOf(&["StorageBox"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox"])),
             },
             ObjectSchema {
                 name: "FairyLights",
+                display_name: None,
+                icon: "fairy_lights",
                 capacity: 0,
+                container_kind: None,
+                starts_closed: false,
+                disposes: false,
                 pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: vec![(ObjectConstraint::AdjacentObstacle, 1.0)],
                 constraint: ObjectConstraint::InRoom,
                 description: "A string of decorative fairy lights.",
-                target: ObjectConstraint::InsideOf(&["StorageBox", "Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["StorageBox", "Drawer"])),
             },
             ObjectSchema {
                 name: "PhotoAlbum",
+                display_name: None,
+                icon: "photo_album",
                 capacity: 20,
-                pickable: true,
-                constraint: ObjectConstraint::InsideOf(&["CoffeeTable", "Bookshelf", "Desk"]),
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
+                pickable: true,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InsideOf(Names::Static(&["CoffeeTable", "Bookshelf", "Desk"])),
                 description: "A leather-bound photo album.",
-                target: ObjectConstraint::InsideOf(&["Bookshelf", "Drawer"]),
+                target: ObjectConstraint::InsideOf(Names::Static(&["Bookshelf", "Drawer"])),
+            },
+            // ——— Task props (only added by delivery tasks) ———
+            ObjectSchema {
+                name: "GroceryBag",
+                display_name: None,
+                icon: "grocery_bag",
+                capacity: 8,
+                container_kind: Some(ContainerKind::Enclosed),
+                starts_closed: false,
+                disposes: false,
+                pickable: false,
+                blocks_movement: false,
+                decays_into: None,
+                transforms: Vec::new(),
+                preference: Vec::new(),
+                constraint: ObjectConstraint::InRoom,
+                description: "A paper bag of groceries waiting to be put away.",
+                target: ObjectConstraint::InRoom,
             },
         ]
     }
 }
 
 impl Object {
-    /// return true if this object is in its correct target placement
-    pub fn check_placement(&self, world: &World) -> bool {
-        // find schema for this object name
-        let schema = ObjectSchema::default_schemas()
-            .into_iter()
-            .find(|s| s.name == self.name)
-            .expect("Schema must exist for object");
+    /// New empty, unlabelled instance of `schema` at (x, y), decaying into built-in schemas
+    pub fn from_schema(id: ObjectId, schema: &ObjectSchema, x: usize, y: usize) -> Self {
+        Self::from_schema_among(id, schema, &[], x, y)
+    }
 
-        // check target constraint
-        if !schema.target.check(world, self.x, self.y) {
-            return false;
+    /// `from_schema` for a schema of the set `schemas`, which what it decays and transforms
+    /// into is looked up in before the built-in catalog
+    pub fn from_schema_among(id: ObjectId, schema: &ObjectSchema, schemas: &[ObjectSchema], x: usize, y: usize) -> Self {
+        Object { decay: Decay::of(schema, schemas), transforms: Transform::of(schema, schemas), ..Self::instance(id, schema, x, y) }
+    }
+
+    /// Instance of `schema` that neither decays nor transforms others
+    fn instance(id: ObjectId, schema: &ObjectSchema, x: usize, y: usize) -> Self {
+        Object {
+            id,
+            name: Cow::Borrowed(schema.name),
+            display_name: schema.resolved_display_name(),
+            icon: Cow::Borrowed(schema.icon),
+            capacity: schema.capacity,
+            container_kind: schema.container_kind,
+            pickable: schema.pickable,
+            blocks_movement: schema.blocks_movement,
+            x,
+            y,
+            contents: Vec::new(),
+            open: !schema.starts_closed,
+            description: Cow::Borrowed(schema.description),
+            label: None,
+            target: Some(schema.target.clone()),
+            disposes: schema.disposes,
+            decay: None,
+            transforms: Vec::new(),
         }
+    }
+
+    /// Turn this object into an instance of another schema, keeping its id, position and contents
+    pub fn transform_into(&mut self, schema: &ObjectSchema) {
+        self.name = Cow::Borrowed(schema.name);
+        self.display_name = schema.resolved_display_name();
+        self.icon = Cow::Borrowed(schema.icon);
+        self.capacity = schema.capacity;
+        self.container_kind = schema.container_kind;
+        self.pickable = schema.pickable;
+        self.blocks_movement = schema.blocks_movement;
+        self.description = Cow::Borrowed(schema.description);
+        self.target = Some(schema.target.clone());
+        self.disposes = schema.disposes;
+        self.decay = Decay::of(schema, &[]);
+        self.transforms = Transform::of(schema, &[]);
+    }
+
+    /// Turn into what this object decays into, keeping its id, position, contents, open
+    /// state and label; false for objects that do not decay
+    pub fn decay_now(&mut self) -> bool {
+        let Some(decay) = self.decay.take() else { return false };
+        self.take_on(*decay.into);
         true
     }
+
+    /// Take on the schema fields of `into`, keeping id, position, contents, open state and label
+    pub(crate) fn take_on(&mut self, into: Object) {
+        self.name = into.name;
+        self.display_name = into.display_name;
+        self.icon = into.icon;
+        self.capacity = into.capacity;
+        self.container_kind = into.container_kind;
+        self.pickable = into.pickable;
+        self.blocks_movement = into.blocks_movement;
+        self.description = into.description;
+        self.target = into.target;
+        self.disposes = into.disposes;
+        self.decay = into.decay;
+        self.transforms = into.transforms;
+    }
+
+    /// This object followed by each object it would decay into, in order
+    pub fn decay_chain(&self) -> impl Iterator<Item = &Object> {
+        std::iter::successors(Some(self), |o| o.decay.as_ref().map(|d| &*d.into))
+    }
+
+    /// Text visible on the object: its custom label or its display name
+    pub fn display_label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| self.display_name.to_string())
+    }
+
+    /// Whether the object can be opened and closed: containers that are not surfaces
+    pub fn closable(&self) -> bool {
+        self.capacity > 0 && self.container_kind != Some(ContainerKind::Surface)
+    }
+
+    /// Whether another object can be put on or into this one.
+    ///
+    /// Generation, perturbation and the simulator all use this rule, and `World::validate`
+    /// rejects containers holding more than their capacity.
+    pub fn has_room(&self) -> bool {
+        self.contents.len() < self.capacity
+    }
+
+    /// Target constraint this object was created with, if any
+    pub fn target(&self) -> Option<&ObjectConstraint> {
+        self.target.as_ref()
+    }
+
+    /// Whether this object sits where its target constraint holds; false without a target
+    pub fn check_placement(&self, world: &World) -> bool {
+        self.target.as_ref().is_some_and(|t| t.check(world, self.x, self.y))
+    }
+}
+
+/// Words of a schema name, lowercase except for acronyms, e.g. "KitchenCabinet" -> "kitchen
+/// cabinet" and "TVStand" -> "TV stand"
+pub fn humanize(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            words.push(String::new());
+            continue;
+        }
+        // a capital starts a word after a lowercase letter or digit, or ends an acronym
+        // when a lowercase letter follows, as the S in "TVStand"
+        let starts_word = i > 0 && c.is_uppercase() && {
+            let prev = chars[i - 1];
+            prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase()))
+        };
+        match words.last_mut() {
+            Some(word) if !starts_word => word.push(c),
+            _ => words.push(c.to_string()),
+        }
+    }
+    let words: Vec<String> = words
+        .into_iter()
+        .filter(|w| !w.is_empty())
+        .map(|w| if w.chars().count() > 1 && !w.chars().any(char::is_lowercase) { w } else { w.to_lowercase() })
+        .collect();
+    words.join(" ")
 }
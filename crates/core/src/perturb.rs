@@ -0,0 +1,148 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::world::World;
+use crate::object::{ObjectId, ObjectSchema};
+
+/// A single controlled change applied by `World::perturb`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Perturbation {
+    /// Move an object (with its contents) to a random other cell or container satisfying its constraint
+    MoveObject { id: ObjectId },
+    /// Exchange the positions (and container memberships) of two objects
+    SwapObjects { a: ObjectId, b: ObjectId },
+    /// Remove an object; anything it contained stays behind loose on the same cell
+    RemoveObject { id: ObjectId },
+    /// Randomly reassign the room names
+    ShuffleRoomNames,
+}
+
+/// Errors when perturbing a world
+#[derive(Debug, PartialEq)]
+pub enum PerturbError {
+    /// No object with this id exists
+    UnknownObject(ObjectId),
+    /// The perturbation does not make sense for the given objects
+    InvalidPerturbation(&'static str),
+    /// No other cell or container satisfies the object's placement constraint
+    NoCandidate,
+    /// The perturbed world violates a structural invariant
+    InvalidWorld(String),
+    /// The perturbation made this object's target unreachable
+    Unsolvable(ObjectId),
+}
+
+impl World {
+    fn object_index(&self, id: ObjectId) -> Result<usize, PerturbError> {
+        self.object_position(id).ok_or(PerturbError::UnknownObject(id))
+    }
+
+    /// Pickable objects whose target cannot be satisfied anywhere in the world
+    fn unsolvable_objects(&self) -> Vec<ObjectId> {
+        let w = self.layout.width;
+        self.objects
+            .iter()
+            .filter(|o| o.pickable)
+            .filter(|o| match o.target() {
+                Some(target) => {
+                    !o.check_placement(self)
+                        && !(0..self.layout.cells.len())
+                            .any(|i| self.layout.cells[i] >= 0 && target.check(self, i % w, i / w))
+                }
+                None => false,
+            })
+            .map(|o| o.id)
+            .collect()
+    }
+
+    /// Produce a copy of this world with exactly one controlled change applied.
+    ///
+    /// The result is deterministic for a given `seed`, is checked with `World::validate`,
+    /// and is rejected if it leaves any object's target unsatisfiable that was satisfiable before.
+    pub fn perturb(&self, perturbation: Perturbation, seed: u64) -> Result<World, PerturbError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut world = self.clone();
+        match perturbation {
+            Perturbation::MoveObject { id } => {
+                let idx = world.object_index(id)?;
+                let schema = ObjectSchema::find(&world.objects[idx].name)
+                    .ok_or(PerturbError::InvalidPerturbation("object has no known schema"))?;
+                // evaluate candidates without the object itself in the world
+                let obj = world.detach_object(id).expect("object exists");
+                let w = world.layout.width;
+                let mut candidates: Vec<(usize, usize, Option<ObjectId>)> = Vec::new();
+                for i in 0..world.layout.cells.len() {
+                    let (x, y) = (i % w, i / w);
+                    if world.layout.cells[i] >= 0
+                        && (x, y) != (obj.x, obj.y)
+                        && world.objects_at(x, y).is_empty()
+                        && schema.constraint.check(&world, x, y)
+                    {
+                        candidates.push((x, y, None));
+                    }
+                }
+                if obj.pickable {
+                    for parent in &world.objects {
+                        if parent.has_room()
+                            && !self.contains_transitively(obj.id, parent.id)
+                            && self.parent_index(id).map(|pi| self.objects[pi].id) != Some(parent.id)
+                            && schema.constraint.check(&world, parent.x, parent.y)
+                        {
+                            candidates.push((parent.x, parent.y, Some(parent.id)));
+                        }
+                    }
+                }
+                let &(x, y, parent) = candidates.choose(&mut rng).ok_or(PerturbError::NoCandidate)?;
+                // back at its old position in `objects`, so the vector order does not change
+                world.objects.insert(idx, obj);
+                world.clear_object_index();
+                if let Some(pid) = parent {
+                    let pi = world.object_index(pid)?;
+                    world.objects[pi].contents.push(id);
+                }
+                world.move_object(id, x, y);
+            }
+            Perturbation::SwapObjects { a, b } => {
+                if a == b {
+                    return Err(PerturbError::InvalidPerturbation("cannot swap an object with itself"));
+                }
+                let (ia, ib) = (world.object_index(a)?, world.object_index(b)?);
+                if world.contains_transitively(a, b) || world.contains_transitively(b, a) {
+                    return Err(PerturbError::InvalidPerturbation("cannot swap an object with its own contents"));
+                }
+                let pos_a = (world.objects[ia].x, world.objects[ia].y);
+                let pos_b = (world.objects[ib].x, world.objects[ib].y);
+                for o in world.objects.iter_mut() {
+                    for c in o.contents.iter_mut() {
+                        if *c == a {
+                            *c = b;
+                        } else if *c == b {
+                            *c = a;
+                        }
+                    }
+                }
+                world.move_object(a, pos_b.0, pos_b.1);
+                world.move_object(b, pos_a.0, pos_a.1);
+            }
+            Perturbation::RemoveObject { id } => {
+                world.detach_object(id).ok_or(PerturbError::UnknownObject(id))?;
+            }
+            Perturbation::ShuffleRoomNames => {
+                let original = world.layout.room_names.clone();
+                if !original.windows(2).any(|pair| pair[0] != pair[1]) {
+                    return Err(PerturbError::InvalidPerturbation("room names cannot be shuffled into a different order"));
+                }
+                while world.layout.room_names == original {
+                    world.layout.room_names.shuffle(&mut rng);
+                }
+            }
+        }
+        world.validate().map_err(PerturbError::InvalidWorld)?;
+        let before = self.unsolvable_objects();
+        if let Some(&id) = world.unsolvable_objects().iter().find(|id| !before.contains(id)) {
+            return Err(PerturbError::Unsolvable(id));
+        }
+        Ok(world)
+    }
+}
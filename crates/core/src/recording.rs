@@ -0,0 +1,137 @@
+//! Action recordings for replaying episodes.
+//!
+//! Unlike a checkpoint, which logs only what changed state, a recording keeps every action
+//! the caller attempted with its result, so a replay can check that each one turns out the
+//! same. Recordings are written as JSONL: a `format` header line, then one action per line.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, ActionError};
+use crate::format::{CompatError, FormatHeader};
+use crate::sim::Simulator;
+use crate::world::World;
+
+/// One attempted action and its result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAction {
+    /// The action as requested, before any hook replaced it
+    pub action: Action,
+    /// `ActionError::name` of the failure, or `None` if the action succeeded
+    pub error: Option<String>,
+    /// `tick` calls made after this action and before the next one
+    #[serde(default)]
+    pub ticks: u32,
+    /// Id of the acting agent, see `Simulator::step_agent`
+    #[serde(default)]
+    pub agent: usize,
+}
+
+/// First line of a recording's JSONL
+#[derive(Serialize, Deserialize)]
+struct HeaderLine {
+    format: FormatHeader,
+}
+
+/// Why a replay stopped
+#[derive(Debug, PartialEq)]
+pub enum ReplayError {
+    /// The world has no walkable start cell there
+    Start(&'static str),
+    /// The action with this index failed differently than when it was recorded
+    Diverged { index: usize, expected: Option<String>, found: Option<String> },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = |error: &Option<String>| error.clone().unwrap_or_else(|| "success".into());
+        match self {
+            ReplayError::Start(e) => write!(f, "cannot start replay: {}", e),
+            ReplayError::Diverged { index, expected, found } => {
+                write!(f, "replay diverged at action {}: recorded {}, got {}", index, result(expected), result(found))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Simulator {
+    /// Start recording every action passed to `step` (and so every direct method and macro
+    /// primitive), replacing any earlier recording
+    pub fn enable_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Actions recorded since `enable_recording` or the last `reset`; empty when not recording
+    pub fn actions(&self) -> &[RecordedAction] {
+        self.recording.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn record_action(&mut self, action: Action, result: Result<(), &ActionError>) {
+        if let Some(recording) = &mut self.recording {
+            let error = result.err().map(|e| e.name().to_string());
+            recording.push(RecordedAction { action, error, ticks: 0, agent: self.active });
+        }
+    }
+
+    /// Count a tick towards the last recorded action; ticks before the first one are not kept
+    pub(crate) fn record_tick(&mut self) {
+        if let Some(last) = self.recording.as_mut().and_then(|r| r.last_mut()) {
+            last.ticks += 1;
+        }
+    }
+
+    /// Replay a recording on a fresh simulator over `world` starting at `start`.
+    ///
+    /// The simulator has default settings and no hooks; to replay an episode that used other
+    /// rules or hooks, set them up on a simulator and call `replay_actions`.
+    pub fn replay(world: World, start: (usize, usize), actions: &[RecordedAction]) -> Result<Simulator, ReplayError> {
+        let mut sim = Simulator::new(world, start.0, start.1).map_err(ReplayError::Start)?;
+        sim.replay_actions(actions)?;
+        Ok(sim)
+    }
+
+    /// Run the recorded actions and the ticks after each, failing at the first action whose
+    /// result differs.
+    ///
+    /// Door changes through `use_door`, inspections and labels are not actions and are not
+    /// recorded, so episodes relying on them may diverge.
+    pub fn replay_actions(&mut self, actions: &[RecordedAction]) -> Result<(), ReplayError> {
+        for (index, recorded) in actions.iter().enumerate() {
+            let found = self.step_agent(recorded.agent, recorded.action).err().map(|e| e.name().to_string());
+            if found != recorded.error {
+                return Err(ReplayError::Diverged { index, expected: recorded.error.clone(), found });
+            }
+            for _ in 0..recorded.ticks {
+                self.tick();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// JSONL of a recording: a `format` header line, then one action per line
+pub fn to_jsonl(actions: &[RecordedAction]) -> Result<String, serde_json::Error> {
+    let mut out = serde_json::to_string(&HeaderLine { format: FormatHeader::current() })?;
+    for action in actions {
+        out.push('\n');
+        out.push_str(&serde_json::to_string(action)?);
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+/// Load a recording written by `to_jsonl`; blank lines are skipped
+pub fn from_jsonl(jsonl: &str) -> Result<Vec<RecordedAction>, CompatError> {
+    let mut lines = jsonl.lines().filter(|line| !line.trim().is_empty()).peekable();
+    if let Some(HeaderLine { format }) = lines.peek().and_then(|line| serde_json::from_str(line).ok()) {
+        format.check()?;
+        lines.next();
+    }
+    lines
+        .enumerate()
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| CompatError::Malformed(format!("action {}: {}", i, e))))
+        .collect()
+}
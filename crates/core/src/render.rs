@@ -0,0 +1,975 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::cell::CellKind;
+use crate::object::Object;
+#[cfg(feature = "sim")]
+use crate::sim::Simulator;
+use crate::world::{Layout, World};
+
+pub type Rgb = [u8; 3];
+
+/// Color and text glyph of one kind of cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellStyle {
+    pub color: Rgb,
+    pub glyph: char,
+}
+
+/// Colors and glyphs shared by every renderer, so one world looks the same in every output.
+///
+/// Deserializing fills missing fields from `Theme::default()`, so a theme JSON only needs
+/// the entries it changes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Theme {
+    pub background: Rgb,
+    /// Room labels and object ids drawn over cells
+    pub text: Rgb,
+    pub wall: CellStyle,
+    pub outside: CellStyle,
+    pub closed_door: CellStyle,
+    pub open_door: CellStyle,
+    pub locked_door: CellStyle,
+    pub window: CellStyle,
+    pub stairs: CellStyle,
+    /// Glyph of room floor cells; their color comes from the room palette
+    pub room_glyph: char,
+    /// Hue of room 0 in degrees; later rooms step around the color wheel by the golden angle
+    pub palette_seed: f32,
+    pub room_saturation: f32,
+    pub room_lightness: f32,
+    /// Per-icon object colors; other objects get a hue derived from their icon
+    pub object_colors: BTreeMap<String, Rgb>,
+    pub object_saturation: f32,
+    pub object_lightness: f32,
+    pub agent: CellStyle,
+    /// Fixed room colors, cycled by room id; empty uses the generated palette
+    pub room_palette: Vec<Rgb>,
+}
+
+/// Extra non-color cues for `render_rgba`, `render_png` and `render_svg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RenderOpts {
+    /// Letter per room (see `room_letter`) near its center and per object (its icon's initial)
+    pub glyphs: bool,
+    /// Fill each room with one of `PATTERNS`, cycled by room id
+    pub pattern_fill: bool,
+    /// Name of each room at the center of its bounding box (`Layout::room_bbox`), in place
+    /// of the room letters; raster images spell it in uppercase glyph letters, leaving gaps
+    /// for other characters
+    pub room_names: bool,
+}
+
+/// Room fill patterns of `RenderOpts::pattern_fill`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    DiagonalStripes,
+    Dots,
+    HorizontalStripes,
+    VerticalStripes,
+    CrossHatch,
+    AntiDiagonalStripes,
+}
+
+pub const PATTERNS: [Pattern; 6] = [
+    Pattern::DiagonalStripes,
+    Pattern::Dots,
+    Pattern::HorizontalStripes,
+    Pattern::VerticalStripes,
+    Pattern::CrossHatch,
+    Pattern::AntiDiagonalStripes,
+];
+
+impl Pattern {
+    /// Whether the pattern covers pixel (x, y) of an image tiled with `period`-pixel cells
+    fn covers(self, x: usize, y: usize, period: usize) -> bool {
+        let (x, y) = (x % period, y % period);
+        let line = |v: usize| v < period.div_ceil(4);
+        match self {
+            Pattern::DiagonalStripes => line((x + y) % period),
+            Pattern::AntiDiagonalStripes => line((x + period - y) % period),
+            Pattern::HorizontalStripes => line(y),
+            Pattern::VerticalStripes => line(x),
+            Pattern::CrossHatch => line(x) || line(y),
+            Pattern::Dots => line(x) && line(y),
+        }
+    }
+
+    /// SVG path of the pattern within one `period`-unit tile
+    fn svg_path(self, period: usize) -> String {
+        let (p, t) = (period, period.div_ceil(4));
+        match self {
+            Pattern::DiagonalStripes => format!("M0 {p} L{p} 0 M{h} {p} L{p} {h} M0 {h} L{h} 0", h = p / 2),
+            Pattern::AntiDiagonalStripes => format!("M0 0 L{p} {p} M{h} 0 L{p} {h} M0 {h} L{h} {p}", h = p / 2),
+            Pattern::HorizontalStripes => format!("M0 {y} H{p}", y = t / 2),
+            Pattern::VerticalStripes => format!("M{x} 0 V{p}", x = t / 2),
+            Pattern::CrossHatch => format!("M0 {y} H{p} M{y} 0 V{p}", y = t / 2),
+            Pattern::Dots => format!("M{y} {y} h0.01", y = t / 2),
+        }
+    }
+}
+
+/// Letter marking a room in the glyph layer: A to Z, then 0 to 9, repeating after 36 rooms
+pub fn room_letter(room: usize) -> char {
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    LETTERS[room % LETTERS.len()] as char
+}
+
+/// Letter marking an object in the glyph layer: the uppercase initial of its icon
+pub fn object_letter(object: &Object) -> char {
+    object.icon.chars().next().map_or('?', |c| c.to_ascii_uppercase())
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            background: [204, 204, 204],
+            text: [0, 0, 0],
+            wall: CellStyle { color: [0, 0, 0], glyph: '■' },
+            outside: CellStyle { color: [255, 255, 255], glyph: ' ' },
+            closed_door: CellStyle { color: [153, 153, 153], glyph: 'D' },
+            open_door: CellStyle { color: [204, 204, 204], glyph: 'd' },
+            locked_door: CellStyle { color: [102, 51, 0], glyph: 'L' },
+            window: CellStyle { color: [135, 206, 235], glyph: '=' },
+            stairs: CellStyle { color: [160, 120, 80], glyph: '#' },
+            room_glyph: '□',
+            palette_seed: 0.0,
+            room_saturation: 0.6,
+            room_lightness: 0.7,
+            object_colors: BTreeMap::new(),
+            object_saturation: 0.6,
+            object_lightness: 0.5,
+            agent: CellStyle { color: [255, 0, 0], glyph: '@' },
+            room_palette: Vec::new(),
+        }
+    }
+}
+
+impl Theme {
+    /// Saturated, widely separated colors on black for low-vision users
+    pub fn high_contrast() -> Self {
+        Theme {
+            background: [0, 0, 0],
+            text: [255, 255, 255],
+            wall: CellStyle { color: [255, 255, 255], glyph: '█' },
+            outside: CellStyle { color: [0, 0, 0], glyph: ' ' },
+            closed_door: CellStyle { color: [255, 255, 0], glyph: 'D' },
+            open_door: CellStyle { color: [0, 255, 255], glyph: 'd' },
+            room_saturation: 1.0,
+            room_lightness: 0.25,
+            object_saturation: 1.0,
+            object_lightness: 0.6,
+            agent: CellStyle { color: [255, 0, 255], glyph: '@' },
+            ..Theme::default()
+        }
+    }
+
+    /// Muted colors on a dark background
+    pub fn dark() -> Self {
+        Theme {
+            background: [24, 24, 27],
+            text: [230, 230, 230],
+            wall: CellStyle { color: [82, 82, 91], glyph: '■' },
+            outside: CellStyle { color: [9, 9, 11], glyph: ' ' },
+            closed_door: CellStyle { color: [161, 98, 7], glyph: 'D' },
+            open_door: CellStyle { color: [113, 113, 122], glyph: 'd' },
+            room_saturation: 0.35,
+            room_lightness: 0.3,
+            object_lightness: 0.6,
+            agent: CellStyle { color: [248, 113, 113], glyph: '@' },
+            ..Theme::default()
+        }
+    }
+
+    /// Okabe–Ito colors, which stay distinguishable with the common color vision deficiencies.
+    ///
+    /// Six rooms get distinct colors before they repeat; combine with `RenderOpts` glyphs
+    /// or patterns to tell more apart.
+    pub fn colorblind_safe() -> Self {
+        Theme {
+            background: [255, 255, 255],
+            closed_door: CellStyle { color: [102, 102, 102], glyph: 'D' },
+            open_door: CellStyle { color: [204, 204, 204], glyph: 'd' },
+            window: CellStyle { color: [86, 180, 233], glyph: '=' },
+            // orange, sky blue, bluish green, yellow, blue, vermillion
+            room_palette: vec![[230, 159, 0], [86, 180, 233], [0, 158, 115], [240, 228, 66], [0, 114, 178], [213, 94, 0]],
+            // reddish purple, the one Okabe–Ito color not used by rooms
+            agent: CellStyle { color: [204, 121, 167], glyph: '@' },
+            ..Theme::default()
+        }
+    }
+
+    /// Default colors with plain ASCII glyphs, for text snapshots in logs and tests. No cell
+    /// glyph is a letter or digit, so none reads as a room letter, object initial or id
+    pub fn ascii() -> Self {
+        Theme {
+            wall: CellStyle { glyph: '#', ..Theme::default().wall },
+            closed_door: CellStyle { glyph: '+', ..Theme::default().closed_door },
+            open_door: CellStyle { glyph: '/', ..Theme::default().open_door },
+            locked_door: CellStyle { glyph: '%', ..Theme::default().locked_door },
+            stairs: CellStyle { glyph: '>', ..Theme::default().stairs },
+            room_glyph: '.',
+            ..Theme::default()
+        }
+    }
+
+    /// Built-in theme by name ("default", "high_contrast", "dark", "colorblind_safe" or "ascii")
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Theme::default()),
+            "high_contrast" => Some(Theme::high_contrast()),
+            "dark" => Some(Theme::dark()),
+            "colorblind_safe" => Some(Theme::colorblind_safe()),
+            "ascii" => Some(Theme::ascii()),
+            _ => None,
+        }
+    }
+
+    /// Palette color of a room id
+    pub fn room_color(&self, room: usize) -> Rgb {
+        if !self.room_palette.is_empty() {
+            return self.room_palette[room % self.room_palette.len()];
+        }
+        // golden angle keeps neighbouring ids far apart for any number of rooms
+        let hue = self.palette_seed + room as f32 * 137.507_77;
+        hsl(hue, self.room_saturation, self.room_lightness)
+    }
+
+    /// Color of a grid cell value
+    pub fn cell_color(&self, cell: i8) -> Rgb {
+        match CellKind::from_i8(cell) {
+            CellKind::Room(id) => self.room_color(id as usize),
+            kind => self.style(kind).color,
+        }
+    }
+
+    /// Text glyph of a grid cell value
+    pub fn cell_glyph(&self, cell: i8) -> char {
+        match CellKind::from_i8(cell) {
+            CellKind::Room(_) => self.room_glyph,
+            kind => self.style(kind).glyph,
+        }
+    }
+
+    /// Style of a non-room cell kind; room cells get the wall style
+    fn style(&self, kind: CellKind) -> &CellStyle {
+        match kind {
+            CellKind::Outside => &self.outside,
+            CellKind::ClosedDoor => &self.closed_door,
+            CellKind::OpenDoor => &self.open_door,
+            CellKind::LockedDoor => &self.locked_door,
+            CellKind::Window => &self.window,
+            CellKind::Stairs(_) => &self.stairs,
+            CellKind::Wall | CellKind::Room(_) => &self.wall,
+        }
+    }
+
+    /// Color of an object: its icon's override or a hue hashed from the icon
+    pub fn object_color(&self, object: &Object) -> Rgb {
+        if let Some(&c) = self.object_colors.get(&*object.icon) {
+            return c;
+        }
+        // FNV-1a over the icon, so every instance of a schema shares a color across worlds
+        let hash = object.icon.bytes().fold(0x811c_9dc5u32, |h, b| (h ^ b as u32).wrapping_mul(0x0100_0193));
+        hsl((hash % 360) as f32, self.object_saturation, self.object_lightness)
+    }
+}
+
+/// `#rrggbb` form of a color, for CSS and SVG
+pub fn css(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn hsl(hue: f32, s: f32, l: f32) -> Rgb {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    [channel(r), channel(g), channel(b)]
+}
+
+/// What the renderers draw: a world and, in a running simulation, the agent on it
+pub trait Scene {
+    fn world(&self) -> &World;
+    /// Cell of the agent, `None` when there is no agent to draw
+    fn agent(&self) -> Option<(usize, usize)>;
+}
+
+impl Scene for World {
+    fn world(&self) -> &World {
+        self
+    }
+
+    fn agent(&self) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+#[cfg(feature = "sim")]
+impl Scene for Simulator {
+    fn world(&self) -> &World {
+        &self.world
+    }
+
+    fn agent(&self) -> Option<(usize, usize)> {
+        Some((self.agent.x, self.agent.y))
+    }
+}
+
+/// Objects drawn on the grid: everything not stored inside a container, highest id first
+/// so the object `World::objects_at` lists first ends up on top of a stack
+fn visible_objects(world: &World) -> Vec<&Object> {
+    let stored: Vec<_> = world.objects.iter().flat_map(|o| o.contents.iter().copied()).collect();
+    let mut visible: Vec<&Object> = world.objects.iter().filter(|o| !stored.contains(&o.id)).collect();
+    visible.sort_by_key(|o| std::cmp::Reverse(o.id));
+    visible
+}
+
+/// Cells with more than one visible object, and how many
+fn stacks(visible: &[&Object]) -> BTreeMap<(usize, usize), usize> {
+    let mut counts = BTreeMap::new();
+    for o in visible {
+        *counts.entry((o.x, o.y)).or_insert(0) += 1;
+    }
+    counts.retain(|_, &mut n| n > 1);
+    counts
+}
+
+/// Text grid of the scene: cell glyphs, object ids (last digit, the lowest id on a
+/// stacked cell) and the agent
+pub fn render_ascii(scene: &impl Scene, theme: &Theme) -> String {
+    render_text(scene, theme, RenderOpts::default())
+}
+
+/// `render_ascii` with `RenderOpts::glyphs` support: each room's letter on one of its cells,
+/// as in `render_rgba`, and objects by `object_letter`, lowercase for pickables, instead of
+/// ids. Patterns do not apply to text.
+pub fn render_text(scene: &impl Scene, theme: &Theme, opts: RenderOpts) -> String {
+    let world = scene.world();
+    let mut rows = text_rows(&world.layout, |c| theme.cell_glyph(c));
+    if opts.glyphs {
+        for (room, anchor) in room_anchors(world).into_iter().enumerate() {
+            if let Some((x, y)) = anchor {
+                rows[y][x] = room_letter(room);
+            }
+        }
+    }
+    for o in visible_objects(world) {
+        rows[o.y][o.x] = match (opts.glyphs, o.pickable) {
+            (true, true) => object_letter(o).to_ascii_lowercase(),
+            (true, false) => object_letter(o),
+            (false, _) => char::from_digit((o.id % 10) as u32, 10).unwrap(),
+        };
+    }
+    if let Some((x, y)) = scene.agent() {
+        rows[y][x] = theme.agent.glyph;
+    }
+    join_rows(rows)
+}
+
+/// Glyph of every cell, one row per grid row
+fn text_rows(layout: &Layout, glyph: impl Fn(i8) -> char) -> Vec<Vec<char>> {
+    layout.cells.chunks(layout.width).map(|row| row.iter().map(|&c| glyph(c)).collect()).collect()
+}
+
+fn join_rows(rows: Vec<Vec<char>>) -> String {
+    rows.into_iter().map(|row| row.into_iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+}
+
+impl Layout {
+    /// Text grid of the cells alone in `Theme::ascii` glyphs, every room cell showing its
+    /// `room_letter`
+    pub fn render_ascii(&self) -> String {
+        let theme = Theme::ascii();
+        join_rows(text_rows(self, |c| match CellKind::from_i8(c) {
+            CellKind::Room(id) => room_letter(id as usize),
+            _ => theme.cell_glyph(c),
+        }))
+    }
+}
+
+#[cfg(feature = "sim")]
+impl Simulator {
+    /// Text grid of the current state in `Theme::ascii` glyphs with room letters and object
+    /// initials, lowercase for pickables (see `render_text`)
+    pub fn render_ascii(&self) -> String {
+        render_text(self, &Theme::ascii(), RenderOpts { glyphs: true, ..RenderOpts::default() })
+    }
+}
+
+/// 3x5 bitmaps of the glyph layer's letters, one row of 3 bits per entry, top row first
+const FONT: [(char, [u8; 5]); 36] = [
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', [0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', [0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', [0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', [0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', [0b010, 0b101, 0b101, 0b101, 0b010]),
+    ('P', [0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', [0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', [0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', [0b011, 0b100, 0b010, 0b001, 0b110]),
+    ('T', [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', [0b111, 0b001, 0b010, 0b100, 0b111]),
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b110, 0b001, 0b010, 0b100, 0b111]),
+    ('3', [0b110, 0b001, 0b010, 0b001, 0b110]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b110, 0b001, 0b110]),
+    ('6', [0b011, 0b100, 0b110, 0b101, 0b010]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b010, 0b101, 0b010, 0b101, 0b010]),
+    ('9', [0b010, 0b101, 0b011, 0b001, 0b110]),
+];
+
+/// Black or white, whichever reads better on `color`
+fn contrast(color: Rgb) -> Rgb {
+    let luma = 0.299 * color[0] as f32 + 0.587 * color[1] as f32 + 0.114 * color[2] as f32;
+    if luma > 140.0 { [0, 0, 0] } else { [255, 255, 255] }
+}
+
+/// Cell of each room id where its letter goes: the room cell closest to the room's
+/// centroid, preferring cells without a visible object
+fn room_anchors(world: &World) -> Vec<Option<(usize, usize)>> {
+    let layout = &world.layout;
+    let occupied: Vec<(usize, usize)> = visible_objects(world).iter().map(|o| (o.x, o.y)).collect();
+    (0..layout.room_names.len())
+        .map(|room| {
+            let cells: Vec<(usize, usize)> =
+                layout.kinds_with_coords().filter(|&(_, _, k)| k.room() == Some(room)).map(|(x, y, _)| (x, y)).collect();
+            let n = cells.len().max(1) as f32;
+            let (cx, cy) = cells.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| (sx + x as f32 / n, sy + y as f32 / n));
+            let dist = |&(x, y): &(usize, usize)| (x as f32 - cx).powi(2) + (y as f32 - cy).powi(2);
+            cells.iter().copied().min_by(|a, b| (occupied.contains(a), dist(a)).partial_cmp(&(occupied.contains(b), dist(b))).unwrap())
+        })
+        .collect()
+}
+
+/// Pixel center and pixel width of each room's bounding box at `scale` pixels per cell,
+/// skipping rooms without cells
+fn room_centers(layout: &Layout, scale: usize) -> Vec<(usize, (usize, usize), usize)> {
+    (0..layout.room_names.len())
+        .filter_map(|room| {
+            let (x0, y0, x1, y1) = layout.room_bbox(room)?;
+            Some((room, ((x0 + x1 + 1) * scale / 2, (y0 + y1 + 1) * scale / 2), (x1 - x0 + 1) * scale))
+        })
+        .collect()
+}
+
+/// Pixel buffer `render_rgba` draws into
+struct Raster {
+    pixels: Vec<u8>,
+    width: usize,
+    scale: usize,
+}
+
+impl Raster {
+    fn fill(&mut self, x: usize, y: usize, inset: usize, color: Rgb) {
+        let s = self.scale;
+        for py in y * s + inset..(y + 1) * s - inset {
+            for px in x * s + inset..(x + 1) * s - inset {
+                self.pixels[(py * self.width + px) * 4..][..3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draw `letter` centered in cell (x, y); cells under 5 pixels are too small for it
+    fn letter(&mut self, x: usize, y: usize, letter: char, color: Rgb) {
+        if self.scale < 5 {
+            return;
+        }
+        let dot = (self.scale / 7).max(1);
+        let (left, top) = (x * self.scale + (self.scale - 3 * dot) / 2, y * self.scale + (self.scale - 5 * dot) / 2);
+        self.glyph(left, top, dot, letter, color);
+    }
+
+    /// Draw `text` in uppercase glyph letters centered on pixel (cx, cy), one dot per 8 pixels
+    /// of cell size or smaller to fit `max_width`; letters that would cross the image border
+    /// are left out
+    fn label(&mut self, cx: usize, cy: usize, max_width: usize, text: &str, color: Rgb) {
+        let dot = (self.scale / 8).min(max_width / (4 * text.chars().count()).max(1)).max(1);
+        let height = self.pixels.len() / 4 / self.width.max(1);
+        let advance = 4 * dot;
+        let total = (text.chars().count() * advance).saturating_sub(dot);
+        let (left, top) = (cx.saturating_sub(total / 2), cy.saturating_sub(5 * dot / 2));
+        if top + 5 * dot > height {
+            return;
+        }
+        for (i, c) in text.chars().enumerate() {
+            let x = left + i * advance;
+            if x + 3 * dot <= self.width {
+                self.glyph(x, top, dot, c.to_ascii_uppercase(), color);
+            }
+        }
+    }
+
+    /// Stack badge in the top-right corner of cell (x, y): a box in `color` showing `count`
+    /// (9 for larger stacks), or just the box in cells under 12 pixels
+    fn badge(&mut self, x: usize, y: usize, count: usize, color: Rgb) {
+        let dot = self.scale / 12;
+        let (bw, bh) = if dot == 0 { ((self.scale / 3).max(1), (self.scale / 3).max(1)) } else { (3 * dot + 2, 5 * dot + 2) };
+        let (left, top) = ((x + 1) * self.scale - bw, y * self.scale);
+        for py in top..top + bh {
+            for px in left..left + bw {
+                self.pixels[(py * self.width + px) * 4..][..3].copy_from_slice(&color);
+            }
+        }
+        if dot > 0 {
+            let digit = char::from_digit(count.min(9) as u32, 10).unwrap();
+            self.glyph(left + 1, top + 1, dot, digit, contrast(color));
+        }
+    }
+
+    /// Draw the `FONT` bitmap of `letter` with its top-left corner at pixel (left, top)
+    fn glyph(&mut self, left: usize, top: usize, dot: usize, letter: char, color: Rgb) {
+        let Some((_, rows)) = FONT.iter().find(|(c, _)| *c == letter) else { return };
+        for (r, bits) in rows.iter().enumerate() {
+            for c in (0..3).filter(|c| bits & (0b100 >> c) != 0) {
+                for py in top + r * dot..top + (r + 1) * dot {
+                    for px in left + c * dot..left + (c + 1) * dot {
+                        self.pixels[(py * self.width + px) * 4..][..3].copy_from_slice(&color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// RGBA image of the scene with `scale` pixels per cell, row-major.
+///
+/// Cells are filled with their color, objects with a centered square of their color and
+/// the agent with a centered square of half the cell size. A cell with several visible
+/// objects shows the lowest id on top and a corner badge with their count. `opts` adds
+/// room patterns and room and object letters, drawn in the theme's text color or
+/// black/white for contrast.
+pub fn render_rgba(scene: &impl Scene, theme: &Theme, scale: usize, opts: RenderOpts) -> Vec<u8> {
+    let world = scene.world();
+    let layout = &world.layout;
+    let (w, h) = (layout.width * scale, layout.height * scale);
+    let mut raster = Raster { pixels: vec![255; w * h * 4], width: w, scale };
+    for (x, y, kind) in layout.kinds_with_coords() {
+        raster.fill(x, y, 0, theme.cell_color(layout.cells[y * layout.width + x]));
+        if let (true, Some(room)) = (opts.pattern_fill, kind.room()) {
+            let pattern = PATTERNS[room % PATTERNS.len()];
+            let ink = contrast(theme.room_color(room));
+            for py in y * scale..(y + 1) * scale {
+                for px in (x * scale..(x + 1) * scale).filter(|&px| pattern.covers(px, py, scale.max(4))) {
+                    let pixel = &mut raster.pixels[(py * w + px) * 4..][..3];
+                    for (channel, &ink) in pixel.iter_mut().zip(&ink) {
+                        *channel = ((*channel as u16 * 3 + ink as u16) / 4) as u8;
+                    }
+                }
+            }
+        }
+    }
+    if opts.room_names {
+        for (room, (cx, cy), width) in room_centers(layout, scale) {
+            raster.label(cx, cy, width, &layout.room_names[room], contrast(theme.room_color(room)));
+        }
+    } else if opts.glyphs {
+        for (room, anchor) in room_anchors(world).into_iter().enumerate() {
+            if let Some((x, y)) = anchor {
+                raster.letter(x, y, room_letter(room), contrast(theme.room_color(room)));
+            }
+        }
+    }
+    let visible = visible_objects(world);
+    for o in &visible {
+        let color = theme.object_color(o);
+        raster.fill(o.x, o.y, scale / 5, color);
+        if opts.glyphs {
+            raster.letter(o.x, o.y, object_letter(o), contrast(color));
+        }
+    }
+    for ((x, y), count) in stacks(&visible) {
+        raster.badge(x, y, count, theme.text);
+    }
+    if let Some((x, y)) = scene.agent() {
+        raster.fill(x, y, scale / 4, theme.agent.color);
+    }
+    raster.pixels
+}
+
+/// PNG file of the `render_rgba` image: 8-bit RGBA, stored without compression, so equal
+/// scenes give byte-identical files for golden-file tests
+pub fn render_png(scene: &impl Scene, theme: &Theme, scale: usize, opts: RenderOpts) -> Vec<u8> {
+    let layout = &scene.world().layout;
+    encode_png(layout.width * scale, layout.height * scale, &render_rgba(scene, theme, scale, opts))
+}
+
+/// Minimal PNG encoder: no row filters and a zlib stream of stored deflate blocks
+fn encode_png(width: usize, height: usize, rgba: &[u8]) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut png, b"IHDR", &ihdr(width, height));
+    push_chunk(&mut png, b"IDAT", &zlib_stored(width, height, rgba));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Animated PNG of equally sized RGBA frames shown `delay_ms` each, looping forever; the
+/// first frame doubles as the still image for viewers without APNG support
+#[cfg(feature = "sim")]
+pub(crate) fn encode_apng(width: usize, height: usize, frames: &[Vec<u8>], delay_ms: u16) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut png, b"IHDR", &ihdr(width, height));
+    // frame count, then 0 plays for an endless loop
+    let mut actl = (frames.len() as u32).to_be_bytes().to_vec();
+    actl.extend_from_slice(&0u32.to_be_bytes());
+    push_chunk(&mut png, b"acTL", &actl);
+    // fcTL and fdAT chunks share one sequence
+    let mut sequence = 0u32;
+    for (i, rgba) in frames.iter().enumerate() {
+        let mut fctl = sequence.to_be_bytes().to_vec();
+        fctl.extend_from_slice(&(width as u32).to_be_bytes());
+        fctl.extend_from_slice(&(height as u32).to_be_bytes());
+        // x and y offsets, then the delay as a fraction of a second
+        fctl.extend_from_slice(&[0; 8]);
+        fctl.extend_from_slice(&delay_ms.to_be_bytes());
+        fctl.extend_from_slice(&1000u16.to_be_bytes());
+        // dispose op none, blend op source: every frame replaces the whole canvas
+        fctl.extend_from_slice(&[0, 0]);
+        push_chunk(&mut png, b"fcTL", &fctl);
+        sequence += 1;
+        let zlib = zlib_stored(width, height, rgba);
+        if i == 0 {
+            push_chunk(&mut png, b"IDAT", &zlib);
+        } else {
+            let mut fdat = sequence.to_be_bytes().to_vec();
+            fdat.extend_from_slice(&zlib);
+            push_chunk(&mut png, b"fdAT", &fdat);
+            sequence += 1;
+        }
+    }
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Animated GIF of equally sized RGBA frames shown `delay_ms` each (in steps of 10 ms),
+/// looping forever. The frames share one palette of their first 256 colors, later colors
+/// take the nearest entry, and alpha is dropped. Sides must fit in 16 bits.
+#[cfg(feature = "sim")]
+pub(crate) fn encode_gif(width: usize, height: usize, frames: &[Vec<u8>], delay_ms: u16) -> Vec<u8> {
+    let palette = gif_palette(frames);
+    // smallest power-of-two table that holds the palette, at least 4 entries for LZW
+    let bits = (2..=8).find(|&bits| palette.len() <= 1 << bits).unwrap_or(8);
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend_from_slice(&(width as u16).to_le_bytes());
+    gif.extend_from_slice(&(height as u16).to_le_bytes());
+    // global color table of 2^bits entries, no background color or aspect ratio
+    gif.extend_from_slice(&[0x80 | (bits - 1) << 4 | (bits - 1), 0, 0]);
+    for i in 0..1 << bits {
+        gif.extend_from_slice(palette.get(i).map_or(&[0; 3], |(rgb, _)| rgb));
+    }
+    // NETSCAPE2.0 application extension: loop count 0 plays forever
+    gif.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+    let lookup: BTreeMap<[u8; 3], u8> = palette.iter().map(|&(rgb, index)| (rgb, index)).collect();
+    for rgba in frames {
+        // graphic control extension: leave the frame in place, delay in hundredths
+        gif.extend_from_slice(&[0x21, 0xf9, 4, 0x04]);
+        gif.extend_from_slice(&(delay_ms / 10).to_le_bytes());
+        gif.extend_from_slice(&[0, 0]);
+        // image descriptor covering the canvas, without a local color table
+        gif.push(0x2c);
+        gif.extend_from_slice(&[0; 4]);
+        gif.extend_from_slice(&(width as u16).to_le_bytes());
+        gif.extend_from_slice(&(height as u16).to_le_bytes());
+        gif.push(0);
+        let indices: Vec<u8> = rgba
+            .chunks_exact(4)
+            .map(|px| {
+                let rgb = [px[0], px[1], px[2]];
+                lookup.get(&rgb).copied().unwrap_or_else(|| nearest(&palette, rgb))
+            })
+            .collect();
+        gif.push(bits);
+        for block in lzw(bits, &indices).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend_from_slice(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    gif
+}
+
+/// The first 256 distinct colors of the frames, each with its palette index
+#[cfg(feature = "sim")]
+fn gif_palette(frames: &[Vec<u8>]) -> Vec<([u8; 3], u8)> {
+    let mut seen = BTreeMap::new();
+    for px in frames.iter().flat_map(|rgba| rgba.chunks_exact(4)) {
+        if seen.len() == 256 {
+            break;
+        }
+        let next = seen.len() as u8;
+        seen.entry([px[0], px[1], px[2]]).or_insert(next);
+    }
+    let mut palette: Vec<([u8; 3], u8)> = seen.into_iter().collect();
+    palette.sort_by_key(|&(_, index)| index);
+    palette
+}
+
+/// Palette index of the entry closest to `rgb`
+#[cfg(feature = "sim")]
+fn nearest(palette: &[([u8; 3], u8)], rgb: [u8; 3]) -> u8 {
+    let distance = |other: &[u8; 3]| (0..3).map(|i| (other[i] as i32 - rgb[i] as i32).pow(2)).sum::<i32>();
+    palette.iter().min_by_key(|(other, _)| distance(other)).map_or(0, |&(_, index)| index)
+}
+
+/// GIF's variable-width LZW code stream of `indices`, packed LSB first
+#[cfg(feature = "sim")]
+fn lzw(min_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_size;
+    let end = clear + 1;
+    let mut codes: BTreeMap<(u16, u8), u16> = BTreeMap::new();
+    let mut next = end + 1;
+    let mut size = min_size + 1;
+    let (mut out, mut acc, mut filled) = (Vec::new(), 0u32, 0u8);
+    let mut emit = |code: u16, size: u8| {
+        acc |= (code as u32) << filled;
+        filled += size;
+        while filled >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            filled -= 8;
+        }
+    };
+    emit(clear, size);
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let Some(code) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+        if let Some(&longer) = codes.get(&(code, index)) {
+            prefix = Some(longer);
+            continue;
+        }
+        emit(code, size);
+        if next == 4096 {
+            // the table is full: start over
+            emit(clear, size);
+            codes.clear();
+            next = end + 1;
+            size = min_size + 1;
+        } else {
+            codes.insert((code, index), next);
+            // the decoder adds this code one step later, so it widens once the code exists
+            if next == 1 << size && size < 12 {
+                size += 1;
+            }
+            next += 1;
+        }
+        prefix = Some(index as u16);
+    }
+    if let Some(code) = prefix {
+        emit(code, size);
+    }
+    emit(end, size);
+    if filled > 0 {
+        out.push(acc as u8);
+    }
+    out
+}
+
+/// IHDR data: 8-bit RGBA, default compression, filter and no interlace
+fn ihdr(width: usize, height: usize) -> Vec<u8> {
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&(width as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(height as u32).to_be_bytes());
+    // bit depth 8, color type 6 (RGBA), default compression, filter and no interlace
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+    ihdr
+}
+
+/// Zlib stream of the unfiltered scanlines of an RGBA image, in stored deflate blocks
+fn zlib_stored(width: usize, height: usize, rgba: &[u8]) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(height * (width * 4 + 1));
+    for row in rgba.chunks(width * 4).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    // zlib header for deflate with a 32K window and no preset dictionary
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(u16::MAX as usize).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push((i + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    let (a, b) = raw.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    zlib.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    zlib
+}
+
+/// Append a PNG chunk: length, type, data and the CRC of type and data
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 (IEEE) of a PNG chunk's type and data
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |c, _| if c & 1 == 1 { (c >> 1) ^ 0xEDB8_8320 } else { c >> 1 })
+    })
+}
+
+/// Text escaped for SVG attributes and text nodes
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// SVG image of the scene with `scale` user units per cell.
+///
+/// Each room is one path labelled with its name through `aria-label`, as are objects
+/// and the agent, so screen readers can list them. See `render_rgba` for `opts`.
+pub fn render_svg(scene: &impl Scene, theme: &Theme, scale: usize, opts: RenderOpts) -> String {
+    let world = scene.world();
+    let layout = &world.layout;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\" role=\"img\" aria-label=\"Apartment with {n} rooms\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"{bg}\"/>\n",
+        w = layout.width * scale,
+        h = layout.height * scale,
+        n = layout.room_names.len(),
+        bg = css(theme.background),
+    );
+    if opts.pattern_fill {
+        svg.push_str("<defs>\n");
+        for (i, pattern) in PATTERNS.iter().enumerate() {
+            svg.push_str(&format!(
+                "<pattern id=\"room-pattern-{i}\" width=\"{s}\" height=\"{s}\" patternUnits=\"userSpaceOnUse\"><path d=\"{d}\" stroke=\"{ink}\" stroke-opacity=\"0.35\" stroke-width=\"{t}\" stroke-linecap=\"round\" fill=\"none\"/></pattern>\n",
+                s = scale.max(4),
+                d = pattern.svg_path(scale.max(4)),
+                ink = css(theme.text),
+                t = scale.max(4).div_ceil(4),
+            ));
+        }
+        svg.push_str("</defs>\n");
+    }
+    let rect = |x: usize, y: usize, inset: usize, color: Rgb, label: Option<&str>| {
+        let aria = label.map_or(String::new(), |l| format!(" role=\"img\" aria-label=\"{}\"", xml_escape(l)));
+        format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" fill=\"{}\"{}/>\n",
+            x * scale + inset,
+            y * scale + inset,
+            css(color),
+            aria,
+            s = scale - 2 * inset,
+        )
+    };
+    let text = |x: usize, y: usize, letter: char, color: Rgb| {
+        format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"monospace\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\" aria-hidden=\"true\">{}</text>\n",
+            x * scale + scale / 2,
+            y * scale + scale / 2,
+            scale * 3 / 5,
+            css(color),
+            xml_escape(&letter.to_string()),
+        )
+    };
+    let mut rooms = vec![String::new(); layout.room_names.len()];
+    for (x, y, kind) in layout.kinds_with_coords() {
+        match kind.room().filter(|&r| r < rooms.len()) {
+            Some(room) => rooms[room].push_str(&format!("M{} {}h{s}v{s}h-{s}z", x * scale, y * scale, s = scale)),
+            None => svg.push_str(&rect(x, y, 0, theme.cell_color(layout.cells[y * layout.width + x]), None)),
+        }
+    }
+    for (room, d) in rooms.iter().enumerate().filter(|(_, d)| !d.is_empty()) {
+        let name = &layout.room_names[room];
+        svg.push_str(&format!(
+            "<path d=\"{}\" fill=\"{}\" role=\"img\" aria-label=\"{}\"/>\n",
+            d,
+            css(theme.room_color(room)),
+            xml_escape(name)
+        ));
+        if opts.pattern_fill {
+            svg.push_str(&format!(
+                "<path d=\"{}\" fill=\"url(#room-pattern-{})\" aria-hidden=\"true\"/>\n",
+                d,
+                room % PATTERNS.len()
+            ));
+        }
+    }
+    if opts.room_names {
+        for (room, (cx, cy), width) in room_centers(layout, scale) {
+            let name = &layout.room_names[room];
+            // sans-serif letters average about 0.6 em, so shrink long names to the room's width
+            let size = scale.min(width * 5 / (3 * name.chars().count()).max(1)).max(1);
+            svg.push_str(&format!(
+                "<text x=\"{cx}\" y=\"{cy}\" font-size=\"{size}\" font-family=\"sans-serif\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\" aria-hidden=\"true\">{}</text>\n",
+                css(contrast(theme.room_color(room))),
+                xml_escape(name),
+            ));
+        }
+    } else if opts.glyphs {
+        for (room, anchor) in room_anchors(world).into_iter().enumerate() {
+            if let Some((x, y)) = anchor {
+                svg.push_str(&text(x, y, room_letter(room), contrast(theme.room_color(room))));
+            }
+        }
+    }
+    let visible = visible_objects(world);
+    for o in &visible {
+        let color = theme.object_color(o);
+        svg.push_str(&rect(o.x, o.y, scale / 5, color, Some(&o.display_label())));
+        if opts.glyphs {
+            svg.push_str(&text(o.x, o.y, object_letter(o), contrast(color)));
+        }
+    }
+    for ((x, y), count) in stacks(&visible) {
+        let r = (scale / 5).max(1);
+        let (cx, cy) = ((x + 1) * scale - r, y * scale + r);
+        svg.push_str(&format!(
+            "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{r}\" fill=\"{}\" role=\"img\" aria-label=\"{count} objects stacked\"/>\n<text x=\"{cx}\" y=\"{cy}\" font-size=\"{}\" font-family=\"monospace\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\" aria-hidden=\"true\">{count}</text>\n",
+            css(theme.text),
+            r * 3 / 2,
+            css(contrast(theme.text)),
+        ));
+    }
+    if let Some((x, y)) = scene.agent() {
+        svg.push_str(&rect(x, y, scale / 4, theme.agent.color, Some("Agent")));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
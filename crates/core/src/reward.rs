@@ -0,0 +1,161 @@
+use crate::nav;
+use crate::object::ObjectId;
+use crate::sim::Simulator;
+
+/// Potential-based shaping: `weight * (gamma * phi(s') - phi(s))`.
+///
+/// Because the term telescopes over an episode, it changes returns only by
+/// `weight * (gamma^T * phi(s_T) - phi(s_0))` and leaves the optimal policy unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapingConfig {
+    /// Discount factor of the learner; must match for policy invariance
+    pub gamma: f32,
+    pub weight: f32,
+}
+
+impl ShapingConfig {
+    /// Shaping term for a transition between two potentials
+    pub fn term(&self, phi_before: f32, phi_after: f32) -> f32 {
+        self.weight * (self.gamma * phi_after - phi_before)
+    }
+}
+
+/// Reward settings for training environments; see `Simulator::reward_config`.
+///
+/// The default pays 1 per object put in place and charges 0.01 per action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewardConfig {
+    /// Charged for every action, failed ones included (see `Simulator::failed_step_reward`)
+    pub step_penalty: f32,
+    /// Paid per object an action puts in its target placement, and charged per object it
+    /// takes out of one, so picking an object up and putting it back earns nothing
+    pub correct_place_bonus: f32,
+    /// Charged when a drop or placement leaves the object out of place
+    pub incorrect_place_penalty: f32,
+    /// Paid per pick-up
+    pub pickup_bonus: f32,
+    /// Paid when an action makes `Simulator::is_success` hold, and charged when one ends it
+    pub done_bonus: f32,
+    /// Optional distance-to-goal shaping; `None` keeps rewards sparse
+    pub shaping: Option<ShapingConfig>,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        RewardConfig {
+            step_penalty: 0.01,
+            correct_place_bonus: 1.0,
+            incorrect_place_penalty: 0.0,
+            pickup_bonus: 0.0,
+            done_bonus: 0.0,
+            shaping: None,
+        }
+    }
+}
+
+impl RewardConfig {
+    /// Shaping reward for a transition, or 0 when shaping is disabled
+    pub fn shaping_reward(&self, phi_before: f32, phi_after: f32) -> f32 {
+        self.shaping.map_or(0.0, |s| s.term(phi_before, phi_after))
+    }
+}
+
+/// Distance maps kept in the cache before it is cleared
+const DISTANCE_CACHE_LIMIT: usize = 64;
+
+/// What `Simulator::shaped_reward` compares against, captured before an action runs
+pub(crate) struct RewardBaseline {
+    config: RewardConfig,
+    held: Option<ObjectId>,
+    /// `is_success` before an action that moves objects; other actions cannot change it
+    success: Option<bool>,
+    /// `potential` before the action, when shaping is on
+    potential: Option<f32>,
+}
+
+impl Simulator {
+    /// Reward `step` returns for a failed action: minus the step penalty of `reward_config`
+    pub fn failed_step_reward(&self) -> f32 {
+        self.reward_config.map_or(0.0, |c| -c.step_penalty)
+    }
+
+    /// State the configured reward depends on, or `None` without a `reward_config`
+    pub(crate) fn reward_baseline(&mut self, moves_objects: bool) -> Option<RewardBaseline> {
+        let config = self.reward_config?;
+        Some(RewardBaseline {
+            config,
+            held: self.holding.as_ref().map(|o| o.id),
+            success: moves_objects.then(|| self.is_success()),
+            potential: config.shaping.map(|_| self.potential()),
+        })
+    }
+
+    /// Configured reward for the action just run, given the change in placed pickables
+    pub(crate) fn shaped_reward(&mut self, before: RewardBaseline, placed_delta: isize) -> f32 {
+        let config = before.config;
+        let mut reward = config.correct_place_bonus * placed_delta as f32 - config.step_penalty;
+        match (before.held, &self.holding) {
+            (None, Some(_)) => reward += config.pickup_bonus,
+            (Some(id), None) if !self.world.is_in_place(id) => reward -= config.incorrect_place_penalty,
+            _ => {}
+        }
+        match (before.success, self.is_success()) {
+            (Some(false), true) => reward += config.done_bonus,
+            (Some(true), false) => reward -= config.done_bonus,
+            _ => {}
+        }
+        if let (Some(shaping), Some(phi)) = (config.shaping, before.potential) {
+            reward += shaping.term(phi, self.potential());
+        }
+        reward
+    }
+
+    /// Negative step distance from the agent to its current goal.
+    ///
+    /// Empty-handed, the goal is the nearest misplaced pickable object; while carrying,
+    /// it is the nearest cell or container satisfying the held object's target. The
+    /// potential is 0 when there is no goal and `-(width * height)` when it is unreachable.
+    /// With `blocking_objects` on, paths avoid blocking furniture. Distance maps are cached
+    /// per goal set and blocked cells.
+    pub fn potential(&mut self) -> f32 {
+        let layout = &self.world.layout;
+        let w = layout.width;
+        let mut goals: Vec<usize> = match &self.holding {
+            Some(held) => match held.target() {
+                Some(target) => (0..layout.cells.len())
+                    .filter(|&i| layout.cells[i] >= 0 && target.check(&self.world, i % w, i / w))
+                    .collect(),
+                None => Vec::new(),
+            },
+            None => self
+                .world
+                .objects
+                .iter()
+                .filter(|o| o.pickable && !o.check_placement(&self.world))
+                .map(|o| o.y * w + o.x)
+                .collect(),
+        };
+        if goals.is_empty() {
+            return 0.0;
+        }
+        goals.sort_unstable();
+        goals.dedup();
+        let agent = self.agent.y * w + self.agent.x;
+        let blocked = self.blocked_cells();
+        let blockers: Vec<usize> = (0..blocked.len()).filter(|&i| blocked[i]).collect();
+        let key = (goals, blockers);
+        if self.distance_cache.len() >= DISTANCE_CACHE_LIMIT && !self.distance_cache.contains_key(&key) {
+            self.distance_cache.clear();
+        }
+        // blocking furniture can still be a goal, worked on from a neighbouring cell
+        let field = self.distance_cache.entry(key).or_insert_with_key(|(goals, _)| {
+            nav::distance_field_over(layout, goals, |i| {
+                nav::walkable(layout, i) && (!blocked[i] || goals.binary_search(&i).is_ok())
+            })
+        });
+        match field[agent] {
+            Some(d) => -(d as f32),
+            None => -((layout.width * layout.height) as f32),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+use crate::action::Action;
+use crate::cell::CellKind;
+use crate::sim::Simulator;
+use crate::view::ViewCache;
+
+/// Per-room aggregates behind `room_graph_observation`, kept until objects change
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RoomAggregates {
+    area: Vec<u32>,
+    misplaced: Vec<u32>,
+    free_capacity: Vec<u32>,
+    /// (door cell, room a, room b) for every door cell between two different rooms
+    doors: Vec<(usize, usize, usize)>,
+}
+
+/// Coarse observation with one entry per room, for hierarchical policies.
+///
+/// Rooms are ordered by id and padded to `max_rooms`: padding rows have `room_ids` -1 and
+/// zeros everywhere else. The matrices are `max_rooms * max_rooms`, row-major and symmetric.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoomGraphObservation {
+    pub max_rooms: usize,
+    /// Number of real rooms before the padding
+    pub rooms: usize,
+    pub room_ids: Vec<i32>,
+    /// Room cells per room
+    pub area: Vec<u32>,
+    pub agent_present: Vec<bool>,
+    /// Pickable objects in the room outside their target; the held object counts nowhere
+    pub misplaced: Vec<u32>,
+    /// Free slots summed over the room's containers
+    pub free_capacity: Vec<u32>,
+    /// Whether a door connects the two rooms
+    pub adjacent: Vec<bool>,
+    /// Whether at least one door between the two rooms is open
+    pub door_open: Vec<bool>,
+}
+
+impl Simulator {
+    /// Room-level observation padded to `max_rooms`; rooms with ids from `max_rooms` up are left out.
+    ///
+    /// Areas, misplaced counts and free capacity are cached until an action other than a
+    /// move, a decay event or `restore` changes the objects; after editing `world` directly,
+    /// call `invalidate_caches`. Agent position and door states are read fresh on every call.
+    pub fn room_graph_observation(&mut self, max_rooms: usize) -> RoomGraphObservation {
+        if self.room_cache.is_none() {
+            self.room_cache = Some(self.room_aggregates());
+        }
+        let cache = self.room_cache.as_ref().unwrap();
+        let layout = &self.world.layout;
+        let rooms = layout.room_names.len().min(max_rooms);
+        let pad = |values: &[u32]| {
+            let mut v = values[..rooms].to_vec();
+            v.resize(max_rooms, 0);
+            v
+        };
+        let mut room_ids: Vec<i32> = (0..rooms as i32).collect();
+        room_ids.resize(max_rooms, -1);
+        let mut agent_present = vec![false; max_rooms];
+        if let Some(room) = layout.kind_at(self.agent.x, self.agent.y).room().filter(|&r| r < rooms) {
+            agent_present[room] = true;
+        }
+        let mut adjacent = vec![false; max_rooms * max_rooms];
+        let mut door_open = vec![false; max_rooms * max_rooms];
+        for &(cell, a, b) in &cache.doors {
+            if a >= rooms || b >= rooms {
+                continue;
+            }
+            let open = CellKind::from_i8(layout.cells[cell]) == CellKind::OpenDoor;
+            for (i, j) in [(a, b), (b, a)] {
+                adjacent[i * max_rooms + j] = true;
+                door_open[i * max_rooms + j] |= open;
+            }
+        }
+        RoomGraphObservation {
+            max_rooms,
+            rooms,
+            room_ids,
+            area: pad(&cache.area),
+            agent_present,
+            misplaced: pad(&cache.misplaced),
+            free_capacity: pad(&cache.free_capacity),
+            adjacent,
+            door_open,
+        }
+    }
+
+    /// Drop cached aggregates and view buffers; needed after changing `world` without going
+    /// through the simulator
+    pub fn invalidate_caches(&mut self) {
+        self.room_cache = None;
+        self.view_cache = ViewCache::default();
+        self.distance_cache.clear();
+    }
+
+    /// Forget the room aggregates and view buffers if `action` may have changed any object
+    /// or door
+    pub(crate) fn invalidate_room_cache(&mut self, action: &Action) {
+        if !matches!(
+            action,
+            Action::MoveUp
+                | Action::MoveDown
+                | Action::MoveLeft
+                | Action::MoveRight
+                | Action::Noop
+                | Action::TurnLeft
+                | Action::TurnRight
+                | Action::MoveForward
+        ) {
+            self.room_cache = None;
+            self.view_cache.clear_state();
+        }
+    }
+
+    fn room_aggregates(&self) -> RoomAggregates {
+        let layout = &self.world.layout;
+        let n = layout.room_names.len();
+        let mut agg = RoomAggregates {
+            area: vec![0; n],
+            misplaced: vec![0; n],
+            free_capacity: vec![0; n],
+            doors: Vec::new(),
+        };
+        for (x, y, kind) in layout.kinds_with_coords() {
+            match kind {
+                CellKind::Room(r) => agg.area[r as usize] += 1,
+                kind if kind.is_door() => {
+                    let mut rooms = Vec::new();
+                    for (dx, dy) in [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)] {
+                        let (nx, ny) = (x as isize + dx, y as isize + dy);
+                        if nx < 0 || ny < 0 || nx >= layout.width as isize || ny >= layout.height as isize {
+                            continue;
+                        }
+                        if let Some(r) = layout.kind_at(nx as usize, ny as usize).room() {
+                            if !rooms.contains(&r) {
+                                rooms.push(r);
+                            }
+                        }
+                    }
+                    for (i, &a) in rooms.iter().enumerate() {
+                        for &b in &rooms[i + 1..] {
+                            agg.doors.push((y * layout.width + x, a, b));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        for o in &self.world.objects {
+            let Some(room) = layout.kind_at(o.x, o.y).room() else { continue };
+            if o.pickable && o.target().is_some() && !o.check_placement(&self.world) {
+                agg.misplaced[room] += 1;
+            }
+            agg.free_capacity[room] += (o.capacity - o.contents.len().min(o.capacity)) as u32;
+        }
+        agg
+    }
+}
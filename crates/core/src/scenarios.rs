@@ -0,0 +1,193 @@
+//! Hand-authored tutorial worlds of increasing difficulty.
+
+use serde::Serialize;
+
+use crate::world::{Layout, World};
+use crate::language;
+use crate::object::ObjectId;
+use crate::sim::Simulator;
+
+/// A small fixed world with a tidying goal
+#[derive(Debug, Clone, Serialize)]
+pub struct Scenario {
+    /// Stable identifier, e.g. `"closed_door"`
+    pub name: &'static str,
+    pub title: &'static str,
+    pub world: World,
+    /// Agent start cell
+    pub start: (usize, usize),
+    /// Objects that must end up in their target placement
+    pub goals: Vec<ObjectId>,
+    /// Primitive step budget, a generous multiple of the oracle solution
+    pub max_steps: u32,
+    /// What to do, generated from the goal objects' targets
+    pub instructions: String,
+}
+
+impl Scenario {
+    /// Fresh simulator at the start of the scenario
+    pub fn simulator(&self) -> Simulator {
+        Simulator::new(self.world.clone(), self.start.0, self.start.1).expect("tutorial start cell is walkable")
+    }
+
+    /// Whether every goal object is in its target placement
+    pub fn is_solved(&self, sim: &Simulator) -> bool {
+        self.goals.iter().all(|&id| sim.world.is_in_place(id))
+    }
+}
+
+/// Objects to place: (schema name, x, y, index of the container it starts in)
+type Placements<'a> = &'a [(&'static str, usize, usize, Option<usize>)];
+
+fn scenario(
+    name: &'static str,
+    title: &'static str,
+    rows: &[&str],
+    room_names: &[&'static str],
+    objects: Placements,
+    start: (usize, usize),
+    max_steps: u32,
+) -> Scenario {
+    let layout = Layout::from_ascii(rows, room_names).expect("tutorial layout is valid");
+    let mut world = World::new(layout, Vec::new());
+    world
+        .transaction(|tx| {
+            let mut ids = Vec::new();
+            for &(schema, x, y, parent) in objects {
+                let id = tx.add_object(schema, x, y)?;
+                if let Some(parent) = parent {
+                    tx.place_into(id, ids[parent])?;
+                }
+                ids.push(id);
+            }
+            Ok(())
+        })
+        .expect("tutorial objects come from the built-in catalog and fit their containers");
+    let goals: Vec<ObjectId> = world.objects.iter().filter(|o| o.pickable).map(|o| o.id).collect();
+    let instructions = world
+        .objects
+        .iter()
+        .filter(|o| o.pickable)
+        .map(|o| format!("The {} is {}. {}", o.display_name, language::describe_location(&world, o.x, o.y), language::describe_target(o)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Scenario { name, title, world, start, goals, max_steps, instructions }
+}
+
+/// The tutorial scenarios, easiest first
+pub fn tutorial() -> Vec<Scenario> {
+    vec![
+        scenario(
+            "first_apple",
+            "Put the apple in the fruit bowl",
+            &[
+                "########",
+                "#000000#",
+                "#000000#",
+                "#000000#",
+                "#000000#",
+                "########",
+            ],
+            &["Kitchen"],
+            &[("FruitBowl", 6, 1, None), ("Apple", 3, 3, None)],
+            (1, 4),
+            40,
+        ),
+        scenario(
+            "closed_door",
+            "Open the door and bring the apple to the kitchen",
+            &[
+                "#############",
+                "#00000#11111#",
+                "#00000#11111#",
+                "#00000D11111#",
+                "#00000D11111#",
+                "#############",
+            ],
+            &["Kitchen", "Living Room"],
+            &[("FruitBowl", 1, 1, None), ("Apple", 10, 2, None)],
+            (3, 4),
+            80,
+        ),
+        scenario(
+            "container_search",
+            "Find the apple stored in the bedroom drawer",
+            &[
+                "##############",
+                "#0000#2222222#",
+                "#0000D2222222#",
+                "#0000D2222222#",
+                "##DD##########",
+                "#111111111111#",
+                "#111111111111#",
+                "##############",
+            ],
+            &["Kitchen", "Hallway", "Bedroom"],
+            &[("Refrigerator", 1, 1, None), ("Drawer", 12, 1, None), ("Apple", 12, 1, Some(1))],
+            (12, 6),
+            120,
+        ),
+        scenario(
+            "dishwasher",
+            "Load the dirty dishes into the dishwasher",
+            &[
+                "##############",
+                "#000000#11111#",
+                "#000000D11111#",
+                "#000000D11111#",
+                "#000000#11111#",
+                "##############",
+            ],
+            &["Kitchen", "Dining Room"],
+            &[
+                ("Dishwasher", 1, 4, None),
+                ("DiningTable", 10, 2, None),
+                ("DirtyPlate", 10, 2, Some(1)),
+                ("DirtyBowl", 11, 3, None),
+            ],
+            (3, 2),
+            120,
+        ),
+        scenario(
+            "rotten_fruit",
+            "Throw the rotten banana away",
+            &[
+                "#########",
+                "#0000000#",
+                "#0000000#",
+                "#0000000#",
+                "#########",
+            ],
+            &["Kitchen"],
+            &[("TrashCan", 1, 1, None), ("RottenBanana", 6, 3, None)],
+            (4, 2),
+            40,
+        ),
+        scenario(
+            "tidy_apartment",
+            "Tidy up three rooms",
+            &[
+                "################",
+                "#000000#1111111#",
+                "#000000D1111111#",
+                "#000000D1111111#",
+                "#000000#1111111#",
+                "###DD######DD###",
+                "#22222222222222#",
+                "#22222222222222#",
+                "################",
+            ],
+            &["Kitchen", "Bedroom", "Living Room"],
+            &[
+                ("FruitBowl", 1, 1, None),
+                ("Dishwasher", 6, 4, None),
+                ("LaundryBasket", 14, 1, None),
+                ("Apple", 13, 7, None),
+                ("DirtyPlate", 9, 3, None),
+                ("DirtyClothes", 2, 7, None),
+            ],
+            (8, 6),
+            300,
+        ),
+    ]
+}
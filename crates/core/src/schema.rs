@@ -0,0 +1,253 @@
+//! Object schemas as JSON data files, for versioning custom object sets.
+//!
+//! A file is an array of schema objects. Only `name` is required:
+//!
+//! ```json
+//! [
+//!   {"name": "Toolbox", "icon": "toolbox", "capacity": 4, "container_kind": "enclosed",
+//!    "starts_closed": true, "constraint": "adjacent_obstacle"},
+//!   {"name": "Hammer", "icon": "hammer", "pickable": true,
+//!    "target": {"or": [{"inside_of": ["Toolbox"]}, {"in_room_named": ["Office"]}]},
+//!    "decays_into": ["RustyHammer", 200], "preference": [["close_to_obstacle", 1.5]]}
+//! ]
+//! ```
+//!
+//! Defaults: `capacity` 0, `pickable`, `blocks_movement`, `starts_closed` and `disposes` false,
+//! `constraint` and `target` `"in_room"`, empty `icon`, `description` and `transforms`, the
+//! humanized `name` as `display_name` (`"TVStand"` reads "TV stand"), and
+//! `container_kind` `"enclosed"` whenever `capacity` is positive (`"surface"` for tables and
+//! shelves; ignored at capacity 0). `transforms` lists an appliance's `[from, to]` name pairs, resolved like
+//! `decays_into` when objects are made with `Object::from_schema_among`.
+//!
+//! Constraints are written as:
+//! - `"in_room"`, `"adjacent_obstacle"`, `"close_to_obstacle"`
+//! - `{"and": [c, ...]}`, `{"or": [c, ...]}` with at least one constraint
+//! - `{"inside_of": [name, ...]}`, `{"world_has": [...]}`, `{"in_zone_with": [...]}` naming
+//!   objects, and `{"in_room_named": [...]}` naming rooms; each list needs at least one name.
+//!   Room names are not checked against a room name pool, since the pool is chosen at
+//!   generation time; `lint_schemas` reports names missing from the one it is given
+//!
+//! The Python bindings take schema dicts in the same form.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::object::{ContainerKind, Names, ObjectConstraint, ObjectSchema};
+use crate::world::{try_intern, MAX_INTERNED_BYTES};
+
+/// Why a schema file could not be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Not valid JSON, or a field has the wrong type
+    Json(String),
+    /// A constraint is malformed, e.g. an unknown form or an empty `and`
+    InvalidConstraint { schema: String, message: String },
+    /// Loading the schema would take the interned names past `world::MAX_INTERNED_BYTES`
+    TooManyNames { schema: String },
+}
+
+impl SchemaError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            SchemaError::Json(_) => "invalid_json",
+            SchemaError::InvalidConstraint { .. } => "invalid_constraint",
+            SchemaError::TooManyNames { .. } => "too_many_names",
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Json(e) => write!(f, "invalid schema file: {}", e),
+            SchemaError::InvalidConstraint { schema, message } => write!(f, "{}: {}", schema, message),
+            SchemaError::TooManyNames { schema } => {
+                write!(f, "{}: schema names exceed the {} bytes of distinct names kept", schema, MAX_INTERNED_BYTES)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KindRepr {
+    Surface,
+    Enclosed,
+}
+
+/// File form of one schema; constraints stay raw JSON so errors can name the schema
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaRepr {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    capacity: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    container_kind: Option<KindRepr>,
+    #[serde(default)]
+    pickable: bool,
+    #[serde(default)]
+    blocks_movement: bool,
+    #[serde(default)]
+    starts_closed: bool,
+    #[serde(default)]
+    disposes: bool,
+    #[serde(default = "in_room")]
+    constraint: Value,
+    #[serde(default = "in_room")]
+    target: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    decays_into: Option<(String, usize)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    transforms: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    preference: Vec<(Value, f32)>,
+}
+
+fn in_room() -> Value {
+    json!("in_room")
+}
+
+fn constraint_to_value(c: &ObjectConstraint) -> Value {
+    let parts = |cs: &[ObjectConstraint]| cs.iter().map(constraint_to_value).collect::<Vec<_>>();
+    match c {
+        ObjectConstraint::InRoom => json!("in_room"),
+        ObjectConstraint::AdjacentObstacle => json!("adjacent_obstacle"),
+        ObjectConstraint::CloseToObstacle => json!("close_to_obstacle"),
+        ObjectConstraint::And(cs) => json!({ "and": parts(cs) }),
+        ObjectConstraint::Or(cs) => json!({ "or": parts(cs) }),
+        ObjectConstraint::InsideOf(names) => json!({ "inside_of": names }),
+        ObjectConstraint::WorldHas(names) => json!({ "world_has": names }),
+        ObjectConstraint::InRoomNamed(names) => json!({ "in_room_named": names }),
+        ObjectConstraint::InZoneWith(names) => json!({ "in_zone_with": names }),
+    }
+}
+
+/// Parse one constraint of schema `schema`; `field` is where it sits, for error messages
+fn constraint_from_value(value: &Value, schema: &str, field: &str) -> Result<ObjectConstraint, SchemaError> {
+    let invalid = |message: String| SchemaError::InvalidConstraint { schema: schema.to_string(), message };
+    if let Some(name) = value.as_str() {
+        return match name {
+            "in_room" => Ok(ObjectConstraint::InRoom),
+            "adjacent_obstacle" => Ok(ObjectConstraint::AdjacentObstacle),
+            "close_to_obstacle" => Ok(ObjectConstraint::CloseToObstacle),
+            other => Err(invalid(format!("{}: unknown constraint '{}'", field, other))),
+        };
+    }
+    let single = value.as_object().filter(|o| o.len() == 1).and_then(|o| o.iter().next());
+    let Some((form, args)) = single else {
+        return Err(invalid(format!("{}: expected a constraint name or an object with one key, got {}", field, value)));
+    };
+    let field = format!("{}.{}", field, form);
+    let list = args
+        .as_array()
+        .filter(|list| !list.is_empty())
+        .ok_or_else(|| invalid(format!("{}: expected a non-empty list", field)))?;
+    let names = || {
+        list.iter()
+            .map(|n| n.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .map(Names::Owned)
+            .ok_or_else(|| invalid(format!("{}: expected a list of names", field)))
+    };
+    let parts = || {
+        list.iter()
+            .enumerate()
+            .map(|(i, c)| constraint_from_value(c, schema, &format!("{}[{}]", field, i)))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    match form.as_str() {
+        "and" => Ok(ObjectConstraint::And(parts()?)),
+        "or" => Ok(ObjectConstraint::Or(parts()?)),
+        "inside_of" => Ok(ObjectConstraint::InsideOf(names()?)),
+        "world_has" => Ok(ObjectConstraint::WorldHas(names()?)),
+        "in_zone_with" => Ok(ObjectConstraint::InZoneWith(names()?)),
+        // any room name loads, since `GenOpts::room_name_pool` can add rooms; `lint_schemas` flags unknown ones
+        "in_room_named" => Ok(ObjectConstraint::InRoomNamed(names()?)),
+        other => Err(invalid(format!("{}: unknown constraint '{}'", field, other))),
+    }
+}
+
+impl SchemaRepr {
+    fn into_schema(self) -> Result<ObjectSchema, SchemaError> {
+        let name = self.name.as_str();
+        let preference = self
+            .preference
+            .iter()
+            .enumerate()
+            .map(|(i, (c, weight))| Ok((constraint_from_value(c, name, &format!("preference[{}]", i))?, *weight)))
+            .collect::<Result<Vec<_>, SchemaError>>()?;
+        let container_kind = (self.capacity > 0).then_some(match self.container_kind {
+            Some(KindRepr::Surface) => ContainerKind::Surface,
+            Some(KindRepr::Enclosed) | None => ContainerKind::Enclosed,
+        });
+        // schemas keep `&'static str` names, so a file cannot add more than the interning budget
+        let schema = self.name.clone();
+        let intern = |text: String| try_intern(text).ok_or_else(|| SchemaError::TooManyNames { schema: schema.clone() });
+        Ok(ObjectSchema {
+            capacity: self.capacity,
+            container_kind,
+            starts_closed: self.starts_closed,
+            disposes: self.disposes,
+            icon: intern(self.icon)?,
+            pickable: self.pickable,
+            blocks_movement: self.blocks_movement,
+            constraint: constraint_from_value(&self.constraint, name, "constraint")?,
+            description: intern(self.description)?,
+            target: constraint_from_value(&self.target, name, "target")?,
+            decays_into: self.decays_into.map(|(into, steps)| Ok((intern(into)?, steps))).transpose()?,
+            transforms: self.transforms.into_iter().map(|(from, to)| Ok((intern(from)?, intern(to)?))).collect::<Result<_, SchemaError>>()?,
+            preference,
+            display_name: self.display_name.map(intern).transpose()?,
+            name: intern(self.name)?,
+        })
+    }
+
+    fn from_schema(s: &ObjectSchema) -> Self {
+        SchemaRepr {
+            name: s.name.to_string(),
+            display_name: s.display_name.map(str::to_string),
+            icon: s.icon.to_string(),
+            description: s.description.to_string(),
+            capacity: s.capacity,
+            container_kind: s.container_kind.map(|kind| match kind {
+                ContainerKind::Surface => KindRepr::Surface,
+                ContainerKind::Enclosed => KindRepr::Enclosed,
+            }),
+            pickable: s.pickable,
+            blocks_movement: s.blocks_movement,
+            starts_closed: s.starts_closed,
+            disposes: s.disposes,
+            constraint: constraint_to_value(&s.constraint),
+            target: constraint_to_value(&s.target),
+            decays_into: s.decays_into.map(|(into, steps)| (into.to_string(), steps)),
+            transforms: s.transforms.iter().map(|&(from, to)| (from.to_string(), to.to_string())).collect(),
+            preference: s.preference.iter().map(|(c, weight)| (constraint_to_value(c), *weight)).collect(),
+        }
+    }
+}
+
+impl ObjectSchema {
+    /// Parse a JSON array of schemas in the form described in the `schema` module docs
+    pub fn from_json(json: &str) -> Result<Vec<Self>, SchemaError> {
+        let reprs: Vec<SchemaRepr> = serde_json::from_str(json).map_err(|e| SchemaError::Json(e.to_string()))?;
+        reprs.into_iter().map(SchemaRepr::into_schema).collect()
+    }
+
+    /// Write schemas as a pretty-printed JSON array that `from_json` reads back
+    pub fn to_json(schemas: &[Self]) -> String {
+        let reprs: Vec<SchemaRepr> = schemas.iter().map(SchemaRepr::from_schema).collect();
+        serde_json::to_string_pretty(&reprs).expect("schemas serialize to JSON")
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use crate::world::World;
+use crate::object::{Object, ObjectId};
+
+/// Lowercase letters and digits only, so "Dish washer", "dishwasher" and "DishWasher" compare equal
+fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let padded: Vec<char> = format!("  {} ", text).chars().collect();
+    padded.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Similarity of a query to a name in `0.0..=1.0`.
+///
+/// Equal after normalization scores 1, a normalized substring at least 0.5 (more the larger
+/// the share of the name it covers), anything else the trigram Dice coefficient scaled below 0.5.
+pub fn fuzzy_score(query: &str, name: &str) -> f32 {
+    let (q, n) = (normalize(query), normalize(name));
+    if q.is_empty() || n.is_empty() {
+        return 0.0;
+    }
+    if q == n {
+        return 1.0;
+    }
+    let (short, long) = if q.len() <= n.len() { (&q, &n) } else { (&n, &q) };
+    if long.contains(short.as_str()) {
+        return 0.5 + 0.49 * short.len() as f32 / long.len() as f32;
+    }
+    let (a, b) = (trigrams(&q), trigrams(&n));
+    let shared = a.intersection(&b).count();
+    0.49 * 2.0 * shared as f32 / (a.len() + b.len()) as f32
+}
+
+/// Best score of a query against an object's schema name and label
+pub(crate) fn object_score(query: &str, object: &Object) -> f32 {
+    let label = object.label.as_deref().map_or(0.0, |l| fuzzy_score(query, l));
+    fuzzy_score(query, &object.name).max(label)
+}
+
+impl World {
+    /// Ids of objects whose schema name or label equals `name`, ignoring case, spaces and
+    /// punctuation ("fruit bowl", "FruitBowl" and "fruit-bowl" are the same name)
+    pub fn find_by_name(&self, name: &str) -> Vec<ObjectId> {
+        self.objects.iter().filter(|o| object_score(name, o) == 1.0).map(|o| o.id).collect()
+    }
+
+    /// Up to `limit` objects most similar to `query` with their `fuzzy_score`, best first.
+    ///
+    /// Objects scoring 0 are left out; equal scores are ordered by id.
+    pub fn find_fuzzy(&self, query: &str, limit: usize) -> Vec<(ObjectId, f32)> {
+        let mut scored: Vec<(ObjectId, f32)> = self
+            .objects
+            .iter()
+            .map(|o| (o.id, object_score(query, o)))
+            .filter(|&(_, score)| score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.truncate(limit);
+        scored
+    }
+}
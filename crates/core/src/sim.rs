@@ -1,10 +1,22 @@
-use crate::gen::World;
+use crate::world::{Cell, Layout, World};
 use crate::object::{Object, ObjectId};
-use crate::agent::Agent;
+use crate::action::Action;
+use crate::agent::{Agent, Direction};
+use crate::cell::CellKind;
+use crate::checkpoint::{Checkpointer, Record, SimState};
+use crate::hooks::Hooks;
+use crate::recording::RecordedAction;
+use crate::reward::RewardConfig;
+use crate::room_graph::RoomAggregates;
+use crate::view::ViewCache;
+use crate::language;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
 
 /// Constants for door types
 use crate::{
-    CLOSED_DOOR, OPEN_DOOR, OBSTACLES,
+    CLOSED_DOOR, OPEN_DOOR,
 };
 
 /// Errors when attempting agent movements or object actions
@@ -24,14 +36,301 @@ pub enum MoveError {
     ContainerFull,
     /// Target invalid or not a container
     InvalidTarget,
+    /// Target is neither at nor orthogonally adjacent to the agent
+    TooFar,
+    /// A simulator hook refused the action
+    Denied(String),
+    /// `auto_place` found no container or floor cell satisfying the held object's target
+    NoValidPlacementNearby,
+    /// The container is closed, or sits inside a closed one at any depth; see
+    /// `Simulator::closed_containers`
+    ContainerClosed,
+    /// Move would step onto this object's cell; see `Simulator::blocking_objects`
+    BlockedByObject(ObjectId),
+    /// Move would step onto the cell of the agent with this id; see `Simulator::new_multi`
+    BlockedByAgent(usize),
 }
 
+impl MoveError {
+    /// Name of the variant, as the bindings report it
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoveError::OutOfBounds => "OutOfBounds",
+            MoveError::HitObstacle => "HitObstacle",
+            MoveError::AlreadyHolding => "AlreadyHolding",
+            MoveError::NothingToPickUp => "NothingToPickUp",
+            MoveError::NotHolding => "NotHolding",
+            MoveError::ContainerFull => "ContainerFull",
+            MoveError::InvalidTarget => "InvalidTarget",
+            MoveError::TooFar => "TooFar",
+            MoveError::Denied(_) => "Denied",
+            MoveError::NoValidPlacementNearby => "NoValidPlacementNearby",
+            MoveError::ContainerClosed => "ContainerClosed",
+            MoveError::BlockedByObject(_) => "BlockedByObject",
+            MoveError::BlockedByAgent(_) => "BlockedByAgent",
+        }
+    }
+}
+
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::OutOfBounds => f.write_str("Move would leave the layout"),
+            MoveError::HitObstacle => f.write_str("Cell is not walkable"),
+            MoveError::AlreadyHolding => f.write_str("Already holding an object"),
+            MoveError::NothingToPickUp => f.write_str("Nothing to pick up here"),
+            MoveError::NotHolding => f.write_str("Not holding anything"),
+            MoveError::ContainerFull => f.write_str("Container is full"),
+            MoveError::InvalidTarget => f.write_str("Target is missing or not usable for this action"),
+            MoveError::TooFar => f.write_str("Target is not on or next to the agent"),
+            MoveError::Denied(reason) => write!(f, "Denied: {}", reason),
+            MoveError::NoValidPlacementNearby => f.write_str("No container or floor cell nearby satisfies the held object's target"),
+            MoveError::ContainerClosed => f.write_str("Container is closed"),
+            MoveError::BlockedByObject(id) => write!(f, "Object {} blocks the way", id),
+            MoveError::BlockedByAgent(id) => write!(f, "Agent {} is in the way", id),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Errors from `Simulator::interact` and `Simulator::use_door`
+#[derive(Debug, PartialEq)]
+pub enum InteractError {
+    /// The target cell lies outside the layout
+    OutOfBounds { x: isize, y: isize },
+    /// `use_door` was pointed at a cell that is not a door
+    NotADoor { x: usize, y: usize },
+    DoorLocked,
+    DoorAlreadyOpen,
+    DoorAlreadyClosed,
+    /// Closing the door would shut this agent inside the doorway
+    DoorwayOccupied(usize),
+    /// The target is a wall, window or outside cell; carries the cell value
+    CellNotRoom(Cell),
+    /// Empty-handed, and the target cell holds no object
+    NothingThere,
+    /// Empty-handed, and none of the objects on the target cell can be picked up
+    NotPickable,
+    /// Holding an object, and every container on the target cell is full
+    ContainerFull,
+    /// Placing the held object into a container on the target cell, or the action a hook
+    /// replaced the interaction with, failed
+    Move(MoveError),
+    /// A simulator hook refused the action
+    Denied(String),
+}
+
+impl InteractError {
+    /// Name of the variant, as the bindings report it
+    pub fn name(&self) -> &'static str {
+        match self {
+            InteractError::OutOfBounds { .. } => "OutOfBounds",
+            InteractError::NotADoor { .. } => "NotADoor",
+            InteractError::DoorLocked => "DoorLocked",
+            InteractError::DoorAlreadyOpen => "DoorAlreadyOpen",
+            InteractError::DoorAlreadyClosed => "DoorAlreadyClosed",
+            InteractError::DoorwayOccupied(_) => "DoorwayOccupied",
+            InteractError::CellNotRoom(_) => "CellNotRoom",
+            InteractError::NothingThere => "NothingThere",
+            InteractError::NotPickable => "NotPickable",
+            InteractError::ContainerFull => "ContainerFull",
+            InteractError::Move(_) => "Move",
+            InteractError::Denied(_) => "Denied",
+        }
+    }
+}
+
+impl std::fmt::Display for InteractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InteractError::OutOfBounds { x, y } => write!(f, "Out of bounds: ({}, {})", x, y),
+            InteractError::NotADoor { x, y } => write!(f, "Not a door: ({}, {})", x, y),
+            InteractError::DoorLocked => f.write_str("Door is locked"),
+            InteractError::DoorAlreadyOpen => f.write_str("Door is already open"),
+            InteractError::DoorAlreadyClosed => f.write_str("Door is already closed"),
+            InteractError::DoorwayOccupied(agent) => write!(f, "Cannot close the door while agent {} stands in the doorway", agent),
+            InteractError::CellNotRoom(cell) => write!(f, "Cannot interact with objects on non-room cell: {}", cell),
+            InteractError::NothingThere => f.write_str("Nothing to interact with"),
+            InteractError::NotPickable => f.write_str("Nothing there can be picked up"),
+            InteractError::ContainerFull => f.write_str("Container is full"),
+            InteractError::Move(e) => write!(f, "{}", e),
+            InteractError::Denied(reason) => write!(f, "Denied: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for InteractError {}
+
+/// Time each action takes on the simulator clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionCosts {
+    /// Walking one cell or turning in place
+    pub move_cell: u32,
+    /// Opening or closing a door or container, or running an appliance
+    pub door: u32,
+    pub pick_up: u32,
+    pub drop: u32,
+    pub place_into: u32,
+    pub inspect: u32,
+}
+
+impl Default for ActionCosts {
+    fn default() -> Self {
+        ActionCosts { move_cell: 1, door: 1, pick_up: 1, drop: 1, place_into: 1, inspect: 1 }
+    }
+}
+
+impl ActionCosts {
+    /// Set a cost by action name ("move", "door", "pick_up", "drop", "place_into" or "inspect")
+    pub fn set(&mut self, action: &str, cost: u32) -> Result<(), String> {
+        let slot = match action {
+            "move" => &mut self.move_cell,
+            "door" => &mut self.door,
+            "pick_up" => &mut self.pick_up,
+            "drop" => &mut self.drop,
+            "place_into" => &mut self.place_into,
+            "inspect" => &mut self.inspect,
+            _ => return Err(format!("Unknown action: {}", action)),
+        };
+        *slot = cost;
+        Ok(())
+    }
+}
+
+/// State changes, in the order they happened.
+///
+/// `Simulator::drain_events` returns all of them; `Simulator::tick` reports decay and
+/// `StepOutcome::events` disposals.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimEvent {
+    /// An object turned into another schema after spending too long outside its target
+    Decayed { id: ObjectId, from: Cow<'static, str>, into: Cow<'static, str> },
+    /// An object, and everything inside it, was thrown into a disposing container
+    Disposed { id: ObjectId, name: Cow<'static, str> },
+    /// An agent stepped to a neighbouring cell
+    Moved { agent: usize, from: (usize, usize), to: (usize, usize) },
+    /// An agent turned in place
+    Turned { agent: usize, facing: Direction },
+    /// A door was opened through the cell (x, y); `door` is its `Layout::doors` id, if registered
+    DoorOpened { door: Option<usize>, x: usize, y: usize },
+    /// A door was closed through the cell (x, y)
+    DoorClosed { door: Option<usize>, x: usize, y: usize },
+    /// An agent took an object into its hand
+    PickedUp { agent: usize, id: ObjectId },
+    /// An agent put its held object on the floor at (x, y)
+    Dropped { agent: usize, id: ObjectId, x: usize, y: usize },
+    /// An agent put its held object into a container; a disposing one follows with `Disposed`
+    PlacedInto { agent: usize, id: ObjectId, container: ObjectId },
+    /// A closable container was opened
+    ContainerOpened { id: ObjectId },
+    /// A closable container was closed
+    ContainerClosed { id: ObjectId },
+    /// An appliance turned an object inside it into another schema
+    Transformed { id: ObjectId, from: Cow<'static, str>, into: Cow<'static, str> },
+}
+
+/// When an episode counts as over, see `Simulator::is_done`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DoneCriteria {
+    /// Successful actions after which the episode is cut short
+    pub max_steps: Option<u32>,
+    /// `Simulator::tidiness` that counts as tidy; without one every pickable must be in place
+    pub min_tidiness: Option<f32>,
+}
+
+/// Events kept for `Simulator::drain_events`; older ones are dropped first
+const EVENT_LOG_CAPACITY: usize = 4096;
+
+/// Container that would accept the held object right now
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerPreview {
+    pub id: ObjectId,
+    pub x: usize,
+    pub y: usize,
+    /// Free slots before the object is placed
+    pub remaining_capacity: usize,
+    /// Whether placing the held object here satisfies its target
+    pub satisfies_target: bool,
+}
+
+/// What `drop` and `place_into` would do with the held object, computed without acting
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DropPreview {
+    /// Id of the held object, `None` when empty-handed
+    pub holding: Option<ObjectId>,
+    /// Whether `drop` would succeed on the agent cell
+    pub can_drop: bool,
+    /// Whether dropping on the agent cell satisfies the held object's target
+    pub drop_satisfies_target: bool,
+    /// Non-full containers on the agent cell or one of its four neighbours
+    pub containers: Vec<ContainerPreview>,
+    /// Loose pickables already on the agent cell, in `World::objects_at` order; a drop
+    /// stacks the held object onto them
+    pub stacked: Vec<ObjectId>,
+}
+
+/// Distance fields keyed by their sorted source cells and blocked cells
+type DistanceCache = HashMap<(Vec<usize>, Vec<usize>), Vec<Option<usize>>>;
+
 /// Simulator state pairing a world with an agent and optional held object
 #[derive(Debug)]
 pub struct Simulator {
     pub world: World,
     pub agent: Agent,
     pub holding: Option<Object>,
+    /// Objects inside `holding`, at any depth, taken out of the world with it and put back
+    /// when it is released
+    pub carried: Vec<Object>,
+    /// Objects whose target the agent has learned by inspecting them
+    pub known_targets: HashSet<ObjectId>,
+    /// Distance fields used by `potential`
+    pub(crate) distance_cache: DistanceCache,
+    /// Whether `tick` advances object decay (off by default)
+    pub decay_enabled: bool,
+    /// Steps left before each decaying object turns into its decayed schema
+    pub decay_remaining: HashMap<ObjectId, usize>,
+    /// Clock cost of each action
+    pub costs: ActionCosts,
+    /// Whether `place_into`, and picking up or dropping through `interact`, need the target
+    /// on or next to the agent (on by default); a rule like the hooks, so `restore` keeps it
+    pub strict_reach: bool,
+    /// Whether closed containers hide their contents and refuse `place_into` and pick-ups
+    /// (off by default, so every container acts open); kept by `restore` like `strict_reach`
+    pub closed_containers: bool,
+    /// Whether objects that block movement (large furniture) keep the agent off their cell
+    /// (off by default, so every room cell is walkable); kept by `restore` like `strict_reach`
+    pub blocking_objects: bool,
+    /// Number of successful actions taken
+    pub steps: u32,
+    /// Elapsed time, advanced by `costs` on every successful action
+    pub time: u32,
+    /// Optional time limit; see `time_exceeded`
+    pub max_time: Option<u32>,
+    /// Success threshold and step budget for `is_done`; kept by `restore` like `strict_reach`
+    pub done_criteria: DoneCriteria,
+    /// Reward `step` pays on top of the hooks' (none by default); kept by `restore` like
+    /// `strict_reach`
+    pub reward_config: Option<RewardConfig>,
+    /// Custom rules consulted by `step`
+    pub(crate) hooks: Hooks,
+    /// Active checkpoint file, see `checkpoint_to`
+    pub(crate) checkpoint: Option<Checkpointer>,
+    /// Per-room aggregates for `room_graph_observation`
+    pub(crate) room_cache: Option<RoomAggregates>,
+    /// Buffers shared by the views `view` hands out
+    pub(crate) view_cache: ViewCache,
+    /// State `reset` returns to, captured by `new`
+    pub(crate) initial: Option<Box<SimState>>,
+    /// Action log, see `enable_recording`
+    pub(crate) recording: Option<Vec<RecordedAction>>,
+    /// Agents other than the one in `agent`/`holding`, with what each holds and carries in
+    /// it; see `new_multi`
+    pub(crate) other_agents: Vec<(Agent, Option<Object>, Vec<Object>)>,
+    /// Id of the agent in `agent`/`holding`: 0, except while `step_agent` runs another agent
+    pub(crate) active: usize,
+    /// Events since the last `drain_events`
+    pub(crate) events: VecDeque<SimEvent>,
 }
 
 impl Simulator {
@@ -42,39 +341,192 @@ impl Simulator {
         if layout.cells.len() != layout.width * layout.height {
             return Err("Layout cells length does not match dimensions");
         }
-        // bounds check
-        if start_x >= layout.width || start_y >= layout.height {
-            return Err("Start position out of bounds");
+        check_start(layout, start_x, start_y)?;
+        let agent = Agent::new(start_x, start_y);
+        let decay_remaining = world
+            .objects
+            .iter()
+            .filter_map(|o| o.decay.as_ref().map(|d| (o.id, d.steps)))
+            .collect();
+        let mut sim = Simulator {
+            world,
+            agent,
+            holding: None,
+            carried: Vec::new(),
+            known_targets: HashSet::new(),
+            distance_cache: HashMap::new(),
+            decay_enabled: false,
+            decay_remaining,
+            costs: ActionCosts::default(),
+            strict_reach: true,
+            closed_containers: false,
+            blocking_objects: false,
+            steps: 0,
+            time: 0,
+            max_time: None,
+            done_criteria: DoneCriteria::default(),
+            reward_config: None,
+            hooks: Hooks::default(),
+            checkpoint: None,
+            room_cache: None,
+            view_cache: ViewCache::default(),
+            initial: None,
+            recording: None,
+            other_agents: Vec::new(),
+            active: 0,
+            events: VecDeque::new(),
+        };
+        sim.initial = Some(Box::new(sim.state()));
+        Ok(sim)
+    }
+
+    /// Simulator with one agent per start, agent `i` at `starts[i]`.
+    ///
+    /// Starts must be distinct and navigable. The single-agent methods and fields (`step`,
+    /// `agent`, `holding`, ...) act on agent 0; `step_agent` runs the others. Agents never
+    /// share a cell: moving onto another agent fails with `MoveError::BlockedByAgent`.
+    pub fn new_multi(world: World, starts: &[(usize, usize)]) -> Result<Self, &'static str> {
+        let (&(x, y), rest) = starts.split_first().ok_or("At least one start position is required")?;
+        let mut sim = Simulator::new(world, x, y)?;
+        for (i, &(x, y)) in rest.iter().enumerate() {
+            check_start(&sim.world.layout, x, y)?;
+            if starts[..=i].contains(&(x, y)) {
+                return Err("Start positions must be distinct");
+            }
+            sim.other_agents.push((Agent::new(x, y), None, Vec::new()));
         }
-        let idx = start_y * layout.width + start_x;
-        if layout.cells[idx] < 0 {
-            return Err("Start position is not navigable");
+        sim.initial = Some(Box::new(sim.state()));
+        Ok(sim)
+    }
+
+    /// Number of agents, 1 unless created with `new_multi`
+    pub fn num_agents(&self) -> usize {
+        self.other_agents.len() + 1
+    }
+
+    /// Agent with the given id, `None` past `num_agents`
+    pub fn agent_by_id(&self, id: usize) -> Option<&Agent> {
+        if id == self.active {
+            return Some(&self.agent);
         }
-        let agent = Agent::new(start_x, start_y);
-        Ok(Simulator { world, agent, holding: None })
+        self.other_agents().find(|&(other, _, _)| other == id).map(|(_, agent, _)| agent)
+    }
+
+    /// Object held by the agent with the given id
+    pub fn holding_by_id(&self, id: usize) -> Option<&Object> {
+        if id == self.active {
+            return self.holding.as_ref();
+        }
+        self.other_agents().find(|&(other, _, _)| other == id).and_then(|(_, _, held)| held)
+    }
+
+    /// What the object held by the agent with the given id contains, see `carried`
+    pub fn carried_by_id(&self, id: usize) -> &[Object] {
+        if id == self.active {
+            return &self.carried;
+        }
+        // while `step_agent` runs agent `active`, agent 0 waits in its slot
+        let slot = if id == 0 { self.active } else { id };
+        self.other_agents.get(slot.wrapping_sub(1)).map_or(&[], |(_, _, carried)| carried)
+    }
+
+    /// Agents other than the one in `agent`/`holding`, with their ids
+    pub(crate) fn other_agents(&self) -> impl Iterator<Item = (usize, &Agent, Option<&Object>)> {
+        // while `step_agent` runs agent `active`, agent 0 waits in its slot
+        let active = self.active;
+        self.other_agents
+            .iter()
+            .enumerate()
+            .map(move |(slot, (agent, held, _))| (if slot + 1 == active { 0 } else { slot + 1 }, agent, held.as_ref()))
+    }
+
+    /// Objects held by any agent, and what the held objects contain
+    pub(crate) fn held_objects(&self) -> impl Iterator<Item = &Object> {
+        let others = self.other_agents.iter().flat_map(|(_, held, carried)| held.iter().chain(carried));
+        self.holding.iter().chain(&self.carried).chain(others)
+    }
+
+    /// The object with this id if an agent holds it or it is inside a held object
+    pub(crate) fn held_object_mut(&mut self, id: ObjectId) -> Option<&mut Object> {
+        self.holding
+            .iter_mut()
+            .chain(&mut self.carried)
+            .chain(self.other_agents.iter_mut().flat_map(|(_, held, carried)| held.iter_mut().chain(carried)))
+            .find(|o| o.id == id)
+    }
+
+    /// Swap the agent in `other_agents[slot]` with the one in `agent`/`holding`/`carried`
+    pub(crate) fn swap_agent(&mut self, slot: usize, active: usize) {
+        let (agent, holding, carried) = &mut self.other_agents[slot];
+        std::mem::swap(&mut self.agent, agent);
+        std::mem::swap(&mut self.holding, holding);
+        std::mem::swap(&mut self.carried, carried);
+        self.active = active;
+    }
+
+    /// Events since the last call, oldest first.
+    ///
+    /// Every state change is reported: moves and turns, doors, pick-ups, drops, placements,
+    /// disposals, containers, appliances and decay. Labels and inspections are not. Only the
+    /// latest 4096 events are kept, and `restore` (so also `reset` and resuming) clears them.
+    pub fn drain_events(&mut self) -> Vec<SimEvent> {
+        self.events.drain(..).collect()
+    }
+
+    pub(crate) fn emit(&mut self, event: SimEvent) {
+        if self.events.len() == EVENT_LOG_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
     }
 
     /// Move agent up
     pub fn up(&mut self) -> Result<(), MoveError> {
-        self.try_move(0, -1)
+        self.step(Action::MoveUp).map(|_| ()).map_err(MoveError::from)
     }
 
     /// Move agent down
     pub fn down(&mut self) -> Result<(), MoveError> {
-        self.try_move(0, 1)
+        self.step(Action::MoveDown).map(|_| ()).map_err(MoveError::from)
     }
 
     /// Move agent left
     pub fn left(&mut self) -> Result<(), MoveError> {
-        self.try_move(-1, 0)
+        self.step(Action::MoveLeft).map(|_| ()).map_err(MoveError::from)
     }
 
     /// Move agent right
     pub fn right(&mut self) -> Result<(), MoveError> {
-        self.try_move(1, 0)
+        self.step(Action::MoveRight).map(|_| ()).map_err(MoveError::from)
     }
 
-    fn try_move(&mut self, dx: isize, dy: isize) -> Result<(), MoveError> {
+    /// Turn the agent a quarter counter-clockwise
+    pub fn turn_left(&mut self) -> Result<(), MoveError> {
+        self.step(Action::TurnLeft).map(|_| ()).map_err(MoveError::from)
+    }
+
+    /// Turn the agent a quarter clockwise
+    pub fn turn_right(&mut self) -> Result<(), MoveError> {
+        self.step(Action::TurnRight).map(|_| ()).map_err(MoveError::from)
+    }
+
+    /// Move the agent one cell in the direction it faces
+    pub fn move_forward(&mut self) -> Result<(), MoveError> {
+        self.step(Action::MoveForward).map(|_| ()).map_err(MoveError::from)
+    }
+
+    /// `interact` with the cell the agent faces
+    pub fn interact_ahead(&mut self) -> Result<(), InteractError> {
+        self.step(Action::InteractAhead).map(|_| ()).map_err(InteractError::from)
+    }
+
+    pub(crate) fn turn(&mut self, facing: Direction) {
+        self.agent.facing = facing;
+        self.charge(self.costs.move_cell);
+        self.emit(SimEvent::Turned { agent: self.active, facing });
+    }
+
+    pub(crate) fn try_move(&mut self, dx: isize, dy: isize) -> Result<(), MoveError> {
         let new_x = self.agent.x as isize + dx;
         let new_y = self.agent.y as isize + dy;
         // bounds
@@ -88,77 +540,157 @@ impl Simulator {
         let new_x = new_x as usize;
         let new_y = new_y as usize;
         let idx = new_y * self.world.layout.width + new_x;
-        // obstacle or closed door
-        if OBSTACLES.contains(&self.world.layout.cells[idx]) {
+        // walls, outside, closed/locked doors and windows
+        if !CellKind::from_i8(self.world.layout.cells[idx]).is_passable() {
             return Err(MoveError::HitObstacle);
         }
+        if let Some(id) = self.blocking_object_at(new_x, new_y) {
+            return Err(MoveError::BlockedByObject(id));
+        }
+        if let Some((id, _, _)) = self.other_agents().find(|(_, a, _)| (a.x, a.y) == (new_x, new_y)) {
+            return Err(MoveError::BlockedByAgent(id));
+        }
+        let from = (self.agent.x, self.agent.y);
         self.agent.x = new_x;
         self.agent.y = new_y;
+        self.agent.facing = Direction::from_offset(dx, dy).unwrap_or(self.agent.facing);
+        self.charge(self.costs.move_cell);
+        self.emit(SimEvent::Moved { agent: self.active, from, to: (new_x, new_y) });
         Ok(())
     }
 
+    /// First object on (x, y) that keeps the agent off the cell, when `blocking_objects` is on
+    pub fn blocking_object_at(&self, x: usize, y: usize) -> Option<ObjectId> {
+        if !self.blocking_objects {
+            return None;
+        }
+        self.world.objects_at(x, y).into_iter().find(|o| o.blocks_movement).map(|o| o.id)
+    }
+
+    /// Row-major cells the agent cannot enter because of `blocking_object_at`
+    pub(crate) fn blocked_cells(&self) -> Vec<bool> {
+        let mut blocked = vec![false; self.world.layout.cells.len()];
+        if self.blocking_objects {
+            for o in self.world.objects.iter().filter(|o| o.blocks_movement) {
+                blocked[o.y * self.world.layout.width + o.x] = true;
+            }
+        }
+        blocked
+    }
+
+    /// Count one action and advance the clock by its cost
+    fn charge(&mut self, cost: u32) {
+        self.steps += 1;
+        self.time += cost;
+    }
+
+    /// Whether the clock has reached `max_time`
+    pub fn time_exceeded(&self) -> bool {
+        self.max_time.is_some_and(|max| self.time >= max)
+    }
+
+    /// Whether the episode is over: the apartment is tidy (`is_success`) or the budget is
+    /// spent (`is_truncated`)
+    pub fn is_done(&self) -> bool {
+        self.is_success() || self.is_truncated()
+    }
+
+    /// Whether no agent holds anything and every pickable is in its target placement, or
+    /// `tidiness` reaches `done_criteria.min_tidiness` when one is set
+    pub fn is_success(&self) -> bool {
+        if self.held_objects().next().is_some() {
+            return false;
+        }
+        match self.done_criteria.min_tidiness {
+            Some(min) => self.tidiness() >= min,
+            // stops at the first misplaced object, since `step` asks after every action
+            None => self.world.objects.iter().all(|o| !o.pickable || o.check_placement(&self.world)),
+        }
+    }
+
+    /// Whether `done_criteria.max_steps` successful actions were taken or the clock has
+    /// reached `max_time`
+    pub fn is_truncated(&self) -> bool {
+        self.done_criteria.max_steps.is_some_and(|max| self.steps >= max) || self.time_exceeded()
+    }
+
     /// Unified interact: doors and objects both handled at target cell
-    pub fn interact(&mut self, dx: isize, dy: isize) -> Result<(), String> {
+    pub fn interact(&mut self, dx: isize, dy: isize) -> Result<(), InteractError> {
+        let (Ok(dx), Ok(dy)) = (i8::try_from(dx), i8::try_from(dy)) else {
+            return Err(InteractError::OutOfBounds { x: self.agent.x as isize + dx, y: self.agent.y as isize + dy });
+        };
+        self.step(Action::Interact { dx, dy }).map(|_| ()).map_err(InteractError::from)
+    }
+
+    pub(crate) fn do_interact(&mut self, dx: isize, dy: isize) -> Result<(), InteractError> {
         // compute target coordinates and bounds
         let tx_i = self.agent.x as isize + dx;
         let ty_i = self.agent.y as isize + dy;
         if tx_i < 0 || tx_i >= self.world.layout.width as isize || ty_i < 0 || ty_i >= self.world.layout.height as isize {
-            return Err(format!("Out of bounds: ({}, {})", tx_i, ty_i));
+            return Err(InteractError::OutOfBounds { x: tx_i, y: ty_i });
         }
         let tx = tx_i as usize;
         let ty = ty_i as usize;
         let idx = ty * self.world.layout.width + tx;
         let cell_value = self.world.layout.cells[idx];
-        // door handling
-        if cell_value == CLOSED_DOOR {
-            self.use_door(tx_i, ty_i, true)?;
-            return Ok(());
+        match CellKind::from_i8(cell_value) {
+            CellKind::ClosedDoor => return self.do_use_door(tx_i, ty_i, true),
+            CellKind::OpenDoor => return self.do_use_door(tx_i, ty_i, false),
+            CellKind::LockedDoor => return Err(InteractError::DoorLocked),
+            CellKind::Room(_) => {}
+            // only on room cells
+            _ => return Err(InteractError::CellNotRoom(cell_value)),
         }
-        if cell_value == OPEN_DOOR {
-            self.use_door(tx_i, ty_i, false)?;
-            return Ok(());
+        // taking and dropping follow the reach rule of `place_into`
+        if self.strict_reach && !self.within_reach(tx, ty) {
+            return Err(InteractError::Move(MoveError::TooFar));
         }
-        // only on room cells
-        if cell_value < 0 {
-            return Err(format!("Cannot interact with objects on non-room cell: {}", cell_value));
-        }
-        // if holding, attempt container placement or drop
+        // if holding, place on or into a container with room, or drop
         if self.holding.is_some() {
-            // place into container if present
-            if let Some(container) = self.world.objects.iter().find(|o| o.x == tx && o.y == ty) {
-                self.place_into(container.id).map_err(|e| format!("{:?}", e))?;
+            let containers: Vec<&Object> = self.world.objects_at(tx, ty).into_iter().filter(|o| o.capacity > 0).collect();
+            if !containers.is_empty() {
+                let containers: Vec<&Object> = containers.into_iter().filter(|o| self.is_open(o) && !self.concealed(o.id)).collect();
+                if containers.is_empty() {
+                    return Err(InteractError::Move(MoveError::ContainerClosed));
+                }
+                // surfaces and enclosed containers share one capacity rule; a full one is not dropped onto
+                let Some(id) = containers.iter().find(|o| o.has_room()).map(|o| o.id) else {
+                    return Err(InteractError::ContainerFull);
+                };
+                self.do_place_into(id).map_err(InteractError::Move)?;
                 return Ok(());
             }
             // else drop on floor
-            let mut obj = self.holding.take().unwrap();
-            obj.x = tx;
-            obj.y = ty;
-            self.world.objects.push(obj);
+            self.drop_held(tx, ty);
             return Ok(());
         }
-        // not holding: pick up pickable at target
-        if let Some(pos) = self.world.objects.iter().position(|o| o.x == tx && o.y == ty && o.pickable) {
-            let obj = self.world.objects.remove(pos);
-            // remove from any container contents
-            let oid = obj.id;
-            for container in self.world.objects.iter_mut() {
-                container.contents.retain(|&cid| cid != oid);
-            }
-            self.holding = Some(obj);
-            return Ok(());
+        // not holding: pick up a visible pickable at target
+        let here: Vec<&Object> = self.world.objects_at(tx, ty).into_iter().filter(|o| !self.concealed(o.id)).collect();
+        if here.is_empty() {
+            return Err(InteractError::NothingThere);
         }
-        Err("Nothing to interact with".into())
+        let Some(id) = here.into_iter().find(|o| o.pickable).map(|o| o.id) else {
+            return Err(InteractError::NotPickable);
+        };
+        self.take_object(id);
+        Ok(())
     }
 
     /// Use a door (open if `open_flag` is true, close otherwise)
-    pub fn use_door(&mut self, x: isize, y: isize, open_flag: bool) -> Result<(), String> {
+    pub fn use_door(&mut self, x: isize, y: isize, open_flag: bool) -> Result<(), InteractError> {
+        self.do_use_door(x, y, open_flag)?;
+        self.log_record(Record::Door { x, y, open: open_flag });
+        Ok(())
+    }
+
+    pub(crate) fn do_use_door(&mut self, x: isize, y: isize, open_flag: bool) -> Result<(), InteractError> {
         // bounds
         if x < 0
             || x >= self.world.layout.width as isize
             || y < 0
             || y >= self.world.layout.height as isize
         {
-            return Err(format!("Out of bounds: ({}, {})", x, y));
+            return Err(InteractError::OutOfBounds { x, y });
         }
         let idx = (y as usize) * self.world.layout.width + (x as usize);
         let change_to = if open_flag {
@@ -167,71 +699,511 @@ impl Simulator {
             CLOSED_DOOR
         };
         let cell_value = self.world.layout.cells[idx];
+        match (CellKind::from_i8(cell_value), open_flag) {
+            (CellKind::ClosedDoor, true) | (CellKind::OpenDoor, false) => {}
+            (CellKind::OpenDoor, true) => return Err(InteractError::DoorAlreadyOpen),
+            (CellKind::ClosedDoor, false) => return Err(InteractError::DoorAlreadyClosed),
+            (CellKind::LockedDoor, _) => return Err(InteractError::DoorLocked),
+            _ => return Err(InteractError::NotADoor { x: x as usize, y: y as usize }),
+        }
 
-        // for every connected cell to x, y with the same value as cell_value, change it to change_to with flood fill
-        let mut stack = vec![(x, y)];
-        while let Some((cx, cy)) = stack.pop() {
-            let idx = (cy as usize) * self.world.layout.width + (cx as usize);
-            if self.world.layout.cells[idx] == cell_value {
-                self.world.layout.cells[idx] = change_to;
-                // push all 4 neighbors
-                stack.push((cx - 1, cy));
-                stack.push((cx + 1, cy));
-                stack.push((cx, cy - 1));
-                stack.push((cx, cy + 1));
+        // the registered door segment, or for door cells outside the registry, e.g. written by
+        // hand, every cell 4-connected to (x, y) with the same door value
+        let door = self.world.layout.doors.iter().position(|d| d.cells.contains(&idx));
+        let cells = match door {
+            Some(d) => self.world.layout.doors[d].cells.clone(),
+            None => self.door_cells(idx, cell_value),
+        };
+        if !open_flag {
+            let mut agents = std::iter::once((self.active, &self.agent)).chain(self.other_agents().map(|(id, a, _)| (id, a)));
+            if let Some((id, _)) = agents.find(|(_, a)| cells.contains(&(a.y * self.world.layout.width + a.x))) {
+                return Err(InteractError::DoorwayOccupied(id));
             }
         }
-        
+        for &i in &cells {
+            self.world.layout.cells[i] = change_to;
+        }
+        self.view_cache.clear_state();
+        // keep the registered door's open flag in step
+        let id = door.map(|d| {
+            let door = &mut self.world.layout.doors[d];
+            door.open = open_flag;
+            door.id
+        });
+        let (ux, uy) = (x as usize, y as usize);
+        self.charge(self.costs.door);
+        self.emit(match open_flag {
+            true => SimEvent::DoorOpened { door: id, x: ux, y: uy },
+            false => SimEvent::DoorClosed { door: id, x: ux, y: uy },
+        });
         Ok(())
     }
 
-    /// Pick up a pickable object at the agent's current location
+    /// Cells 4-connected to `idx` that hold `value`, including `idx`
+    fn door_cells(&self, idx: usize, value: Cell) -> Vec<usize> {
+        let layout = &self.world.layout;
+        let (w, h) = (layout.width, layout.height);
+        let mut visited = vec![false; layout.cells.len()];
+        visited[idx] = true;
+        let mut stack = vec![idx];
+        let mut cells = Vec::new();
+        while let Some(i) = stack.pop() {
+            cells.push(i);
+            let (cx, cy) = (i % w, i / w);
+            let neighbors = [
+                (cx > 0).then(|| i - 1),
+                (cx + 1 < w).then(|| i + 1),
+                (cy > 0).then(|| i - w),
+                (cy + 1 < h).then(|| i + w),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if !visited[n] && layout.cells[n] == value {
+                    visited[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        cells
+    }
+
+    /// Pick up the pickable object with the lowest id at the agent's current location
     pub fn pick_up(&mut self) -> Result<(), MoveError> {
+        self.step(Action::PickUp).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_pick_up(&mut self) -> Result<(), MoveError> {
         if self.holding.is_some() {
             return Err(MoveError::AlreadyHolding);
         }
         let (ax, ay) = (self.agent.x, self.agent.y);
-        if let Some(pos) = self.world.objects.iter().position(|o| o.x == ax && o.y == ay && o.pickable) {
-            let obj = self.world.objects.remove(pos);
-            self.holding = Some(obj);
-            Ok(())
-        } else {
-            Err(MoveError::NothingToPickUp)
+        // lowest id first, see `World::objects_at`
+        let id = self.world.objects_at(ax, ay).into_iter().find(|o| o.pickable && !self.concealed(o.id)).map(|o| o.id);
+        let id = id.ok_or(MoveError::NothingToPickUp)?;
+        self.take_object(id);
+        Ok(())
+    }
+
+    /// Pick up a specific pickable object at the agent's current location, for cells
+    /// holding several
+    pub fn pick_up_by_id(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        self.step(Action::PickUpById(id)).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_pick_up_by_id(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        if self.holding.is_some() {
+            return Err(MoveError::AlreadyHolding);
+        }
+        let obj = self.world.object(id).ok_or(MoveError::InvalidTarget)?;
+        if !obj.pickable {
+            return Err(MoveError::InvalidTarget);
+        }
+        if (obj.x, obj.y) != (self.agent.x, self.agent.y) {
+            return Err(MoveError::TooFar);
+        }
+        if self.concealed(id) {
+            return Err(MoveError::ContainerClosed);
+        }
+        self.take_object(id);
+        Ok(())
+    }
+
+    /// Put the held object into the world at (x, y), bringing along everything it contains
+    fn release_held(&mut self, x: usize, y: usize) {
+        let obj = self.holding.take().expect("holding an object");
+        let id = obj.id;
+        self.world.add_object(obj);
+        for inner in std::mem::take(&mut self.carried) {
+            self.world.add_object(inner);
+        }
+        self.world.move_object(id, x, y);
+    }
+
+    /// Drop the held object on the floor at (x, y)
+    fn drop_held(&mut self, x: usize, y: usize) {
+        let id = self.holding.as_ref().expect("holding an object").id;
+        self.release_held(x, y);
+        self.charge(self.costs.drop);
+        self.emit(SimEvent::Dropped { agent: self.active, id, x, y });
+    }
+
+    /// Move an object, with everything it contains, from the world into the agent's hand
+    fn take_object(&mut self, id: ObjectId) {
+        self.holding = Some(self.world.detach_object(id).expect("object exists"));
+        self.carry_contents();
+        self.charge(self.costs.pick_up);
+        self.emit(SimEvent::PickedUp { agent: self.active, id });
+    }
+
+    /// Move whatever the held object contains, at any depth, from the world into `carried`,
+    /// so nothing can reach it until the held object is released
+    pub(crate) fn carry_contents(&mut self) {
+        let mut stack = self.holding.as_ref().map_or_else(Vec::new, |o| o.contents.clone());
+        while let Some(id) = stack.pop() {
+            if let Some(inner) = self.world.detach_object(id) {
+                stack.extend(inner.contents.iter().copied());
+                self.carried.push(inner);
+            }
         }
     }
 
     /// Drop held object at the agent's current location or into the world
     pub fn drop(&mut self) -> Result<(), MoveError> {
-        if let Some(mut obj) = self.holding.take() {
-            obj.x = self.agent.x;
-            obj.y = self.agent.y;
-            self.world.objects.push(obj);
-            Ok(())
-        } else {
-            Err(MoveError::NotHolding)
+        self.step(Action::Drop).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_drop(&mut self) -> Result<(), MoveError> {
+        if self.holding.is_none() {
+            return Err(MoveError::NotHolding);
         }
+        self.drop_held(self.agent.x, self.agent.y);
+        Ok(())
     }
 
-    /// Place held object into a container object
+    /// Place held object into a container object on or orthogonally next to the agent; with
+    /// `strict_reach` off the container may be anywhere
     pub fn place_into(&mut self, target_id: ObjectId) -> Result<(), MoveError> {
+        self.step(Action::PlaceInto(target_id)).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_place_into(&mut self, target_id: ObjectId) -> Result<(), MoveError> {
         if self.holding.is_none() {
             return Err(MoveError::NotHolding);
         }
-        // find container
-        if let Some(container) = self.world.objects.iter_mut().find(|o| o.id == target_id) {
-            // determine capacity
-            if container.contents.len() >= container.capacity {
-                return Err(MoveError::ContainerFull);
+        let ci = self.world.object_position(target_id).ok_or(MoveError::InvalidTarget)?;
+        // a container hidden in a closed one cannot be reached into either
+        let closed = self.closed_containers && (!self.world.objects[ci].open || self.concealed(target_id));
+        let container = &mut self.world.objects[ci];
+        if self.strict_reach && self.agent.x.abs_diff(container.x) + self.agent.y.abs_diff(container.y) > 1 {
+            return Err(MoveError::TooFar);
+        }
+        if closed {
+            return Err(MoveError::ContainerClosed);
+        }
+        if !container.has_room() {
+            return Err(MoveError::ContainerFull);
+        }
+        let (x, y) = (container.x, container.y);
+        let id = self.holding.as_ref().unwrap().id;
+        let disposes = container.disposes;
+        if !disposes {
+            container.contents.push(id);
+        }
+        self.emit(SimEvent::PlacedInto { agent: self.active, id, container: target_id });
+        if disposes {
+            self.dispose_held(x, y);
+        } else {
+            self.release_held(x, y);
+        }
+        self.charge(self.costs.place_into);
+        Ok(())
+    }
+
+    /// Move the held object and everything it contains from the agent's hand into
+    /// `World::disposed`, at (x, y)
+    fn dispose_held(&mut self, x: usize, y: usize) {
+        let held = self.holding.take().expect("holding an object");
+        for mut obj in std::iter::once(held).chain(std::mem::take(&mut self.carried)) {
+            (obj.x, obj.y) = (x, y);
+            self.decay_remaining.remove(&obj.id);
+            self.emit(SimEvent::Disposed { id: obj.id, name: obj.name.clone() });
+            self.world.disposed.push(obj);
+        }
+    }
+
+    /// Open a closed container or close an open one, on or orthogonally next to the agent;
+    /// with `strict_reach` off the container may be anywhere
+    pub fn toggle_container(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        self.step(Action::ToggleContainer(id)).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_toggle_container(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        let ci = self.world.object_position(id).ok_or(MoveError::InvalidTarget)?;
+        let container = &mut self.world.objects[ci];
+        if !container.closable() {
+            return Err(MoveError::InvalidTarget);
+        }
+        if self.strict_reach && self.agent.x.abs_diff(container.x) + self.agent.y.abs_diff(container.y) > 1 {
+            return Err(MoveError::TooFar);
+        }
+        container.open = !container.open;
+        let event = if container.open { SimEvent::ContainerOpened { id } } else { SimEvent::ContainerClosed { id } };
+        self.charge(self.costs.door);
+        self.emit(event);
+        Ok(())
+    }
+
+    /// Run an appliance on or orthogonally next to the agent (with `strict_reach` off, anywhere).
+    ///
+    /// Every object inside it named on the `from` side of its `transforms` table turns into
+    /// the matching schema, keeping its id and position; for the Dishwasher that makes a
+    /// DirtyPlate a Plate, which belongs in a Cupboard. Objects without an entry stay as they
+    /// are. Custom appliances use the table of the schema set they were made from.
+    pub fn run_appliance(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        self.step(Action::RunAppliance(id)).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_run_appliance(&mut self, id: ObjectId) -> Result<(), MoveError> {
+        let appliance = self.world.object(id).ok_or(MoveError::InvalidTarget)?;
+        if appliance.transforms.is_empty() {
+            return Err(MoveError::InvalidTarget);
+        }
+        if self.strict_reach && !self.within_reach(appliance.x, appliance.y) {
+            return Err(MoveError::TooFar);
+        }
+        let transforms = appliance.transforms.clone();
+        for content in appliance.contents.clone() {
+            let i = self.world.object_position(content).expect("contents are in the world");
+            let obj = &mut self.world.objects[i];
+            let Some(transform) = transforms.iter().find(|t| t.from == obj.name) else { continue };
+            let from = obj.name.clone();
+            let into = transform.into.name.clone();
+            obj.take_on((*transform.into).clone());
+            match &obj.decay {
+                Some(decay) => self.decay_remaining.insert(content, decay.steps),
+                None => self.decay_remaining.remove(&content),
+            };
+            self.emit(SimEvent::Transformed { id: content, from, into });
+        }
+        self.charge(self.costs.door);
+        Ok(())
+    }
+
+    /// Whether the agent can see into and use `container`: always, unless
+    /// `closed_containers` is on and it is closed
+    pub fn is_open(&self, container: &Object) -> bool {
+        !self.closed_containers || container.open
+    }
+
+    /// Whether an object is hidden inside a closed container, at any depth
+    pub fn concealed(&self, id: ObjectId) -> bool {
+        self.closed_container_of(id).is_some()
+    }
+
+    /// Innermost closed container around an object, when `closed_containers` is on
+    pub(crate) fn closed_container_of(&self, mut id: ObjectId) -> Option<ObjectId> {
+        if !self.closed_containers {
+            return None;
+        }
+        while let Some(pi) = self.world.parent_index(id) {
+            let parent = &self.world.objects[pi];
+            if !parent.open {
+                return Some(parent.id);
+            }
+            id = parent.id;
+        }
+        None
+    }
+
+    /// Put the held object where it belongs, if that is on or next to the agent.
+    ///
+    /// Checks the agent cell, then the cells above, below, left and right. A non-full
+    /// container satisfying the held object's target anywhere there wins over the floor;
+    /// otherwise the object is dropped on the first room cell that satisfies it.
+    pub fn auto_place(&mut self) -> Result<(), MoveError> {
+        self.step(Action::AutoPlace).map(|_| ()).map_err(MoveError::from)
+    }
+
+    pub(crate) fn do_auto_place(&mut self) -> Result<(), MoveError> {
+        let preview = self.drop_preview();
+        if preview.holding.is_none() {
+            return Err(MoveError::NotHolding);
+        }
+        if let Some(c) = preview.containers.iter().find(|c| c.satisfies_target) {
+            return self.do_place_into(c.id);
+        }
+        let target = self.holding.as_ref().and_then(|o| o.target()).ok_or(MoveError::NoValidPlacementNearby)?;
+        let (ax, ay) = (self.agent.x as isize, self.agent.y as isize);
+        let layout = &self.world.layout;
+        let cell = [(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)]
+            .iter()
+            .map(|&(dx, dy)| (ax + dx, ay + dy))
+            .filter(|&(x, y)| x >= 0 && y >= 0 && x < layout.width as isize && y < layout.height as isize)
+            .map(|(x, y)| (x as usize, y as usize))
+            .find(|&(x, y)| layout.kind_at(x, y).room().is_some() && target.check(&self.world, x, y));
+        let (x, y) = cell.ok_or(MoveError::NoValidPlacementNearby)?;
+        self.drop_held(x, y);
+        Ok(())
+    }
+
+    /// Preview dropping or placing the held object without changing any state
+    pub fn drop_preview(&self) -> DropPreview {
+        let Some(held) = &self.holding else {
+            return DropPreview::default();
+        };
+        let target = held.target();
+        let satisfies = |x, y| target.is_some_and(|t| t.check(&self.world, x, y));
+        let (ax, ay) = (self.agent.x as isize, self.agent.y as isize);
+        let (w, h) = (self.world.layout.width as isize, self.world.layout.height as isize);
+        let mut containers = Vec::new();
+        for (dx, dy) in [(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)] {
+            let (x, y) = (ax + dx, ay + dy);
+            if x < 0 || y < 0 || x >= w || y >= h {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            containers.extend(
+                self.world
+                    .objects_at(x, y)
+                    .into_iter()
+                    .filter(|o| o.has_room() && self.is_open(o) && !self.concealed(o.id))
+                    .map(|o| ContainerPreview {
+                        id: o.id,
+                        x,
+                        y,
+                        remaining_capacity: o.capacity - o.contents.len(),
+                        satisfies_target: satisfies(x, y),
+                    }),
+            );
+        }
+        let stacked = self
+            .world
+            .objects_at(self.agent.x, self.agent.y)
+            .into_iter()
+            .filter(|o| o.pickable && self.world.parent_index(o.id).is_none())
+            .map(|o| o.id)
+            .collect();
+        DropPreview {
+            holding: Some(held.id),
+            can_drop: true,
+            drop_satisfies_target: satisfies(self.agent.x, self.agent.y),
+            containers,
+            stacked,
+        }
+    }
+
+    /// Advance time by one step; call once per agent action.
+    ///
+    /// When decay is enabled, every decaying object outside its target loses one step
+    /// (counters pause while the object is correctly stored, and held objects always age)
+    /// and objects reaching zero turn into their decayed schema.
+    pub fn tick(&mut self) -> Vec<SimEvent> {
+        let events = self.advance_decay();
+        if !events.is_empty() {
+            self.room_cache = None;
+            self.view_cache.clear_state();
+        }
+        for event in &events {
+            self.emit(event.clone());
+        }
+        self.log_record(Record::Tick);
+        self.record_tick();
+        events
+    }
+
+    fn advance_decay(&mut self) -> Vec<SimEvent> {
+        let mut events = Vec::new();
+        if !self.decay_enabled {
+            return events;
+        }
+        let mut ids: Vec<ObjectId> = self.decay_remaining.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            let held = self.held_objects().any(|o| o.id == id);
+            if !held {
+                match self.world.object(id) {
+                    Some(obj) if obj.check_placement(&self.world) => continue,
+                    Some(_) => {}
+                    None => {
+                        self.decay_remaining.remove(&id);
+                        continue;
+                    }
+                }
+            }
+            let remaining = self.decay_remaining.get_mut(&id).unwrap();
+            *remaining = remaining.saturating_sub(1);
+            if *remaining > 0 {
+                continue;
+            }
+            self.decay_remaining.remove(&id);
+            let obj = if held {
+                self.held_object_mut(id).unwrap()
+            } else {
+                let i = self.world.object_position(id).unwrap();
+                &mut self.world.objects[i]
+            };
+            let from = obj.name.clone();
+            if !obj.decay_now() {
+                continue;
+            }
+            let into = obj.name.clone();
+            if let Some(steps) = obj.decay.as_ref().map(|d| d.steps) {
+                self.decay_remaining.insert(id, steps);
             }
-            // place object
-            let mut obj = self.holding.take().unwrap();
-            obj.x = container.x;
-            obj.y = container.y;
-            container.contents.push(obj.id);
-            self.world.objects.push(obj);
-            Ok(())
+            events.push(SimEvent::Decayed { id, from, into });
+        }
+        events
+    }
+
+    /// Override the label of an object (held or in the world); `None` restores the default
+    pub fn set_label(&mut self, object_id: ObjectId, label: Option<String>) -> Result<(), MoveError> {
+        let obj = match self.world.object_position(object_id) {
+            Some(i) => &mut self.world.objects[i],
+            None => self.held_object_mut(object_id).ok_or(MoveError::InvalidTarget)?,
+        };
+        obj.label = label.clone();
+        self.log_record(Record::Label(object_id, label));
+        Ok(())
+    }
+
+    /// Steps left before an object decays, if it decays at all
+    pub fn decay_remaining(&self, object_id: ObjectId) -> Option<usize> {
+        self.decay_remaining.get(&object_id).copied()
+    }
+
+    /// Whether (x, y) is the agent's cell or orthogonally adjacent to it
+    fn within_reach(&self, x: usize, y: usize) -> bool {
+        self.agent.x.abs_diff(x) + self.agent.y.abs_diff(y) <= 1
+    }
+
+    /// Read an object's label: learn its target and return the goal description.
+    ///
+    /// The held object can always be read; others follow the rules of `interact`: they
+    /// must be on or next to the agent under `strict_reach`, and not hidden in a closed
+    /// container under `closed_containers`.
+    pub fn inspect(&mut self, object_id: ObjectId) -> Result<String, MoveError> {
+        let obj = match &self.holding {
+            Some(held) if held.id == object_id => held,
+            _ => {
+                let obj = self.world.object(object_id).ok_or(MoveError::InvalidTarget)?;
+                if self.strict_reach && !self.within_reach(obj.x, obj.y) {
+                    return Err(MoveError::TooFar);
+                }
+                if self.concealed(object_id) {
+                    return Err(MoveError::ContainerClosed);
+                }
+                obj
+            }
+        };
+        let description = language::describe_target(obj);
+        self.known_targets.insert(object_id);
+        self.charge(self.costs.inspect);
+        self.log_record(Record::Inspect(object_id));
+        Ok(description)
+    }
+
+    /// Whether the agent has inspected the given object
+    pub fn is_target_known(&self, object_id: ObjectId) -> bool {
+        self.known_targets.contains(&object_id)
+    }
+
+    /// Goal description for an object, hidden until the object has been inspected
+    pub fn describe_goal(&self, object_id: ObjectId) -> Option<String> {
+        let obj = self.held_objects().chain(self.world.objects.iter()).find(|o| o.id == object_id)?;
+        if self.is_target_known(object_id) {
+            Some(language::describe_target(obj))
         } else {
-            Err(MoveError::InvalidTarget)
+            Some(format!("The {} has an unknown destination.", obj.display_name))
         }
     }
 }
+
+/// Why an agent cannot start on (x, y)
+fn check_start(layout: &Layout, x: usize, y: usize) -> Result<(), &'static str> {
+    if x >= layout.width || y >= layout.height {
+        return Err("Start position out of bounds");
+    }
+    if layout.kind_at(x, y).room().is_none() {
+        return Err("Start position is not navigable");
+    }
+    Ok(())
+}
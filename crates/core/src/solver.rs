@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::action::Action;
+use crate::cell::CellKind;
+use crate::goto::GoToError;
+use crate::nav;
+use crate::object::ObjectId;
+use crate::sim::Simulator;
+
+/// Why the oracle could not tidy every requested object
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    /// The object is neither in the world nor held
+    UnknownObject(ObjectId),
+    /// No reachable cell satisfies the object's target
+    NoTarget(ObjectId),
+    /// Walking to the object or its target failed
+    Route(ObjectId, GoToError),
+    /// Picking up, dropping or placing failed, or picked up a different object
+    Action(ObjectId, String),
+    /// The step budget ran out; carries the primitive steps taken
+    StepLimit(u32),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownObject(id) => write!(f, "unknown object {}", id),
+            SolveError::NoTarget(id) => write!(f, "object {} has no reachable target", id),
+            SolveError::Route(id, e) => write!(f, "could not walk for object {}: {}", id, e),
+            SolveError::Action(id, e) => write!(f, "could not handle object {}: {}", id, e),
+            SolveError::StepLimit(steps) => write!(f, "step limit reached after {} steps", steps),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Scripted oracle: tidy the given objects one at a time, nearest first.
+///
+/// Walks with the `go_to_*` macros, picks up by interacting with the agent's own cell, and
+/// places into a container when that satisfies the target (dropping otherwise). With
+/// `closed_containers` on it opens the containers it takes from or puts into; with
+/// `blocking_objects` on it works on cells of blocking objects from next to them. Objects
+/// already in place are left alone. Returns the primitive steps taken.
+///
+/// Pick-up takes the first pickable object on a cell, so each goal object must share its
+/// cell only with other goal objects.
+pub fn solve(sim: &mut Simulator, objects: &[ObjectId], max_steps: u32) -> Result<u32, SolveError> {
+    let mut steps = 0;
+    loop {
+        if let Some(held) = sim.holding.as_ref().map(|o| o.id) {
+            steps += put_away(sim, held)?;
+        } else {
+            let w = sim.world.layout.width;
+            let dist = nav::distance_field(&sim.world.layout, &[sim.agent.y * w + sim.agent.x]);
+            let mut pending = Vec::new();
+            for &id in objects {
+                // thrown away goals are done; they can no longer be picked up
+                if sim.world.disposed_object(id).is_some() {
+                    continue;
+                }
+                let obj = sim.world.object(id).ok_or(SolveError::UnknownObject(id))?;
+                if !obj.check_placement(&sim.world) {
+                    pending.push((dist[obj.y * w + obj.x].unwrap_or(usize::MAX), id));
+                }
+            }
+            let Some(&(_, id)) = pending.iter().min() else { return Ok(steps) };
+            steps += sim.go_to_object(id).map_err(|e| SolveError::Route(id, e))?.primitive_steps;
+            while let Some(container) = sim.closed_container_of(id) {
+                sim.toggle_container(container).map_err(|e| SolveError::Action(id, e.to_string()))?;
+                steps += 1;
+            }
+            let obj = sim.world.object(id).ok_or(SolveError::UnknownObject(id))?;
+            let (dx, dy) = offset_to(sim, obj.x, obj.y);
+            sim.interact(dx as isize, dy as isize).map_err(|e| SolveError::Action(id, e.to_string()))?;
+            steps += 1;
+            // several goals can share a cell (e.g. a bag of groceries); any of them will do
+            if !sim.holding.as_ref().is_some_and(|o| objects.contains(&o.id)) {
+                return Err(SolveError::Action(id, "picked up a different object".into()));
+            }
+        }
+        if steps > max_steps {
+            return Err(SolveError::StepLimit(steps));
+        }
+    }
+}
+
+/// Carry the held object to the nearest cell satisfying its target and put it down there
+fn put_away(sim: &mut Simulator, id: ObjectId) -> Result<u32, SolveError> {
+    let cells = put_cells(sim).ok_or(SolveError::NoTarget(id))?;
+    let outcome = sim.follow_route(&approach(sim, &cells)).map_err(|e| match e {
+        GoToError::Unreachable => SolveError::NoTarget(id),
+        e => SolveError::Route(id, e),
+    })?;
+    let mut steps = outcome.primitive_steps + 1;
+    let (x, y) = work_cell(sim, &cells).ok_or(SolveError::NoTarget(id))?;
+    if let Some(closed) = closed_container_at(sim, x, y) {
+        sim.toggle_container(closed).map_err(|e| SolveError::Action(id, e.to_string()))?;
+        steps += 1;
+    }
+    match put_action(sim, x, y) {
+        Action::PlaceInto(c) => sim.place_into(c).map_err(|e| SolveError::Action(id, e.to_string()))?,
+        Action::AutoPlace => sim.auto_place().map_err(|e| SolveError::Action(id, e.to_string()))?,
+        _ => sim.drop().map_err(|e| SolveError::Action(id, e.to_string()))?,
+    }
+    Ok(steps)
+}
+
+/// Room cells satisfying the held object's target
+fn put_cells(sim: &Simulator) -> Option<Vec<usize>> {
+    let target = sim.holding.as_ref()?.target()?;
+    let layout = &sim.world.layout;
+    let w = layout.width;
+    Some((0..layout.cells.len()).filter(|&i| layout.cells[i] >= 0 && target.check(&sim.world, i % w, i / w)).collect())
+}
+
+/// Cells to walk to for working on `cells`: each cell itself, or the cells next to it when
+/// a blocking object keeps the agent off it
+fn approach(sim: &Simulator, cells: &[usize]) -> Vec<usize> {
+    let layout = &sim.world.layout;
+    let mut goals = Vec::new();
+    for &i in cells {
+        if sim.blocking_object_at(i % layout.width, i / layout.width).is_none() {
+            goals.push(i);
+        } else {
+            goals.extend(nav::neighbors(layout, i));
+        }
+    }
+    goals
+}
+
+/// The cell of `cells` worked on from where the agent stands: its own, or a neighbouring one
+/// a blocking object keeps it off
+fn work_cell(sim: &Simulator, cells: &[usize]) -> Option<(usize, usize)> {
+    let layout = &sim.world.layout;
+    let w = layout.width;
+    let agent = sim.agent.y * w + sim.agent.x;
+    if cells.contains(&agent) && sim.blocking_object_at(sim.agent.x, sim.agent.y).is_none() {
+        return Some((sim.agent.x, sim.agent.y));
+    }
+    nav::neighbors(layout, agent)
+        .find(|&n| cells.contains(&n) && sim.blocking_object_at(n % w, n / w).is_some())
+        .map(|n| (n % w, n / w))
+}
+
+/// Offset from the agent to the cell (x, y), which is on or next to it
+fn offset_to(sim: &Simulator, x: usize, y: usize) -> (i8, i8) {
+    (x as i8 - sim.agent.x as i8, y as i8 - sim.agent.y as i8)
+}
+
+/// How to put the held object on the cell (x, y): into a container there satisfying its
+/// target, else dropped; a neighbouring cell is dropped onto with `auto_place`, which finds
+/// a target cell next to the agent
+fn put_action(sim: &Simulator, x: usize, y: usize) -> Action {
+    let preview = sim.drop_preview();
+    match preview.containers.iter().find(|c| c.satisfies_target && (c.x, c.y) == (x, y)) {
+        Some(c) => Action::PlaceInto(c.id),
+        None if (x, y) == (sim.agent.x, sim.agent.y) => Action::Drop,
+        None => Action::AutoPlace,
+    }
+}
+
+/// A closed container with room on the cell (x, y), when no open one there takes the held
+/// object; opening it lets the oracle place into it as it would with every container open
+fn closed_container_at(sim: &Simulator, x: usize, y: usize) -> Option<ObjectId> {
+    if sim.drop_preview().containers.iter().any(|c| (c.x, c.y) == (x, y)) {
+        return None;
+    }
+    sim.world.objects_at(x, y).into_iter().find(|o| o.has_room() && !sim.is_open(o)).map(|o| o.id)
+}
+
+/// The oracle one primitive action at a time: the action `solve` would take next.
+///
+/// Follows the same plan as `solve`, replanned from the current state on every call, so
+/// it copes with whatever happened since. Returns `None` when every object is in place
+/// and nothing is held, or when the next object or target cannot be reached.
+pub fn next_action(sim: &Simulator, objects: &[ObjectId]) -> Option<Action> {
+    let layout = &sim.world.layout;
+    let w = layout.width;
+    let agent = sim.agent.y * w + sim.agent.x;
+    let mut next_object = None;
+    let goals: Vec<usize> = match &sim.holding {
+        Some(_) => approach(sim, &put_cells(sim)?),
+        None => {
+            let dist = nav::distance_field(layout, &[agent]);
+            let pending = sim
+                .world
+                .objects
+                .iter()
+                .filter(|o| objects.contains(&o.id) && !o.check_placement(&sim.world))
+                .filter_map(|o| Some((dist[o.y * w + o.x]?, o.y * w + o.x, o.id)))
+                .min()?;
+            next_object = Some(pending.2);
+            approach(sim, &[pending.1])
+        }
+    };
+    if goals.contains(&agent) {
+        if let Some(id) = next_object {
+            let obj = sim.world.object(id)?;
+            let (dx, dy) = offset_to(sim, obj.x, obj.y);
+            return Some(sim.closed_container_of(id).map_or(Action::Interact { dx, dy }, Action::ToggleContainer));
+        }
+        let (x, y) = work_cell(sim, &put_cells(sim)?)?;
+        if let Some(closed) = closed_container_at(sim, x, y) {
+            return Some(Action::ToggleContainer(closed));
+        }
+        return Some(put_action(sim, x, y));
+    }
+    let next = *sim.walking_route(agent, &goals)?.first()?;
+    let (dx, dy) = ((next % w) as i8 - sim.agent.x as i8, (next / w) as i8 - sim.agent.y as i8);
+    if layout.kind_at(next % w, next / w) == CellKind::ClosedDoor {
+        return Some(Action::Interact { dx, dy });
+    }
+    Some(match (dx, dy) {
+        (0, -1) => Action::MoveUp,
+        (0, 1) => Action::MoveDown,
+        (-1, 0) => Action::MoveLeft,
+        _ => Action::MoveRight,
+    })
+}
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, VecDeque};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::object::Object;
+use crate::world::World;
+use crate::nav;
+use crate::cell::CellKind;
+
+/// Summary statistics of a single world
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorldStats {
+    pub width: usize,
+    pub height: usize,
+    pub room_count: usize,
+    /// Floor cells per room, indexed by room id
+    pub room_areas: Vec<usize>,
+    /// Number of door segments (connected runs of door cells)
+    pub door_count: usize,
+    pub object_count: usize,
+    /// Object count per schema name
+    pub objects_by_name: BTreeMap<String, usize>,
+    /// `contents / capacity` for every container
+    pub container_fill: Vec<f32>,
+    /// Fraction of pickable objects in their target placement (1 when there are none)
+    pub tidy_score: f32,
+    /// Fraction of pickable objects lying loose on the floor with no wall, door or furniture
+    /// next to them (0 when there are none)
+    pub stranded_fraction: f32,
+    /// Longest shortest walking path between two floor or door cells
+    pub diameter: usize,
+}
+
+/// Mean, extremes and percentiles of one quantity over many worlds
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Summary {
+    pub mean: f32,
+    pub min: f32,
+    pub max: f32,
+    pub p10: f32,
+    pub p50: f32,
+    pub p90: f32,
+}
+
+impl Summary {
+    /// Summarize a list of values; all fields are 0 when it is empty
+    pub fn of(mut values: Vec<f32>) -> Self {
+        if values.is_empty() {
+            return Summary::default();
+        }
+        values.sort_by(f32::total_cmp);
+        // nearest-rank percentile
+        let pct = |p: f32| values[((p * values.len() as f32).ceil() as usize).clamp(1, values.len()) - 1];
+        Summary {
+            mean: values.iter().sum::<f32>() / values.len() as f32,
+            min: values[0],
+            max: values[values.len() - 1],
+            p10: pct(0.1),
+            p50: pct(0.5),
+            p90: pct(0.9),
+        }
+    }
+}
+
+/// Statistics over a collection of worlds
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AggregateStats {
+    pub worlds: usize,
+    pub room_count: Summary,
+    /// Over every room of every world
+    pub room_area: Summary,
+    pub door_count: Summary,
+    pub object_count: Summary,
+    /// Over every container of every world
+    pub container_fill: Summary,
+    pub tidy_score: Summary,
+    pub stranded_fraction: Summary,
+    pub diameter: Summary,
+    /// Total object count per schema name
+    pub objects_by_name: BTreeMap<String, usize>,
+}
+
+/// Compute summary statistics for a world.
+///
+/// Only reads the world, so it can run on a shared `&World` from many threads. Besides the
+/// returned data it allocates a few scratch buffers, reused across every cell.
+pub fn world_stats(world: &World) -> WorldStats {
+    let layout = &world.layout;
+    let (w, h) = (layout.width, layout.height);
+    let mut room_areas = vec![0; layout.room_names.len()];
+    for &c in &layout.cells {
+        if c >= 0 {
+            room_areas[c as usize] += 1;
+        }
+    }
+
+    let is_door = |c| CellKind::from_i8(c).is_door();
+    let mut seen = vec![false; w * h];
+    let mut door_count = 0;
+    for start in 0..w * h {
+        if seen[start] || !is_door(layout.cells[start]) {
+            continue;
+        }
+        door_count += 1;
+        seen[start] = true;
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            let (x, y) = (i % w, i / w);
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (y > 0).then(|| i - w),
+                (y + 1 < h).then(|| i + w),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if !seen[n] && is_door(layout.cells[n]) {
+                    seen[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+    }
+
+    let mut objects_by_name = BTreeMap::new();
+    for o in &world.objects {
+        *objects_by_name.entry(o.name.to_string()).or_insert(0) += 1;
+    }
+    let container_fill = world
+        .objects
+        .iter()
+        .filter(|o| o.capacity > 0)
+        .map(|o| o.contents.len() as f32 / o.capacity as f32)
+        .collect();
+    let pickable = world.objects.iter().filter(|o| o.pickable).count();
+    let tidy = world.objects.iter().filter(|o| o.pickable && o.check_placement(world)).count();
+    let tidy_score = if pickable == 0 { 1.0 } else { tidy as f32 / pickable as f32 };
+    let stranded = world.objects.iter().filter(|o| o.pickable && is_stranded(world, o)).count();
+    let stranded_fraction = if pickable == 0 { 0.0 } else { stranded as f32 / pickable as f32 };
+
+    let (mut dist, mut queue) = (Vec::new(), VecDeque::new());
+    let mut diameter = 0;
+    for i in (0..w * h).filter(|&i| nav::walkable(layout, i)) {
+        nav::distance_field_into(layout, &[i], &mut dist, &mut queue);
+        diameter = dist.iter().flatten().fold(diameter, |a, &d| a.max(d));
+    }
+
+    WorldStats {
+        width: w,
+        height: h,
+        room_count: room_areas.iter().filter(|&&a| a > 0).count(),
+        room_areas,
+        door_count,
+        object_count: world.objects.len(),
+        objects_by_name,
+        container_fill,
+        tidy_score,
+        stranded_fraction,
+        diameter,
+    }
+}
+
+/// Loose on the floor, with every 4-neighbour a room cell free of furniture
+fn is_stranded(world: &World, object: &Object) -> bool {
+    if world.parent_index(object.id).is_some() {
+        return false;
+    }
+    let (w, h) = (world.layout.width, world.layout.height);
+    let neighbors = [
+        (object.x > 0).then(|| (object.x - 1, object.y)),
+        (object.x + 1 < w).then(|| (object.x + 1, object.y)),
+        (object.y > 0).then(|| (object.x, object.y - 1)),
+        (object.y + 1 < h).then(|| (object.x, object.y + 1)),
+    ];
+    neighbors.iter().all(|n| match *n {
+        Some((x, y)) => {
+            world.layout.cells[y * w + x] >= 0 && world.objects_at(x, y).iter().all(|o| o.pickable)
+        }
+        None => false,
+    })
+}
+
+/// Aggregate per-world statistics into distribution summaries
+pub fn aggregate<'a>(stats: impl IntoIterator<Item = &'a WorldStats>) -> AggregateStats {
+    let mut out = AggregateStats::default();
+    let (mut rooms, mut areas, mut doors, mut objects, mut fill, mut tidy, mut stranded, mut diameter) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for s in stats {
+        out.worlds += 1;
+        rooms.push(s.room_count as f32);
+        areas.extend(s.room_areas.iter().filter(|&&a| a > 0).map(|&a| a as f32));
+        doors.push(s.door_count as f32);
+        objects.push(s.object_count as f32);
+        fill.extend_from_slice(&s.container_fill);
+        tidy.push(s.tidy_score);
+        stranded.push(s.stranded_fraction);
+        diameter.push(s.diameter as f32);
+        for (name, count) in &s.objects_by_name {
+            *out.objects_by_name.entry(name.clone()).or_insert(0) += count;
+        }
+    }
+    out.room_count = Summary::of(rooms);
+    out.room_area = Summary::of(areas);
+    out.door_count = Summary::of(doors);
+    out.object_count = Summary::of(objects);
+    out.container_fill = Summary::of(fill);
+    out.tidy_score = Summary::of(tidy);
+    out.stranded_fraction = Summary::of(stranded);
+    out.diameter = Summary::of(diameter);
+    out
+}
@@ -0,0 +1,259 @@
+#[cfg(feature = "gen")]
+use rand::rngs::StdRng;
+#[cfg(feature = "gen")]
+use rand::seq::SliceRandom;
+#[cfg(feature = "gen")]
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "gen")]
+use crate::cell::CellKind;
+use crate::world::World;
+#[cfg(feature = "language")]
+use crate::language;
+#[cfg(feature = "gen")]
+use crate::nav;
+use crate::object::ObjectId;
+#[cfg(any(feature = "gen", feature = "sim"))]
+use crate::object::ObjectConstraint;
+#[cfg(feature = "gen")]
+use crate::object::ObjectSchema;
+#[cfg(feature = "sim")]
+use crate::sim::Simulator;
+#[cfg(feature = "sim")]
+use crate::solver;
+
+/// Schema of the temporary container delivery tasks start from; never placed by the general placement loop
+pub const GROCERY_BAG: &str = "GroceryBag";
+
+/// Schemas a grocery bag is filled from; each belongs inside a Refrigerator or Cupboard
+pub const GROCERIES: &[&str] =
+    &["MilkCarton", "Egg", "CerealBox", "BreadLoaf", "CheeseBlock", "YogurtCup", "JuiceBottle", "WaterBottle"];
+
+/// Step budget of the oracle run that bounds `TidyTask::max_actions`
+#[cfg(feature = "sim")]
+const TASK_ORACLE_STEPS: u32 = 10_000;
+
+/// Fewest and most items put in a grocery bag
+#[cfg(feature = "gen")]
+const BAG_ITEMS: (usize, usize) = (3, 5);
+
+/// What the agent is asked to do in a generated world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TaskKind {
+    /// Put every pickable object in its target placement
+    #[default]
+    Tidy,
+    /// Put away the groceries from a bag near the agent start, leaving the bag empty
+    Delivery,
+    /// Both: the delivery and every other pickable object
+    Mixed,
+}
+
+impl TaskKind {
+    /// Whether worlds of this kind get a grocery bag
+    pub fn has_delivery(self) -> bool {
+        matches!(self, TaskKind::Delivery | TaskKind::Mixed)
+    }
+
+    /// Objects the agent must put in place, e.g. for `solver::solve`
+    pub fn goals(self, world: &World, delivery: Option<&DeliveryTask>) -> Vec<ObjectId> {
+        match (self, delivery) {
+            (TaskKind::Delivery, Some(d)) => d.items.clone(),
+            (TaskKind::Delivery, None) => Vec::new(),
+            _ => world.objects.iter().filter(|o| o.pickable && o.target().is_some()).map(|o| o.id).collect(),
+        }
+    }
+
+    #[cfg(feature = "sim")]
+    /// Whether the task is complete: nothing held, every goal in place and, with a delivery, the bag empty
+    pub fn is_done(self, sim: &Simulator, delivery: Option<&DeliveryTask>) -> bool {
+        if sim.holding.is_some() {
+            return false;
+        }
+        if self.has_delivery() && !delivery.is_some_and(|d| d.is_done(sim)) {
+            return false;
+        }
+        let goals = self.goals(&sim.world, delivery);
+        goals.iter().all(|&id| sim.world.is_in_place(id))
+    }
+}
+
+/// A grocery bag and the items that started in it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeliveryTask {
+    pub bag: ObjectId,
+    pub items: Vec<ObjectId>,
+}
+
+impl DeliveryTask {
+    /// Read the task back from a freshly generated world: its grocery bag and everything in it
+    pub fn from_world(world: &World) -> Option<Self> {
+        let bag = world.objects.iter().find(|o| o.name == GROCERY_BAG)?;
+        Some(DeliveryTask { bag: bag.id, items: bag.contents.clone() })
+    }
+
+    #[cfg(feature = "language")]
+    /// Instruction for the agent, e.g. "Put away the groceries from the bag by the door."
+    pub fn instructions(&self, world: &World) -> String {
+        let Some(bag) = world.object(self.bag) else {
+            return "Put away the groceries.".into();
+        };
+        if next_to_door(world, bag.x, bag.y) {
+            "Put away the groceries from the bag by the door.".into()
+        } else {
+            format!("Put away the groceries from the bag {}.", language::describe_location(world, bag.x, bag.y))
+        }
+    }
+
+    #[cfg(feature = "sim")]
+    /// Whether the bag is empty, nothing is held and every item is in its target placement
+    pub fn is_done(&self, sim: &Simulator) -> bool {
+        let world = &sim.world;
+        sim.holding.is_none()
+            && world.object(self.bag).is_some_and(|b| b.contents.is_empty())
+            && self.items.iter().all(|&id| world.is_in_place(id))
+    }
+}
+
+/// One misplaced object of a `TidyTask` and where it belongs
+#[cfg(feature = "sim")]
+#[derive(Debug, Clone)]
+pub struct Goal {
+    pub object_id: ObjectId,
+    pub target: ObjectConstraint,
+    /// Target in words, e.g. "inside a dishwasher"
+    pub description: String,
+}
+
+/// The objects to put away in a simulator's world, for reporting per-goal completion
+#[cfg(feature = "sim")]
+#[derive(Debug, Clone)]
+pub struct TidyTask {
+    /// One goal per pickable object out of place when the task was read, by ascending id
+    pub goals: Vec<Goal>,
+    /// Primitive steps the oracle (`solver::solve`) takes to complete every goal from the
+    /// state the task was read in, an upper bound on the optimum; `None` when it fails
+    pub max_actions: Option<u32>,
+}
+
+#[cfg(feature = "sim")]
+impl TidyTask {
+    /// Read the task from the simulator's current state, held object included.
+    ///
+    /// Descriptions name every target, whether or not the agent has inspected the object.
+    pub fn from_sim(sim: &Simulator) -> Self {
+        let mut goals: Vec<Goal> = sim
+            .world
+            .objects
+            .iter()
+            .filter(|o| o.pickable && !o.check_placement(&sim.world))
+            .chain(sim.holding.iter())
+            .filter_map(|o| {
+                let target = o.target()?.clone();
+                let description = language::describe_constraint(&target);
+                Some(Goal { object_id: o.id, target, description })
+            })
+            .collect();
+        goals.sort_by_key(|g| g.object_id);
+        let ids: Vec<ObjectId> = goals.iter().map(|g| g.object_id).collect();
+        // the oracle runs on a copy, so the simulator and its step counter stay untouched
+        let max_actions = if ids.is_empty() {
+            Some(0)
+        } else {
+            Simulator::new(sim.world.clone(), sim.agent.x, sim.agent.y).ok().and_then(|mut copy| {
+                copy.restore(&sim.state()).ok()?;
+                solver::solve(&mut copy, &ids, TASK_ORACLE_STEPS).ok()
+            })
+        };
+        TidyTask { goals, max_actions }
+    }
+
+    /// Per goal, whether its object sits in `world` where its target holds; a held object is not in place
+    pub fn completed(&self, world: &World) -> Vec<bool> {
+        self.goals
+            .iter()
+            .map(|g| world.objects.iter().find(|o| o.id == g.object_id).is_some_and(|o| g.target.check(world, o.x, o.y)))
+            .collect()
+    }
+
+    /// Whether every goal is complete in `world`
+    pub fn is_complete(&self, world: &World) -> bool {
+        self.completed(world).into_iter().all(|done| done)
+    }
+}
+
+#[cfg(any(feature = "gen", feature = "language"))]
+fn next_to_door(world: &World, x: usize, y: usize) -> bool {
+    let layout = &world.layout;
+    [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)].iter().any(|&(dx, dy)| {
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        nx >= 0
+            && ny >= 0
+            && (nx as usize) < layout.width
+            && (ny as usize) < layout.height
+            && layout.kind_at(nx as usize, ny as usize).is_door()
+    })
+}
+
+#[cfg(feature = "gen")]
+/// Put a grocery bag near the agent start (the first room cell) and fill it.
+///
+/// The bag goes on the free cell next to a door closest to the start, or the closest free
+/// cell when no door has one. Items are only chosen while some Refrigerator or Cupboard still
+/// has a slot for them, so a world without kitchen storage gets no bag. Returns the task,
+/// or why none was added.
+pub(crate) fn add_delivery(world: &mut World, rng: &mut StdRng) -> Result<DeliveryTask, String> {
+    let layout = &world.layout;
+    let start = layout.cells.iter().position(|&c| c >= 0).ok_or("no room cells")?;
+    let dist = nav::distance_field(layout, &[start]);
+    let free = |i: usize| {
+        let (x, y) = (i % layout.width, i / layout.width);
+        matches!(layout.kind_at(x, y), CellKind::Room(_)) && world.objects_at(x, y).is_empty()
+    };
+    let nearest = |by_door: bool| {
+        (0..layout.cells.len())
+            .filter(|&i| free(i) && (!by_door || next_to_door(world, i % layout.width, i / layout.width)))
+            .filter_map(|i| dist[i].map(|d| (d, i)))
+            .min()
+    };
+    let (_, cell) = nearest(true).or_else(|| nearest(false)).ok_or("no free cell reachable from the start")?;
+    let (x, y) = (cell % layout.width, cell / layout.width);
+
+    // free slots per container, reserved as items are chosen
+    let mut slots: Vec<usize> = world.objects.iter().map(|o| o.capacity.saturating_sub(o.contents.len())).collect();
+    let count = rng.gen_range(BAG_ITEMS.0..=BAG_ITEMS.1);
+    let mut items = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut pool = GROCERIES.to_vec();
+        pool.shuffle(rng);
+        let chosen = pool.into_iter().find_map(|name| {
+            let ObjectConstraint::InsideOf(targets) = &ObjectSchema::find(name)?.target else { return None };
+            let ci = world.objects.iter().zip(&slots).position(|(o, &s)| s > 0 && targets.contains(&o.name))?;
+            Some((name, ci))
+        });
+        let Some((name, ci)) = chosen else { break };
+        slots[ci] -= 1;
+        items.push(name);
+    }
+    if items.is_empty() {
+        return Err("no Refrigerator or Cupboard with room for groceries".into());
+    }
+
+    world
+        .transaction(|tx| {
+            let bag = tx.add_object(GROCERY_BAG, x, y)?;
+            let mut ids = Vec::with_capacity(items.len());
+            for name in &items {
+                let id = tx.add_object(name, x, y)?;
+                tx.place_into(id, bag)?;
+                ids.push(id);
+            }
+            Ok(DeliveryTask { bag, items: ids })
+        })
+        .map_err(|e| e.to_string())
+}
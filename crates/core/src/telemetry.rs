@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gen::{generate_inner, GenError, GenOpts, GenReport, GenTrace, SkipReason, SplitRejection, World};
+
+/// Counters accumulated over a batch of generations, for comparing generator settings.
+///
+/// Opt-in: feed it through `generate_batch` (or `record` with a report and trace) and read
+/// the totals, `report()` for a text summary, or serialize it for plotting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenTelemetry {
+    /// Worlds recorded
+    pub worlds: usize,
+    /// Extra attempts with derived seeds after a generation without rooms
+    pub retries: usize,
+    /// Worlds given up on after every attempt failed; not counted in `worlds`
+    pub failures: usize,
+    /// Accepted BSP splits
+    pub splits: usize,
+    /// Valid split lines summed over accepted splits
+    pub split_candidates: usize,
+    /// Rejected split lines by reason
+    pub split_rejections: BTreeMap<SplitRejection, usize>,
+    /// Number of carved doors by width in cells
+    pub door_widths: BTreeMap<usize, usize>,
+    /// Placement passes over the schema list
+    pub passes: usize,
+    pub placed_objects: usize,
+    /// Valid candidates summed over all placed objects
+    pub placement_candidates: usize,
+    /// Number of worlds in which each schema ran out of candidates
+    pub skipped_schemas: BTreeMap<String, usize>,
+    /// Skipped schemas by reason, summed over worlds
+    pub skip_reasons: BTreeMap<SkipReason, usize>,
+}
+
+impl GenTelemetry {
+    /// Add one generated world's report and layout trace
+    pub fn record(&mut self, report: &GenReport, trace: &GenTrace) {
+        self.worlds += 1;
+        self.splits += trace.splits.len();
+        self.split_candidates += trace.splits.iter().map(|s| s.candidates).sum::<usize>();
+        for r in &trace.rejected {
+            *self.split_rejections.entry(r.reason).or_default() += 1;
+        }
+        for door in &trace.doors {
+            *self.door_widths.entry(door.cells.len()).or_default() += 1;
+        }
+        self.passes += report.passes;
+        self.placed_objects += report.placed_objects;
+        self.placement_candidates += report.provenance.iter().map(|p| p.candidates_considered).sum::<usize>();
+        for (name, &reason) in report.exhausted_schemas.iter().zip(&report.exhausted_reasons) {
+            *self.skipped_schemas.entry(name.to_string()).or_default() += 1;
+            *self.skip_reasons.entry(reason).or_default() += 1;
+        }
+    }
+
+    /// Mean valid split lines per accepted split
+    pub fn mean_split_candidates(&self) -> f32 {
+        self.split_candidates as f32 / self.splits.max(1) as f32
+    }
+
+    /// Mean valid candidates per placed object
+    pub fn mean_placement_candidates(&self) -> f32 {
+        self.placement_candidates as f32 / self.placed_objects.max(1) as f32
+    }
+
+    /// Multi-line text summary of all counters
+    pub fn report(&self) -> String {
+        let per_world = |n: usize| n as f32 / self.worlds.max(1) as f32;
+        let mut out = String::new();
+        let _ = writeln!(out, "worlds: {} ({} retries, {} failures)", self.worlds, self.retries, self.failures);
+        let _ = writeln!(
+            out,
+            "splits: {:.2} per world, {:.2} candidates per split",
+            per_world(self.splits),
+            self.mean_split_candidates()
+        );
+        for (reason, n) in &self.split_rejections {
+            let _ = writeln!(out, "  rejected {:?}: {:.2} per world", reason, per_world(*n));
+        }
+        let doors: usize = self.door_widths.values().sum();
+        let _ = writeln!(out, "doors: {:.2} per world", per_world(doors));
+        for (width, n) in &self.door_widths {
+            let _ = writeln!(out, "  width {}: {:.1}%", width, 100.0 * *n as f32 / doors.max(1) as f32);
+        }
+        let _ = writeln!(
+            out,
+            "objects: {:.2} per world, {:.2} candidates per object, {:.2} passes per world",
+            per_world(self.placed_objects),
+            self.mean_placement_candidates(),
+            per_world(self.passes)
+        );
+        for (reason, n) in &self.skip_reasons {
+            let _ = writeln!(out, "  skipped {:?}: {:.2} schemas per world", reason, per_world(*n));
+        }
+        let mut skipped: Vec<_> = self.skipped_schemas.iter().collect();
+        skipped.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        for (name, n) in skipped.into_iter().take(10) {
+            let _ = writeln!(out, "  {}: skipped in {:.1}% of worlds", name, 100.0 * *n as f32 / self.worlds.max(1) as f32);
+        }
+        out
+    }
+}
+
+/// Generate `count` worlds with seeds `opts.seed`, `opts.seed + 1`, ...
+///
+/// A world that fails to generate (see `generate`) is retried up to `attempts - 1` times; retry `r` of world `i` uses
+/// seed `opts.seed + i + r * count`, so retries never repeat another world's seed. Worlds that
+/// fail every attempt are left out. When `telemetry` is given, every kept world is recorded in it.
+pub fn generate_batch(
+    opts: &GenOpts,
+    count: usize,
+    attempts: u32,
+    mut telemetry: Option<&mut GenTelemetry>,
+) -> Result<Vec<World>, GenError> {
+    opts.validate()?;
+    let mut worlds = Vec::with_capacity(count);
+    for i in 0..count {
+        let mut generated = None;
+        for retry in 0..attempts.max(1) as u64 {
+            let seed = opts.seed.wrapping_add(i as u64).wrapping_add(retry.wrapping_mul(count as u64));
+            let opts = GenOpts { seed, ..opts.clone() };
+            let trace = telemetry.is_some().then(|| GenTrace { width: opts.width, height: opts.height, ..Default::default() });
+            let result = generate_inner(&opts, trace);
+            if retry > 0 {
+                if let Some(t) = telemetry.as_deref_mut() {
+                    t.retries += 1;
+                }
+            }
+            if let Ok((world, report, trace)) = result {
+                if let (Some(t), Some(trace)) = (telemetry.as_deref_mut(), trace) {
+                    t.record(&report, &trace);
+                }
+                generated = Some(world);
+                break;
+            }
+        }
+        match generated {
+            Some(world) => worlds.push(world),
+            None => {
+                if let Some(t) = telemetry.as_deref_mut() {
+                    t.failures += 1;
+                }
+            }
+        }
+    }
+    Ok(worlds)
+}
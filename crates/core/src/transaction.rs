@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::world::World;
+use crate::object::{Object, ObjectId, ObjectSchema};
+
+/// Why a single transaction operation was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxOpError {
+    /// No object with this id exists
+    UnknownObject(ObjectId),
+    /// No schema with this name exists in the catalog
+    UnknownSchema(String),
+    /// The target cell is out of bounds or not a room cell
+    NotRoomCell { x: usize, y: usize },
+    /// The object has capacity 0
+    NotAContainer(ObjectId),
+    /// The container already holds as many objects as its capacity
+    ContainerFull(ObjectId),
+    /// Placing `id` into `container` would put an object inside itself
+    Cycle { id: ObjectId, container: ObjectId },
+}
+
+impl fmt::Display for TxOpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxOpError::UnknownObject(id) => write!(f, "unknown object {}", id),
+            TxOpError::UnknownSchema(name) => write!(f, "unknown schema '{}'", name),
+            TxOpError::NotRoomCell { x, y } => write!(f, "({}, {}) is not a room cell", x, y),
+            TxOpError::NotAContainer(id) => write!(f, "object {} is not a container", id),
+            TxOpError::ContainerFull(id) => write!(f, "container {} is full", id),
+            TxOpError::Cycle { id, container } => write!(f, "object {} cannot go into {}, which it contains", id, container),
+        }
+    }
+}
+
+/// Why a transaction was rolled back
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxError {
+    /// Operation number `index` (counting from 0) failed
+    Op { index: usize, error: TxOpError },
+    /// Every operation succeeded but the result failed `World::validate`
+    Invalid(String),
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxError::Op { index, error } => write!(f, "operation {} failed: {}", index, error),
+            TxError::Invalid(e) => write!(f, "invalid world after transaction: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TxError {}
+
+/// Pending edits to a world, made on a private copy and applied all at once by `commit`.
+///
+/// Every operation checks its arguments before changing anything and is numbered in call
+/// order, failed ones included, so a `TxError::Op` points at the offending call.
+#[derive(Debug, Clone)]
+pub struct WorldTx {
+    world: World,
+    ops: usize,
+}
+
+impl WorldTx {
+    /// Start a transaction over a copy of `world`
+    pub fn new(world: &World) -> Self {
+        WorldTx { world: world.clone(), ops: 0 }
+    }
+
+    /// The world with the operations so far applied
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Validate the edited world and return it
+    pub fn commit(self) -> Result<World, TxError> {
+        self.world.validate().map_err(TxError::Invalid)?;
+        Ok(self.world)
+    }
+
+    /// Number the next operation and run it
+    fn op<T>(&mut self, f: impl FnOnce(&mut World) -> Result<T, TxOpError>) -> Result<T, TxError> {
+        let index = self.ops;
+        self.ops += 1;
+        f(&mut self.world).map_err(|error| TxError::Op { index, error })
+    }
+
+    /// Add a new object of a catalog schema on a room cell; returns its id (one above the highest)
+    pub fn add_object(&mut self, schema: &str, x: usize, y: usize) -> Result<ObjectId, TxError> {
+        self.op(|world| {
+            let schema = ObjectSchema::find(schema).ok_or_else(|| TxOpError::UnknownSchema(schema.to_string()))?;
+            check_room_cell(world, x, y)?;
+            let id = world.objects.iter().map(|o| o.id + 1).max().unwrap_or(0);
+            world.add_object(Object::from_schema(id, schema, x, y));
+            Ok(id)
+        })
+    }
+
+    /// Move an object, with everything inside it, loose onto a room cell
+    pub fn move_object(&mut self, id: ObjectId, x: usize, y: usize) -> Result<(), TxError> {
+        self.op(|world| {
+            check_object(world, id)?;
+            check_room_cell(world, x, y)?;
+            if let Some(pi) = world.parent_index(id) {
+                world.objects[pi].contents.retain(|&c| c != id);
+            }
+            world.move_object(id, x, y);
+            Ok(())
+        })
+    }
+
+    /// Put an object, with everything inside it, into a container
+    pub fn place_into(&mut self, id: ObjectId, container: ObjectId) -> Result<(), TxError> {
+        self.op(|world| {
+            check_object(world, id)?;
+            let target = world.object(container).ok_or(TxOpError::UnknownObject(container))?;
+            if target.contents.contains(&id) {
+                return Ok(());
+            }
+            if target.capacity == 0 {
+                return Err(TxOpError::NotAContainer(container));
+            }
+            if !target.has_room() {
+                return Err(TxOpError::ContainerFull(container));
+            }
+            if id == container || world.contains_transitively(id, container) {
+                return Err(TxOpError::Cycle { id, container });
+            }
+            let (x, y) = (target.x, target.y);
+            if let Some(pi) = world.parent_index(id) {
+                world.objects[pi].contents.retain(|&c| c != id);
+            }
+            let ci = world.object_position(container).unwrap();
+            world.objects[ci].contents.push(id);
+            world.move_object(id, x, y);
+            Ok(())
+        })
+    }
+
+    /// Remove an object; anything it contained stays behind loose on the same cell
+    pub fn remove_object(&mut self, id: ObjectId) -> Result<(), TxError> {
+        self.op(|world| {
+            check_object(world, id)?;
+            world.detach_object(id);
+            Ok(())
+        })
+    }
+
+    /// Set or clear an object's custom label
+    pub fn set_label(&mut self, id: ObjectId, label: Option<String>) -> Result<(), TxError> {
+        self.op(|world| {
+            let idx = check_object(world, id)?;
+            world.objects[idx].label = label;
+            Ok(())
+        })
+    }
+}
+
+fn check_object(world: &World, id: ObjectId) -> Result<usize, TxOpError> {
+    world.object_position(id).ok_or(TxOpError::UnknownObject(id))
+}
+
+fn check_room_cell(world: &World, x: usize, y: usize) -> Result<(), TxOpError> {
+    let layout = &world.layout;
+    if x >= layout.width || y >= layout.height || layout.cells[y * layout.width + x] < 0 {
+        return Err(TxOpError::NotRoomCell { x, y });
+    }
+    Ok(())
+}
+
+impl World {
+    /// Apply several edits atomically.
+    ///
+    /// `edit` runs against a `WorldTx`; if it returns an error, or the result fails
+    /// `World::validate`, the world is left exactly as it was and the error is returned.
+    pub fn transaction<T>(&mut self, edit: impl FnOnce(&mut WorldTx) -> Result<T, TxError>) -> Result<T, TxError> {
+        let mut tx = WorldTx::new(self);
+        let value = edit(&mut tx)?;
+        *self = tx.commit()?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,274 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use crate::world::{tidiness_share, Cell};
+use crate::object::ObjectId;
+use crate::sim::Simulator;
+use crate::agent::Direction;
+
+use crate::cell::CellKind;
+use crate::OUTSIDE;
+
+/// Buffers of the last `Simulator::view`, shared by later views until the simulator changes
+/// what they hold, and the containment lookup of `Simulator::local_view`
+#[derive(Debug, Default)]
+pub(crate) struct ViewCache {
+    cells: OnceLock<Arc<[Cell]>>,
+    room_names: OnceLock<Arc<[Cow<'static, str>]>>,
+    objects: OnceLock<Arc<[ObjectLite]>>,
+    /// Position in `World::objects` of the container of every contained object
+    parents: OnceLock<HashMap<ObjectId, usize>>,
+}
+
+impl ViewCache {
+    /// Forget the cells and objects, after an action that may have toggled a door or moved
+    /// an object; room names only change with the world itself
+    pub(crate) fn clear_state(&mut self) {
+        self.cells = OnceLock::new();
+        self.objects = OnceLock::new();
+        self.parents = OnceLock::new();
+    }
+}
+
+/// Minimal per-object record stored in a `WorldView`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectLite {
+    pub id: ObjectId,
+    pub name: Cow<'static, str>,
+    pub x: usize,
+    pub y: usize,
+    pub pickable: bool,
+    /// Whether the object currently satisfies its target constraint
+    pub placed: bool,
+}
+
+/// Immutable, point-in-time snapshot of a simulator.
+///
+/// A view reflects the state at the moment `Simulator::view` was called and never
+/// changes afterwards; later simulator steps are only visible through a new view.
+/// All buffers are reference counted, so cloning a view or sending it to another
+/// thread is O(1) regardless of the world size.
+#[derive(Debug, Clone)]
+pub struct WorldView {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major cells, including the current open/closed state of every door
+    pub cells: Arc<[Cell]>,
+    pub room_names: Arc<[Cow<'static, str>]>,
+    pub objects: Arc<[ObjectLite]>,
+    pub agent_x: usize,
+    pub agent_y: usize,
+    pub holding: Option<ObjectId>,
+    /// Fraction of pickable objects (held one included) in their target placement
+    pub score: f32,
+    /// Successful actions taken so far
+    pub steps: u32,
+    /// Elapsed simulator time
+    pub time: u32,
+}
+
+impl WorldView {
+    /// Door state at a cell: `Some(true)` for open, `Some(false)` for closed, `None` if not a door
+    pub fn door_open(&self, x: usize, y: usize) -> Option<bool> {
+        match CellKind::from_i8(self.cells[y * self.width + x]) {
+            CellKind::OpenDoor => Some(true),
+            CellKind::ClosedDoor | CellKind::LockedDoor => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Objects located at the given cell
+    pub fn objects_at(&self, x: usize, y: usize) -> impl Iterator<Item = &ObjectLite> {
+        self.objects.iter().filter(move |o| o.x == x && o.y == y)
+    }
+}
+
+/// Egocentric observation of the cells and objects within a Chebyshev radius of the agent.
+///
+/// The default view is empty; pass one to `Simulator::local_view_into` to fill it, and keep
+/// it across steps so the buffers are reused.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LocalView {
+    pub radius: usize,
+    /// Row-major `(2 * radius + 1)²` cells centred on the agent; cells past the grid border are `OUTSIDE`
+    pub cells: Vec<Cell>,
+    /// `(id, dx, dy)` of every visible object relative to the agent, in world order
+    pub objects: Vec<(ObjectId, isize, isize)>,
+    pub holding: bool,
+}
+
+impl LocalView {
+    /// Side length of the square cell grid
+    pub fn side(&self) -> usize {
+        2 * self.radius + 1
+    }
+
+    /// Grow the view to `radius` with `OUTSIDE` cells around it, e.g. to the radius a
+    /// `local_view` asked for before it was capped at the grid; smaller radii keep it as is
+    pub fn pad_to(&mut self, radius: usize) {
+        if radius <= self.radius {
+            return;
+        }
+        let (inner, side, offset) = (self.side(), 2 * radius + 1, radius - self.radius);
+        self.cells.resize(side * side, OUTSIDE);
+        // rows only move towards the end, so copying the last one first overwrites nothing unread
+        for row in (0..inner).rev() {
+            self.cells.copy_within(row * inner..(row + 1) * inner, (row + offset) * side + offset);
+        }
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            let (x, y) = (i % side, i / side);
+            if !(offset..offset + inner).contains(&x) || !(offset..offset + inner).contains(&y) {
+                *cell = OUTSIDE;
+            }
+        }
+        self.radius = radius;
+    }
+
+    /// The view turned into the frame of an agent facing `facing`: ahead is up (negative dy)
+    /// and the agent's right is positive dx, see `Direction::to_agent_frame`
+    pub fn rotated(&self, facing: Direction) -> LocalView {
+        let mut view = LocalView::default();
+        self.rotate_into(facing, &mut view);
+        view
+    }
+
+    /// `rotated` written into `out`, reusing its buffers
+    pub fn rotate_into(&self, facing: Direction, out: &mut LocalView) {
+        let (r, side) = (self.radius as isize, self.side() as isize);
+        out.radius = self.radius;
+        out.holding = self.holding;
+        out.cells.clear();
+        for y in -r..=r {
+            for x in -r..=r {
+                let (dx, dy) = facing.from_agent_frame(x, y);
+                out.cells.push(self.cells[((dy + r) * side + dx + r) as usize]);
+            }
+        }
+        out.objects.clear();
+        out.objects.extend(self.objects.iter().map(|&(id, dx, dy)| {
+            let (x, y) = facing.to_agent_frame(dx, dy);
+            (id, x, y)
+        }));
+    }
+}
+
+impl Simulator {
+    /// Cells and objects within `radius` of the agent in both directions.
+    ///
+    /// Objects inside a container are left out unless `include_contained` is set, and even
+    /// then objects hidden in a closed container (see `Simulator::closed_containers`) are;
+    /// the container itself is always visible. The radius is capped at the larger grid side,
+    /// which already covers the whole grid from any cell, so `radius` of the view may be
+    /// smaller than asked for; `LocalView::pad_to` grows it back.
+    pub fn local_view(&self, radius: usize, include_contained: bool) -> LocalView {
+        let mut view = LocalView::default();
+        self.local_view_into(radius, include_contained, &mut view);
+        view
+    }
+
+    /// `local_view` written into `view`, reusing its buffers: once they have grown to the
+    /// radius and the number of visible objects, repeated calls do not allocate. Which
+    /// objects are contained is looked up once per world state, like the buffers of `view`
+    pub fn local_view_into(&self, radius: usize, include_contained: bool, view: &mut LocalView) {
+        let layout = &self.world.layout;
+        let radius = radius.min(layout.width.max(layout.height));
+        let (ax, ay) = (self.agent.x as isize, self.agent.y as isize);
+        let r = radius as isize;
+        view.radius = radius;
+        view.holding = self.holding.is_some();
+        view.cells.clear();
+        for y in ay - r..=ay + r {
+            for x in ax - r..=ax + r {
+                let inside = x >= 0 && y >= 0 && (x as usize) < layout.width && (y as usize) < layout.height;
+                view.cells.push(if inside { layout.cells[y as usize * layout.width + x as usize] } else { OUTSIDE });
+            }
+        }
+        let objects = &self.world.objects;
+        let parents = self.view_cache.parents.get_or_init(|| {
+            objects.iter().enumerate().flat_map(|(i, o)| o.contents.iter().map(move |&id| (id, i))).collect()
+        });
+        // whether a closed container encloses `id`, at any depth, as in `concealed`
+        let concealed = |mut id: ObjectId| {
+            while let Some(&pi) = parents.get(&id) {
+                if !self.is_open(&objects[pi]) {
+                    return true;
+                }
+                id = objects[pi].id;
+            }
+            false
+        };
+        view.objects.clear();
+        view.objects.extend(
+            objects
+                .iter()
+                .map(|o| (o.id, o.x as isize - ax, o.y as isize - ay))
+                .filter(|&(_, dx, dy)| dx.abs() <= r && dy.abs() <= r)
+                .filter(|&(id, _, _)| if include_contained { !concealed(id) } else { !parents.contains_key(&id) }),
+        );
+    }
+
+    /// Fraction of pickable objects in their target placement, counting held objects as
+    /// misplaced; the `score` of `view` without building the snapshot
+    pub fn tidiness(&self) -> f32 {
+        let (placed, total) = self.placement_counts();
+        tidiness_share(placed, total)
+    }
+
+    /// Pickables in their target placement and all pickables, held and disposed ones
+    /// included; `tidiness` is their ratio
+    pub(crate) fn placement_counts(&self) -> (usize, usize) {
+        let held = self.held_objects().count();
+        let (disposed, disposed_misplaced) = self.world.disposed_counts();
+        let total = self.world.objects.iter().filter(|o| o.pickable).count() + held + disposed;
+        (total - self.misplaced_objects().len() - disposed_misplaced, total)
+    }
+
+    /// `World::misplaced_objects` plus the objects the agents hold, ascending
+    pub fn misplaced_objects(&self) -> Vec<ObjectId> {
+        let mut ids = self.world.misplaced_objects();
+        ids.extend(self.held_objects().map(|o| o.id));
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Take an immutable snapshot that can be shared with other threads.
+    ///
+    /// Views share their cells, room names and objects with earlier ones until an action
+    /// other than a move or turn, a decay event or `restore` changes them; after editing
+    /// `world` directly, call `invalidate_caches`.
+    pub fn view(&self) -> WorldView {
+        let world = &self.world;
+        let objects = self.view_cache.objects.get_or_init(|| {
+            world
+                .objects
+                .iter()
+                .map(|o| ObjectLite {
+                    id: o.id,
+                    name: o.name.clone(),
+                    x: o.x,
+                    y: o.y,
+                    pickable: o.pickable,
+                    placed: o.check_placement(world),
+                })
+                .collect()
+        });
+        let (disposed, disposed_misplaced) = world.disposed_counts();
+        let total = objects.iter().filter(|o| o.pickable).count() + self.held_objects().count() + disposed;
+        let placed = objects.iter().filter(|o| o.pickable && o.placed).count() + disposed - disposed_misplaced;
+        let score = tidiness_share(placed, total);
+        WorldView {
+            width: world.layout.width,
+            height: world.layout.height,
+            cells: self.view_cache.cells.get_or_init(|| world.layout.cells.as_slice().into()).clone(),
+            room_names: self.view_cache.room_names.get_or_init(|| world.layout.room_names.as_slice().into()).clone(),
+            objects: objects.clone(),
+            agent_x: self.agent.x,
+            agent_y: self.agent.y,
+            holding: self.holding.as_ref().map(|o| o.id),
+            score,
+            steps: self.steps,
+            time: self.time,
+        }
+    }
+}
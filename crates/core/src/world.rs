@@ -0,0 +1,905 @@
+//! Layouts and worlds: the grid, its rooms and zones, and the objects placed in it.
+//!
+//! These types are shared by every feature; `gen` builds them, `sim` plays them.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Mutex, OnceLock};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::format::CompatError;
+use crate::cell::CellKind;
+use crate::object::{Object, ObjectId};
+
+use crate::{
+    OUTSIDE, WALL, CLOSED_DOOR, OPEN_DOOR
+};
+
+pub type Cell = i8;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "LayoutRepr"))]
+pub struct Layout {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Cell>,
+    pub room_names: Vec<Cow<'static, str>>,
+    /// Per-cell zone id (index into `zone_names`), `NO_ZONE` outside zoned rooms
+    pub zones: Vec<Cell>,
+    pub zone_names: Vec<Cow<'static, str>>,
+    /// Door segments; a door's index is its id
+    pub doors: Vec<Door>,
+    /// Cells per room, built from `cells` on the first room query
+    #[cfg_attr(feature = "serde", serde(skip))]
+    room_index: RoomIndex,
+}
+
+/// `Layout` as read back from JSON; layouts written before the door registry get theirs from `cells`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LayoutRepr {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    room_names: Vec<Cow<'static, str>>,
+    zones: Vec<Cell>,
+    zone_names: Vec<Cow<'static, str>>,
+    #[serde(default)]
+    doors: Option<Vec<Door>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<LayoutRepr> for Layout {
+    fn from(r: LayoutRepr) -> Self {
+        let mut layout = Layout { zones: r.zones, zone_names: r.zone_names, ..Layout::new(r.width, r.height, r.cells, r.room_names) };
+        if let Some(doors) = r.doors {
+            layout.doors = doors;
+        }
+        layout
+    }
+}
+
+/// A door segment: the door cells carved into one wall between two rooms
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Door {
+    pub id: usize,
+    /// Row-major flat indices
+    pub cells: Vec<usize>,
+    /// Room ids on either side, lower first; `OUTSIDE` stands in for a side without a room
+    pub rooms: (Cell, Cell),
+    /// Whether the door is open; locked doors are closed
+    pub open: bool,
+}
+
+/// Lazily built per-room cell lists and bounding boxes of a `Layout`
+#[derive(Clone, Default)]
+struct RoomIndex(OnceLock<Vec<RoomCells>>);
+
+#[derive(Clone)]
+struct RoomCells {
+    /// Row-major flat indices
+    cells: Vec<usize>,
+    /// (min x, min y, max x, max y), inclusive
+    bbox: (usize, usize, usize, usize),
+}
+
+impl std::fmt::Debug for RoomIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.get().is_some() { "RoomIndex(built)" } else { "RoomIndex(empty)" })
+    }
+}
+
+/// Zone id of cells that do not belong to any zone
+pub const NO_ZONE: Cell = -1;
+
+impl Layout {
+    pub fn new<N: Into<Cow<'static, str>>>(
+        width: usize,
+        height: usize,
+        cells: Vec<Cell>,
+        room_names: Vec<N>,
+    ) -> Self {
+        let room_names = room_names.into_iter().map(Into::into).collect();
+        let zones = vec![NO_ZONE; width * height];
+        let doors = find_doors(width, height, &cells);
+        Self { width, height, cells, room_names, zones, zone_names: Vec::new(), doors, room_index: RoomIndex::default() }
+    }
+
+    /// The door with a cell at the given flat index
+    pub fn door_at(&self, index: usize) -> Option<&Door> {
+        self.doors.iter().find(|d| d.cells.contains(&index))
+    }
+
+    /// Rooms reachable from `room` through one door, as (room id, door id) by door id
+    pub fn adjacent_rooms(&self, room: usize) -> Vec<(usize, usize)> {
+        self.doors
+            .iter()
+            .filter_map(|d| {
+                let other = match d.rooms {
+                    (a, b) if a as isize == room as isize => b,
+                    (a, b) if b as isize == room as isize => a,
+                    _ => return None,
+                };
+                usize::try_from(other).ok().map(|other| (other, d.id))
+            })
+            .collect()
+    }
+
+    /// Set every door's `open` flag from its cells, e.g. after restoring `cells`
+    #[cfg(feature = "sim")]
+    pub(crate) fn sync_doors(&mut self) {
+        for door in &mut self.doors {
+            door.open = door.cells.iter().all(|&i| self.cells[i] == OPEN_DOOR);
+        }
+    }
+
+    fn room_index(&self) -> &[RoomCells] {
+        self.room_index.0.get_or_init(|| {
+            let mut rooms: Vec<RoomCells> = Vec::new();
+            for (i, &c) in self.cells.iter().enumerate() {
+                let Ok(room) = usize::try_from(c) else { continue };
+                let (x, y) = (i % self.width, i / self.width);
+                if rooms.len() <= room {
+                    rooms.resize(room + 1, RoomCells { cells: Vec::new(), bbox: (usize::MAX, usize::MAX, 0, 0) });
+                }
+                let r = &mut rooms[room];
+                r.cells.push(i);
+                r.bbox = (r.bbox.0.min(x), r.bbox.1.min(y), r.bbox.2.max(x), r.bbox.3.max(y));
+            }
+            rooms
+        })
+    }
+
+    /// Forget the room index after changing room ids in `cells`; door changes keep it valid
+    pub fn clear_room_index(&mut self) {
+        self.room_index = RoomIndex::default();
+    }
+
+    /// Cells of a room as (x, y), row by row; empty for unknown rooms
+    pub fn room_cells(&self, room: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let width = self.width;
+        let cells = self.room_index().get(room).map_or(&[][..], |r| &r.cells[..]);
+        cells.iter().map(move |&i| (i % width, i / width))
+    }
+
+    /// Number of cells in a room; 0 for unknown rooms
+    pub fn room_area(&self, room: usize) -> usize {
+        self.room_index().get(room).map_or(0, |r| r.cells.len())
+    }
+
+    /// Inclusive bounding box (min x, min y, max x, max y) of a room; `None` if it has no cells
+    pub fn room_bbox(&self, room: usize) -> Option<(usize, usize, usize, usize)> {
+        self.room_index().get(room).filter(|r| !r.cells.is_empty()).map(|r| r.bbox)
+    }
+
+    /// Zone id at the given cell, if the cell lies in a zoned room
+    pub fn zone_at(&self, x: usize, y: usize) -> Option<usize> {
+        let zone = self.zones[y * self.width + x];
+        if zone < 0 { None } else { Some(zone as usize) }
+    }
+
+    /// Name of the room at the given cell; `None` for walls, doors, windows, outside cells
+    /// and cells off the grid
+    pub fn room_name_at(&self, x: usize, y: usize) -> Option<&str> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let room = usize::try_from(self.cells[y * self.width + x]).ok()?;
+        self.room_names.get(room).map(|name| &**name)
+    }
+}
+
+/// Share of `total` pickables that are `placed`; 1 when there are none
+pub(crate) fn tidiness_share(placed: usize, total: usize) -> f32 {
+    if total == 0 { 1.0 } else { placed as f32 / total as f32 }
+}
+
+/// Door segments of a grid: 4-connected runs of equal door cells, ordered by their first cell
+pub(crate) fn find_doors(width: usize, height: usize, cells: &[Cell]) -> Vec<Door> {
+    let is_door = |c: Cell| CellKind::from_i8(c).is_door();
+    let mut seen = vec![false; cells.len()];
+    let mut doors = Vec::new();
+    for start in 0..cells.len() {
+        if seen[start] || !is_door(cells[start]) {
+            continue;
+        }
+        seen[start] = true;
+        let (mut segment, mut stack, mut rooms) = (Vec::new(), vec![start], BTreeSet::new());
+        while let Some(i) = stack.pop() {
+            segment.push(i);
+            let (x, y) = (i % width, i / width);
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < width).then(|| i + 1),
+                (y > 0).then(|| i - width),
+                (y + 1 < height).then(|| i + width),
+            ];
+            for n in neighbors.into_iter().flatten() {
+                if cells[n] >= 0 {
+                    rooms.insert(cells[n]);
+                } else if !seen[n] && cells[n] == cells[start] {
+                    seen[n] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        segment.sort_unstable();
+        let mut sides = rooms.into_iter();
+        let (a, b) = (sides.next().unwrap_or(OUTSIDE), sides.next().unwrap_or(OUTSIDE));
+        let id = doors.len();
+        doors.push(Door { id, cells: segment, rooms: (a.min(b), a.max(b)), open: cells[start] == OPEN_DOOR });
+    }
+    doors
+}
+
+/// Separator between the component names of a merged open-plan room
+pub const COMPOUND_NAME_SEPARATOR: &str = " / ";
+
+/// Most bytes of distinct names `intern` leaks before `try_intern` refuses new ones, so
+/// untrusted schema files cannot grow the set without bound; worlds own their names and
+/// never intern
+pub const MAX_INTERNED_BYTES: usize = 1 << 20;
+
+/// Names leaked so far and their total length in bytes
+#[derive(Default)]
+struct Interner {
+    names: BTreeMap<String, &'static str>,
+    bytes: usize,
+}
+
+fn interner() -> std::sync::MutexGuard<'static, Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Leak `name` once and hand out the same `&'static str` for every later equal name, for the
+/// `&'static str` fields of `ObjectSchema`. Names from untrusted input go through `try_intern`.
+pub fn intern(name: String) -> &'static str {
+    let mut interner = interner();
+    if let Some(&interned) = interner.names.get(&name) {
+        return interned;
+    }
+    interner.bytes += name.len();
+    let interned: &'static str = Box::leak(name.clone().into_boxed_str());
+    interner.names.insert(name, interned);
+    interned
+}
+
+/// `intern`, or None for a name not seen before once `MAX_INTERNED_BYTES` would be exceeded
+pub fn try_intern(name: String) -> Option<&'static str> {
+    let interner = interner();
+    if let Some(&interned) = interner.names.get(&name) {
+        return Some(interned);
+    }
+    if interner.bytes + name.len() > MAX_INTERNED_BYTES {
+        return None;
+    }
+    drop(interner);
+    Some(intern(name))
+}
+
+/// Total length in bytes of the names interned so far
+pub fn interned_bytes() -> usize {
+    interner().bytes
+}
+
+/// Leak a list of names once, interning each, for `&'static [&'static str]` lists built at
+/// runtime; equal lists share one slice
+pub fn intern_names(names: Vec<String>) -> &'static [&'static str] {
+    intern_list(names.into_iter().map(intern).collect())
+}
+
+/// `intern_names` under the `try_intern` budget, which the list itself counts against
+pub fn try_intern_names(names: Vec<String>) -> Option<&'static [&'static str]> {
+    let names = names.into_iter().map(try_intern).collect::<Option<Vec<_>>>()?;
+    let len = names.len() * std::mem::size_of::<&str>();
+    if !list_interned(&names) && interned_bytes() + len > MAX_INTERNED_BYTES {
+        return None;
+    }
+    Some(intern_list(names))
+}
+
+static LISTS: OnceLock<Mutex<BTreeMap<Vec<&'static str>, &'static [&'static str]>>> = OnceLock::new();
+
+fn list_interned(names: &[&'static str]) -> bool {
+    LISTS.get_or_init(Default::default).lock().unwrap().contains_key(names)
+}
+
+fn intern_list(names: Vec<&'static str>) -> &'static [&'static str] {
+    let mut lists = LISTS.get_or_init(Default::default).lock().unwrap();
+    if let Some(&list) = lists.get(&names) {
+        return list;
+    }
+    interner().bytes += names.len() * std::mem::size_of::<&str>();
+    let list: &'static [&'static str] = Box::leak(names.clone().into_boxed_slice());
+    lists.insert(names, list);
+    list
+}
+
+
+/// Base names a room name stands for: the components of a merged open-plan room, each
+/// without the " 2", " 3", ... suffix `World::compose` adds to duplicate names
+pub fn room_name_parts(name: &str) -> impl Iterator<Item = &str> {
+    name.split(COMPOUND_NAME_SEPARATOR).map(|part| match part.rsplit_once(' ') {
+        Some((base, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => part,
+    })
+}
+
+/// A room name sign placed just inside one of the room's doors
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoomSign {
+    pub room: usize,
+    pub x: usize,
+    pub y: usize,
+    pub text: Cow<'static, str>,
+}
+
+impl Layout {
+    /// One sign per room that has a door, anchored at the first room cell (row-major) next to a door
+    pub fn room_signs(&self) -> Vec<RoomSign> {
+        let w = self.width;
+        let mut signs: Vec<RoomSign> = Vec::new();
+        for i in 0..self.cells.len() {
+            let room = self.cells[i];
+            if room < 0 || signs.iter().any(|s| s.room == room as usize) {
+                continue;
+            }
+            let (x, y) = (i % w, i / w);
+            let neighbors = [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (y > 0).then(|| i - w),
+                (y + 1 < self.height).then(|| i + w),
+            ];
+            if neighbors.into_iter().flatten().any(|n| matches!(self.cells[n], CLOSED_DOOR | OPEN_DOOR)) {
+                let text = self.room_names.get(room as usize).cloned().unwrap_or(Cow::Borrowed("unnamed room"));
+                signs.push(RoomSign { room: room as usize, x, y, text });
+            }
+        }
+        signs.sort_by_key(|s| s.room);
+        signs
+    }
+}
+
+/// Simulation world bundling layout and objects.
+///
+/// Generation assigns object ids 0, 1, 2, ... in placement order, which depends only on the
+/// options and seed, so ids are a stable key for a given world. Serializing with
+/// `serde` directly keeps the current vector order; `to_json`/`to_msgpack` canonicalize first.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct World {
+    pub layout: Layout,
+    /// Change through `add_object`, `detach_object` and `move_object`, which keep the object
+    /// index current; call `clear_object_index` after editing positions, ids or order directly
+    pub objects: Vec<Object>,
+    /// Objects thrown into a disposing container such as the trash can, in disposal order,
+    /// each at the cell it was thrown away on; no longer part of the world
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub disposed: Vec<Object>,
+    /// Objects by cell and by id, built on the first lookup
+    #[cfg_attr(feature = "serde", serde(skip))]
+    index: ObjectIndex,
+}
+
+/// Lazily built lookup tables over `World::objects`, boxed to keep `World` small
+#[derive(Clone, Default)]
+struct ObjectIndex(OnceLock<Box<ObjectMaps>>);
+
+#[derive(Clone)]
+struct ObjectMaps {
+    /// Ids of the objects on each cell, ascending
+    by_cell: HashMap<(usize, usize), Vec<ObjectId>>,
+    /// Position of each id in `objects`
+    by_id: HashMap<ObjectId, usize>,
+}
+
+impl std::fmt::Debug for ObjectIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.get().is_some() { "ObjectIndex(built)" } else { "ObjectIndex(empty)" })
+    }
+}
+
+impl ObjectMaps {
+    fn build(objects: &[Object]) -> Self {
+        let mut maps = ObjectMaps { by_cell: HashMap::new(), by_id: HashMap::with_capacity(objects.len()) };
+        for (i, o) in objects.iter().enumerate() {
+            // with duplicate ids (see `World::validate`) the first one wins, as in a linear search
+            maps.by_id.entry(o.id).or_insert(i);
+            maps.place(o.id, (o.x, o.y));
+        }
+        maps
+    }
+
+    fn place(&mut self, id: ObjectId, cell: (usize, usize)) {
+        let ids = self.by_cell.entry(cell).or_default();
+        let at = ids.partition_point(|&other| other < id);
+        ids.insert(at, id);
+    }
+
+    fn unplace(&mut self, id: ObjectId, cell: (usize, usize)) {
+        if let Some(ids) = self.by_cell.get_mut(&cell) {
+            ids.retain(|&other| other != id);
+            if ids.is_empty() {
+                self.by_cell.remove(&cell);
+            }
+        }
+    }
+}
+
+/// A single difference between two worlds, as reported by `World::diff`
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldDiff {
+    /// Grid dimensions differ; no cell-level comparison is made
+    Dimensions,
+    Cell { index: usize, before: Cell, after: Cell },
+    RoomName { room: usize, before: Cow<'static, str>, after: Cow<'static, str> },
+    ObjectAdded(ObjectId),
+    ObjectRemoved(ObjectId),
+    ObjectMoved { id: ObjectId, from: (usize, usize), to: (usize, usize) },
+    ContentsChanged(ObjectId),
+}
+
+/// Why `World::compose` could not combine two worlds
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposeError {
+    /// A cell of the second world lands on a non-outside cell of the first; shared walls are allowed
+    Overlap { x: usize, y: usize },
+    /// The composite would have more rooms or zones than a cell id can address
+    TooManyRooms(usize),
+    /// `connect` was set but no wall separates a free cell of one world from a free cell of the other
+    NoSharedWall,
+}
+
+impl ComposeError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            ComposeError::Overlap { .. } => "overlap",
+            ComposeError::TooManyRooms(_) => "too_many_rooms",
+            ComposeError::NoSharedWall => "no_shared_wall",
+        }
+    }
+}
+
+impl std::fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComposeError::Overlap { x, y } => write!(f, "worlds overlap at ({}, {})", x, y),
+            ComposeError::TooManyRooms(n) => write!(f, "composite would have {} rooms or zones; at most {} fit", n, Cell::MAX as usize + 1),
+            ComposeError::NoSharedWall => write!(f, "no wall between the worlds can take a connecting door"),
+        }
+    }
+}
+
+impl std::error::Error for ComposeError {}
+
+/// Where the parts of the second world ended up in a composite, see `World::compose`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComposeMap {
+    /// Object ids of the second world, disposed ones included, mapped to their ids in the
+    /// composite
+    pub objects: BTreeMap<ObjectId, ObjectId>,
+    /// Composite room id of each room of the second world, by its original id
+    pub rooms: Vec<Cell>,
+    /// Cells carved into the connecting door, empty unless `connect` was set
+    pub door: Vec<(usize, usize)>,
+}
+
+impl World {
+    pub fn new(layout: Layout, objects: Vec<Object>) -> Self {
+        World { layout, objects, disposed: Vec::new(), index: ObjectIndex::default() }
+    }
+
+    fn object_maps(&self) -> &ObjectMaps {
+        self.index.0.get_or_init(|| Box::new(ObjectMaps::build(&self.objects)))
+    }
+
+    /// Forget the object index after changing `objects` other than through the `World`
+    /// mutation methods
+    pub fn clear_object_index(&mut self) {
+        self.index = ObjectIndex::default();
+    }
+
+    /// The object with the given id, if it is in the world (held objects are not)
+    pub fn object(&self, id: ObjectId) -> Option<&Object> {
+        self.object_position(id).map(|i| &self.objects[i])
+    }
+
+    /// Position of an object in `objects`
+    pub(crate) fn object_position(&self, id: ObjectId) -> Option<usize> {
+        self.object_maps().by_id.get(&id).copied()
+    }
+
+    /// Add an object at its `x`, `y`; the caller links it into a container's `contents`
+    pub fn add_object(&mut self, object: Object) {
+        if let Some(maps) = self.index.0.get_mut() {
+            maps.by_id.entry(object.id).or_insert(self.objects.len());
+            maps.place(object.id, (object.x, object.y));
+        }
+        self.objects.push(object);
+    }
+
+    /// Check structural invariants: object ids are unique, objects sit on room cells,
+    /// no two fixtures (non-pickable objects) share a cell, container contents reference
+    /// existing objects at the container's position, no object is contained twice and no
+    /// container holds more than its capacity. Pickables may share a cell; see `objects_at`.
+    pub fn validate(&self) -> Result<(), String> {
+        let layout = &self.layout;
+        if layout.cells.len() != layout.width * layout.height {
+            return Err("Layout cells length does not match dimensions".into());
+        }
+        let mut ids = BTreeSet::new();
+        for o in &self.objects {
+            if !ids.insert(o.id) {
+                return Err(format!("Duplicate object id {}", o.id));
+            }
+            if o.x >= layout.width || o.y >= layout.height || layout.cells[o.y * layout.width + o.x] < 0 {
+                return Err(format!("Object {} is not on a room cell", o.id));
+            }
+        }
+        let mut fixtures = BTreeMap::new();
+        for o in self.objects.iter().filter(|o| !o.pickable) {
+            if let Some(other) = fixtures.insert((o.x, o.y), o.id) {
+                return Err(format!("Fixtures {} and {} share cell ({}, {})", other, o.id, o.x, o.y));
+            }
+        }
+        let mut contained = BTreeSet::new();
+        for o in &self.objects {
+            if o.contents.len() > o.capacity {
+                return Err(format!("Object {} holds {} objects but has capacity {}", o.id, o.contents.len(), o.capacity));
+            }
+            for &cid in &o.contents {
+                let inner = self
+                    .objects
+                    .iter()
+                    .find(|c| c.id == cid)
+                    .ok_or_else(|| format!("Object {} contains unknown object {}", o.id, cid))?;
+                if inner.x != o.x || inner.y != o.y {
+                    return Err(format!("Object {} is not at the position of its container {}", cid, o.id));
+                }
+                if !contained.insert(cid) {
+                    return Err(format!("Object {} is contained more than once", cid));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the world in canonical form: objects sorted by id and every `contents` sorted.
+    /// Room names are left as they are, since `InRoomNamed` targets match them exactly.
+    ///
+    /// Worlds with the same layout and object placements serialize identically afterwards,
+    /// however their vectors were ordered.
+    pub fn canonicalize(&mut self) {
+        self.objects.sort_by_key(|o| o.id);
+        self.clear_object_index();
+        for o in &mut self.objects {
+            o.contents.sort_unstable();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    /// Canonical JSON of the world with a `format` header, see `canonicalize`
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let mut world = self.clone();
+        world.canonicalize();
+        crate::format::to_json(&world)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Canonical msgpack of the world with a `format` header, see `canonicalize`
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        let mut world = self.clone();
+        world.canonicalize();
+        crate::format::to_msgpack(&world)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a world written by `to_json`, by this or an older build; it must pass `validate`.
+    ///
+    /// Objects keep the targets they were saved with, so worlds of custom schemas round-trip;
+    /// worlds saved before targets were stored take them from the built-in catalog.
+    pub fn from_json(json: &str) -> Result<World, CompatError> {
+        let world: World = crate::format::from_json(json)?;
+        world.validate().map_err(CompatError::Malformed)?;
+        Ok(world)
+    }
+
+    /// List the differences between this world and `other`
+    pub fn diff(&self, other: &World) -> Vec<WorldDiff> {
+        let mut diffs = Vec::new();
+        let (a, b) = (&self.layout, &other.layout);
+        if a.width != b.width || a.height != b.height {
+            diffs.push(WorldDiff::Dimensions);
+        } else {
+            for (index, (&before, &after)) in a.cells.iter().zip(&b.cells).enumerate() {
+                if before != after {
+                    diffs.push(WorldDiff::Cell { index, before, after });
+                }
+            }
+        }
+        for room in 0..a.room_names.len().max(b.room_names.len()) {
+            let before = a.room_names.get(room).cloned().unwrap_or_default();
+            let after = b.room_names.get(room).cloned().unwrap_or_default();
+            if before != after {
+                diffs.push(WorldDiff::RoomName { room, before, after });
+            }
+        }
+        for o in &self.objects {
+            match other.objects.iter().find(|p| p.id == o.id) {
+                None => diffs.push(WorldDiff::ObjectRemoved(o.id)),
+                Some(p) => {
+                    if (o.x, o.y) != (p.x, p.y) {
+                        diffs.push(WorldDiff::ObjectMoved { id: o.id, from: (o.x, o.y), to: (p.x, p.y) });
+                    }
+                    if o.contents != p.contents {
+                        diffs.push(WorldDiff::ContentsChanged(o.id));
+                    }
+                }
+            }
+        }
+        for p in &other.objects {
+            if self.objects.iter().all(|o| o.id != p.id) {
+                diffs.push(WorldDiff::ObjectAdded(p.id));
+            }
+        }
+        diffs
+    }
+
+    /// Objects on a cell in ascending id order.
+    ///
+    /// A cell can hold a container with its contents, and any number of loose pickables
+    /// dropped onto it. Ascending id is the one tie-break for all of them: `pick_up` and
+    /// `interact` take the first pickable, placing onto a cell uses the first container
+    /// with room, renderers draw the first object on top and the bindings list a cell's
+    /// objects in this order.
+    pub fn objects_at(&self, x: usize, y: usize) -> Vec<&Object> {
+        self.objects_at_iter(x, y).collect()
+    }
+
+    /// `objects_at` without collecting, for checks run on every step
+    pub fn objects_at_iter(&self, x: usize, y: usize) -> impl Iterator<Item = &Object> {
+        let maps = self.object_maps();
+        maps.by_cell.get(&(x, y)).into_iter().flatten().map(|id| &self.objects[maps.by_id[id]])
+    }
+
+    /// Index of the container holding `id`, if any
+    pub(crate) fn parent_index(&self, id: ObjectId) -> Option<usize> {
+        self.objects.iter().position(|o| o.contents.contains(&id))
+    }
+
+    /// Remove an object from the world and from the contents of the container holding it.
+    ///
+    /// Whatever the object contains keeps its ids in the returned object's `contents`.
+    pub fn detach_object(&mut self, id: ObjectId) -> Option<Object> {
+        let idx = self.object_position(id)?;
+        if let Some(pi) = self.parent_index(id) {
+            self.objects[pi].contents.retain(|&c| c != id);
+        }
+        let object = self.objects.remove(idx);
+        if let Some(maps) = self.index.0.get_mut() {
+            maps.unplace(id, (object.x, object.y));
+            maps.by_id.remove(&id);
+            for i in maps.by_id.values_mut().filter(|i| **i > idx) {
+                *i -= 1;
+            }
+        }
+        Some(object)
+    }
+
+    /// Ids of pickable objects not in their target placement, ascending
+    pub fn misplaced_objects(&self) -> Vec<ObjectId> {
+        let mut ids: Vec<ObjectId> =
+            self.objects.iter().filter(|o| o.pickable && !o.check_placement(self)).map(|o| o.id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Fraction of pickable objects in their target placement; 1 when there are none.
+    ///
+    /// Disposed pickables count too, as placed when they were thrown away where their target
+    /// holds (a rotten banana in the trash can). `Simulator::tidiness` also counts a held
+    /// object, as misplaced.
+    pub fn tidiness(&self) -> f32 {
+        let (disposed, disposed_misplaced) = self.disposed_counts();
+        let total = self.objects.iter().filter(|o| o.pickable).count() + disposed;
+        tidiness_share(total - self.misplaced_objects().len() - disposed_misplaced, total)
+    }
+
+    /// Disposed pickables, and how many of them were thrown away outside their target
+    pub(crate) fn disposed_counts(&self) -> (usize, usize) {
+        let pickable = self.disposed.iter().filter(|o| o.pickable);
+        (pickable.clone().count(), pickable.filter(|o| !o.check_placement(self)).count())
+    }
+
+    /// A disposed object by id
+    pub fn disposed_object(&self, id: ObjectId) -> Option<&Object> {
+        self.disposed.iter().find(|o| o.id == id)
+    }
+
+    /// Whether an object is in its target placement, or was thrown away where its target holds
+    pub fn is_in_place(&self, id: ObjectId) -> bool {
+        self.object(id).or_else(|| self.disposed_object(id)).is_some_and(|o| o.check_placement(self))
+    }
+
+    /// Whether `inner` is (transitively) contained in `outer`
+    pub(crate) fn contains_transitively(&self, outer: ObjectId, inner: ObjectId) -> bool {
+        let mut stack = vec![outer];
+        while let Some(id) = stack.pop() {
+            if let Some(o) = self.object(id) {
+                if o.contents.contains(&inner) {
+                    return true;
+                }
+                stack.extend(o.contents.iter().copied());
+            }
+        }
+        false
+    }
+
+    /// Move an object and everything it (transitively) contains to (x, y); container
+    /// links stay as they are
+    pub fn move_object(&mut self, id: ObjectId, x: usize, y: usize) {
+        let mut stack = vec![id];
+        while let Some(cur) = stack.pop() {
+            let Some(i) = self.object_position(cur) else { continue };
+            let o = &mut self.objects[i];
+            let from = (o.x, o.y);
+            (o.x, o.y) = (x, y);
+            stack.extend(o.contents.iter().copied());
+            if let Some(maps) = self.index.0.get_mut() {
+                maps.unplace(cur, from);
+                maps.place(cur, (x, y));
+            }
+        }
+    }
+
+    /// Stable hash of the static layout: size, walls, room and zone assignment, with doors
+    /// counted the same whether open or closed. Objects are not included.
+    pub fn fingerprint(&self) -> u64 {
+        // FNV-1a, so fingerprints stay comparable across builds
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for &b in bytes {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        let layout = &self.layout;
+        feed(&(layout.width as u64).to_le_bytes());
+        feed(&(layout.height as u64).to_le_bytes());
+        for &c in &layout.cells {
+            feed(&[if c == OPEN_DOOR { CLOSED_DOOR } else { c } as u8]);
+        }
+        for &z in &layout.zones {
+            feed(&[z as u8]);
+        }
+        for name in layout.room_names.iter().chain(&layout.zone_names) {
+            feed(name.as_bytes());
+            feed(&[0]);
+        }
+        hash
+    }
+
+    /// Stitch two worlds into one, `b` shifted right and down by `offset` cells.
+    ///
+    /// The grid grows to cover both; cells neither world covers are outside. `a` keeps its
+    /// room, zone and object ids. `b`'s rooms and zones are numbered after `a`'s, and its
+    /// objects get the ids after `a`'s largest, in order of their original ids, so the
+    /// result depends only on the inputs. Disposed objects count as objects here and are
+    /// kept, `a`'s first. A room name `a` already uses gets the first free
+    /// suffix (" 2", " 3", ...), which placement constraints ignore. `b` may only cover
+    /// `a`'s outside cells or share its walls.
+    ///
+    /// With `connect`, a closed door is carved through the thinnest wall between a room
+    /// cell of `a` and one of `b`, both without objects; ties go to the first `a` cell in
+    /// row-major order.
+    pub fn compose(a: World, b: World, offset: (usize, usize), connect: bool) -> Result<(World, ComposeMap), ComposeError> {
+        let (la, lb) = (&a.layout, &b.layout);
+        let (ox, oy) = offset;
+        let width = la.width.max(ox + lb.width);
+        let height = la.height.max(oy + lb.height);
+        let room_base = la.room_names.len();
+        let zone_base = la.zone_names.len();
+        let ids = (room_base + lb.room_names.len()).max(zone_base + lb.zone_names.len());
+        if ids > Cell::MAX as usize + 1 {
+            return Err(ComposeError::TooManyRooms(ids));
+        }
+
+        let mut cells = vec![OUTSIDE; width * height];
+        let mut zones = vec![NO_ZONE; width * height];
+        for y in 0..la.height {
+            let (from, to) = (y * la.width, y * width);
+            cells[to..to + la.width].copy_from_slice(&la.cells[from..from + la.width]);
+            zones[to..to + la.width].copy_from_slice(&la.zones[from..from + la.width]);
+        }
+        let shift = |c: Cell, base: usize| if c >= 0 { c + base as Cell } else { c };
+        for (i, (&c, &z)) in lb.cells.iter().zip(&lb.zones).enumerate() {
+            if c == OUTSIDE {
+                continue;
+            }
+            let (x, y) = (ox + i % lb.width, oy + i / lb.width);
+            let j = y * width + x;
+            match (cells[j], c) {
+                (OUTSIDE, _) => {}
+                (WALL, WALL) => continue,
+                _ => return Err(ComposeError::Overlap { x, y }),
+            }
+            cells[j] = shift(c, room_base);
+            zones[j] = shift(z, zone_base);
+        }
+
+        let mut room_names = la.room_names.clone();
+        for name in &lb.room_names {
+            let unique = (1..)
+                .map(|n| if n == 1 { name.clone() } else { Cow::Owned(format!("{} {}", name, n)) })
+                .find(|candidate| !room_names.contains(candidate))
+                .expect("some suffix is free");
+            room_names.push(unique);
+        }
+        let mut zone_names = la.zone_names.clone();
+        zone_names.extend(lb.zone_names.iter().cloned());
+
+        let mut map = ComposeMap { rooms: (0..lb.room_names.len()).map(|r| (room_base + r) as Cell).collect(), ..Default::default() };
+        let next = a.objects.iter().chain(&a.disposed).map(|o| o.id + 1).max().unwrap_or(0);
+        let mut originals: Vec<ObjectId> = b.objects.iter().chain(&b.disposed).map(|o| o.id).collect();
+        originals.sort_unstable();
+        map.objects = originals.into_iter().enumerate().map(|(k, id)| (id, next + k)).collect();
+
+        let moved = |mut o: Object| {
+            o.id = map.objects[&o.id];
+            o.x += ox;
+            o.y += oy;
+            for c in &mut o.contents {
+                *c = map.objects[c];
+            }
+            o
+        };
+        let mut objects = a.objects;
+        objects.extend(b.objects.into_iter().map(moved));
+        let mut disposed = a.disposed;
+        disposed.extend(b.disposed.into_iter().map(moved));
+
+        if connect {
+            let free = |i: usize| !objects.iter().any(|o| o.y * width + o.x == i);
+            let mut best: Option<(usize, usize, (isize, isize))> = None;
+            for start in (0..cells.len()).filter(|&i| (0..room_base as Cell).contains(&cells[i]) && free(i)) {
+                for step in [(1isize, 0isize), (0, 1), (-1, 0), (0, -1)] {
+                    let (mut x, mut y) = ((start % width) as isize, (start / width) as isize);
+                    let mut gap = 0;
+                    let end = loop {
+                        x += step.0;
+                        y += step.1;
+                        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+                            break None;
+                        }
+                        let i = y as usize * width + x as usize;
+                        match cells[i] {
+                            WALL => gap += 1,
+                            c if c >= room_base as Cell => break Some(i),
+                            _ => break None,
+                        }
+                    };
+                    if gap > 0 && end.is_some_and(free) && best.is_none_or(|(g, _, _)| gap < g) {
+                        best = Some((gap, start, step));
+                    }
+                }
+            }
+            let (gap, start, step) = best.ok_or(ComposeError::NoSharedWall)?;
+            for k in 1..=gap as isize {
+                let x = (start % width) as isize + step.0 * k;
+                let y = (start / width) as isize + step.1 * k;
+                cells[y as usize * width + x as usize] = CLOSED_DOOR;
+                map.door.push((x as usize, y as usize));
+            }
+        }
+
+        let layout = Layout { zones, zone_names, ..Layout::new(width, height, cells, room_names) };
+        Ok((World { disposed, ..World::new(layout, objects) }, map))
+    }
+}
@@ -0,0 +1,85 @@
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::{Layout, MoveError, Object, ObjectSchema, Simulator, World};
+
+/// Belongs in a rack or anywhere in the kitchen
+fn cup() -> ObjectSchema {
+    let target = ObjectConstraint::Or(vec![ObjectConstraint::InsideOf(Names::Static(&["Rack"])), ObjectConstraint::InRoomNamed(Names::Static(&["Kitchen"]))]);
+    ObjectSchema { name: "Cup", pickable: true, target, ..Default::default() }
+}
+
+fn rack() -> ObjectSchema {
+    ObjectSchema { name: "Rack", capacity: 1, ..Default::default() }
+}
+
+fn bin() -> ObjectSchema {
+    ObjectSchema { name: "Bin", capacity: 5, ..Default::default() }
+}
+
+/// A hall (x 1-2) and a kitchen (x 4-7) joined by an open door at x 3, with the agent
+/// holding the cup (id 0) at `at` and the given containers from id 1 on
+fn holding_cup(at: usize, containers: &[(ObjectSchema, usize)]) -> Simulator {
+    let layout = Layout::from_ascii(&["#########", "#11d0000#", "#########"], &["Kitchen", "Hall"]).unwrap();
+    let mut objects = vec![Object::from_schema(0, &cup(), at, 1)];
+    objects.extend(containers.iter().enumerate().map(|(i, (schema, x))| Object::from_schema(i + 1, schema, *x, 1)));
+    let mut sim = Simulator::new(World::new(layout, objects), at, 1).unwrap();
+    sim.pick_up().unwrap();
+    sim
+}
+
+fn container(sim: &mut Simulator, id: usize) -> &mut Object {
+    sim.world.objects.iter_mut().find(|o| o.id == id).unwrap()
+}
+
+#[test]
+fn auto_place_prefers_containers_in_cell_order_over_the_floor() {
+    // the agent cell satisfies the cup, but a rack next to it still wins
+    let mut sim = holding_cup(5, &[(rack(), 6)]);
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(1).unwrap().contents, [0]);
+
+    // a bin in the hall satisfies nothing, so it is passed over for the rack
+    let mut sim = holding_cup(2, &[(bin(), 2), (rack(), 1)]);
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(2).unwrap().contents, [0]);
+    assert!(sim.world.object(1).unwrap().contents.is_empty());
+
+    // the agent cell, then up, down, left and right: the left rack beats the right one
+    let mut sim = holding_cup(5, &[(rack(), 6), (rack(), 4)]);
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(2).unwrap().contents, [0]);
+    let mut sim = holding_cup(5, &[(rack(), 4), (rack(), 5)]);
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(2).unwrap().contents, [0]);
+
+    // full and closed racks are passed over
+    let mut sim = holding_cup(5, &[(rack(), 4), (rack(), 6), (cup(), 4)]);
+    container(&mut sim, 1).contents.push(3);
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(2).unwrap().contents, [0]);
+    let mut sim = holding_cup(5, &[(rack(), 4)]);
+    sim.closed_containers = true;
+    container(&mut sim, 1).open = false;
+    sim.auto_place().unwrap();
+    assert!(sim.world.object(1).unwrap().contents.is_empty());
+    assert_eq!(sim.world.object(0).map(|o| (o.x, o.y)), Some((5, 1)));
+
+    // without a container, the first satisfying room cell: here the kitchen right of the door
+    let mut sim = holding_cup(2, &[]);
+    sim.right().unwrap();
+    sim.auto_place().unwrap();
+    assert_eq!(sim.world.object(0).map(|o| (o.x, o.y)), Some((4, 1)));
+}
+
+#[test]
+fn auto_place_fails_with_nothing_valid_nearby() {
+    // in the hall, with only a bin next to the agent
+    let mut sim = holding_cup(2, &[(bin(), 1)]);
+    let steps = sim.steps;
+    assert_eq!(sim.auto_place(), Err(MoveError::NoValidPlacementNearby));
+    assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(0));
+    assert_eq!(sim.steps, steps);
+    assert!(sim.world.object(1).unwrap().contents.is_empty());
+
+    sim.drop().unwrap();
+    assert_eq!(sim.auto_place(), Err(MoveError::NotHolding));
+}
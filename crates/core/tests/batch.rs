@@ -0,0 +1,117 @@
+use std::alloc::{GlobalAlloc, Layout as AllocLayout, System};
+use std::cell::Cell;
+
+use tidy_core::{generate, Action, BatchError, GenOpts, SimBatch, Simulator};
+
+/// The system allocator, counting allocations per thread
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: AllocLayout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: AllocLayout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: AllocLayout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn batch(n: u64) -> SimBatch {
+    let sims = (0..n)
+        .map(|seed| {
+            let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+            let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+            let width = world.layout.width;
+            Simulator::new(world, start % width, start / width).unwrap()
+        })
+        .collect();
+    SimBatch::new(sims)
+}
+
+#[test]
+fn step_all_fills_the_slab_with_each_local_view() {
+    let mut batch = batch(4);
+    let radius = 3;
+    let len = SimBatch::observation_len(radius);
+    let (mut cells, mut rewards, mut done) = (vec![0; 4 * len], vec![0.0; 4], vec![false; 4]);
+    let actions = [Action::MoveRight, Action::MoveDown, Action::TurnLeft, Action::Noop];
+    batch.step_all(&actions, radius, &mut cells, &mut rewards, &mut done).unwrap();
+    for (sim, observed) in batch.sims.iter().zip(cells.chunks(len)) {
+        assert_eq!(observed, sim.local_view(radius, false).cells);
+    }
+    assert_eq!(batch.sims[2].steps, 1);
+
+    assert_eq!(
+        batch.step_all(&actions[..3], radius, &mut cells, &mut rewards, &mut done),
+        Err(BatchError::ActionCount { expected: 4, found: 3 })
+    );
+    assert_eq!(
+        batch.observe_all(radius, &mut cells[1..]),
+        Err(BatchError::BufferSize { buffer: "cells", expected: 4 * len, found: 4 * len - 1 })
+    );
+    assert_eq!(batch.sims[2].steps, 1);
+}
+
+#[test]
+fn steady_state_steps_do_not_allocate() {
+    let mut batch = batch(8);
+    let radius = 5;
+    let len = SimBatch::observation_len(radius);
+    let (mut cells, mut rewards, mut done) = (vec![0; 8 * len], vec![0.0; 8], vec![false; 8]);
+    let moves = [Action::MoveUp, Action::MoveRight, Action::MoveDown, Action::MoveLeft, Action::TurnLeft, Action::Noop];
+    let mut actions = [Action::Noop; 8];
+    let mut run = |steps: std::ops::Range<usize>| {
+        for step in steps {
+            for (i, action) in actions.iter_mut().enumerate() {
+                *action = moves[(step + i) % moves.len()];
+            }
+            batch.step_all(&actions, radius, &mut cells, &mut rewards, &mut done).unwrap();
+        }
+    };
+    // grows the scratch view and the simulators' bounded event logs
+    run(0..6000);
+    let before = allocations();
+    run(6000..6200);
+    assert_eq!(allocations() - before, 0);
+}
+
+#[test]
+fn radii_past_the_grid_pad_the_capped_view_with_outside() {
+    let mut batch = batch(2);
+    let (width, height) = (batch.sims[0].world.layout.width, batch.sims[0].world.layout.height);
+    let capped = width.max(height);
+    let view = batch.sims[0].local_view(usize::MAX, true);
+    assert_eq!(view, batch.sims[0].local_view(capped, true));
+
+    let radius = capped + 2;
+    let len = SimBatch::observation_len(radius);
+    let mut cells = vec![0; 2 * len];
+    batch.observe_all(radius, &mut cells).unwrap();
+    for (sim, observed) in batch.sims.iter().zip(cells.chunks(len)) {
+        let (ax, ay) = (sim.agent.x as isize, sim.agent.y as isize);
+        let side = 2 * radius as isize + 1;
+        for (i, &cell) in observed.iter().enumerate() {
+            let (x, y) = (ax + i as isize % side - radius as isize, ay + i as isize / side - radius as isize);
+            let inside = x >= 0 && y >= 0 && (x as usize) < sim.world.layout.width && (y as usize) < sim.world.layout.height;
+            let expected = if inside { sim.world.layout.cells[y as usize * sim.world.layout.width + x as usize] } else { tidy_core::OUTSIDE };
+            assert_eq!(cell, expected, "cell {}", i);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use tidy_core::world::Cell;
+use tidy_core::{CellKind, Layout, CLOSED_DOOR, LOCKED_DOOR, OPEN_DOOR, OUTSIDE, STAIRS_BASE, WALL, WINDOW};
+
+#[test]
+fn every_cell_value_round_trips_through_its_kind() {
+    for c in i8::MIN..=i8::MAX {
+        let kind = CellKind::from_i8(c);
+        assert_eq!(kind.to_i8(), c, "{:?}", kind);
+        assert_eq!(Cell::from(CellKind::from(c)), c);
+    }
+    // one of every variant, with payloads at both ends of their ranges
+    let kinds = [
+        CellKind::Room(0),
+        CellKind::Room(127),
+        CellKind::Wall,
+        CellKind::Outside,
+        CellKind::ClosedDoor,
+        CellKind::OpenDoor,
+        CellKind::LockedDoor,
+        CellKind::Window,
+        CellKind::Stairs(0),
+        CellKind::Stairs(121),
+    ];
+    for kind in kinds {
+        assert_eq!(CellKind::from_i8(kind.to_i8()), kind);
+    }
+    assert_eq!((CellKind::Stairs(0).to_i8(), CellKind::Stairs(121).to_i8()), (STAIRS_BASE, i8::MIN));
+    let stairs: HashSet<Cell> = (0..=121).map(|n| CellKind::Stairs(n).to_i8()).collect();
+    assert_eq!(stairs, (i8::MIN..=STAIRS_BASE).collect());
+    // the kinds without payload and the payload families are told apart by name
+    let names: HashSet<&str> = kinds.iter().map(|k| k.name()).collect();
+    assert_eq!(names.len(), 8);
+}
+
+#[test]
+#[should_panic(expected = "staircase id out of range")]
+fn staircases_past_the_range_do_not_wrap() {
+    CellKind::Stairs(122).to_i8();
+}
+
+#[test]
+#[should_panic(expected = "room id out of range")]
+fn rooms_past_the_range_do_not_wrap() {
+    CellKind::Room(128).to_i8();
+}
+
+#[test]
+fn kinds_are_the_raw_constants() {
+    for (raw, kind) in [
+        (WALL, CellKind::Wall),
+        (OUTSIDE, CellKind::Outside),
+        (CLOSED_DOOR, CellKind::ClosedDoor),
+        (OPEN_DOOR, CellKind::OpenDoor),
+        (LOCKED_DOOR, CellKind::LockedDoor),
+        (WINDOW, CellKind::Window),
+        (STAIRS_BASE, CellKind::Stairs(0)),
+        (0, CellKind::Room(0)),
+        (i8::MAX, CellKind::Room(127)),
+    ] {
+        assert_eq!(CellKind::from_i8(raw), kind);
+        assert_eq!(kind.to_i8(), raw, "{:?}", kind);
+    }
+
+    let layout = Layout::from_ascii(&["#####", "#0d1=", "#DL# "], &["Hall", "Study"]).unwrap();
+    let kinds: Vec<CellKind> = layout.kinds().collect();
+    assert_eq!(kinds.iter().map(|&k| k.to_i8()).collect::<Vec<_>>(), layout.cells);
+    for (x, y, kind) in layout.kinds_with_coords() {
+        assert_eq!(layout.kind(x, y), Some(kind));
+        assert_eq!(layout.kind_at(x, y), kind);
+    }
+    assert_eq!([1, 2, 3, 4].map(|x| layout.kind_at(x, 1)), [CellKind::Room(0), CellKind::OpenDoor, CellKind::Room(1), CellKind::Window]);
+    assert_eq!([1, 2, 4].map(|x| layout.kind_at(x, 2)), [CellKind::ClosedDoor, CellKind::LockedDoor, CellKind::Outside]);
+    assert_eq!((layout.kind(5, 0), layout.kind(0, 3)), (None, None));
+}
@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use tidy_core::checkpoint::read_records;
+use tidy_core::{generate, Action, CheckpointError, GenOpts, Record, SimState, Simulator};
+
+fn simulator(seed: u64) -> Simulator {
+    let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+    let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    let width = world.layout.width;
+    Simulator::new(world, start % width, start / width).unwrap()
+}
+
+/// Checkpoint file for one test, removed first
+fn scratch(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tidy-checkpoint-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Play a fixed walk with checkpointing on; the state before the walk and after each
+/// logged action
+fn play(path: &Path, every: u32) -> Vec<SimState> {
+    let mut sim = simulator(4);
+    sim.checkpoint_to(path, every).unwrap();
+    let mut states = vec![sim.state()];
+    for i in 0..40 {
+        let action = Action::from_id([0, 1, 2, 3, 4, 5][i * 7 % 6]).unwrap();
+        // failed actions are not logged
+        if sim.step(action).is_ok() {
+            states.push(sim.state());
+        }
+    }
+    sim.stop_checkpointing().unwrap();
+    assert!(states.len() > 10);
+    states
+}
+
+#[test]
+fn resume_replays_to_the_last_action() {
+    let path = scratch("full");
+    let states = play(&path, 8);
+    let mut sim = simulator(4);
+    sim.resume_from(&path).unwrap();
+    assert_eq!(&sim.state(), states.last().unwrap());
+}
+
+#[test]
+fn truncated_files_resume_to_the_last_complete_record() {
+    let path = scratch("truncated");
+    let states = play(&path, 5);
+    let bytes = std::fs::read(&path).unwrap();
+    // ends of the records: a `u32` length followed by that many bytes
+    let mut ends = Vec::new();
+    let mut end = 0;
+    while end < bytes.len() {
+        end += 4 + u32::from_le_bytes(bytes[end..end + 4].try_into().unwrap()) as usize;
+        ends.push(end);
+    }
+    assert_eq!(end, bytes.len());
+    let complete = |len: usize| read_records(&bytes[..len]).unwrap();
+    // cut at every record end after the first snapshot, inside every length prefix and
+    // halfway into every payload
+    let cuts = ends[1..].windows(2).flat_map(|w| [w[0], w[0] + 2, (w[0] + 4 + w[1]) / 2]).chain([bytes.len()]);
+    for len in cuts {
+        let records = complete(len);
+        let actions = records.iter().filter(|r| matches!(r, Record::Action(_))).count();
+        let mut sim = simulator(4);
+        // only the records after the last complete snapshot are replayed
+        let since_snapshot = records.len() - 1 - records.iter().rposition(|r| matches!(r, Record::Snapshot(_))).unwrap();
+        assert_eq!(sim.resume_records(records).unwrap(), since_snapshot);
+        assert_eq!(sim.state(), states[actions], "truncated to {} of {} bytes", len, bytes.len());
+    }
+
+    let mut sim = simulator(4);
+    assert!(matches!(sim.resume_records(complete(ends[1] - 1)), Err(CheckpointError::NoSnapshot)));
+    // a checkpoint is only good for the world it was written in
+    let mut other = simulator(5);
+    assert!(matches!(other.resume_from(&path), Err(CheckpointError::FingerprintMismatch { .. })));
+}
+
+/// Take `n` pseudo-random discrete actions, failed ones included
+fn wander(sim: &mut Simulator, rng: &mut u64, n: usize) {
+    for _ in 0..n {
+        *rng = rng.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let _ = sim.step(Action::DISCRETE[(*rng >> 33) as usize % Action::DISCRETE.len()].1);
+    }
+}
+
+/// Everything that can change, as bytes: the state plus the world `serde` sees
+fn bytes(sim: &Simulator) -> (Vec<u8>, String) {
+    (sim.state().to_msgpack().unwrap(), serde_json::to_string(&sim.world).unwrap())
+}
+
+#[test]
+fn restore_and_reset_rewind_byte_for_byte() {
+    for seed in 0..5 {
+        let mut sim = simulator(seed);
+        let initial = bytes(&sim);
+        let mut rng = seed;
+        wander(&mut sim, &mut rng, 50);
+        let state = sim.state();
+        let saved = bytes(&sim);
+        wander(&mut sim, &mut rng, 50);
+        assert_ne!(bytes(&sim), saved, "seed {}: the second walk changed nothing", seed);
+        sim.restore(&state).unwrap();
+        assert_eq!(bytes(&sim), saved, "seed {}", seed);
+
+        wander(&mut sim, &mut rng, 50);
+        sim.reset().unwrap();
+        assert_eq!(bytes(&sim), initial, "seed {}", seed);
+        // a state belongs to its world
+        assert!(matches!(simulator(seed + 10).restore(&state), Err(CheckpointError::FingerprintMismatch { .. })));
+    }
+}
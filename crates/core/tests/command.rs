@@ -0,0 +1,77 @@
+use tidy_core::command::{parse_command, ParseError};
+use tidy_core::{Action, Layout, Object, ObjectSchema, Simulator, World};
+
+/// The agent on an apple (0) in a kitchen, a dirty plate (1) west of it, a dishwasher (2)
+/// holding nothing and a dirty bowl (3) south of it, a closed door east of it and an orange (4)
+/// out of reach behind the door
+fn sim() -> Simulator {
+    let layout = Layout::from_ascii(&["##########", "#000D1111#", "#000#1111#", "##########"], &["Kitchen", "Hallway"]).unwrap();
+    let objects = [("Apple", 3, 1), ("DirtyPlate", 2, 1), ("Dishwasher", 3, 2), ("DirtyBowl", 3, 2), ("Orange", 6, 1)]
+        .iter()
+        .enumerate()
+        .map(|(id, &(name, x, y))| Object::from_schema(id, ObjectSchema::find(name).unwrap(), x, y))
+        .collect();
+    Simulator::new(World::new(layout, objects), 3, 1).unwrap()
+}
+
+fn parse(sim: &Simulator, text: &str) -> Result<Action, ParseError> {
+    parse_command(text, &sim.world, &sim.agent)
+}
+
+#[test]
+fn phrases_parse_to_actions() {
+    let sim = sim();
+    let cases = [
+        ("go north", Action::MoveUp),
+        ("Move left.", Action::MoveLeft),
+        ("turn right", Action::TurnRight),
+        ("pick up the apple", Action::PickUpById(0)),
+        ("pick the dirty plate up", Action::PickUpById(1)),
+        ("take the bowl", Action::PickUpById(3)),
+        ("open the door to the east", Action::Interact { dx: 1, dy: 0 }),
+        ("put it in the dishwasher", Action::PlaceInto(2)),
+        ("put it down", Action::Drop),
+        ("drop", Action::Drop),
+        ("wait", Action::Noop),
+    ];
+    for (text, action) in cases {
+        assert_eq!(parse(&sim, text), Ok(action), "{:?}", text);
+    }
+}
+
+#[test]
+fn parsed_actions_drive_the_simulator() {
+    let mut sim = sim();
+    let script = ["move left", "pick up the plate", "move right", "put it in the dishwasher", "open the door to the east", "go east", "go east"];
+    for text in script {
+        let action = parse(&sim, text).unwrap();
+        sim.step(action).unwrap_or_else(|e| panic!("{:?}: {}", text, e));
+    }
+    assert_eq!((sim.agent.x, sim.agent.y), (5, 1));
+    assert_eq!(sim.world.object(2).unwrap().contents, [1]);
+}
+
+#[test]
+fn ambiguous_and_unknown_references_are_errors() {
+    let mut sim = sim();
+    sim.world.objects[0].label = Some("mine".into());
+    sim.world.objects[1].label = Some("mine".into());
+    assert_eq!(
+        parse(&sim, "take mine"),
+        Err(ParseError::AmbiguousObject {
+            query: "mine".into(),
+            candidates: vec!["apple labelled 'mine' here".into(), "dirty plate labelled 'mine' to the west".into()],
+        })
+    );
+    // a direction narrows the search to one cell
+    assert_eq!(parse(&sim, "take mine here"), Ok(Action::PickUpById(0)));
+    // only objects at or next to the agent count
+    assert_eq!(parse(&sim, "pick up the orange"), Err(ParseError::UnknownObject("orange".into())));
+    assert_eq!(parse(&sim, "open the fridge"), Err(ParseError::UnknownObject("fridge".into())));
+
+    assert_eq!(parse(&sim, "  "), Err(ParseError::Empty));
+    assert_eq!(parse(&sim, "fly north"), Err(ParseError::UnknownVerb("fly".into())));
+    assert_eq!(parse(&sim, "go"), Err(ParseError::MissingArgument("direction")));
+    assert_eq!(parse(&sim, "go sideways"), Err(ParseError::UnknownDirection("sideways".into())));
+    assert_eq!(parse(&sim, "put it"), Err(ParseError::MissingArgument("container")));
+}
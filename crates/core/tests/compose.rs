@@ -0,0 +1,66 @@
+use tidy_core::{generate, solver, GenOpts, ObjectId, Simulator, World};
+
+fn world(seed: u64) -> World {
+    generate(&GenOpts { seed, width: 16, height: 16, max_objects: 8, ..Default::default() }).unwrap()
+}
+
+fn first_room_cell(world: &World) -> (usize, usize) {
+    let i = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    (i % world.layout.width, i / world.layout.width)
+}
+
+fn pickables(world: &World) -> Vec<ObjectId> {
+    world.objects.iter().filter(|o| o.pickable).map(|o| o.id).collect()
+}
+
+/// Whether the oracle puts every goal in place, starting from `start`
+fn solvable(world: &World, start: (usize, usize), goals: &[ObjectId]) -> bool {
+    let mut sim = Simulator::new(world.clone(), start.0, start.1).unwrap();
+    solver::solve(&mut sim, goals, 2000).is_ok() && goals.iter().all(|&id| sim.world.is_in_place(id))
+}
+
+#[test]
+fn both_tidy_tasks_stay_solvable_side_by_side() {
+    for seed in 0..6 {
+        let (a, b) = (world(seed), world(seed + 100));
+        let (goals_a, goals_b) = (pickables(&a), pickables(&b));
+        let (start_a, start_b) = (first_room_cell(&a), first_room_cell(&b));
+        assert!(solvable(&a, start_a, &goals_a) && solvable(&b, start_b, &goals_b), "seed {}", seed);
+        // b's west wall on a's east wall
+        let offset = (a.layout.width - 1, 0);
+        let (world, map) = World::compose(a.clone(), b.clone(), offset, true).unwrap();
+        world.validate().unwrap();
+        assert!(!map.door.is_empty(), "seed {}", seed);
+        assert_eq!(world.objects.len(), a.objects.len() + b.objects.len());
+        assert_eq!(world.layout.room_names.len(), a.layout.room_names.len() + b.layout.room_names.len());
+        for o in &b.objects {
+            let moved = world.object(map.objects[&o.id]).unwrap();
+            assert_eq!((&moved.name, moved.x, moved.y), (&o.name, o.x + offset.0, o.y + offset.1), "seed {}", seed);
+        }
+
+        // a keeps its ids; b's goals are found under their new ones
+        let goals_b: Vec<ObjectId> = goals_b.iter().map(|id| map.objects[id]).collect();
+        assert!(solvable(&world, start_a, &goals_a), "seed {}: a's task", seed);
+        assert!(solvable(&world, (start_b.0 + offset.0, start_b.1 + offset.1), &goals_b), "seed {}: b's task", seed);
+    }
+}
+
+#[test]
+fn disposed_objects_are_kept_and_renumbered() {
+    let (mut a, mut b) = (world(1), world(2));
+    for w in [&mut a, &mut b] {
+        let id = pickables(w)[0];
+        let thrown = w.detach_object(id).unwrap();
+        w.disposed.push(thrown);
+    }
+    let (thrown_a, thrown_b) = (a.disposed[0].id, b.disposed[0].id);
+    let offset = (a.layout.width - 1, 0);
+    let (world, map) = World::compose(a.clone(), b.clone(), offset, false).unwrap();
+    world.validate().unwrap();
+    assert_eq!(world.disposed.len(), 2);
+    assert_eq!(world.disposed_object(thrown_a).unwrap().name, a.disposed[0].name);
+    let moved = world.disposed_object(map.objects[&thrown_b]).unwrap();
+    assert_eq!((&moved.name, moved.x, moved.y), (&b.disposed[0].name, b.disposed[0].x + offset.0, b.disposed[0].y + offset.1));
+    // no live object shares an id with a disposed one
+    assert!(world.disposed.iter().all(|d| world.object(d.id).is_none()));
+}
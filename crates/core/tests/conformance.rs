@@ -0,0 +1,10 @@
+use tidy_core::conformance::{run_core, spec};
+
+#[test]
+fn core_simulator_passes_the_conformance_spec() {
+    let spec = spec();
+    assert!(!spec.cases.is_empty());
+    let failures = run_core(&spec);
+    let report: Vec<String> = failures.iter().map(ToString::to_string).collect();
+    assert!(failures.is_empty(), "{}", report.join("\n"));
+}
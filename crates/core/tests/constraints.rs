@@ -0,0 +1,107 @@
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::world::NO_ZONE;
+use tidy_core::{generate, CellKind, GenOpts, Layout, Object, ObjectSchema, World};
+
+/// Every constraint a catalog schema uses, preferences included
+fn constraints() -> Vec<&'static ObjectConstraint> {
+    ObjectSchema::catalog()
+        .iter()
+        .flat_map(|s| [&s.constraint, &s.target].into_iter().chain(s.preference.iter().map(|(c, _)| c)))
+        .collect()
+}
+
+#[test]
+fn every_cell_passes_through_every_constraint() {
+    let constraints = constraints();
+    for seed in 0..5 {
+        let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+        let (w, h) = (world.layout.width, world.layout.height);
+        for constraint in &constraints {
+            for y in 0..h {
+                for x in 0..w {
+                    let holds = constraint.check(&world, x, y);
+                    if let ObjectConstraint::InRoomNamed(_) = constraint {
+                        assert!(!holds || world.layout.kind_at(x, y).room().is_some(), "seed {} at ({}, {})", seed, x, y);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cells_of_unnamed_rooms_match_no_room_name() {
+    let mut world = generate(&GenOpts { seed: 3, ..Default::default() }).unwrap();
+    // drop every name, so each room id runs past the list
+    world.layout.room_names.clear();
+    let world = World::new(world.layout, Vec::new());
+    assert!(world.layout.cells.iter().any(|&c| matches!(CellKind::from_i8(c), CellKind::Room(_))));
+    let (w, h) = (world.layout.width, world.layout.height);
+    for constraint in constraints() {
+        for (x, y) in (0..h).flat_map(|y| (0..w).map(move |x| (x, y))) {
+            let holds = constraint.check(&world, x, y);
+            if let ObjectConstraint::InRoomNamed(_) = constraint {
+                assert!(!holds, "({}, {}) is {:?}", x, y, world.layout.kind_at(x, y));
+            }
+        }
+    }
+}
+
+/// A Living Room zoned into west (x <= 2) and east halves with a TV stand in the west one,
+/// and a Bedroom through a door
+fn zoned_living_room() -> World {
+    let mut layout = Layout::from_ascii(&["#########", "#0000D11#", "#0000#11#", "#########"], &["Living Room", "Bedroom"]).unwrap();
+    layout.zone_names = vec!["seating area".into(), "window side".into()];
+    for (i, &cell) in layout.cells.clone().iter().enumerate() {
+        if cell == 0 {
+            layout.zones[i] = if i % layout.width <= 2 { 0 } else { 1 };
+        }
+    }
+    let tv_stand = ObjectSchema::find("TVStand").unwrap();
+    World::new(layout, vec![Object::from_schema(0, tv_stand, 1, 1)])
+}
+
+#[test]
+fn in_zone_with_needs_the_named_object_in_the_same_zone() {
+    let near_tv = ObjectConstraint::InZoneWith(Names::Static(&["TVStand"]));
+    let mut world = zoned_living_room();
+    assert!(near_tv.check(&world, 2, 2));
+    // the other zone of the same room, the next room, the door and a wall
+    assert!(!near_tv.check(&world, 4, 1));
+    assert!(!near_tv.check(&world, 6, 1));
+    assert!(!near_tv.check(&world, 5, 1));
+    assert!(!near_tv.check(&world, 0, 0));
+    assert!(!ObjectConstraint::InZoneWith(Names::Static(&["Bed"])).check(&world, 2, 2));
+
+    // without zones the whole room counts
+    world.layout.zones.fill(NO_ZONE);
+    assert!(near_tv.check(&world, 4, 1));
+    assert!(!near_tv.check(&world, 6, 1));
+}
+
+#[test]
+fn generated_zones_split_large_rooms_in_two() {
+    let mut partnered = 0;
+    for seed in 0..20 {
+        let world = generate(&GenOpts { seed, zone_min_area: 12, ..Default::default() }).unwrap();
+        let layout = &world.layout;
+        assert!(!layout.zone_names.is_empty(), "seed {}", seed);
+        for room in 0..layout.room_names.len() {
+            let cells: Vec<_> = layout.room_cells(room).collect();
+            let zones: std::collections::BTreeSet<_> = cells.iter().map(|&(x, y)| layout.zone_at(x, y)).collect();
+            let expected = if cells.len() >= 12 { 2 } else { 1 };
+            assert_eq!(zones.len(), expected, "seed {} room {}: {:?}", seed, room, zones);
+            assert_eq!(zones.contains(&None), cells.len() < 12, "seed {} room {}", seed, room);
+        }
+        // zones never reach past the rooms
+        for (i, &zone) in layout.zones.iter().enumerate() {
+            assert!(zone == NO_ZONE || layout.cells[i] >= 0, "seed {} cell {}", seed, i);
+        }
+        // the sofa by the TV stand and the nightstand by the bed were placed by their constraints
+        for o in world.objects.iter().filter(|o| ["Sofa", "Nightstand"].contains(&&*o.name)) {
+            assert!(ObjectSchema::find(&o.name).unwrap().constraint.check(&world, o.x, o.y), "seed {} {}", seed, o.name);
+            partnered += 1;
+        }
+    }
+    assert!(partnered > 0);
+}
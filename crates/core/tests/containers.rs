@@ -0,0 +1,226 @@
+use tidy_core::language::describe_room;
+use tidy_core::object::ObjectConstraint;
+use tidy_core::render::{render_ascii, render_text, RenderOpts, Theme};
+use tidy_core::{generate, CellKind, ContainerKind, GenOpts, Layout, Object, ObjectSchema, Simulator, World};
+
+fn bowl() -> ObjectSchema {
+    ObjectSchema { name: "Bowl", capacity: 2, pickable: true, target: ObjectConstraint::InRoom, ..Default::default() }
+}
+
+/// A corridor with a Bowl holding a Sculpture on the agent's cell
+fn corridor() -> World {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Hallway"]).unwrap();
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    let mut objects = vec![Object::from_schema(0, &bowl(), 1, 1), Object::from_schema(1, sculpture, 1, 1)];
+    objects[0].contents.push(1);
+    World::new(layout, objects)
+}
+
+#[test]
+fn carried_containers_take_their_contents_along() {
+    let mut sim = Simulator::new(corridor(), 1, 1).unwrap();
+    let tidiness = sim.tidiness();
+    sim.pick_up().unwrap();
+    assert_eq!(sim.holding.as_ref().unwrap().id, 0);
+    assert!(sim.world.objects_at(1, 1).is_empty());
+    assert!(sim.world.object(1).is_none());
+    assert_eq!(sim.carried.iter().map(|o| o.id).collect::<Vec<_>>(), [1]);
+    assert_eq!(sim.pick_up_by_id(1), Err(tidy_core::MoveError::AlreadyHolding));
+    // both count as held, so misplaced, while carried
+    assert_eq!(sim.misplaced_objects(), [0, 1]);
+
+    // a saved state keeps the contents in the hand
+    let state = sim.state();
+    sim.right().unwrap();
+    sim.drop().unwrap();
+    assert!(sim.carried.is_empty());
+    assert_eq!(sim.world.objects_at(2, 1).iter().map(|o| o.id).collect::<Vec<_>>(), [0, 1]);
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+    sim.world.validate().unwrap();
+    assert_eq!(sim.tidiness(), tidiness);
+
+    sim.restore(&state).unwrap();
+    assert!(sim.world.object(1).is_none());
+    assert_eq!(sim.carried.len(), 1);
+    sim.drop().unwrap();
+    assert_eq!(sim.world.object(1).map(|o| (o.x, o.y)), Some((1, 1)));
+    sim.world.validate().unwrap();
+}
+
+#[test]
+fn placing_a_carried_container_keeps_its_contents_inside() {
+    let mut world = corridor();
+    let shelf = ObjectSchema { name: "Shelf", capacity: 2, ..Default::default() };
+    world.add_object(Object::from_schema(2, &shelf, 2, 1));
+    let mut sim = Simulator::new(world, 1, 1).unwrap();
+    sim.pick_up().unwrap();
+    sim.place_into(2).unwrap();
+    assert!(sim.carried.is_empty());
+    assert_eq!(sim.world.object(2).unwrap().contents, [0]);
+    assert_eq!(sim.world.object(1).map(|o| (o.x, o.y)), Some((2, 1)));
+    sim.world.validate().unwrap();
+}
+
+#[test]
+fn picking_up_from_a_drawer_leaves_no_stale_contents() {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Bedroom"]).unwrap();
+    let drawer = ObjectSchema::find("Drawer").unwrap();
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    let mut objects = vec![Object::from_schema(0, drawer, 1, 1), Object::from_schema(1, sculpture, 1, 1)];
+    objects[0].contents.push(1);
+    let mut sim = Simulator::new(World::new(layout, objects), 1, 1).unwrap();
+    sim.closed_containers = true;
+    // a closed drawer hides its contents
+    assert!(sim.pick_up().is_err());
+    sim.toggle_container(0).unwrap();
+    sim.pick_up().unwrap();
+    assert!(sim.world.object(0).unwrap().contents.is_empty());
+    assert!(sim.world.object(1).is_none());
+    sim.world.validate().unwrap();
+}
+
+#[test]
+fn local_views_only_show_the_contents_of_open_containers() {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Bedroom"]).unwrap();
+    let drawer = ObjectSchema::find("Drawer").unwrap();
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    let mut objects = vec![Object::from_schema(0, drawer, 2, 1), Object::from_schema(1, &bowl(), 2, 1), Object::from_schema(2, sculpture, 2, 1)];
+    objects[0].contents.push(1);
+    objects[1].contents.push(2);
+    objects[0].open = false;
+    let mut sim = Simulator::new(World::new(layout, objects), 1, 1).unwrap();
+    let ids = |sim: &Simulator| sim.local_view(2, true).objects.iter().map(|o| o.0).collect::<Vec<_>>();
+    // every container acts open until closed containers are on
+    assert_eq!(ids(&sim), [0, 1, 2]);
+    sim.closed_containers = true;
+    assert_eq!(ids(&sim), [0]);
+    assert_eq!(sim.local_view(2, false).objects.len(), 1);
+    sim.toggle_container(0).unwrap();
+    assert_eq!(ids(&sim), [0, 1, 2]);
+}
+
+#[test]
+fn generated_drawer_contents_leave_the_drawer_when_taken() {
+    let mut checked = 0;
+    for seed in 0..40 {
+        let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+        let Some(drawer) = world.objects.iter().find(|o| o.name == "Drawer" && !o.contents.is_empty()) else { continue };
+        let id = drawer.id;
+        // a free room cell next to the drawer to reach in from
+        let free = |x: usize, y: usize| {
+            matches!(world.layout.kind_at(x, y), CellKind::Room(_)) && world.objects_at(x, y).iter().all(|o| !o.blocks_movement)
+        };
+        let (dx, dy) = (drawer.x, drawer.y);
+        let Some((x, y)) = [(dx + 1, dy), (dx - 1, dy), (dx, dy + 1), (dx, dy - 1)].into_iter().find(|&(x, y)| free(x, y)) else { continue };
+        // interact takes the lowest pickable id on the cell
+        let taken = world.objects_at(dx, dy).into_iter().find(|o| o.pickable).unwrap().id;
+        assert!(drawer.contents.contains(&taken), "seed {}", seed);
+        let mut sim = Simulator::new(world, x, y).unwrap();
+        sim.closed_containers = true;
+        sim.toggle_container(id).unwrap();
+        sim.interact(dx as isize - x as isize, dy as isize - y as isize).unwrap();
+        assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(taken), "seed {}", seed);
+        assert!(!sim.world.object(id).unwrap().contents.contains(&taken), "seed {}", seed);
+        assert!(sim.world.object(taken).is_none());
+        sim.world.validate().unwrap();
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn nothing_goes_into_a_container_inside_a_closed_one() {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Bedroom"]).unwrap();
+    let drawer = ObjectSchema::find("Drawer").unwrap();
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    let mut objects = vec![Object::from_schema(0, drawer, 2, 1), Object::from_schema(1, &bowl(), 2, 1), Object::from_schema(2, sculpture, 1, 1)];
+    objects[0].contents.push(1);
+    objects[0].open = false;
+    let mut sim = Simulator::new(World::new(layout, objects), 1, 1).unwrap();
+    sim.closed_containers = true;
+    sim.pick_up().unwrap();
+    assert_eq!(sim.place_into(1), Err(tidy_core::MoveError::ContainerClosed));
+    sim.toggle_container(0).unwrap();
+    sim.place_into(1).unwrap();
+    assert_eq!(sim.world.object(1).unwrap().contents, [2]);
+    sim.world.validate().unwrap();
+}
+
+#[test]
+fn surfaces_and_enclosed_containers_fill_to_capacity_and_no_further() {
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    for kind in [ContainerKind::Surface, ContainerKind::Enclosed] {
+        let schema = ObjectSchema { name: "Crate", capacity: 2, container_kind: Some(kind), ..Default::default() };
+        let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Storage"]).unwrap();
+        let mut objects = vec![Object::from_schema(0, &schema, 2, 1), Object::from_schema(1, sculpture, 2, 1)];
+        objects.extend((2..4).map(|id| Object::from_schema(id, sculpture, 1, 1)));
+        objects[0].contents.push(1);
+        let mut sim = Simulator::new(World::new(layout, objects), 1, 1).unwrap();
+        sim.closed_containers = true;
+        if kind == ContainerKind::Enclosed {
+            sim.world.objects[0].open = false;
+        }
+
+        // one slot left: a surface takes the item as it is, an enclosed container once opened
+        sim.pick_up().unwrap();
+        if kind == ContainerKind::Enclosed {
+            assert_eq!(sim.interact(1, 0), Err(tidy_core::InteractError::Move(tidy_core::MoveError::ContainerClosed)));
+            sim.toggle_container(0).unwrap();
+        }
+        sim.interact(1, 0).unwrap();
+        assert_eq!(sim.world.object(0).unwrap().contents, [1, 2], "{:?}", kind);
+        sim.world.validate().unwrap();
+
+        // at capacity: neither interacting nor placing adds a third
+        sim.pick_up().unwrap();
+        assert_eq!(sim.interact(1, 0), Err(tidy_core::InteractError::ContainerFull), "{:?}", kind);
+        assert_eq!(sim.place_into(0), Err(tidy_core::MoveError::ContainerFull), "{:?}", kind);
+        assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(3));
+        assert_eq!(sim.world.object(0).unwrap().contents, [1, 2]);
+        sim.world.validate().unwrap();
+
+        // and a world holding one over is invalid
+        sim.drop().unwrap();
+        sim.world.move_object(3, 2, 1);
+        sim.world.objects[0].contents.push(3);
+        let err = sim.world.validate().unwrap_err();
+        assert!(err.contains("capacity 2"), "{:?}: {}", kind, err);
+    }
+}
+
+#[test]
+fn three_stacked_items_are_handled_lowest_id_first_everywhere() {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Bedroom"]).unwrap();
+    // listed out of id order; everything goes by id
+    let schemas = ["Apple", "Mug", "Sculpture"].map(|name| ObjectSchema::find(name).unwrap());
+    let objects = vec![Object::from_schema(2, schemas[0], 2, 1), Object::from_schema(0, schemas[1], 2, 1), Object::from_schema(1, schemas[2], 2, 1)];
+    let world = World::new(layout, objects);
+    world.validate().unwrap();
+    let ids = |world: &World| world.objects_at(2, 1).iter().map(|o| o.id).collect::<Vec<_>>();
+    assert_eq!(ids(&world), [0, 1, 2]);
+
+    // describe, render and serialize
+    let room = describe_room(&world, 0);
+    for name in ["apple", "mug", "sculpture"] {
+        assert!(room.contains(name), "{}", room);
+    }
+    assert_eq!(render_ascii(&world, &Theme::ascii()).lines().nth(1), Some("#.0.#"));
+    let glyphs = render_text(&world, &Theme::ascii(), RenderOpts { glyphs: true, ..Default::default() });
+    assert_eq!(glyphs.lines().nth(1).unwrap().chars().nth(2), Some('m'));
+    let loaded = World::from_json(&world.to_json().unwrap()).unwrap();
+    assert_eq!(ids(&loaded), [0, 1, 2]);
+    loaded.validate().unwrap();
+
+    // pick up the lowest id, or a given one
+    let mut sim = Simulator::new(world, 2, 1).unwrap();
+    sim.pick_up().unwrap();
+    assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(0));
+    assert_eq!(sim.drop_preview().stacked, [1, 2]);
+    assert_eq!(ids(&sim.world), [1, 2]);
+    sim.drop().unwrap();
+    assert_eq!(ids(&sim.world), [0, 1, 2]);
+    sim.pick_up_by_id(2).unwrap();
+    assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(2));
+    assert_eq!(ids(&sim.world), [0, 1]);
+    sim.world.validate().unwrap();
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+use tidy_core::coords::{coords_from_index, flat_index, transpose_cells, Coord};
+use tidy_core::{Layout, Object, ObjectSchema, Simulator, World, OUTSIDE};
+
+/// An L-shaped room wider than it is tall, with a second room in the notch of the L
+fn l_shaped() -> World {
+    let mut layout = Layout::from_ascii(
+        &[
+            "##########",
+            "#00#######",
+            "#00#######",
+            "#00000d11#",
+            "#00000#11#",
+            "##########",
+        ],
+        &["Hall", "Study"],
+    )
+    .unwrap();
+    // the upright of the L is one zone, its foot another
+    layout.zone_names = vec![Cow::Borrowed("nook"), Cow::Borrowed("foot")];
+    for i in 0..layout.cells.len() {
+        if layout.cells[i] == 0 {
+            layout.zones[i] = if i / layout.width < 3 { 0 } else { 1 };
+        }
+    }
+    let mug = Object::from_schema(0, ObjectSchema::find("Mug").unwrap(), 8, 4);
+    World::new(layout, vec![mug])
+}
+
+#[test]
+fn an_l_shaped_layout_reads_the_same_through_every_representation() {
+    let world = l_shaped();
+    let layout = &world.layout;
+    let (w, h) = (layout.width, layout.height);
+    assert_eq!((w, h), (10, 6));
+
+    let ((rows, cols), data) = layout.to_ndarray_order();
+    assert_eq!((rows, cols), (h, w));
+    let transposed = transpose_cells(&layout.cells, w, h);
+    let zones_transposed = transpose_cells(&layout.zones, w, h);
+    for y in 0..h {
+        for x in 0..w {
+            let at = Coord::new(x, y);
+            let i = flat_index(x, y, w);
+            assert_eq!((at.index(w), coords_from_index(i, w), Coord::from_index(i, w)), (i, at, at));
+            assert_eq!(layout.cell(at), data[i]);
+            assert_eq!(transposed[x * h + y], data[i], "({}, {})", x, y);
+            assert_eq!(zones_transposed[x * h + y], layout.zones[i], "({}, {})", x, y);
+            assert_eq!(layout.zone_at(x, y).map(|z| z as i8), Some(layout.zones[i]).filter(|&z| z >= 0));
+        }
+    }
+    // spot checks that x is the column and y the row, not the other way round
+    assert_eq!(layout.room_name_at(1, 1), Some("Hall"));
+    assert_eq!(layout.room_name_at(4, 3), Some("Hall"));
+    assert_eq!(layout.room_name_at(3, 1), None);
+    assert_eq!(layout.room_name_at(8, 4), Some("Study"));
+    assert_eq!((layout.zone_at(2, 2), layout.zone_at(4, 4), layout.zone_at(8, 4)), (Some(0), Some(1), None));
+
+    let sim = Simulator::new(world.clone(), 1, 1).unwrap();
+    let view = sim.view();
+    assert_eq!((view.width, view.height), (w, h));
+    assert_eq!(&view.cells[..], &layout.cells[..]);
+    assert_eq!(view.door_open(6, 3), Some(true));
+    assert_eq!(view.door_open(3, 3), None);
+    assert_eq!(view.objects_at(8, 4).map(|o| o.id).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(view.objects_at(4, 4).count(), 0);
+
+    // local views line up with the grid from either arm of the L, including past its border
+    for (ax, ay) in [(1, 1), (8, 3)] {
+        let sim = Simulator::new(world.clone(), ax, ay).unwrap();
+        let local = sim.local_view(3, false);
+        let r = local.radius as isize;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let (x, y) = (ax as isize + dx, ay as isize + dy);
+                let expected = if x >= 0 && y >= 0 && (x as usize) < w && (y as usize) < h { layout.cell(Coord::new(x as usize, y as usize)) } else { OUTSIDE };
+                let i = (dy + r) as usize * local.side() + (dx + r) as usize;
+                assert_eq!(local.cells[i], expected, "agent ({}, {}) offset ({}, {})", ax, ay, dx, dy);
+            }
+        }
+        let visible = (8 - ax as isize).abs() <= r && (4 - ay as isize).abs() <= r;
+        let expected: Vec<_> = if visible { vec![(0, 8 - ax as isize, 4 - ay as isize)] } else { Vec::new() };
+        assert_eq!(local.objects, expected, "agent ({}, {})", ax, ay);
+    }
+}
@@ -0,0 +1,91 @@
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::{Layout, Object, ObjectSchema, SimEvent, Simulator, World};
+
+fn schemas() -> Vec<ObjectSchema> {
+    vec![
+        ObjectSchema {
+            name: "Milk",
+            pickable: true,
+            target: ObjectConstraint::InsideOf(Names::Static(&["Fridge"])),
+            decays_into: Some(("SourMilk", 3)),
+            ..Default::default()
+        },
+        ObjectSchema {
+            name: "SourMilk",
+            pickable: true,
+            target: ObjectConstraint::InsideOf(Names::Static(&["Bin"])),
+            ..Default::default()
+        },
+        ObjectSchema { name: "Bin", capacity: 4, disposes: true, ..Default::default() },
+    ]
+}
+
+/// A corridor with custom-schema milk on the agent's cell and a bin next to it
+fn corridor() -> World {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Hallway"]).unwrap();
+    let schemas = schemas();
+    let objects = vec![
+        Object::from_schema_among(0, &schemas[0], &schemas, 1, 1),
+        Object::from_schema_among(1, &schemas[2], &schemas, 2, 1),
+    ];
+    World::new(layout, objects)
+}
+
+#[test]
+fn custom_schemas_decay_and_dispose() {
+    let mut sim = Simulator::new(corridor(), 1, 1).unwrap();
+    sim.decay_enabled = true;
+    assert_eq!(sim.decay_remaining(0), Some(3));
+    let events: Vec<SimEvent> = (0..3).flat_map(|_| sim.tick()).collect();
+    assert_eq!(events, [SimEvent::Decayed { id: 0, from: "Milk".into(), into: "SourMilk".into() }]);
+    assert_eq!(sim.world.object(0).unwrap().name, "SourMilk");
+    assert_eq!(sim.decay_remaining(0), None);
+
+    sim.pick_up().unwrap();
+    sim.place_into(1).unwrap();
+    assert!(sim.world.object(0).is_none());
+    assert_eq!(sim.world.disposed_object(0).unwrap().name, "SourMilk");
+    assert!(sim.world.is_in_place(0));
+}
+
+#[test]
+fn decay_survives_a_json_round_trip() {
+    let world = World::from_json(&corridor().to_json().unwrap()).unwrap();
+    assert!(world.object(1).unwrap().disposes);
+    let mut sim = Simulator::new(world, 1, 1).unwrap();
+    sim.decay_enabled = true;
+    for _ in 0..3 {
+        sim.tick();
+    }
+    assert_eq!(sim.world.object(0).unwrap().name, "SourMilk");
+}
+
+#[test]
+fn decay_chains_end_at_a_cycle() {
+    let schemas = [
+        ObjectSchema { name: "Ice", decays_into: Some(("Water", 2)), ..Default::default() },
+        ObjectSchema { name: "Water", decays_into: Some(("Ice", 5)), ..Default::default() },
+    ];
+    let ice = Object::from_schema_among(0, &schemas[0], &schemas, 0, 0);
+    let names: Vec<&str> = ice.decay_chain().map(|o| &*o.name).collect();
+    assert_eq!(names, ["Ice", "Water", "Ice"]);
+}
+
+#[test]
+fn custom_appliances_transform_with_their_own_table() {
+    let mut schemas = schemas();
+    schemas.push(ObjectSchema { name: "Churn", capacity: 2, transforms: vec![("Milk", "Butter")], ..Default::default() });
+    schemas.push(ObjectSchema { name: "Butter", pickable: true, decays_into: Some(("SourMilk", 5)), ..Default::default() });
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Kitchen"]).unwrap();
+    let mut churn = Object::from_schema_among(0, &schemas[3], &schemas, 2, 1);
+    churn.contents.push(1);
+    let world = World::new(layout, vec![churn, Object::from_schema_among(1, &schemas[0], &schemas, 2, 1)]);
+    // the table survives saving, so the loaded churn still works
+    let mut sim = Simulator::new(World::from_json(&world.to_json().unwrap()).unwrap(), 1, 1).unwrap();
+    sim.run_appliance(0).unwrap();
+    assert_eq!(sim.drain_events(), [SimEvent::Transformed { id: 1, from: "Milk".into(), into: "Butter".into() }]);
+    let butter = sim.world.object(1).unwrap();
+    assert_eq!((&*butter.name, butter.decay.as_ref().map(|d| d.steps)), ("Butter", Some(5)));
+    // objects without a table are no appliances
+    assert!(sim.run_appliance(1).is_err());
+}
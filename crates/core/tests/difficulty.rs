@@ -0,0 +1,37 @@
+use tidy_core::{difficulty, generate, solver, GenOpts, Simulator};
+
+#[test]
+fn difficulty_scores_rise_with_oracle_episode_lengths() {
+    // (score, oracle steps) of every seed the oracle can solve; it gives up on stacked goals
+    let mut episodes = Vec::new();
+    for seed in 0..40 {
+        let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+        let score = difficulty::estimate(&world).score;
+        let goals = world.misplaced_objects();
+        let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+        let (x, y) = (start % world.layout.width, start / world.layout.width);
+        let mut sim = Simulator::new(world, x, y).unwrap();
+        if let Ok(steps) = solver::solve(&mut sim, &goals, 2000) {
+            episodes.push((score, steps));
+        }
+    }
+    assert!(episodes.len() >= 30, "the oracle solved only {} seeds", episodes.len());
+    episodes.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    // ordered by score, the mean episode length grows from quarter to quarter
+    let quarter = episodes.len() / 4;
+    let means: Vec<f32> = episodes.chunks(quarter).take(4).map(|c| c.iter().map(|e| e.1 as f32).sum::<f32>() / c.len() as f32).collect();
+    assert!(means.windows(2).all(|m| m[0] < m[1]), "{:?}", means);
+
+    // and most pairs of seeds are ordered the same way by both
+    let (mut agree, mut pairs) = (0, 0);
+    for (i, a) in episodes.iter().enumerate() {
+        for b in &episodes[i + 1..] {
+            if a.0 < b.0 && a.1 != b.1 {
+                pairs += 1;
+                agree += usize::from(a.1 < b.1);
+            }
+        }
+    }
+    assert!(agree as f32 >= 0.75 * pairs as f32, "{} of {} pairs agree", agree, pairs);
+}
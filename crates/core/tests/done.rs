@@ -0,0 +1,72 @@
+use tidy_core::task::DeliveryTask;
+use tidy_core::{generate_with_report, solver, Action, DoneCriteria, GenOpts, Layout, Object, ObjectSchema, Simulator, TaskKind, World};
+
+/// A kitchen with a cupboard (0) and a mug (1) on the floor two cells east of it; the agent
+/// stands on the mug
+fn kitchen() -> Simulator {
+    let layout = Layout::from_ascii(&["######", "#0000#", "######"], &["Kitchen"]).unwrap();
+    let objects = vec![
+        Object::from_schema(0, ObjectSchema::find("Cupboard").unwrap(), 1, 1),
+        Object::from_schema(1, ObjectSchema::find("Mug").unwrap(), 3, 1),
+    ];
+    Simulator::new(World::new(layout, objects), 3, 1).unwrap()
+}
+
+#[test]
+fn done_flips_once_the_object_is_put_away() {
+    let mut sim = kitchen();
+    assert!(!sim.is_done());
+    assert!(!sim.step(Action::PickUp).unwrap().done);
+    // nothing is misplaced in the world, but the mug is still in hand
+    assert!(sim.world.misplaced_objects().is_empty());
+    assert!(!sim.step(Action::MoveLeft).unwrap().done);
+    let outcome = sim.step(Action::Interact { dx: -1, dy: 0 }).unwrap();
+    assert!(outcome.done);
+    assert!(sim.is_success() && !sim.is_truncated());
+}
+
+#[test]
+fn done_criteria_set_a_budget_and_a_threshold() {
+    let mut sim = kitchen();
+    sim.done_criteria = DoneCriteria { max_steps: Some(2), min_tidiness: None };
+    assert!(!sim.step(Action::MoveLeft).unwrap().done);
+    assert!(sim.step(Action::MoveRight).unwrap().done);
+    assert!(sim.is_truncated() && !sim.is_success());
+
+    // with one of one pickables misplaced, only a threshold of 0 is met
+    let mut sim = kitchen();
+    sim.done_criteria.min_tidiness = Some(0.5);
+    assert!(!sim.is_done());
+    sim.done_criteria.min_tidiness = Some(0.0);
+    assert!(sim.is_done());
+}
+
+#[test]
+fn delivery_tasks_are_solvable_and_done_once_the_bag_is_empty() {
+    for seed in 0..20 {
+        // a kitchen brings the Refrigerator and Cupboard the groceries go into
+        let opts = GenOpts { seed, task: TaskKind::Delivery, required_rooms: vec!["Kitchen".into()], ..Default::default() };
+        let (world, report) = generate_with_report(&opts).unwrap();
+        let delivery = report.delivery.unwrap_or_else(|| panic!("seed {}: no delivery in {:?}", seed, report.warnings));
+        assert_eq!(DeliveryTask::from_world(&world).as_ref(), Some(&delivery), "seed {}", seed);
+        assert!(delivery.instructions(&world).starts_with("Put away the groceries from the bag"), "seed {}", seed);
+        // the bag sits next to the first room cell, where the agent starts
+        let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+        let (x, y) = (start % world.layout.width, start / world.layout.width);
+        let mut sim = Simulator::new(world, x, y).unwrap();
+        let goals = TaskKind::Delivery.goals(&sim.world, Some(&delivery));
+        assert_eq!(goals, delivery.items);
+
+        // the oracle's script, checking done after every action
+        let mut put_away = 0;
+        while let Some(action) = solver::next_action(&sim, &goals) {
+            assert!(!delivery.is_done(&sim) && !TaskKind::Delivery.is_done(&sim, Some(&delivery)), "seed {}", seed);
+            sim.step(action).unwrap_or_else(|e| panic!("seed {}: {:?} failed: {}", seed, action, e));
+            put_away = put_away.max(goals.iter().filter(|&&id| sim.world.is_in_place(id)).count());
+            assert!(sim.steps < 2000, "seed {}: the oracle did not finish", seed);
+        }
+        assert_eq!(put_away, goals.len(), "seed {}", seed);
+        assert!(sim.world.object(delivery.bag).unwrap().contents.is_empty(), "seed {}", seed);
+        assert!(delivery.is_done(&sim) && TaskKind::Delivery.is_done(&sim, Some(&delivery)), "seed {}", seed);
+    }
+}
@@ -0,0 +1,126 @@
+use tidy_core::{generate, Action, CellKind, GenOpts, InteractError, Layout, Simulator, World};
+
+fn sim(rows: &[&str]) -> Simulator {
+    let layout = Layout::from_ascii(rows, &["Hallway", "Kitchen"]).unwrap();
+    Simulator::new(World::new(layout, Vec::new()), 1, 1).unwrap()
+}
+
+fn kind(sim: &Simulator, x: usize, y: usize) -> CellKind {
+    sim.world.layout.kind_at(x, y)
+}
+
+#[test]
+fn doors_on_the_shell_boundary_open_and_close() {
+    for registered in [true, false] {
+        let mut sim = sim(&["D###D", "D000#", "###D#"]);
+        if !registered {
+            sim.world.layout.doors.clear();
+        }
+        // the corners and edges of the grid
+        sim.use_door(0, 0, true).unwrap();
+        assert_eq!(kind(&sim, 0, 0), CellKind::OpenDoor);
+        assert_eq!(kind(&sim, 0, 1), CellKind::OpenDoor);
+        assert_eq!(kind(&sim, 4, 0), CellKind::ClosedDoor);
+        sim.use_door(4, 0, true).unwrap();
+        sim.use_door(3, 2, true).unwrap();
+        assert_eq!(kind(&sim, 3, 2), CellKind::OpenDoor);
+        sim.use_door(0, 1, false).unwrap();
+        assert_eq!(kind(&sim, 0, 0), CellKind::ClosedDoor);
+        assert_eq!(kind(&sim, 0, 1), CellKind::ClosedDoor);
+        assert_eq!(sim.use_door(-1, 0, true), Err(InteractError::OutOfBounds { x: -1, y: 0 }));
+        assert_eq!(sim.use_door(5, 2, true), Err(InteractError::OutOfBounds { x: 5, y: 2 }));
+        // the rooms are untouched
+        assert!((1..4).all(|x| kind(&sim, x, 1) == CellKind::Room(0)));
+    }
+}
+
+#[test]
+fn multi_cell_doors_switch_as_one_segment() {
+    for registered in [true, false] {
+        let mut sim = sim(&["#####", "#000#", "#DDD#", "#111#", "#####"]);
+        if !registered {
+            sim.world.layout.doors.clear();
+        }
+        sim.use_door(2, 2, true).unwrap();
+        assert!((1..4).all(|x| kind(&sim, x, 2) == CellKind::OpenDoor));
+        assert_eq!(sim.use_door(1, 2, true), Err(InteractError::DoorAlreadyOpen));
+        // nothing spreads past the segment into the rooms on either side
+        assert!((1..4).all(|x| kind(&sim, x, 1) == CellKind::Room(0) && kind(&sim, x, 3) == CellKind::Room(1)));
+        // an agent on any cell of the segment keeps it open
+        sim.down().unwrap();
+        assert_eq!(sim.use_door(3, 2, false), Err(InteractError::DoorwayOccupied(0)));
+        sim.up().unwrap();
+        sim.use_door(3, 2, false).unwrap();
+        assert!((1..4).all(|x| kind(&sim, x, 2) == CellKind::ClosedDoor));
+        if registered {
+            assert!(!sim.world.layout.doors[0].open);
+        }
+    }
+}
+
+#[test]
+fn room_cells_are_not_doors() {
+    let mut sim = sim(&["#####", "#000#", "#DDD#", "#111#", "#####"]);
+    let cells = sim.world.layout.cells.clone();
+    assert_eq!(sim.use_door(2, 1, true), Err(InteractError::NotADoor { x: 2, y: 1 }));
+    assert_eq!(sim.use_door(0, 0, false), Err(InteractError::NotADoor { x: 0, y: 0 }));
+    assert_eq!(sim.world.layout.cells, cells);
+}
+
+#[test]
+fn the_path_through_a_closed_door_opens_it() {
+    let mut sim = sim(&["#########", "#000D111#", "#########"]);
+    assert_eq!(sim.path_to(7, 1, false), None);
+    assert_eq!(sim.distance_to(7, 1), Some(6));
+    assert_eq!(sim.distance_to(9, 1), None);
+    let path = sim.path_to(7, 1, true).unwrap();
+    assert_eq!(
+        path,
+        [
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::Interact { dx: 1, dy: 0 },
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+            Action::MoveRight,
+        ]
+    );
+    for action in path {
+        sim.step(action).unwrap();
+    }
+    assert_eq!((sim.agent.x, sim.agent.y), (7, 1));
+    assert_eq!(kind(&sim, 4, 1), CellKind::OpenDoor);
+    // with the door open, the way back needs no closed doors
+    assert_eq!(sim.path_to(1, 1, false).map(|p| p.len()), Some(6));
+}
+
+#[test]
+fn generated_doors_open_onto_room_cells_on_both_sides_along_their_width() {
+    for (seed, (width, height, door_max)) in (0..200).zip([(20, 20, 2), (30, 24, 4), (16, 28, 3)].into_iter().cycle()) {
+        let world = generate(&GenOpts { seed, width, height, max_rooms: 8, door_max, ..Default::default() }).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        let layout = &world.layout;
+        let room = |x: usize, y: usize| layout.kind(x, y).and_then(CellKind::room);
+        // a room cell with another room cell next to it, so the doorway leads somewhere
+        let open = |x: usize, y: usize| {
+            room(x, y).is_some() && [(x + 1, y), (x, y + 1), (x.wrapping_sub(1), y), (x, y.wrapping_sub(1))].iter().any(|&(nx, ny)| room(nx, ny).is_some())
+        };
+        for door in layout.doors.iter().filter(|d| d.rooms.0 >= 0 && d.rooms.1 >= 0) {
+            for &i in &door.cells {
+                let (x, y) = (i % layout.width, i / layout.width);
+                let sides = if room(x.wrapping_sub(1), y).is_some() && room(x + 1, y).is_some() {
+                    [(x - 1, y), (x + 1, y)]
+                } else {
+                    [(x, y.wrapping_sub(1)), (x, y + 1)]
+                };
+                let rooms = sides.map(|(x, y)| room(x, y));
+                assert!(sides.iter().all(|&(x, y)| open(x, y)), "seed {}: door {} at ({}, {}) opens onto {:?}", seed, door.id, x, y, rooms);
+                let mut expected = [door.rooms.0 as usize, door.rooms.1 as usize];
+                let mut found = rooms.map(Option::unwrap);
+                expected.sort();
+                found.sort();
+                assert_eq!(found, expected, "seed {}: door {} at ({}, {})", seed, door.id, x, y);
+            }
+        }
+    }
+}
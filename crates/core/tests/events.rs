@@ -0,0 +1,71 @@
+use tidy_core::{Action, Layout, Object, ObjectSchema, SimEvent, Simulator, World};
+
+/// A kitchen with a cupboard (0) behind a closed door from a dining room with a trash can (1),
+/// a mug (2) and a rotten banana (3); the agent stands in the kitchen next to the door
+fn apartment() -> Simulator {
+    let layout = Layout::from_ascii(
+        &["#########", "#000D111#", "#000#111#", "#########"],
+        &["Kitchen", "Dining Room"],
+    )
+    .unwrap();
+    let objects = [("Cupboard", 1, 1), ("TrashCan", 7, 2), ("Mug", 6, 1), ("RottenBanana", 6, 2)]
+        .iter()
+        .enumerate()
+        .map(|(id, &(name, x, y))| Object::from_schema(id, ObjectSchema::find(name).unwrap(), x, y))
+        .collect();
+    Simulator::new(World::new(layout, objects), 3, 1).unwrap()
+}
+
+#[test]
+fn pick_and_place_reports_each_change_in_order() {
+    let mut sim = apartment();
+    let script = [
+        Action::Interact { dx: 1, dy: 0 },
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::PickUp,
+        Action::MoveDown,
+        Action::Drop,
+        Action::PickUpById(3),
+        Action::Interact { dx: 1, dy: 0 },
+        Action::PickUp,
+        Action::MoveUp,
+        Action::MoveLeft,
+        Action::MoveLeft,
+        Action::MoveLeft,
+        Action::MoveLeft,
+        Action::Interact { dx: -1, dy: 0 },
+    ];
+    for action in script {
+        sim.step(action).unwrap_or_else(|e| panic!("{:?}: {}", action, e));
+    }
+    let moved = |from, to| SimEvent::Moved { agent: 0, from, to };
+    assert_eq!(
+        sim.drain_events(),
+        [
+            SimEvent::DoorOpened { door: Some(0), x: 4, y: 1 },
+            moved((3, 1), (4, 1)),
+            moved((4, 1), (5, 1)),
+            moved((5, 1), (6, 1)),
+            SimEvent::PickedUp { agent: 0, id: 2 },
+            moved((6, 1), (6, 2)),
+            SimEvent::Dropped { agent: 0, id: 2, x: 6, y: 2 },
+            SimEvent::PickedUp { agent: 0, id: 3 },
+            SimEvent::PlacedInto { agent: 0, id: 3, container: 1 },
+            SimEvent::Disposed { id: 3, name: "RottenBanana".into() },
+            SimEvent::PickedUp { agent: 0, id: 2 },
+            moved((6, 2), (6, 1)),
+            moved((6, 1), (5, 1)),
+            moved((5, 1), (4, 1)),
+            moved((4, 1), (3, 1)),
+            moved((3, 1), (2, 1)),
+            SimEvent::PlacedInto { agent: 0, id: 2, container: 0 },
+        ]
+    );
+    assert_eq!(sim.drain_events(), []);
+
+    // failed actions change nothing, so they report nothing
+    assert!(sim.step(Action::Drop).is_err());
+    assert_eq!(sim.drain_events(), []);
+}
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use tidy_core::export::{episode_frames, render_episode};
+use tidy_core::render::render_rgba;
+use tidy_core::{scenarios, Action, EpisodeFormat, EpisodeRenderOpts, Simulator};
+
+fn simulator() -> Simulator {
+    scenarios::tutorial()[0].simulator()
+}
+
+fn actions() -> Vec<Action> {
+    [Action::MoveRight, Action::MoveDown, Action::MoveLeft, Action::MoveUp, Action::Noop].repeat(5)
+}
+
+#[test]
+fn frame_count_follows_frame_skip() {
+    let actions = actions();
+    let count = |opts: EpisodeRenderOpts| episode_frames(&mut simulator(), &actions, &opts).len();
+    assert_eq!(count(EpisodeRenderOpts::default()), 26);
+    assert_eq!(count(EpisodeRenderOpts { frame_skip: 5, ..Default::default() }), 6);
+    // the final state is kept even off the stride
+    assert_eq!(count(EpisodeRenderOpts { frame_skip: 10, ..Default::default() }), 4);
+    assert_eq!(count(EpisodeRenderOpts { max_frames: Some(6), ..Default::default() }), 6);
+    assert_eq!(episode_frames(&mut simulator(), &[], &EpisodeRenderOpts::default()).len(), 1);
+}
+
+#[test]
+fn last_frame_is_the_final_state() {
+    let opts = EpisodeRenderOpts { frame_skip: 4, ..Default::default() };
+    let mut sim = simulator();
+    let frames = episode_frames(&mut sim, &actions(), &opts);
+    assert_eq!(frames.first(), Some(&render_rgba(&simulator(), &opts.theme, opts.scale, opts.render)));
+    assert_eq!(frames.last(), Some(&render_rgba(&sim, &opts.theme, opts.scale, opts.render)));
+}
+
+#[test]
+fn apng_declares_every_frame() {
+    let opts = EpisodeRenderOpts { frame_skip: 5, ..Default::default() };
+    let png = render_episode(&mut simulator(), &actions(), &opts);
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let actl = png.windows(4).position(|w| w == b"acTL").expect("acTL chunk");
+    assert_eq!(u32::from_be_bytes(png[actl + 4..actl + 8].try_into().unwrap()), 6);
+    assert_eq!(png.windows(4).filter(|w| w == b"fcTL").count(), 6);
+    assert_eq!(png.windows(4).filter(|w| w == b"fdAT").count(), 5);
+}
+
+#[test]
+fn gif_decodes_to_the_rendered_frames() {
+    let opts = EpisodeRenderOpts { format: EpisodeFormat::Gif, frame_skip: 5, frame_delay_ms: 120, ..Default::default() };
+    let frames = episode_frames(&mut simulator(), &actions(), &opts);
+    let bytes = render_episode(&mut simulator(), &actions(), &opts);
+    let mut decoder = gif::DecodeOptions::new();
+    decoder.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = decoder.read_info(bytes.as_slice()).unwrap();
+    let mut decoded = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 12);
+        decoded.push(frame.buffer.to_vec());
+    }
+    // the renderer stays within the shared palette, so no color is approximated
+    assert_eq!(decoded, frames);
+    assert_eq!(EpisodeFormat::from_path(Path::new("out.GIF")), EpisodeFormat::Gif);
+    assert_eq!(EpisodeFormat::from_path(Path::new("out.png")), EpisodeFormat::Apng);
+}
@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::gen::ROOM_NAME_POOL;
+use tidy_core::nav::distance_field;
+use tidy_core::world::room_name_parts;
+use tidy_core::{
+    generate, generate_with_report, generate_with_schemas, generate_with_trace, CellKind, ContainerKind, GenError, GenJob, GenOpts,
+    GenProgress, GenTrace, ObjectSchema,
+};
+
+#[test]
+fn oversized_request_on_tiny_world_terminates_and_reports() {
+    let opts = GenOpts { width: 16, height: 12, max_rooms: 3, max_objects: 200, ..Default::default() };
+    for seed in 0..10 {
+        let start = Instant::now();
+        let (world, report) = generate_with_report(&GenOpts { seed, ..opts.clone() }).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5), "seed {} took {:?}", seed, start.elapsed());
+        assert_eq!(report.requested_objects, 200);
+        assert_eq!(report.placed_objects, world.objects.len(), "seed {}", seed);
+        assert!(report.placed_objects <= 200);
+        assert_eq!(report.exhausted_schemas.len(), report.exhausted_reasons.len());
+        let warned = report.warnings.iter().any(|w| w.contains("of 200 requested"));
+        assert_eq!(warned, report.placed_objects < 100, "seed {}: {:?}", seed, report.warnings);
+    }
+}
+
+#[test]
+fn schemas_are_retried_once_their_container_exists() {
+    let schemas = [
+        ObjectSchema {
+            name: "Toolbox",
+            capacity: 4,
+            container_kind: Some(ContainerKind::Enclosed),
+            ..Default::default()
+        },
+        ObjectSchema {
+            name: "Mallet",
+            pickable: true,
+            target: ObjectConstraint::InsideOf(Names::Static(&["Toolbox"])),
+            ..Default::default()
+        },
+    ];
+    for seed in 0..50 {
+        let opts = GenOpts { seed, max_objects: 10, ..Default::default() };
+        let world = generate_with_schemas(&opts, &schemas).unwrap();
+        assert!(world.objects.iter().any(|o| o.name == "Mallet"), "seed {} placed no Mallet", seed);
+    }
+}
+
+#[test]
+fn dropped_blockers_leave_the_report() {
+    let opts = GenOpts { width: 16, height: 12, max_objects: 200, ..Default::default() };
+    for seed in 0..100 {
+        let (world, report) = generate_with_report(&GenOpts { seed, ..opts.clone() }).unwrap();
+        assert_eq!(report.placed_objects, world.objects.len(), "seed {}", seed);
+        for p in &report.provenance {
+            assert!(world.object(p.object_id).is_some(), "seed {}: provenance of dropped object {}", seed, p.object_id);
+            if let Some(parent) = p.inside_parent {
+                assert!(world.object(parent).is_some(), "seed {}: dropped parent {}", seed, parent);
+            }
+        }
+    }
+}
+
+#[test]
+fn oversized_shells_are_refused_before_generation() {
+    for (width, height) in [(usize::MAX, 20), (20, usize::MAX), (tidy_core::gen::MAX_SHELL_SIDE + 1, 20)] {
+        let opts = GenOpts { width, height, ..Default::default() };
+        assert_eq!(opts.validate(), Err(GenError::ShellTooLarge { width, height }));
+    }
+    let opts = GenOpts { width: tidy_core::gen::MAX_SHELL_SIDE, ..Default::default() };
+    assert_eq!(opts.validate(), Ok(()));
+}
+
+#[test]
+fn misplaced_ratio_holds_over_misplaceable_objects() {
+    for ratio in [0.0, 0.2, 0.5, 1.0] {
+        let (mut misplaced, mut misplaceable) = (0, 0);
+        for seed in 0..100 {
+            let opts = GenOpts { seed, misplaced_ratio: Some(ratio), ..Default::default() };
+            let (world, report) = generate_with_report(&opts).unwrap();
+            let ids = world.misplaced_objects();
+            assert!(ids.iter().all(|id| report.misplaceable_objects.contains(id)), "seed {}", seed);
+            misplaced += ids.len();
+            misplaceable += report.misplaceable_objects.len();
+        }
+        let realized = misplaced as f32 / misplaceable as f32;
+        assert!((realized - ratio).abs() < 0.05, "ratio {} realized {} over {} objects", ratio, realized, misplaceable);
+    }
+}
+
+#[test]
+fn replaying_the_trace_rebuilds_the_walls() {
+    for seed in 0..30 {
+        let opts = GenOpts { seed, width: 24 + seed as usize % 8, max_rooms: 3 + seed as usize % 5, ..Default::default() };
+        let (world, trace) = generate_with_trace(&opts).unwrap();
+        let layout = &world.layout;
+        let walls: Vec<bool> = layout.cells.iter().map(|&c| CellKind::from_i8(c) == CellKind::Wall).collect();
+        assert_eq!(trace.replay_walls(), walls, "seed {}", seed);
+        assert_eq!(trace.splits.len() + 1, layout.room_names.len(), "seed {}", seed);
+        let sorted = |mut cells: Vec<usize>| {
+            cells.sort_unstable();
+            cells
+        };
+        let doors = sorted(layout.doors.iter().flat_map(|d| d.cells.iter().copied()).collect());
+        let carved = sorted(trace.doors.iter().flat_map(|d| d.cells.iter().copied()).collect());
+        assert_eq!(carved, doors, "seed {}", seed);
+
+        // the trace survives JSON and leaves the world as plain generation builds it
+        let json = serde_json::to_string(&trace).unwrap();
+        assert_eq!(serde_json::from_str::<GenTrace>(&json).unwrap().replay_walls(), walls);
+        assert_eq!(generate(&opts).unwrap().layout.cells, layout.cells, "seed {}", seed);
+    }
+}
+
+#[test]
+fn required_rooms_are_always_present() {
+    const REQUIRED: [&str; 2] = ["Kitchen", "Bathroom"];
+    let required = || REQUIRED.map(String::from).to_vec();
+    for seed in 0..100 {
+        let opts = GenOpts { seed, required_rooms: required(), ..Default::default() };
+        let world = generate(&opts).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        // an open-plan merge keeps the required name as one of its parts
+        let rooms: Vec<&str> = world.layout.room_names.iter().flat_map(|name| room_name_parts(name)).collect();
+        for room in &REQUIRED {
+            assert!(rooms.contains(room), "seed {}: no {} in {:?}", seed, room, world.layout.room_names);
+        }
+    }
+    let err = |opts: GenOpts| generate(&opts).unwrap_err();
+    assert_eq!(err(GenOpts { max_rooms: 1, required_rooms: required(), ..Default::default() }), GenError::TooFewRooms { rooms: 1, required: 2 });
+    assert_eq!(err(GenOpts { required_rooms: vec!["Attic".into()], ..Default::default() }), GenError::UnknownRoom("Attic".into()));
+}
+
+#[test]
+fn the_layout_seed_fixes_the_floor_plan_and_the_object_seed_the_objects() {
+    let placements = |world: &tidy_core::World| world.objects.iter().map(|o| (o.name.clone(), o.x, o.y)).collect::<Vec<_>>();
+    for layout_seed in 0..10 {
+        let opts = |seed: u64, object_seed: u64| GenOpts { seed, layout_seed: Some(layout_seed), object_seed: Some(object_seed), ..Default::default() };
+        let a = generate(&opts(0, 1)).unwrap();
+        let b = generate(&opts(5, 2)).unwrap();
+        assert_eq!(a.layout.cells, b.layout.cells, "layout seed {}", layout_seed);
+        assert_eq!(a.layout.room_names, b.layout.room_names, "layout seed {}", layout_seed);
+        assert_ne!(placements(&a), placements(&b), "layout seed {}", layout_seed);
+        // `seed` no longer matters once both are set
+        assert_eq!(placements(&generate(&opts(9, 1)).unwrap()), placements(&a), "layout seed {}", layout_seed);
+    }
+}
+
+#[test]
+fn gen_jobs_build_the_world_generate_builds() {
+    for seed in 0..20 {
+        let opts = GenOpts { seed, ..Default::default() };
+        let expected = generate(&opts).unwrap().to_json().unwrap();
+        // the slice size only changes how often the job yields
+        for budget in [1, 7, 1000] {
+            let mut job = GenJob::new(&opts);
+            let world = loop {
+                match job.run_for(budget) {
+                    GenProgress::InProgress(_) => {}
+                    GenProgress::Done(world) => break world,
+                    GenProgress::Failed(e) => panic!("seed {}: {}", seed, e),
+                }
+            };
+            assert_eq!(world.to_json().unwrap(), expected, "seed {} budget {}", seed, budget);
+        }
+    }
+}
+
+#[test]
+fn open_plan_layouts_validate_and_stay_connected() {
+    let mut merged = 0;
+    for seed in 0..100 {
+        let world = generate(&GenOpts { seed, max_rooms: 6, open_plan_prob: 1.0, ..Default::default() }).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        world.validate().unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        let layout = &world.layout;
+        merged += layout.room_names.iter().filter(|name| room_name_parts(name).count() > 1).count();
+        // every room keeps cells, and every room cell is reachable from every other
+        for room in 0..layout.room_names.len() {
+            assert!(layout.cells.contains(&(room as i8)), "seed {}: room {} has no cells", seed, room);
+        }
+        let start = layout.cells.iter().position(|&c| c >= 0).unwrap();
+        let dist = distance_field(layout, &[start]);
+        let cut_off = (0..layout.cells.len()).filter(|&i| layout.cells[i] >= 0 && dist[i].is_none()).count();
+        assert_eq!(cut_off, 0, "seed {}: {} room cells unreachable", seed, cut_off);
+    }
+    assert!(merged > 0, "no seed merged any rooms");
+}
+
+#[test]
+fn generated_kitchens_always_have_a_trash_can() {
+    let mut kitchens = 0;
+    for seed in 0..200 {
+        let world = generate(&GenOpts { seed, ..Default::default() }).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        let layout = &world.layout;
+        for (room, name) in layout.room_names.iter().enumerate() {
+            if !room_name_parts(name).any(|part| part == "Kitchen") {
+                continue;
+            }
+            kitchens += 1;
+            let has_trash_can = world.objects.iter().any(|o| o.name == "TrashCan" && layout.cells[o.y * layout.width + o.x] == room as i8);
+            assert!(has_trash_can, "seed {}: the {} has no trash can", seed, name);
+        }
+    }
+    assert!(kitchens >= 100, "only {} kitchens over 200 seeds", kitchens);
+}
+
+#[test]
+fn room_cells_and_the_other_cells_cover_the_grid_once() {
+    for seed in 0..30 {
+        let world = generate(&GenOpts { seed, max_rooms: 8, open_plan_prob: 0.3, ..Default::default() }).unwrap();
+        let layout = &world.layout;
+        let mut covered = vec![0; layout.cells.len()];
+        for room in 0..layout.room_names.len() {
+            let cells: Vec<(usize, usize)> = layout.room_cells(room).collect();
+            assert_eq!(cells.len(), layout.room_area(room), "seed {} room {}", seed, room);
+            let (x0, y0, x1, y1) = layout.room_bbox(room).unwrap();
+            assert!(cells.iter().any(|&(x, _)| x == x0) && cells.iter().any(|&(x, _)| x == x1), "seed {} room {}", seed, room);
+            assert!(cells.iter().any(|&(_, y)| y == y0) && cells.iter().any(|&(_, y)| y == y1), "seed {} room {}", seed, room);
+            for (x, y) in cells {
+                assert!((x0..=x1).contains(&x) && (y0..=y1).contains(&y));
+                assert_eq!(layout.kind_at(x, y), CellKind::Room(room as u8));
+                covered[y * layout.width + x] += 1;
+            }
+        }
+        // walls, doors, windows and outside fill the rest
+        for (x, y, kind) in layout.kinds_with_coords() {
+            let expected = usize::from(kind.room().is_some());
+            assert_eq!(covered[y * layout.width + x], expected, "seed {} at ({}, {}): {:?}", seed, x, y, kind);
+        }
+        let rooms: usize = (0..layout.room_names.len()).map(|r| layout.room_area(r)).sum();
+        assert_eq!(rooms + layout.kinds().filter(|k| k.room().is_none()).count(), layout.width * layout.height);
+        assert_eq!((layout.room_cells(layout.room_names.len()).count(), layout.room_bbox(layout.room_names.len())), (0, None));
+    }
+}
+
+#[test]
+fn twenty_rooms_on_a_large_grid_are_all_named_from_a_custom_pool() {
+    const POOL: [&str; 3] = ["Den", "Studio", "Loft"];
+    let mut numbered = false;
+    for seed in 0..10 {
+        for pool in [None, Some(POOL.map(String::from).to_vec())] {
+            let opts = GenOpts { seed, max_rooms: 20, width: 60, height: 60, room_name_pool: pool.clone(), ..Default::default() };
+            let world = generate(&opts).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+            let names = &world.layout.room_names;
+            // every room id in the grid has a name, and no two rooms share one
+            let rooms = world.layout.kinds().filter_map(|k| k.room()).max().unwrap() as usize + 1;
+            assert_eq!(names.len(), rooms, "seed {}: {:?}", seed, names);
+            let mut distinct = names.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), names.len(), "seed {}: {:?}", seed, names);
+            // past the end of the pool, names are numbered variants of pool names
+            let known: &[&str] = if pool.is_some() { &POOL } else { ROOM_NAME_POOL };
+            assert!(names.iter().flat_map(|name| room_name_parts(name)).all(|part| known.contains(&part)), "seed {}: {:?}", seed, names);
+            numbered |= names.len() > POOL.len() && pool.is_some();
+        }
+    }
+    assert!(numbered, "no seed needed more rooms than the custom pool has names");
+}
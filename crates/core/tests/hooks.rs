@@ -0,0 +1,79 @@
+use tidy_core::action::ActionError;
+use tidy_core::{Action, HookDecision, Layout, MoveError, Object, ObjectSchema, PickupTax, RoomBan, SimHook, Simulator, StepOutcome, World};
+
+/// A Kitchen and a Bathroom joined by an open door, the agent at the Kitchen's west end
+/// on a pickable cup
+fn two_rooms() -> Simulator {
+    let layout = Layout::from_ascii(&["########", "#000d11#", "########"], &["Kitchen", "Bathroom"]).unwrap();
+    let cup = ObjectSchema { name: "Cup", pickable: true, ..Default::default() };
+    Simulator::new(World::new(layout, vec![Object::from_schema(0, &cup, 1, 1)]), 1, 1).unwrap()
+}
+
+#[test]
+fn room_ban_stops_entering_the_room() {
+    let mut sim = two_rooms();
+    sim.add_hook(Box::new(RoomBan { room: "Bathroom".into() }));
+    for _ in 0..3 {
+        sim.step(Action::MoveRight).unwrap();
+    }
+    assert_eq!((sim.agent.x, sim.agent.y), (4, 1));
+    let denied = ActionError::Denied("entering the Bathroom is not allowed".into());
+    assert_eq!(sim.step(Action::MoveRight).unwrap_err(), denied);
+    // the legacy methods go through `step`, hooks included
+    assert_eq!(sim.right(), Err(MoveError::Denied("entering the Bathroom is not allowed".into())));
+    assert_eq!((sim.agent.x, sim.agent.y), (4, 1));
+    sim.step(Action::MoveLeft).unwrap();
+
+    sim.clear_hooks();
+    assert_eq!(sim.hook_count(), 0);
+    sim.step(Action::MoveRight).unwrap();
+    sim.step(Action::MoveRight).unwrap();
+    assert_eq!((sim.agent.x, sim.agent.y), (5, 1));
+}
+
+#[test]
+fn pickup_tax_charges_every_pick_up() {
+    let mut sim = two_rooms();
+    sim.add_hook(Box::new(PickupTax::new(0.5)));
+    assert_eq!(sim.step(Action::PickUp).unwrap().reward, -0.5);
+    assert_eq!(sim.step(Action::MoveRight).unwrap().reward, 0.0);
+    assert_eq!(sim.step(Action::Drop).unwrap().reward, 0.0);
+    assert_eq!(sim.step(Action::Interact { dx: 0, dy: 0 }).unwrap().reward, -0.5);
+    assert!(sim.holding.is_some());
+}
+
+/// Turns every move right into a move left and pays 1 per action
+struct Mirror;
+
+impl SimHook for Mirror {
+    fn before_action(&mut self, _sim: &Simulator, action: &Action) -> HookDecision {
+        match action {
+            Action::MoveRight => HookDecision::Replace(Action::MoveLeft),
+            _ => HookDecision::Allow,
+        }
+    }
+
+    fn after_action(&mut self, _sim: &Simulator, _outcome: &StepOutcome) -> f32 {
+        1.0
+    }
+}
+
+#[test]
+fn replaced_actions_run_and_are_seen_by_later_hooks() {
+    let mut sim = two_rooms();
+    for _ in 0..3 {
+        sim.step(Action::MoveRight).unwrap();
+    }
+    // in the doorway, a move right would leave the Kitchen behind, but the ban sees the
+    // move left it was replaced with
+    sim.add_hook(Box::new(Mirror));
+    sim.add_hook(Box::new(RoomBan { room: "Kitchen".into() }));
+    assert!(matches!(sim.step(Action::MoveRight), Err(ActionError::Denied(_))));
+
+    sim.clear_hooks();
+    sim.add_hook(Box::new(Mirror));
+    let outcome = sim.step(Action::MoveRight).unwrap();
+    assert_eq!(outcome.action, Action::MoveLeft);
+    assert_eq!(outcome.reward, 1.0);
+    assert_eq!((sim.agent.x, sim.agent.y), (3, 1));
+}
@@ -0,0 +1,55 @@
+use tidy_core::checkpoint::SimState;
+use tidy_core::world::{interned_bytes, MAX_INTERNED_BYTES};
+use tidy_core::schema::SchemaError;
+use tidy_core::{generate, GenOpts, ObjectSchema, Simulator, World};
+
+// one test, since the interner is shared by the whole process
+#[test]
+fn worlds_load_without_interning_and_schema_files_within_the_budget() {
+    let world = generate(&GenOpts { seed: 3, ..Default::default() }).unwrap();
+    let json = world.to_json().unwrap();
+    let loaded = World::from_json(&json).unwrap();
+    assert_eq!(loaded.layout.cells, world.layout.cells);
+    assert_eq!(loaded.layout.room_names, world.layout.room_names);
+    for o in &world.objects {
+        let l = loaded.object(o.id).unwrap();
+        assert_eq!((&l.name, l.x, l.y, &l.contents), (&o.name, o.x, o.y, &o.contents));
+    }
+
+    // agent state survives next to the world
+    let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    let (x, y) = (start % world.layout.width, start / world.layout.width);
+    let mut sim = Simulator::new(world, x, y).unwrap();
+    let _ = sim.up();
+    let _ = sim.turn_left();
+    let state: SimState = serde_json::from_str(&serde_json::to_string(&sim.state()).unwrap()).unwrap();
+    let mut restored = Simulator::new(loaded, x, y).unwrap();
+    restored.restore(&state).unwrap();
+    assert_eq!((restored.agent.x, restored.agent.y, restored.agent.facing), (sim.agent.x, sim.agent.y, sim.agent.facing));
+
+    // worlds own their names: fresh ones well past the schema budget load and leak nothing
+    let before = interned_bytes();
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    for i in 0..(MAX_INTERNED_BYTES >> 16) + 2 {
+        value["objects"][0]["description"] = format!("{}{}", i, "x".repeat(1 << 16)).into();
+        value["objects"][0]["name"] = format!("Custom{}", i).into();
+        value["layout"]["room_names"][0] = format!("Room {}", i).into();
+        let loaded = World::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.layout.room_names[0], format!("Room {}", i));
+    }
+    assert_eq!(interned_bytes(), before);
+
+    // schema files do intern, and fresh names are refused once the budget is spent
+    let mut refused = None;
+    for i in 0..(MAX_INTERNED_BYTES >> 16) + 2 {
+        let json = serde_json::json!([{ "name": format!("Schema{}", i), "description": format!("{}{}", i, "x".repeat(1 << 16)) }]);
+        if let Err(e) = ObjectSchema::from_json(&json.to_string()) {
+            refused = Some(e);
+            break;
+        }
+    }
+    assert!(matches!(refused, Some(SchemaError::TooManyNames { .. })), "{:?}", refused);
+    assert!(interned_bytes() <= MAX_INTERNED_BYTES);
+    // names already kept still load
+    ObjectSchema::from_json(r#"[{"name": "Schema0"}]"#).unwrap();
+}
@@ -0,0 +1,72 @@
+use tidy_core::language::{instruction_for, task_instructions};
+use tidy_core::object::humanize;
+use tidy_core::{generate, GenOpts, Layout, Object, ObjectSchema, World};
+
+fn object(id: usize, name: &str, x: usize, y: usize) -> Object {
+    Object::from_schema(id, ObjectSchema::find(name).unwrap(), x, y)
+}
+
+#[test]
+fn task_instructions_of_fixed_seeds() {
+    let instructions = |seed: u64| task_instructions(&generate(&GenOpts { seed, ..Default::default() }).unwrap());
+    assert_eq!(
+        instructions(7),
+        [
+            "Put the keyboard into the desk in the Study.",
+            "Put the umbrella into the storage box in the Office.",
+            "Put the wallet into the storage box in the Office.",
+            "Put the bucket into the storage box in the Office.",
+        ]
+    );
+    assert_eq!(
+        instructions(11),
+        [
+            "Put the water bottle into the refrigerator in the Kitchen.",
+            "Put the spoiled lettuce into the trash can in the Kitchen.",
+            "Put the razor into the bathroom cabinet in the Bathroom.",
+            "Put the moldy bread slice into the trash can in the Kitchen.",
+        ]
+    );
+}
+
+#[test]
+fn instructions_name_the_nearest_container_or_fall_back_to_the_target() {
+    let layout = Layout::from_ascii(&["###########", "#0000d1111#", "###########"], &["Kitchen", "Living Room"]).unwrap();
+    let cupboards = |mug_x: usize| {
+        World::new(layout.clone(), vec![object(0, "Cupboard", 1, 1), object(1, "Cupboard", 9, 1), object(2, "Mug", mug_x, 1)])
+    };
+    assert_eq!(instruction_for(&cupboards(7), 2).as_deref(), Some("Put the mug into the cupboard in the Living Room."));
+    assert_eq!(instruction_for(&cupboards(2), 2).as_deref(), Some("Put the mug into the cupboard in the Kitchen."));
+    // no cupboard was generated
+    let world = World::new(layout.clone(), vec![object(0, "Mug", 7, 1)]);
+    assert_eq!(instruction_for(&world, 0).as_deref(), Some("Put the mug inside a cupboard."));
+    // unknown ids, fixtures and objects in place get none
+    let mut world = cupboards(1);
+    world.objects[0].contents.push(2);
+    assert_eq!(instruction_for(&world, 2), None);
+    assert_eq!(instruction_for(&world, 0), None);
+    assert_eq!(instruction_for(&world, 9), None);
+    assert!(task_instructions(&world).is_empty());
+}
+
+#[test]
+fn custom_surfaces_hold_their_contents_on_them() {
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Workshop"]).unwrap();
+    let schemas = ObjectSchema::from_json(r#"[{"name": "Workbench", "icon": "workbench", "capacity": 2, "container_kind": "surface"}]"#).unwrap();
+    let mut bench = Object::from_schema(0, &schemas[0], 2, 1);
+    bench.contents.push(1);
+    let world = World::new(layout, vec![bench, object(1, "Mug", 2, 1)]);
+    let sim = tidy_core::Simulator::new(world, 1, 1).unwrap();
+    assert!(!sim.world.objects[0].closable());
+    let text = tidy_core::language::describe_surroundings(&sim, 2);
+    assert!(text.contains("a workbench with a mug on it"), "{}", text);
+}
+
+#[test]
+fn goals_and_names_read_as_words() {
+    assert_eq!(humanize("TVStand"), "TV stand");
+    assert_eq!(humanize("DirtySilverware"), "dirty silverware");
+    let layout = Layout::from_ascii(&["#####", "#000#", "#####"], &["Living Room"]).unwrap();
+    let sim = tidy_core::Simulator::new(World::new(layout, vec![object(0, "TVStand", 3, 1)]), 1, 1).unwrap();
+    assert_eq!(sim.describe_goal(0).as_deref(), Some("The TV stand has an unknown destination."));
+}
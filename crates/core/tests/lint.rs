@@ -0,0 +1,57 @@
+use tidy_core::gen::ROOM_NAME_POOL;
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::{lint_schemas, ContainerKind, ObjectSchema};
+
+/// A copy of the catalog Mug under another name
+fn mug(name: &'static str) -> ObjectSchema {
+    ObjectSchema { name, ..ObjectSchema::find("Mug").unwrap().clone() }
+}
+
+fn kinds(schemas: &[ObjectSchema], schema: &str) -> Vec<&'static str> {
+    lint_schemas(schemas, ROOM_NAME_POOL).iter().filter(|l| l.schema() == schema).map(|l| l.kind()).collect()
+}
+
+#[test]
+fn the_default_catalog_has_no_dangling_names() {
+    // decor such as rugs and curtains spawns where it belongs; everything else must be clean
+    let lints: Vec<_> = ObjectSchema::lint_defaults().into_iter().filter(|l| l.kind() != "always_tidy").collect();
+    assert_eq!(lints, []);
+}
+
+#[test]
+fn each_mistake_raises_its_lint() {
+    let mut schemas = ObjectSchema::catalog().to_vec();
+    schemas.extend([
+        mug("Plain"),
+        mug("Twice"),
+        mug("Twice"),
+        ObjectSchema { constraint: ObjectConstraint::InsideOf(Names::Static(&["Teapot"])), ..mug("Dangling") },
+        ObjectSchema { constraint: ObjectConstraint::InsideOf(Names::Static(&["Plain"])), ..mug("IntoAMug") },
+        ObjectSchema { target: ObjectConstraint::InRoomNamed(Names::Static(&["Attic"])), ..mug("Attic") },
+        ObjectSchema {
+            constraint: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+            target: ObjectConstraint::Or(vec![ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])), ObjectConstraint::InRoomNamed(Names::Static(&["Office"]))]),
+            ..mug("Tidy")
+        },
+        ObjectSchema { icon: "Mug Icon", ..mug("Icon") },
+        ObjectSchema { capacity: 3, container_kind: None, ..mug("Bowl") },
+        ObjectSchema { starts_closed: true, container_kind: Some(ContainerKind::Surface), capacity: 2, ..mug("Tray") },
+    ]);
+    assert_eq!(kinds(&schemas, "Cupboard"), [] as [&str; 0]);
+    assert_eq!(kinds(&schemas, "Plain"), [] as [&str; 0]);
+    assert_eq!(kinds(&schemas, "Twice"), ["duplicate_name"]);
+    assert_eq!(kinds(&schemas, "Dangling"), ["unknown_object"]);
+    assert_eq!(kinds(&schemas, "IntoAMug"), ["zero_capacity_container"]);
+    assert_eq!(kinds(&schemas, "Attic"), ["unknown_room", "unsatisfiable_target"]);
+    assert_eq!(kinds(&schemas, "Tidy"), ["always_tidy"]);
+    assert_eq!(kinds(&schemas, "Icon"), ["invalid_icon"]);
+    assert_eq!(kinds(&schemas, "Bowl"), ["container_kind_mismatch"]);
+    assert_eq!(kinds(&schemas, "Tray"), ["container_kind_mismatch"]);
+}
+
+#[test]
+fn custom_rooms_load_and_are_linted_against_the_pool_in_use() {
+    let schemas = ObjectSchema::from_json(r#"[{"name": "Ledger", "icon": "ledger", "pickable": true, "target": {"in_room_named": ["Vault"]}}]"#).unwrap();
+    assert_eq!(kinds(&schemas, "Ledger"), ["unknown_room", "unsatisfiable_target"]);
+    assert_eq!(lint_schemas(&schemas, &["Vault", "Hall"]), []);
+}
@@ -0,0 +1,62 @@
+use tidy_core::{Action, ActionError, InteractError, Layout, MoveError, Object, ObjectSchema, Simulator, World};
+
+/// A corridor with a DecorativeBowl holding a Sculpture at (2, 1) and a loose Sculpture at (4, 1)
+fn corridor() -> World {
+    let layout = Layout::from_ascii(&["#######", "#00000#", "#######"], &["Hallway"]).unwrap();
+    let bowl = ObjectSchema::find("DecorativeBowl").unwrap();
+    let sculpture = ObjectSchema::find("Sculpture").unwrap();
+    let mut objects =
+        vec![Object::from_schema(0, bowl, 2, 1), Object::from_schema(1, sculpture, 2, 1), Object::from_schema(2, sculpture, 4, 1)];
+    objects[0].contents.push(1);
+    World::new(layout, objects)
+}
+
+#[test]
+fn agents_hand_objects_over_through_the_floor() {
+    let mut sim = Simulator::new_multi(corridor(), &[(3, 1), (5, 1)]).unwrap();
+    sim.step_agent(1, Action::MoveLeft).unwrap();
+    sim.step_agent(1, Action::PickUp).unwrap();
+    assert_eq!(sim.holding_by_id(1).map(|o| o.id), Some(2));
+    assert!(sim.holding_by_id(0).is_none());
+    // agent 0 stands between, so agent 1 passes the sculpture over by dropping it next to it
+    assert!(matches!(sim.step_agent(1, Action::MoveLeft), Err(ActionError::Move(MoveError::BlockedByAgent(0)))));
+    sim.step_agent(1, Action::Drop).unwrap();
+    sim.step_agent(1, Action::MoveRight).unwrap();
+    sim.right().unwrap();
+    sim.pick_up().unwrap();
+    assert_eq!(sim.holding_by_id(0).map(|o| o.id), Some(2));
+    assert!(sim.holding_by_id(1).is_none());
+    sim.world.validate().unwrap();
+}
+
+#[test]
+fn a_carried_container_is_out_of_reach_of_other_agents() {
+    let mut sim = Simulator::new_multi(corridor(), &[(2, 1), (1, 1)]).unwrap();
+    sim.pick_up().unwrap();
+    assert_eq!(sim.carried_by_id(0).len(), 1);
+    assert!(sim.carried_by_id(1).is_empty());
+    // agent 1 can neither take the sculpture from the bowl nor find it on the cell
+    assert!(sim.step_agent(1, Action::PickUpById(1)).is_err());
+    let reach = sim.step_agent(1, Action::Interact { dx: 1, dy: 0 });
+    assert!(matches!(reach, Err(ActionError::Interact(InteractError::NothingThere))), "{:?}", reach);
+
+    // an action of agent 1 leaves agent 0's carried contents with agent 0
+    let state = sim.state();
+    assert_eq!(state.carried.len(), 1);
+    sim.step_agent(1, Action::TurnLeft).unwrap();
+    assert_eq!(sim.carried_by_id(0).len(), 1);
+    sim.restore(&state).unwrap();
+
+    sim.right().unwrap();
+    sim.drop().unwrap();
+    sim.world.validate().unwrap();
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+    assert_eq!(sim.world.object(1).map(|o| (o.x, o.y)), Some((3, 1)));
+    // once agent 0 steps away, agent 1 can take it out
+    sim.right().unwrap();
+    sim.step_agent(1, Action::MoveRight).unwrap();
+    sim.step_agent(1, Action::MoveRight).unwrap();
+    sim.step_agent(1, Action::PickUpById(1)).unwrap();
+    assert!(sim.world.object(0).unwrap().contents.is_empty());
+    sim.world.validate().unwrap();
+}
@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+
+use tidy_core::nav::articulation_cells;
+use tidy_core::{generate, CellKind, GenOpts, Layout};
+
+/// Cells of the graph `articulation_cells` works on: the room's cells outside `blocked`, and
+/// the doors next to the room
+fn room_graph(layout: &Layout, room: i8, blocked: &[bool]) -> Vec<bool> {
+    let w = layout.width;
+    let touches_room = |i: usize| [i.wrapping_sub(w), i + w, i.wrapping_sub(1), i + 1].iter().any(|&n| layout.cells.get(n) == Some(&room));
+    (0..layout.cells.len())
+        .map(|i| (layout.cells[i] == room && !blocked[i]) || (CellKind::from_i8(layout.cells[i]).is_door() && touches_room(i)))
+        .collect()
+}
+
+/// Connected components of the marked cells, by breadth-first search
+fn components(layout: &Layout, nodes: &[bool]) -> usize {
+    let w = layout.width;
+    let mut seen = vec![false; nodes.len()];
+    let mut count = 0;
+    for start in 0..nodes.len() {
+        if !nodes[start] || seen[start] {
+            continue;
+        }
+        count += 1;
+        seen[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(i) = queue.pop_front() {
+            let (x, y) = (i % w, i / w);
+            let next = [(y > 0).then(|| i - w), (y + 1 < layout.height).then(|| i + w), (x > 0).then(|| i - 1), (x + 1 < w).then(|| i + 1)];
+            for n in next.into_iter().flatten() {
+                if nodes[n] && !seen[n] {
+                    seen[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+    count
+}
+
+#[test]
+fn articulation_cells_are_exactly_the_cells_that_cut_narrow_rooms_apart() {
+    let corridor = Layout::from_ascii(&["#########", "#1d000d2#", "#########"], &["Hall", "Study", "Den"]).unwrap();
+    let bend = Layout::from_ascii(
+        &["#######", "#00####", "#00####", "#00000#", "#00000#", "###d###", "#11111#", "#######"],
+        &["Hallway", "Bedroom"],
+    )
+    .unwrap();
+    let w = bend.width;
+    let mut furnished = vec![false; bend.cells.len()];
+    // furniture along the bend leaves a one-cell passage
+    for (x, y) in [(2, 2), (3, 3), (4, 3)] {
+        furnished[y * w + x] = true;
+    }
+    let cases = [(&corridor, 0, vec![false; corridor.cells.len()]), (&bend, 0, vec![false; bend.cells.len()]), (&bend, 0, furnished), (&bend, 1, vec![false; bend.cells.len()])];
+    for (case, (layout, room, blocked)) in cases.iter().enumerate() {
+        let nodes = room_graph(layout, *room, blocked);
+        let before = components(layout, &nodes);
+        let cut = articulation_cells(layout, *room, blocked);
+        for i in (0..nodes.len()).filter(|&i| layout.cells[i] == *room && !blocked[i]) {
+            let mut without = nodes.clone();
+            without[i] = false;
+            let splits = components(layout, &without) > before;
+            assert_eq!(cut.contains(&i), splits, "case {} at ({}, {})", case, i % layout.width, i / layout.width);
+        }
+    }
+    // a one-wide corridor between two doors is all cut cells, the cell in front of a lone door always one
+    assert_eq!(articulation_cells(&corridor, 0, &cases[0].2), [12, 13, 14]);
+    assert!(articulation_cells(&bend, 1, &cases[3].2).contains(&(6 * w + 3)));
+}
+
+#[test]
+fn blocking_furniture_never_cuts_a_room_of_a_thin_apartment_apart() {
+    for (seed, (width, height)) in (0..40).zip([(40, 8), (8, 40), (30, 10), (12, 12)].into_iter().cycle()) {
+        let world = generate(&GenOpts { seed, width, height, max_objects: 80, ..Default::default() }).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+        let layout = &world.layout;
+        // the agent walks past furniture that does not block movement
+        let mut blocked = vec![false; layout.cells.len()];
+        for o in world.objects.iter().filter(|o| o.blocks_movement) {
+            blocked[o.y * layout.width + o.x] = true;
+        }
+        for room in 0..layout.room_names.len() as i8 {
+            let nodes = room_graph(layout, room, &blocked);
+            assert!(components(layout, &nodes) <= 1, "seed {} ({}x{}): room {} is cut apart", seed, width, height, room);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use tidy_core::{generate, GenOpts, ObjectId, PerturbError, Perturbation, World, WorldDiff};
+
+fn world(seed: u64) -> World {
+    generate(&GenOpts { seed, max_objects: 25, ..Default::default() }).unwrap()
+}
+
+/// Id of the container holding `id`, if any
+fn parent(world: &World, id: ObjectId) -> Option<ObjectId> {
+    world.objects.iter().find(|o| o.contents.contains(&id)).map(|o| o.id)
+}
+
+/// Pickables without contents, in no container
+fn loose(world: &World) -> Vec<ObjectId> {
+    world.objects.iter().filter(|o| o.pickable && o.contents.is_empty() && parent(world, o.id).is_none()).map(|o| o.id).collect()
+}
+
+/// Objects a diff entry is about
+fn subject(diff: &WorldDiff) -> Option<ObjectId> {
+    match *diff {
+        WorldDiff::ObjectAdded(id) | WorldDiff::ObjectRemoved(id) | WorldDiff::ContentsChanged(id) => Some(id),
+        WorldDiff::ObjectMoved { id, .. } => Some(id),
+        _ => None,
+    }
+}
+
+#[test]
+fn move_object_moves_only_that_object() {
+    let mut moved = 0;
+    for seed in 0..10 {
+        let world = world(seed);
+        for id in world.objects.iter().filter(|o| o.pickable).map(|o| o.id) {
+            let Ok(after) = world.perturb(Perturbation::MoveObject { id }, seed) else { continue };
+            moved += 1;
+            let diffs = world.diff(&after);
+            // to another cell, or into another container on the same one
+            let place = |w: &World| (w.object(id).map(|o| (o.x, o.y)), parent(w, id));
+            assert_ne!(place(&world), place(&after), "seed {} moving {}", seed, id);
+            // besides the object (and what it carries), only the containers it left and entered change
+            let carried = &world.object(id).unwrap().contents;
+            let containers = [parent(&world, id), parent(&after, id)];
+            for d in &diffs {
+                let subject = subject(d).unwrap_or_else(|| panic!("seed {}: {:?}", seed, d));
+                assert!(
+                    subject == id || carried.contains(&subject) || containers.contains(&Some(subject)),
+                    "seed {} moving {}: {:?}",
+                    seed,
+                    id,
+                    diffs
+                );
+                assert!(!matches!(d, WorldDiff::ContentsChanged(_)) || containers.contains(&Some(subject)));
+            }
+            // the same seed picks the same cell
+            assert!(after.diff(&world.perturb(Perturbation::MoveObject { id }, seed).unwrap()).is_empty());
+        }
+    }
+    assert!(moved > 0);
+}
+
+#[test]
+fn swap_and_remove_change_only_the_named_objects() {
+    // the first world with two loose objects on different cells
+    let (world, a, b) = (0..)
+        .find_map(|seed| {
+            let world = world(seed);
+            let loose = loose(&world);
+            let at = |id: ObjectId| world.object(id).map(|o| (o.x, o.y));
+            let b = *loose.iter().find(|&&b| at(b) != at(loose[0]))?;
+            Some((world, loose[0], b))
+        })
+        .unwrap();
+    let position = |id: ObjectId| world.object(id).map(|o| (o.x, o.y)).unwrap();
+    let swapped = world.perturb(Perturbation::SwapObjects { a, b }, 0).unwrap();
+    assert_eq!(
+        world.diff(&swapped),
+        [
+            WorldDiff::ObjectMoved { id: a.min(b), from: position(a.min(b)), to: position(a.max(b)) },
+            WorldDiff::ObjectMoved { id: a.max(b), from: position(a.max(b)), to: position(a.min(b)) },
+        ]
+    );
+    assert_eq!(world.diff(&world.perturb(Perturbation::RemoveObject { id: a }, 0).unwrap()), [WorldDiff::ObjectRemoved(a)]);
+
+    // a contained object leaves its container's contents too
+    let contained = world.objects.iter().flat_map(|o| o.contents.iter().map(move |&c| (o.id, c))).next();
+    if let Some((container, id)) = contained {
+        let mut diffs = world.diff(&world.perturb(Perturbation::RemoveObject { id }, 0).unwrap());
+        diffs.sort_by_key(|d| format!("{:?}", d));
+        assert_eq!(diffs, [WorldDiff::ContentsChanged(container), WorldDiff::ObjectRemoved(id)]);
+    }
+}
+
+#[test]
+fn shuffled_room_names_are_the_only_change() {
+    let world = world(2);
+    let after = world.perturb(Perturbation::ShuffleRoomNames, 9).unwrap();
+    let diffs = world.diff(&after);
+    assert!(!diffs.is_empty());
+    assert!(diffs.iter().all(|d| matches!(d, WorldDiff::RoomName { .. })), "{:?}", diffs);
+    let mut before = world.layout.room_names.clone();
+    let mut names = after.layout.room_names.clone();
+    before.sort_unstable();
+    names.sort_unstable();
+    assert_eq!(names, before);
+}
+
+#[test]
+fn bad_perturbations_are_refused() {
+    let world = world(0);
+    let id = loose(&world)[0];
+    assert_eq!(world.perturb(Perturbation::RemoveObject { id: 10_000 }, 0).unwrap_err(), PerturbError::UnknownObject(10_000));
+    assert!(matches!(world.perturb(Perturbation::SwapObjects { a: id, b: id }, 0), Err(PerturbError::InvalidPerturbation(_))));
+}
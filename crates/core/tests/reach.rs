@@ -0,0 +1,89 @@
+use tidy_core::{InteractError, Layout, MoveError, Object, ObjectSchema, Simulator, World};
+
+/// A corridor with a Cupboard at (1, 1), a Mug at (3, 1) and the agent at (3, 1)
+fn corridor() -> Simulator {
+    let layout = Layout::from_ascii(&["#######", "#00000#", "#######"], &["Hallway"]).unwrap();
+    let cupboard = ObjectSchema::find("Cupboard").unwrap();
+    let mug = ObjectSchema::find("Mug").unwrap();
+    let world = World::new(layout, vec![Object::from_schema(0, cupboard, 1, 1), Object::from_schema(1, mug, 3, 1)]);
+    Simulator::new(world, 3, 1).unwrap()
+}
+
+#[test]
+fn place_into_needs_the_container_within_reach() {
+    let mut sim = corridor();
+    sim.pick_up().unwrap();
+    assert_eq!(sim.place_into(0), Err(MoveError::TooFar));
+    assert!(sim.holding.is_some());
+    // from the neighbouring cell
+    sim.left().unwrap();
+    sim.place_into(0).unwrap();
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+
+    // from the container's own cell
+    sim.pick_up_by_id(1).unwrap_err();
+    sim.left().unwrap();
+    sim.pick_up_by_id(1).unwrap();
+    sim.place_into(0).unwrap();
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+}
+
+#[test]
+fn place_into_reaches_anywhere_without_strict_reach() {
+    let mut sim = corridor();
+    sim.strict_reach = false;
+    sim.pick_up().unwrap();
+    sim.place_into(0).unwrap();
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+}
+
+#[test]
+fn interact_needs_the_target_within_reach() {
+    let too_far = Err(InteractError::Move(MoveError::TooFar));
+    let mut sim = corridor();
+    sim.right().unwrap();
+    sim.right().unwrap();
+    // picking the mug up two cells away
+    assert_eq!(sim.interact(-2, 0), too_far);
+    assert!(sim.holding.is_none());
+    assert_eq!(sim.interact(-1, 0), Err(InteractError::NothingThere));
+    sim.left().unwrap();
+    sim.interact(-1, 0).unwrap();
+    assert_eq!(sim.holding.as_ref().map(|o| o.id), Some(1));
+
+    // dropping two cells away
+    assert_eq!(sim.interact(-2, 0), too_far);
+    assert!(sim.holding.is_some());
+    sim.interact(1, 0).unwrap();
+    assert_eq!(sim.world.object(1).map(|o| (o.x, o.y)), Some((5, 1)));
+
+    // the rule is off with strict_reach
+    sim.strict_reach = false;
+    sim.interact(1, 0).unwrap();
+    sim.interact(-3, 0).unwrap();
+    assert_eq!(sim.world.object(1).map(|o| (o.x, o.y)), Some((1, 1)));
+    assert_eq!(sim.world.object(0).unwrap().contents, [1]);
+}
+
+#[test]
+fn inspect_follows_reach_and_closed_containers() {
+    let mut sim = corridor();
+    // the cupboard is two cells away
+    assert_eq!(sim.inspect(0), Err(MoveError::TooFar));
+    sim.strict_reach = false;
+    assert!(sim.inspect(0).is_ok());
+    assert!(sim.is_target_known(0));
+
+    // a mug in a closed cupboard cannot be read until the cupboard is opened
+    let mut mug = sim.world.detach_object(1).unwrap();
+    (mug.x, mug.y) = (1, 1);
+    sim.world.add_object(mug);
+    let cupboard = sim.world.objects.iter_mut().find(|o| o.id == 0).unwrap();
+    cupboard.contents.push(1);
+    cupboard.open = false;
+    sim.closed_containers = true;
+    assert_eq!(sim.inspect(1), Err(MoveError::ContainerClosed));
+    assert!(!sim.is_target_known(1));
+    sim.toggle_container(0).unwrap();
+    assert!(sim.inspect(1).is_ok());
+}
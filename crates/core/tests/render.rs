@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use tidy_core::render::{render_png, render_rgba, room_letter, RenderOpts, Theme};
+use tidy_core::{generate, CellKind, GenOpts, Simulator};
+
+/// Width, height and RGBA pixels of a PNG written by `render_png`: one IDAT chunk of stored
+/// deflate blocks, each row behind a zero filter byte
+fn decode_png(png: &[u8]) -> (usize, usize, Vec<u8>) {
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let (mut at, mut size, mut zlib) = (8, (0, 0), Vec::new());
+    while at < png.len() {
+        let len = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+        let data = &png[at + 8..at + 8 + len];
+        match &png[at + 4..at + 8] {
+            b"IHDR" => {
+                let side = |i: usize| u32::from_be_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+                assert_eq!(&data[8..10], &[8, 6], "8-bit RGBA");
+                size = (side(0), side(4));
+            }
+            b"IDAT" => zlib.extend_from_slice(data),
+            _ => {}
+        }
+        at += len + 12;
+    }
+    let (mut raw, mut block) = (Vec::new(), 2);
+    loop {
+        let last = zlib[block] & 1 == 1;
+        let len = u16::from_le_bytes([zlib[block + 1], zlib[block + 2]]) as usize;
+        raw.extend_from_slice(&zlib[block + 5..block + 5 + len]);
+        block += 5 + len;
+        if last {
+            break;
+        }
+    }
+    let rgba = raw.chunks(size.0 * 4 + 1).flat_map(|row| {
+        assert_eq!(row[0], 0, "unfiltered rows");
+        row[1..].to_vec()
+    });
+    (size.0, size.1, rgba.collect())
+}
+
+#[test]
+fn png_and_rgba_renders_agree_for_every_theme() {
+    let themes = [Theme::default(), Theme::high_contrast(), Theme::dark(), Theme::colorblind_safe()];
+    let opts = [RenderOpts::default(), RenderOpts { glyphs: true, pattern_fill: true, room_names: false }];
+    for seed in 0..4 {
+        let world = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+        let layout = &world.layout;
+        for (t, theme) in themes.iter().enumerate() {
+            for &opts in &opts {
+                let scale = 6;
+                let rgba = render_rgba(&world, theme, scale, opts);
+                let (w, h, decoded) = decode_png(&render_png(&world, theme, scale, opts));
+                assert_eq!((w, h), (layout.width * scale, layout.height * scale));
+                assert!(decoded == rgba, "seed {} theme {} {:?}: PNG pixels differ from RGBA", seed, t, opts);
+            }
+            // at one pixel per cell, every cell without objects is the theme's color for it
+            let rgba = render_rgba(&world, theme, 1, RenderOpts::default());
+            for (i, &cell) in layout.cells.iter().enumerate() {
+                let (x, y) = (i % layout.width, i / layout.width);
+                if world.objects_at_iter(x, y).next().is_none() {
+                    assert_eq!(rgba[i * 4..i * 4 + 3], theme.cell_color(cell), "seed {} theme {} at ({}, {})", seed, t, x, y);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn cell_glyphs_are_distinct_and_ascii_ones_never_read_as_letters() {
+    let kinds = [
+        CellKind::Room(0),
+        CellKind::Wall,
+        CellKind::Outside,
+        CellKind::ClosedDoor,
+        CellKind::OpenDoor,
+        CellKind::LockedDoor,
+        CellKind::Window,
+        CellKind::Stairs(0),
+    ];
+    for name in ["default", "high_contrast", "dark", "colorblind_safe", "ascii"] {
+        let theme = Theme::named(name).unwrap();
+        let mut glyphs: Vec<char> = kinds.iter().map(|k| theme.cell_glyph(k.to_i8())).collect();
+        glyphs.push(theme.agent.glyph);
+        let distinct: HashSet<char> = glyphs.iter().copied().collect();
+        assert_eq!(distinct.len(), glyphs.len(), "{}: {:?}", name, glyphs);
+        if name == "ascii" {
+            assert!(glyphs.iter().all(|g| g.is_ascii() && !g.is_ascii_alphanumeric()), "{:?}", glyphs);
+        }
+    }
+
+    // the text render tells every room and object apart from the cells around them
+    let world = generate(&GenOpts { seed: 1, ..Default::default() }).unwrap();
+    let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap();
+    let (x, y) = (start % world.layout.width, start / world.layout.width);
+    let sim = Simulator::new(world, x, y).unwrap();
+    let text: Vec<Vec<char>> = sim.render_ascii().lines().map(|l| l.chars().collect()).collect();
+    let layout = &sim.world.layout;
+    let letters: Vec<char> = (0..layout.room_names.len()).map(room_letter).collect();
+    assert_eq!(letters.iter().collect::<HashSet<_>>().len(), letters.len());
+    for (x, y, kind) in layout.kinds_with_coords() {
+        let glyph = text[y][x];
+        let object = sim.world.objects_at_iter(x, y).next().is_some();
+        if glyph.is_ascii_alphabetic() {
+            assert!(object || kind.room().is_some_and(|r| letters[r] == glyph), "({}, {}) {:?} shows {}", x, y, kind, glyph);
+        } else if (x, y) != (sim.agent.x, sim.agent.y) {
+            assert!(!object, "({}, {}) hides an object behind {}", x, y, glyph);
+            assert_eq!(glyph, Theme::ascii().cell_glyph(kind.to_i8()), "({}, {})", x, y);
+        }
+    }
+}
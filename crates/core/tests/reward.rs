@@ -0,0 +1,123 @@
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::{Action, Layout, Object, ObjectSchema, RewardConfig, ShapingConfig, Simulator, World};
+
+/// A ring corridor with the agent in the top-left corner and a misplaced object in the
+/// top-right one
+fn ring(with_blocker: bool) -> World {
+    let layout = Layout::from_ascii(
+        &["#######", "#00000#", "#0###0#", "#00000#", "#######"],
+        &["Hallway"],
+    )
+    .unwrap();
+    let cup = ObjectSchema {
+        name: "Cup",
+        pickable: true,
+        target: ObjectConstraint::InsideOf(Names::Static(&["Cupboard"])),
+        ..Default::default()
+    };
+    let crate_ = ObjectSchema { name: "Crate", blocks_movement: true, ..Default::default() };
+    let mut objects = vec![Object::from_schema(0, &cup, 5, 1)];
+    if with_blocker {
+        objects.push(Object::from_schema(1, &crate_, 3, 1));
+    }
+    World::new(layout, objects)
+}
+
+#[test]
+fn potential_routes_around_blocking_furniture() {
+    let mut sim = Simulator::new(ring(true), 1, 1).unwrap();
+    assert_eq!(sim.potential(), -4.0);
+    sim.blocking_objects = true;
+    assert_eq!(sim.potential(), -8.0);
+
+    let mut open = Simulator::new(ring(false), 1, 1).unwrap();
+    open.blocking_objects = true;
+    assert_eq!(open.potential(), -4.0);
+}
+
+/// Discounted return of `actions` with and without `shaping`, and the potentials at the
+/// start and end of the episode
+fn returns(shaping: ShapingConfig, actions: &[Action]) -> (f32, f32, f32, f32) {
+    let config = RewardConfig { shaping: Some(shaping), ..Default::default() };
+    let mut shaped = Simulator::new(ring(false), 1, 1).unwrap();
+    shaped.reward_config = Some(config);
+    let mut sparse = Simulator::new(ring(false), 1, 1).unwrap();
+    sparse.reward_config = Some(RewardConfig { shaping: None, ..config });
+    let phi_start = shaped.potential();
+    let (mut with, mut without, mut discount) = (0.0, 0.0, 1.0);
+    for &action in actions {
+        with += discount * shaped.step(action).unwrap().reward;
+        without += discount * sparse.step(action).unwrap().reward;
+        discount *= shaping.gamma;
+    }
+    (with, without, phi_start, shaped.potential())
+}
+
+#[test]
+fn shaped_returns_telescope() {
+    // walk to the cup, pick it up (there is no cupboard, so the potential drops to 0) and walk on
+    let actions = [
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::Interact { dx: 1, dy: 0 },
+        Action::MoveLeft,
+        Action::MoveLeft,
+    ];
+    for gamma in [1.0f32, 0.9] {
+        let shaping = ShapingConfig { gamma, weight: 0.5 };
+        let (with, without, phi_start, phi_end) = returns(shaping, &actions);
+        assert_eq!((phi_start, phi_end), (-4.0, 0.0));
+        let expected = shaping.weight * (gamma.powi(actions.len() as i32) * phi_end - phi_start);
+        assert!((with - without - expected).abs() < 1e-5, "gamma {}: {} - {} != {}", gamma, with, without, expected);
+    }
+
+    // with gamma 1 the shaping only depends on where an episode starts and ends, so a detour
+    // to the same state earns the same shaping as the direct route
+    let shaping = ShapingConfig { gamma: 1.0, weight: 0.5 };
+    let direct = [Action::MoveRight, Action::MoveRight];
+    let detour = [Action::MoveDown, Action::MoveDown, Action::MoveUp, Action::MoveUp, Action::MoveRight, Action::MoveRight];
+    let (with, without, _, _) = returns(shaping, &direct);
+    let (detour_with, detour_without, _, _) = returns(shaping, &detour);
+    assert!(((with - without) - (detour_with - detour_without)).abs() < 1e-5);
+}
+
+#[test]
+fn an_optimal_episode_earns_the_expected_return() {
+    // a cupboard at the west end, mugs two and four cells east of it, the agent on the first
+    let layout = Layout::from_ascii(&["#######", "#00000#", "#######"], &["Kitchen"]).unwrap();
+    let objects = [("Cupboard", 1), ("Mug", 3), ("Mug", 5)]
+        .iter()
+        .enumerate()
+        .map(|(id, &(name, x))| Object::from_schema(id, ObjectSchema::find(name).unwrap(), x, 1))
+        .collect();
+    let mut sim = Simulator::new(World::new(layout, objects), 3, 1).unwrap();
+    let config = RewardConfig { pickup_bonus: 0.1, done_bonus: 5.0, ..Default::default() };
+    sim.reward_config = Some(config);
+    let put_away = Action::Interact { dx: -1, dy: 0 };
+    let script = [
+        Action::PickUp,
+        Action::MoveLeft,
+        put_away,
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::MoveRight,
+        Action::PickUp,
+        Action::MoveLeft,
+        Action::MoveLeft,
+        Action::MoveLeft,
+        put_away,
+    ];
+    let rewards: Vec<f32> = script.iter().map(|&a| sim.step(a).unwrap().reward).collect();
+    assert!(sim.is_success());
+    // two placements, two pick-ups and the finish, less the step penalty for every action
+    let expected = 2.0 * config.correct_place_bonus + 2.0 * config.pickup_bonus + config.done_bonus - 11.0 * config.step_penalty;
+    assert!((rewards.iter().sum::<f32>() - expected).abs() < 1e-5, "{:?}", rewards);
+    assert!((rewards[2] - (config.correct_place_bonus - config.step_penalty)).abs() < 1e-6);
+    assert!((rewards[10] - (config.correct_place_bonus + config.done_bonus - config.step_penalty)).abs() < 1e-6);
+
+    // taking a placed mug back out costs what putting it in earned
+    let mut sim = Simulator::new(sim.world.clone(), 2, 1).unwrap();
+    sim.reward_config = Some(RewardConfig::default());
+    assert!((sim.step(Action::Interact { dx: -1, dy: 0 }).unwrap().reward - (-1.0 - 0.01)).abs() < 1e-6);
+}
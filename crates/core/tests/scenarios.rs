@@ -0,0 +1,22 @@
+use std::collections::HashSet;
+
+use tidy_core::{scenarios, solver};
+
+#[test]
+fn the_oracle_solves_every_tutorial_scenario_within_its_step_limit() {
+    let tutorial = scenarios::tutorial();
+    assert!(tutorial.len() >= 4);
+    let names: HashSet<_> = tutorial.iter().map(|sc| sc.name).collect();
+    assert_eq!(names.len(), tutorial.len());
+    // the bindings ship the scenarios as JSON
+    assert!(serde_json::to_string(&tutorial).is_ok());
+    for scenario in &tutorial {
+        let mut sim = scenario.simulator();
+        assert!(!scenario.goals.is_empty() && !scenario.instructions.is_empty(), "{}", scenario.name);
+        assert!(!scenario.is_solved(&sim), "{} starts solved", scenario.name);
+        let steps = solver::solve(&mut sim, &scenario.goals, scenario.max_steps)
+            .unwrap_or_else(|e| panic!("{}: {}", scenario.name, e));
+        assert!(steps <= scenario.max_steps, "{}: {} steps", scenario.name, steps);
+        assert!(scenario.is_solved(&sim), "{} is not solved", scenario.name);
+    }
+}
@@ -0,0 +1,49 @@
+use tidy_core::search::fuzzy_score;
+use tidy_core::{Layout, Object, ObjectSchema, World};
+
+/// A kitchen row holding objects of the default catalog, ids in the order named
+fn kitchen() -> World {
+    let names = ["Mug", "DirtyMug", "Dishwasher", "MixingBowl", "DirtyBowl", "FruitBowl", "Kettle", "Mop"];
+    let layout = Layout::from_ascii(&["##########", "#00000000#", "##########"], &["Kitchen"]).unwrap();
+    let objects = names.iter().enumerate().map(|(i, name)| Object::from_schema(i, ObjectSchema::find(name).unwrap(), i + 1, 1)).collect();
+    let mut world = World::new(layout, objects);
+    world.objects[6].label = Some("teapot".into());
+    world
+}
+
+#[test]
+fn exact_names_rank_above_substrings_above_fuzzy_matches() {
+    let world = kitchen();
+    let ranked = |query: &str| world.find_fuzzy(query, 10);
+
+    let mug = ranked("mug");
+    assert_eq!(mug.iter().take(3).map(|r| r.0).collect::<Vec<_>>(), [0, 1, 7], "{:?}", mug);
+    assert_eq!(mug[0].1, 1.0);
+    assert!((0.5..1.0).contains(&mug[1].1), "{:?}", mug);
+    assert!(mug[2..].iter().all(|r| r.1 > 0.0 && r.1 < 0.5), "{:?}", mug);
+
+    // substrings score by how much of the name they cover, ties by id
+    let bowl = ranked("bowl");
+    assert_eq!(bowl.iter().take(3).map(|r| r.0).collect::<Vec<_>>(), [4, 5, 3], "{:?}", bowl);
+    assert_eq!(bowl[0].1, bowl[1].1);
+    assert!(bowl[1].1 > bowl[2].1);
+
+    // spacing and case do not matter, typos still find the closest name
+    assert_eq!(ranked("dish washer")[0], (2, 1.0));
+    let typo = ranked("dishwashr");
+    assert_eq!(typo[0].0, 2);
+    assert!(typo[0].1 < 0.5 && typo.iter().skip(1).all(|r| r.1 < typo[0].1), "{:?}", typo);
+    assert_eq!(fuzzy_score("fruit", "FruitBowl"), fuzzy_score("FRUIT", "fruit bowl"));
+    assert_eq!(world.find_fuzzy("mug", 1), &mug[..1]);
+    assert!(ranked("xyzzy").is_empty());
+}
+
+#[test]
+fn find_by_name_matches_names_and_labels_exactly() {
+    let world = kitchen();
+    assert_eq!(world.find_by_name("dirty mug"), [1]);
+    assert_eq!(world.find_by_name("MUG"), [0]);
+    assert_eq!(world.find_by_name("Teapot"), [6]);
+    assert_eq!(world.find_by_name("Kettle"), [6]);
+    assert!(world.find_by_name("bowl").is_empty());
+}
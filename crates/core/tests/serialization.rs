@@ -0,0 +1,80 @@
+use tidy_core::object::{Names, ObjectConstraint};
+use tidy_core::{generate, generate_with_schemas, ContainerKind, GenOpts, ObjectSchema, World};
+
+#[test]
+fn custom_schema_targets_and_display_names_survive_a_json_round_trip() {
+    let schemas = [
+        ObjectSchema {
+            name: "Toolbox",
+            capacity: 4,
+            container_kind: Some(ContainerKind::Enclosed),
+            ..Default::default()
+        },
+        ObjectSchema {
+            name: "Mallet",
+            display_name: Some("wooden mallet"),
+            pickable: true,
+            target: ObjectConstraint::Or(vec![ObjectConstraint::InsideOf(Names::Static(&["Toolbox"])), ObjectConstraint::InRoomNamed(Names::Static(&["Garage"]))]),
+            ..Default::default()
+        },
+    ];
+    for seed in 0..20 {
+        let world = generate_with_schemas(&GenOpts { seed, max_objects: 10, ..Default::default() }, &schemas).unwrap();
+        let json = world.to_json().unwrap();
+        let loaded = World::from_json(&json).unwrap();
+        assert_eq!(loaded.tidiness(), world.tidiness(), "seed {}", seed);
+        for o in &world.objects {
+            let target = loaded.object(o.id).unwrap().target.as_ref();
+            assert_eq!(format!("{:?}", target), format!("{:?}", o.target.as_ref()), "seed {}", seed);
+            assert_eq!(loaded.object(o.id).unwrap().display_name, o.display_name, "seed {}", seed);
+        }
+        // only names loading could not work out are written
+        let mallets = world.objects.iter().filter(|o| o.name == "Mallet").count();
+        assert_eq!(json.matches("\"display_name\"").count(), mallets, "seed {}", seed);
+    }
+}
+
+#[test]
+fn worlds_saved_without_targets_take_them_from_the_catalog() {
+    let world = tidy_core::generate(&GenOpts { seed: 3, ..Default::default() }).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&world.to_json().unwrap()).unwrap();
+    for o in json["objects"].as_array_mut().unwrap() {
+        o.as_object_mut().unwrap().remove("target");
+    }
+    let loaded = World::from_json(&json.to_string()).unwrap();
+    assert_eq!(loaded.tidiness(), world.tidiness());
+    assert!(loaded.objects.iter().all(|o| o.target.is_some() == world.object(o.id).unwrap().target.is_some()));
+}
+
+#[test]
+fn canonicalize_undoes_reordering_and_keeps_room_names() {
+    for seed in 0..10 {
+        let original = generate(&GenOpts { seed, ..Default::default() }).unwrap();
+        let mut shuffled = original.clone();
+        shuffled.objects.reverse();
+        let half = shuffled.objects.len() / 2;
+        shuffled.objects.rotate_left(half);
+        for o in &mut shuffled.objects {
+            o.contents.reverse();
+        }
+        shuffled.clear_object_index();
+        assert_ne!(serde_json::to_string(&shuffled).unwrap(), serde_json::to_string(&original).unwrap(), "seed {}", seed);
+        // canonical output does not wait for `canonicalize`
+        assert_eq!(shuffled.to_json().unwrap(), original.to_json().unwrap(), "seed {}", seed);
+
+        shuffled.canonicalize();
+        assert_eq!(shuffled.fingerprint(), original.fingerprint(), "seed {}", seed);
+        assert!(shuffled.diff(&original).is_empty(), "seed {}", seed);
+        // generation hands out ids in placement order, so its worlds are canonical already
+        assert_eq!(serde_json::to_string(&shuffled).unwrap(), serde_json::to_string(&original).unwrap(), "seed {}", seed);
+
+        // room names keep their case, so targets naming them still hold
+        let mut lowered = original.clone();
+        for name in &mut lowered.layout.room_names {
+            *name = name.to_lowercase().into();
+        }
+        let names = lowered.layout.room_names.clone();
+        lowered.canonicalize();
+        assert_eq!(lowered.layout.room_names, names, "seed {}", seed);
+    }
+}
@@ -0,0 +1,73 @@
+use tidy_core::stats::{aggregate, world_stats};
+use tidy_core::{generate, GenOpts, Summary, WorldStats};
+
+fn seed_7() -> WorldStats {
+    world_stats(&generate(&GenOpts { seed: 7, ..Default::default() }).unwrap())
+}
+
+#[test]
+fn stats_of_a_known_seed() {
+    let stats = seed_7();
+    assert_eq!((stats.width, stats.height), (20, 20));
+    assert_eq!(stats.room_count, 4);
+    assert_eq!(stats.room_areas, [90, 75, 35, 72]);
+    assert_eq!(stats.door_count, 3);
+    assert_eq!(stats.object_count, 15);
+    let names: Vec<_> = stats.objects_by_name.iter().map(|(name, &count)| (name.as_str(), count)).collect();
+    assert_eq!(
+        names,
+        [
+            ("Blinds", 1),
+            ("Broom", 1),
+            ("Bucket", 1),
+            ("Chandelier", 1),
+            ("Desk", 1),
+            ("DogLeash", 1),
+            ("FireExtinguisher", 1),
+            ("Keyboard", 1),
+            ("Monitor", 1),
+            ("Sofa", 1),
+            ("Speaker", 1),
+            ("StorageBox", 1),
+            ("Toolbox", 1),
+            ("Umbrella", 1),
+            ("Wallet", 1),
+        ]
+    );
+    assert_eq!(stats.container_fill, [0.0, 0.0, 0.0, 1.0 / 15.0, 0.0]);
+    assert_eq!(stats.tidy_score, 1.0 / 3.0);
+    assert_eq!(stats.stranded_fraction, 0.0);
+    assert_eq!(stats.diameter, 34);
+}
+
+#[test]
+fn aggregate_summarizes_every_world() {
+    let stats = [seed_7(), seed_7()];
+    let agg = aggregate(&stats);
+    assert_eq!(agg.worlds, 2);
+    assert_eq!(agg.room_count, Summary { mean: 4.0, min: 4.0, max: 4.0, p10: 4.0, p50: 4.0, p90: 4.0 });
+    assert_eq!(agg.room_area, Summary::of([90.0, 75.0, 35.0, 72.0].repeat(2)));
+    assert_eq!(agg.room_area.max, 90.0);
+    assert_eq!(agg.objects_by_name["Sofa"], 2);
+    // nearest-rank percentiles
+    let ten = Summary::of((1..=10).map(|v| v as f32).collect());
+    assert_eq!((ten.mean, ten.p10, ten.p50, ten.p90), (5.5, 1.0, 5.0, 9.0));
+    assert_eq!(Summary::of(Vec::new()), Summary::default());
+
+    let json = serde_json::to_string(&stats[0]).unwrap();
+    assert_eq!(serde_json::from_str::<WorldStats>(&json).unwrap(), stats[0]);
+}
+
+#[test]
+fn placement_preferences_strand_fewer_pickables_on_the_floor() {
+    let stranded = |placement_temperature: f32| {
+        let stats: Vec<WorldStats> = (0..60)
+            .map(|seed| world_stats(&generate(&GenOpts { seed, placement_temperature, ..Default::default() }).unwrap()))
+            .collect();
+        aggregate(&stats).stranded_fraction.mean
+    };
+    // an infinite temperature ignores the preferences and samples spawn cells uniformly
+    let (preferred, uniform) = (stranded(GenOpts::default().placement_temperature), stranded(f32::INFINITY));
+    assert!(uniform > 0.0);
+    assert!(preferred < 0.5 * uniform, "{} stranded with preferences, {} without", preferred, uniform);
+}
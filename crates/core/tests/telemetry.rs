@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use tidy_core::{generate_batch, generate_with_report, generate_with_trace, GenOpts, GenTelemetry};
+
+fn total<K>(counts: &BTreeMap<K, usize>) -> usize {
+    counts.values().sum()
+}
+
+#[test]
+fn telemetry_counters_add_up_over_the_batch() {
+    let opts = GenOpts { seed: 100, ..Default::default() };
+    let count = 25;
+    let mut telemetry = GenTelemetry::default();
+    let worlds = generate_batch(&opts, count, 3, Some(&mut telemetry)).unwrap();
+    assert_eq!(telemetry.worlds, worlds.len());
+    assert_eq!(telemetry.worlds + telemetry.failures, count);
+
+    // without retries world `i` comes from seed `opts.seed + i`, so recount every world on its own
+    assert_eq!(telemetry.retries, 0);
+    let (mut splits, mut split_candidates, mut rejections, mut doors) = (0, 0, 0, 0);
+    let (mut placed, mut passes, mut provenance, mut skipped) = (0, 0, 0, 0);
+    for seed in opts.seed..opts.seed + count as u64 {
+        let opts = GenOpts { seed, ..opts.clone() };
+        let (_, report) = generate_with_report(&opts).unwrap();
+        let (_, trace) = generate_with_trace(&opts).unwrap();
+        splits += trace.splits.len();
+        split_candidates += trace.splits.iter().map(|s| s.candidates).sum::<usize>();
+        rejections += trace.rejected.len();
+        doors += trace.doors.len();
+        placed += report.placed_objects;
+        passes += report.passes;
+        provenance += report.provenance.len();
+        skipped += report.exhausted_schemas.len();
+    }
+    assert_eq!((telemetry.splits, telemetry.split_candidates), (splits, split_candidates));
+    assert_eq!((total(&telemetry.split_rejections), total(&telemetry.door_widths)), (rejections, doors));
+    assert_eq!((telemetry.placed_objects, telemetry.passes), (placed, passes));
+    // every skipped schema has one reason, and every placed object considered at least one cell
+    assert_eq!((total(&telemetry.skipped_schemas), total(&telemetry.skip_reasons)), (skipped, skipped));
+    assert!(telemetry.placement_candidates >= provenance);
+    assert!(telemetry.split_candidates >= telemetry.splits);
+
+    assert!(telemetry.report().starts_with(&format!("worlds: {} (0 retries, 0 failures)", count)));
+    let json = serde_json::to_string(&telemetry).unwrap();
+    assert_eq!(serde_json::from_str::<GenTelemetry>(&json).unwrap(), telemetry);
+}
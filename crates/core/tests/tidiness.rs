@@ -0,0 +1,43 @@
+use tidy_core::{Action, Layout, Simulator, World};
+
+/// A kitchen with a cupboard holding two mugs, two more mugs on the floor and a table
+fn kitchen() -> World {
+    let layout = Layout::from_ascii(&["#######", "#00000#", "#00000#", "#######"], &["Kitchen"]).unwrap();
+    let mut world = World::new(layout, Vec::new());
+    world
+        .transaction(|tx| {
+            let cupboard = tx.add_object("Cupboard", 1, 1)?;
+            for _ in 0..2 {
+                let mug = tx.add_object("Mug", 1, 1)?;
+                tx.place_into(mug, cupboard)?;
+            }
+            tx.add_object("Mug", 3, 1)?;
+            tx.add_object("Mug", 5, 2)?;
+            tx.add_object("DiningTable", 4, 2)?;
+            Ok(())
+        })
+        .unwrap();
+    world
+}
+
+#[test]
+fn tidiness_is_the_share_of_pickables_in_place() {
+    let world = kitchen();
+    assert_eq!(world.misplaced_objects(), [3, 4]);
+    assert_eq!(world.tidiness(), 0.5);
+    assert_eq!(World::new(world.layout.clone(), Vec::new()).tidiness(), 1.0);
+
+    let mut sim = Simulator::new(world, 3, 1).unwrap();
+    assert_eq!(sim.tidiness(), 0.5);
+    // a held object is misplaced for the simulator and gone from the world
+    sim.step(Action::PickUp).unwrap();
+    assert_eq!(sim.misplaced_objects(), [3, 4]);
+    assert_eq!(sim.tidiness(), 0.5);
+    assert_eq!(sim.world.tidiness(), 2.0 / 3.0);
+
+    // carried into the cupboard, one more of the four is in place
+    sim.step(Action::MoveLeft).unwrap();
+    sim.step(Action::Interact { dx: -1, dy: 0 }).unwrap();
+    assert_eq!(sim.misplaced_objects(), [4]);
+    assert_eq!(sim.tidiness(), 0.75);
+}
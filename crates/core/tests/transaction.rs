@@ -0,0 +1,76 @@
+use tidy_core::{Layout, TxError, TxOpError, World};
+
+/// A kitchen with a microwave (capacity 1) holding an apple, a fruit bowl and a loose orange
+fn kitchen() -> World {
+    let layout = Layout::from_ascii(&["########", "#000000#", "#000000#", "########"], &["Kitchen"]).unwrap();
+    let mut world = World::new(layout, Vec::new());
+    world
+        .transaction(|tx| {
+            let microwave = tx.add_object("Microwave", 1, 1)?;
+            let apple = tx.add_object("Apple", 1, 1)?;
+            tx.place_into(apple, microwave)?;
+            tx.add_object("FruitBowl", 6, 2)?;
+            tx.add_object("Orange", 3, 2)?;
+            Ok(())
+        })
+        .unwrap();
+    world
+}
+
+/// Everything `serde` sees, so "unchanged" includes vector order
+fn snapshot(world: &World) -> String {
+    serde_json::to_string(world).unwrap()
+}
+
+#[test]
+fn a_failing_middle_operation_leaves_the_world_untouched() {
+    let mut world = kitchen();
+    let before = snapshot(&world);
+    let result = world.transaction(|tx| {
+        tx.move_object(3, 5, 1)?;
+        tx.set_label(3, Some("ripe".into()))?;
+        // the microwave already holds the apple
+        tx.place_into(3, 0)?;
+        tx.remove_object(2)?;
+        Ok(())
+    });
+    assert_eq!(result, Err(TxError::Op { index: 2, error: TxOpError::ContainerFull(0) }));
+    assert_eq!(snapshot(&world), before);
+
+    // failures the closure swallows still count, so indices match the calls
+    let result = world.transaction(|tx| {
+        let _ = tx.move_object(9, 1, 1);
+        tx.move_object(3, 0, 0)
+    });
+    assert_eq!(result, Err(TxError::Op { index: 1, error: TxOpError::NotRoomCell { x: 0, y: 0 } }));
+    assert_eq!(world.transaction(|tx| tx.place_into(3, 1)), Err(TxError::Op { index: 0, error: TxOpError::NotAContainer(1) }));
+    assert_eq!(snapshot(&world), before);
+}
+
+#[test]
+fn commit_validates_the_result() {
+    let mut world = kitchen();
+    let before = snapshot(&world);
+    // two fixtures on one cell pass every operation's checks but not `validate`
+    let result = world.transaction(|tx| tx.add_object("FruitBowl", 1, 1));
+    assert!(matches!(result, Err(TxError::Invalid(_))), "{:?}", result);
+    assert_eq!(snapshot(&world), before);
+}
+
+#[test]
+fn a_successful_transaction_applies_every_operation() {
+    let mut world = kitchen();
+    world
+        .transaction(|tx| {
+            tx.move_object(1, 4, 1)?;
+            tx.place_into(3, 2)?;
+            // the edits so far are visible to later operations
+            assert_eq!(tx.world().object(3).map(|o| (o.x, o.y)), Some((6, 2)));
+            tx.remove_object(0)
+        })
+        .unwrap();
+    assert!(world.object(0).is_none());
+    assert_eq!(world.object(1).map(|o| (o.x, o.y)), Some((4, 1)));
+    assert_eq!(world.object(2).unwrap().contents, [3]);
+    world.validate().unwrap();
+}
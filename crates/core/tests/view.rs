@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use tidy_core::{scenarios, Action, Direction, Layout, LocalView, Object, ObjectSchema, Simulator, World};
+
+#[test]
+fn local_view_into_matches_local_view_and_reuses_buffers() {
+    let mut sim = scenarios::tutorial()[0].simulator();
+    let mut view = LocalView::default();
+    let mut turned = LocalView::default();
+    sim.local_view_into(3, false, &mut view);
+    view.objects.reserve(sim.world.objects.len());
+    let (cells, objects) = (view.cells.as_ptr(), view.objects.as_ptr());
+    for step in 0..8 {
+        for include_contained in [false, true] {
+            sim.local_view_into(3, include_contained, &mut view);
+            assert_eq!(view, sim.local_view(3, include_contained), "step {}", step);
+            assert_eq!((view.cells.as_ptr(), view.objects.as_ptr()), (cells, objects), "step {} reallocated", step);
+        }
+        for facing in [Direction::North, Direction::East, Direction::South, Direction::West] {
+            view.rotate_into(facing, &mut turned);
+            assert_eq!(turned, view.rotated(facing));
+        }
+        let _ = if step < 4 { sim.right() } else { sim.down() };
+    }
+    // a smaller radius shrinks the view in place
+    sim.local_view_into(1, false, &mut view);
+    assert_eq!(view, sim.local_view(1, false));
+    assert_eq!(view.cells.as_ptr(), cells);
+}
+
+#[test]
+fn world_views_share_buffers_until_the_simulator_changes_them() {
+    let layout = Layout::from_ascii(&["#######", "#00d11#", "#######"], &["Hallway", "Study"]).unwrap();
+    let mug = Object::from_schema(0, ObjectSchema::find("Mug").unwrap(), 1, 1);
+    let mut sim = Simulator::new(World::new(layout, vec![mug]), 1, 1).unwrap();
+    let first = sim.view();
+    sim.right().unwrap();
+    let moved = sim.view();
+    assert!(Arc::ptr_eq(&first.cells, &moved.cells) && Arc::ptr_eq(&first.objects, &moved.objects));
+    assert_eq!((moved.agent_x, moved.agent_y), (2, 1));
+
+    sim.use_door(3, 1, false).unwrap();
+    let closed = sim.view();
+    assert!(!Arc::ptr_eq(&moved.cells, &closed.cells));
+    assert_eq!((moved.door_open(3, 1), closed.door_open(3, 1)), (Some(true), Some(false)));
+    assert!(Arc::ptr_eq(&moved.room_names, &closed.room_names));
+
+    sim.left().unwrap();
+    sim.pick_up().unwrap();
+    assert!(sim.view().objects.is_empty());
+    assert_eq!(closed.objects.len(), 1);
+
+    // direct edits show up once the caches are dropped
+    sim.world.layout.room_names[1] = "Library".into();
+    assert_eq!(sim.view().room_names[1], "Study");
+    sim.invalidate_caches();
+    assert_eq!(sim.view().room_names[1], "Library");
+}
+
+#[test]
+fn four_quarter_turns_restore_the_facing() {
+    let mut sim = scenarios::tutorial()[0].simulator();
+    let at = (sim.agent.x, sim.agent.y);
+    for start in [Direction::North, Direction::East, Direction::South, Direction::West] {
+        sim.agent.facing = start;
+        for turn in [Action::TurnRight, Action::TurnLeft] {
+            let mut seen = Vec::new();
+            for _ in 0..4 {
+                sim.step(turn).unwrap();
+                seen.push(sim.agent.facing);
+            }
+            assert_eq!(seen[3], start, "{:?} from {:?}", turn, start);
+            seen.sort_by_key(|d| d.name());
+            seen.dedup();
+            assert_eq!(seen.len(), 4, "{:?} from {:?} skipped a direction", turn, start);
+            assert_eq!((sim.agent.x, sim.agent.y), at);
+        }
+        // one each way is no turn at all
+        sim.turn_right().unwrap();
+        sim.turn_left().unwrap();
+        assert_eq!(sim.agent.facing, start);
+    }
+}
@@ -0,0 +1,85 @@
+#!/usr/bin/env python3
+"""
+Run the core conformance spec (`tidy_core::conformance`) through PySimulator.
+Run this after building the Python module with: maturin develop
+
+Every operation is looked up by its Python name, so a method missing from the
+bindings fails with the operation name just like a diverging result does.
+Exits with status 1 on any failure.
+"""
+
+import json
+import sys
+
+import tidyenv
+
+
+def normalize(value, returns):
+    """Turn a return value into the spec's outcome form"""
+    if returns == "nothing":
+        return {"kind": "ok"}
+    if returns == "bool":
+        return {"kind": "bool", "value": bool(value)}
+    if returns == "object":
+        return {"kind": "id", "value": None if value is None else value.id}
+    if returns == "objects":
+        return {"kind": "ids", "value": [getattr(v, "id", v) for v in value]}
+    if returns == "number":
+        return {"kind": "number", "value": int(value)}
+    raise ValueError(f"unknown return kind {returns}")
+
+
+def call(sim, op, args):
+    """Outcome of one operation; raises LookupError when the binding lacks it"""
+    if not hasattr(sim, op["python"]):
+        raise LookupError(f"PySimulator has no '{op['python']}'")
+    attr = getattr(sim, op["python"])
+    try:
+        value = attr(*args) if callable(attr) else attr
+    except Exception:
+        return {"kind": "failed"}
+    return normalize(value, op["returns"])
+
+
+def state(sim):
+    holding = sim.get_holding()
+    return [sim.agent_x, sim.agent_y], None if holding is None else holding.id
+
+
+def run(spec):
+    operations = {op["name"]: op for op in spec["operations"]}
+    failures = []
+    for case in spec["cases"]:
+        # every case starts from a fresh simulator, like the core and wasm runners
+        sim = {sc[0]: sc[5] for sc in tidyenv.tutorial()}.get(case["scenario"])
+        if sim is None:
+            failures.append(f"{case['scenario']} step 0 (start): unknown tutorial scenario")
+            continue
+        for i, step in enumerate(case["steps"]):
+            where = f"{case['scenario']} step {i} ({step['op']})"
+            try:
+                outcome = call(sim, operations[step["op"]], step["args"])
+            except LookupError as e:
+                failures.append(f"{where}: {e}")
+                break
+            agent, holding = state(sim)
+            found = (outcome, agent, holding)
+            expected = (step["expect"], list(step["agent"]), step["holding"])
+            if found != expected:
+                failures.append(f"{where}: expected {expected}, got {found}")
+                break
+    return failures
+
+
+def main():
+    spec = json.loads(tidyenv.conformance_spec())
+    failures = run(spec)
+    for failure in failures:
+        print(failure)
+    steps = sum(len(case["steps"]) for case in spec["cases"])
+    print(f"python conformance: {len(failures)} failure(s) over {steps} steps")
+    sys.exit(1 if failures else 0)
+
+
+if __name__ == "__main__":
+    main()
@@ -1,6 +1,13 @@
+// pyo3 0.20 macros expand to impls nested in functions
+#![allow(non_local_definitions)]
+
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
-use tidy_core;
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::types::{PyBytes, PyDict};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
 
 /// Python wrapper for GenOpts
 #[pyclass]
@@ -16,29 +23,191 @@ pub struct PyGenOpts {
     pub height: usize,
     #[pyo3(get, set)]
     pub max_objects: usize,
+    #[pyo3(get, set)]
+    pub zone_min_area: usize,
+    #[pyo3(get, set)]
+    pub open_plan_prob: f32,
+    #[pyo3(get, set)]
+    pub strict: bool,
+    pub task: tidy_core::TaskKind,
+    #[pyo3(get, set)]
+    pub placement_temperature: f32,
+    #[pyo3(get, set)]
+    pub min_room_area: usize,
+    #[pyo3(get, set)]
+    pub min_room_thickness: usize,
+    #[pyo3(get, set)]
+    pub min_aspect_ratio: f64,
+    #[pyo3(get, set)]
+    pub max_aspect_ratio: f64,
+    #[pyo3(get, set)]
+    pub door_min: usize,
+    #[pyo3(get, set)]
+    pub door_max: usize,
+    #[pyo3(get, set)]
+    pub required_rooms: Vec<String>,
+    #[pyo3(get, set)]
+    pub room_name_pool: Option<Vec<String>>,
+    #[pyo3(get, set)]
+    pub layout_seed: Option<u64>,
+    #[pyo3(get, set)]
+    pub object_seed: Option<u64>,
+    #[pyo3(get, set)]
+    pub misplaced_ratio: Option<f32>,
 }
 
 #[pymethods]
 impl PyGenOpts {
+    /// `task` is "tidy", "delivery" or "mixed"; the room size, aspect ratio and door width
+    /// limits default to the core `GenOpts` values
     #[new]
-    fn new(seed: u64, max_rooms: usize, width: usize, height: usize, max_objects: usize) -> Self {
-        PyGenOpts {
+    #[pyo3(signature = (
+        seed, max_rooms, width, height, max_objects, zone_min_area=0, open_plan_prob=0.0, strict=false, task="tidy",
+        placement_temperature=0.5, *, min_room_area=None, min_room_thickness=None, min_aspect_ratio=None,
+        max_aspect_ratio=None, door_min=None, door_max=None, required_rooms=Vec::new(),
+        room_name_pool=None, layout_seed=None, object_seed=None, misplaced_ratio=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        seed: u64,
+        max_rooms: usize,
+        width: usize,
+        height: usize,
+        max_objects: usize,
+        zone_min_area: usize,
+        open_plan_prob: f32,
+        strict: bool,
+        task: &str,
+        placement_temperature: f32,
+        min_room_area: Option<usize>,
+        min_room_thickness: Option<usize>,
+        min_aspect_ratio: Option<f64>,
+        max_aspect_ratio: Option<f64>,
+        door_min: Option<usize>,
+        door_max: Option<usize>,
+        required_rooms: Vec<String>,
+        room_name_pool: Option<Vec<String>>,
+        layout_seed: Option<u64>,
+        object_seed: Option<u64>,
+        misplaced_ratio: Option<f32>,
+    ) -> PyResult<Self> {
+        let d = tidy_core::GenOpts::default();
+        Ok(PyGenOpts {
             seed,
             max_rooms,
             width,
             height,
             max_objects,
-        }
+            zone_min_area,
+            open_plan_prob,
+            strict,
+            task: parse_task(task)?,
+            placement_temperature,
+            min_room_area: min_room_area.unwrap_or(d.min_room_area),
+            min_room_thickness: min_room_thickness.unwrap_or(d.min_room_thickness),
+            min_aspect_ratio: min_aspect_ratio.unwrap_or(d.min_aspect_ratio),
+            max_aspect_ratio: max_aspect_ratio.unwrap_or(d.max_aspect_ratio),
+            door_min: door_min.unwrap_or(d.door_min),
+            door_max: door_max.unwrap_or(d.door_max),
+            required_rooms,
+            room_name_pool,
+            layout_seed,
+            object_seed,
+            misplaced_ratio,
+        })
+    }
+
+    #[getter]
+    fn task(&self) -> String {
+        task_name(self.task)
+    }
+
+    #[setter]
+    fn set_task(&mut self, task: &str) -> PyResult<()> {
+        self.task = parse_task(task)?;
+        Ok(())
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "PyGenOpts(seed={}, max_rooms={}, width={}, height={}, max_objects={})",
-            self.seed, self.max_rooms, self.width, self.height, self.max_objects
+            "PyGenOpts(seed={}, max_rooms={}, width={}, height={}, max_objects={}, zone_min_area={}, open_plan_prob={}, strict={}, task={:?}, placement_temperature={}, min_room_area={}, min_room_thickness={}, min_aspect_ratio={}, max_aspect_ratio={}, door_min={}, door_max={}, required_rooms={:?}, room_name_pool={}, layout_seed={}, object_seed={}, misplaced_ratio={})",
+            self.seed, self.max_rooms, self.width, self.height, self.max_objects, self.zone_min_area, self.open_plan_prob, self.strict, task_name(self.task), self.placement_temperature,
+            self.min_room_area, self.min_room_thickness, self.min_aspect_ratio, self.max_aspect_ratio, self.door_min, self.door_max, self.required_rooms,
+            self.room_name_pool.as_ref().map_or_else(|| "None".to_string(), |pool| format!("{:?}", pool)),
+            seed_repr(self.layout_seed), seed_repr(self.object_seed),
+            self.misplaced_ratio.map_or_else(|| "None".to_string(), |r| r.to_string())
         )
     }
 }
 
+/// Action by name, as accepted by `PySimulator.step`
+fn parse_action(action: &str, dx: i8, dy: i8, object_id: Option<usize>) -> PyResult<tidy_core::Action> {
+    Ok(match action {
+        "move_up" => tidy_core::Action::MoveUp,
+        "move_down" => tidy_core::Action::MoveDown,
+        "move_left" => tidy_core::Action::MoveLeft,
+        "move_right" => tidy_core::Action::MoveRight,
+        "pick_up" => tidy_core::Action::PickUp,
+        "drop" => tidy_core::Action::Drop,
+        "interact" => tidy_core::Action::Interact { dx, dy },
+        "place_into" => {
+            tidy_core::Action::PlaceInto(object_id.ok_or_else(|| PyValueError::new_err("place_into requires object_id"))?)
+        }
+        "auto_place" => tidy_core::Action::AutoPlace,
+        "noop" => tidy_core::Action::Noop,
+        "pick_up_by_id" => {
+            tidy_core::Action::PickUpById(object_id.ok_or_else(|| PyValueError::new_err("pick_up_by_id requires object_id"))?)
+        }
+        "toggle_container" => tidy_core::Action::ToggleContainer(
+            object_id.ok_or_else(|| PyValueError::new_err("toggle_container requires object_id"))?,
+        ),
+        "run_appliance" => {
+            tidy_core::Action::RunAppliance(object_id.ok_or_else(|| PyValueError::new_err("run_appliance requires object_id"))?)
+        }
+        "turn_left" => tidy_core::Action::TurnLeft,
+        "turn_right" => tidy_core::Action::TurnRight,
+        "move_forward" => tidy_core::Action::MoveForward,
+        "interact_ahead" => tidy_core::Action::InteractAhead,
+        other => return Err(PyValueError::new_err(format!("Unknown action: {}", other))),
+    })
+}
+
+/// Name of an action as accepted by `parse_action`
+fn action_name(action: tidy_core::Action) -> &'static str {
+    match action {
+        tidy_core::Action::MoveUp => "move_up",
+        tidy_core::Action::MoveDown => "move_down",
+        tidy_core::Action::MoveLeft => "move_left",
+        tidy_core::Action::MoveRight => "move_right",
+        tidy_core::Action::PickUp => "pick_up",
+        tidy_core::Action::Drop => "drop",
+        tidy_core::Action::Interact { .. } => "interact",
+        tidy_core::Action::PlaceInto(_) => "place_into",
+        tidy_core::Action::AutoPlace => "auto_place",
+        tidy_core::Action::Noop => "noop",
+        tidy_core::Action::PickUpById(_) => "pick_up_by_id",
+        tidy_core::Action::ToggleContainer(_) => "toggle_container",
+        tidy_core::Action::RunAppliance(_) => "run_appliance",
+        tidy_core::Action::TurnLeft => "turn_left",
+        tidy_core::Action::TurnRight => "turn_right",
+        tidy_core::Action::MoveForward => "move_forward",
+        tidy_core::Action::InteractAhead => "interact_ahead",
+    }
+}
+
+fn parse_task(task: &str) -> PyResult<tidy_core::TaskKind> {
+    serde_json::from_value(serde_json::Value::String(task.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("Unknown task: {} (expected tidy, delivery or mixed)", task)))
+}
+
+fn task_name(task: tidy_core::TaskKind) -> String {
+    serde_json::to_value(task).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+fn seed_repr(seed: Option<u64>) -> String {
+    seed.map_or_else(|| "None".to_string(), |s| s.to_string())
+}
+
 impl From<PyGenOpts> for tidy_core::GenOpts {
     fn from(opts: PyGenOpts) -> Self {
         tidy_core::GenOpts {
@@ -47,6 +216,22 @@ impl From<PyGenOpts> for tidy_core::GenOpts {
             width: opts.width,
             height: opts.height,
             max_objects: opts.max_objects,
+            zone_min_area: opts.zone_min_area,
+            open_plan_prob: opts.open_plan_prob,
+            strict: opts.strict,
+            task: opts.task,
+            placement_temperature: opts.placement_temperature,
+            min_room_area: opts.min_room_area,
+            min_room_thickness: opts.min_room_thickness,
+            min_aspect_ratio: opts.min_aspect_ratio,
+            max_aspect_ratio: opts.max_aspect_ratio,
+            door_min: opts.door_min,
+            door_max: opts.door_max,
+            required_rooms: opts.required_rooms,
+            room_name_pool: opts.room_name_pool,
+            layout_seed: opts.layout_seed,
+            object_seed: opts.object_seed,
+            misplaced_ratio: opts.misplaced_ratio,
         }
     }
 }
@@ -59,18 +244,36 @@ pub struct PyObject {
     pub id: usize,
     #[pyo3(get)]
     pub name: String,
+    /// Name used in text, e.g. "dirty plate"
+    #[pyo3(get)]
+    pub display_name: String,
+    /// Stable sprite key for renderers
+    #[pyo3(get)]
+    pub icon: String,
     #[pyo3(get)]
     pub capacity: usize,
     #[pyo3(get)]
     pub pickable: bool,
+    /// Whether the object keeps the agent off its cell while `blocking_objects` is on
+    #[pyo3(get)]
+    pub blocks_movement: bool,
     #[pyo3(get, set)]
     pub x: usize,
     #[pyo3(get, set)]
     pub y: usize,
     #[pyo3(get)]
     pub contents: Vec<usize>,
+    /// Whether the container is open; from a simulator, always True unless `closed_containers` is on
+    #[pyo3(get)]
+    pub is_open: bool,
     #[pyo3(get)]
     pub description: String,
+    /// Steps left before the object decays (None if it does not decay)
+    #[pyo3(get)]
+    pub decay_remaining: Option<usize>,
+    /// Visible label: the custom label or the humanized name
+    #[pyo3(get)]
+    pub label: String,
 }
 
 #[pymethods]
@@ -86,12 +289,18 @@ impl PyObject {
         Ok(PyObject {
             id: self.id,
             name: self.name.clone(),
+            display_name: self.display_name.clone(),
+            icon: self.icon.clone(),
             capacity: self.capacity,
             pickable: self.pickable,
+            blocks_movement: self.blocks_movement,
             x: self.x,
             y: self.y,
             contents: self.contents.clone(),
+            is_open: self.is_open,
             description: self.description.clone(),
+            decay_remaining: self.decay_remaining,
+            label: self.label.clone(),
         })
     }
 }
@@ -101,12 +310,149 @@ impl From<&tidy_core::Object> for PyObject {
         PyObject {
             id: obj.id,
             name: obj.name.to_string(),
+            display_name: obj.display_name.to_string(),
+            icon: obj.icon.to_string(),
             capacity: obj.capacity,
             pickable: obj.pickable,
+            blocks_movement: obj.blocks_movement,
             x: obj.x,
             y: obj.y,
             contents: obj.contents.clone(),
+            is_open: obj.open,
             description: obj.description.to_string(),
+            decay_remaining: None,
+            label: obj.display_label(),
+        }
+    }
+}
+
+/// Python wrapper for PlacementProvenance
+#[pyclass]
+#[derive(Clone)]
+pub struct PyPlacementProvenance {
+    #[pyo3(get)]
+    pub object_id: usize,
+    #[pyo3(get)]
+    pub schema_name: String,
+    #[pyo3(get)]
+    pub chosen_cell: (usize, usize),
+    #[pyo3(get)]
+    pub inside_parent: Option<usize>,
+    #[pyo3(get)]
+    pub satisfied_branch: String,
+    #[pyo3(get)]
+    pub candidates_considered: usize,
+}
+
+#[pymethods]
+impl PyPlacementProvenance {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyPlacementProvenance(object_id={}, schema_name='{}', chosen_cell={:?}, satisfied_branch='{}')",
+            self.object_id, self.schema_name, self.chosen_cell, self.satisfied_branch
+        )
+    }
+}
+
+impl From<&tidy_core::PlacementProvenance> for PyPlacementProvenance {
+    fn from(p: &tidy_core::PlacementProvenance) -> Self {
+        PyPlacementProvenance {
+            object_id: p.object_id,
+            schema_name: p.schema_name.to_string(),
+            chosen_cell: p.chosen_cell,
+            inside_parent: p.inside_parent,
+            satisfied_branch: p.satisfied_branch.clone(),
+            candidates_considered: p.candidates_considered,
+        }
+    }
+}
+
+/// Python wrapper for DifficultyScore
+#[pyclass]
+#[derive(Clone)]
+pub struct PyDifficultyScore {
+    #[pyo3(get)]
+    pub misplaced: usize,
+    #[pyo3(get)]
+    pub mean_distance: f32,
+    #[pyo3(get)]
+    pub closed_doors: usize,
+    #[pyo3(get)]
+    pub container_targets: usize,
+    #[pyo3(get)]
+    pub unreachable: usize,
+    #[pyo3(get)]
+    pub estimated_steps: usize,
+    /// Difficulty in [0, 1)
+    #[pyo3(get)]
+    pub score: f32,
+}
+
+impl From<tidy_core::DifficultyScore> for PyDifficultyScore {
+    fn from(d: tidy_core::DifficultyScore) -> Self {
+        PyDifficultyScore {
+            misplaced: d.misplaced,
+            mean_distance: d.mean_distance,
+            closed_doors: d.closed_doors,
+            container_targets: d.container_targets,
+            unreachable: d.unreachable,
+            estimated_steps: d.estimated_steps,
+            score: d.score,
+        }
+    }
+}
+
+/// Python wrapper for GenReport
+#[pyclass]
+pub struct PyGenReport {
+    #[pyo3(get)]
+    pub requested_objects: usize,
+    #[pyo3(get)]
+    pub placed_objects: usize,
+    #[pyo3(get)]
+    pub exhausted_schemas: Vec<String>,
+    #[pyo3(get)]
+    pub passes: usize,
+    #[pyo3(get)]
+    pub warnings: Vec<String>,
+    /// (room name, fixture) pairs of room kits that could not be placed
+    #[pyo3(get)]
+    pub kit_failures: Vec<(String, String)>,
+    #[pyo3(get)]
+    pub difficulty: PyDifficultyScore,
+    /// (grocery bag id, item ids) for delivery tasks
+    #[pyo3(get)]
+    pub delivery: Option<(usize, Vec<usize>)>,
+    provenance: Vec<PyPlacementProvenance>,
+}
+
+#[pymethods]
+impl PyGenReport {
+    /// Placement record of an object, or None for unknown ids
+    fn provenance(&self, object_id: usize) -> Option<PyPlacementProvenance> {
+        self.provenance.iter().find(|p| p.object_id == object_id).cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyGenReport(requested_objects={}, placed_objects={}, passes={})",
+            self.requested_objects, self.placed_objects, self.passes
+        )
+    }
+}
+
+impl From<&tidy_core::GenReport> for PyGenReport {
+    fn from(report: &tidy_core::GenReport) -> Self {
+        PyGenReport {
+            requested_objects: report.requested_objects,
+            placed_objects: report.placed_objects,
+            exhausted_schemas: report.exhausted_schemas.iter().map(|s| s.to_string()).collect(),
+            passes: report.passes,
+            warnings: report.warnings.clone(),
+            kit_failures: report.kit_failures.clone(),
+            difficulty: PyDifficultyScore::from(report.difficulty),
+            delivery: report.delivery.as_ref().map(|d| (d.bag, d.items.clone())),
+            provenance: report.provenance.iter().map(PyPlacementProvenance::from).collect(),
         }
     }
 }
@@ -118,10 +464,18 @@ pub struct PyLayout {
     pub width: usize,
     #[pyo3(get)]
     pub height: usize,
+    /// Row-major cells; cell (x, y) is at y * width + x
     #[pyo3(get)]
     pub cells: Vec<i8>,
     #[pyo3(get)]
     pub room_names: Vec<String>,
+    /// Row-major zone ids; cell (x, y) is at y * width + x
+    #[pyo3(get)]
+    pub zones: Vec<i8>,
+    #[pyo3(get)]
+    pub zone_names: Vec<String>,
+    /// Backs the room queries with the core per-room index
+    layout: tidy_core::Layout,
 }
 
 #[pymethods]
@@ -140,11 +494,48 @@ impl PyLayout {
         Ok(self.cells[y * self.width + x])
     }
 
-    fn get_room_name(&self, room_id: usize) -> PyResult<String> {
+    /// ((height, width), cells) so that `np.array(cells).reshape(shape)` is indexed [y, x]
+    fn to_ndarray_order(&self) -> ((usize, usize), Vec<i8>) {
+        ((self.height, self.width), self.cells.clone())
+    }
+
+    fn get_room_name(&self, room_id: usize) -> PyResult<&str> {
+        if room_id >= self.room_names.len() {
+            return Err(PyValueError::new_err("Room ID out of bounds"));
+        }
+        Ok(&self.room_names[room_id])
+    }
+
+    /// (x, y) cells of a room, row by row
+    fn get_room_cells(&self, room_id: usize) -> PyResult<Vec<(usize, usize)>> {
         if room_id >= self.room_names.len() {
             return Err(PyValueError::new_err("Room ID out of bounds"));
         }
-        Ok(self.room_names[room_id].clone())
+        Ok(self.layout.room_cells(room_id).collect())
+    }
+
+    /// Number of cells in a room
+    fn get_room_area(&self, room_id: usize) -> PyResult<usize> {
+        if room_id >= self.room_names.len() {
+            return Err(PyValueError::new_err("Room ID out of bounds"));
+        }
+        Ok(self.layout.room_area(room_id))
+    }
+
+    /// Inclusive (min_x, min_y, max_x, max_y) of a room; None if it has no cells
+    fn get_room_bbox(&self, room_id: usize) -> PyResult<Option<(usize, usize, usize, usize)>> {
+        if room_id >= self.room_names.len() {
+            return Err(PyValueError::new_err("Room ID out of bounds"));
+        }
+        Ok(self.layout.room_bbox(room_id))
+    }
+
+    fn get_zone(&self, x: usize, y: usize) -> PyResult<Option<&str>> {
+        if x >= self.width || y >= self.height {
+            return Err(PyValueError::new_err("Coordinates out of bounds"));
+        }
+        let zone = self.zones[y * self.width + x];
+        Ok(if zone < 0 { None } else { Some(&self.zone_names[zone as usize]) })
     }
 }
 
@@ -154,23 +545,267 @@ impl From<&tidy_core::Layout> for PyLayout {
             width: layout.width,
             height: layout.height,
             cells: layout.cells.clone(),
-            room_names: layout.room_names.iter().map(|&s| s.to_string()).collect(),
+            room_names: layout.room_names.iter().map(|name| name.to_string()).collect(),
+            zones: layout.zones.clone(),
+            zone_names: layout.zone_names.iter().map(|name| name.to_string()).collect(),
+            layout: layout.clone(),
+        }
+    }
+}
+
+/// What dropping or placing the held object would do right now
+#[pyclass]
+#[derive(Clone)]
+pub struct PyDropPreview {
+    #[pyo3(get)]
+    pub holding: Option<usize>,
+    #[pyo3(get)]
+    pub can_drop: bool,
+    #[pyo3(get)]
+    pub drop_satisfies_target: bool,
+    /// (id, x, y, remaining_capacity, satisfies_target) per accepting container
+    #[pyo3(get)]
+    pub containers: Vec<(usize, usize, usize, usize, bool)>,
+    /// Ids of loose pickables a drop would stack onto, lowest first
+    #[pyo3(get)]
+    pub stacked: Vec<usize>,
+}
+
+impl From<tidy_core::DropPreview> for PyDropPreview {
+    fn from(p: tidy_core::DropPreview) -> Self {
+        PyDropPreview {
+            holding: p.holding,
+            can_drop: p.can_drop,
+            drop_satisfies_target: p.drop_satisfies_target,
+            containers: p
+                .containers
+                .iter()
+                .map(|c| (c.id, c.x, c.y, c.remaining_capacity, c.satisfies_target))
+                .collect(),
+            stacked: p.stacked,
+        }
+    }
+}
+
+/// One entry per room, padded to `max_rooms`; matrices are flat `max_rooms * max_rooms` lists
+#[pyclass]
+#[derive(Clone)]
+pub struct PyRoomGraph {
+    #[pyo3(get)]
+    pub max_rooms: usize,
+    #[pyo3(get)]
+    pub rooms: usize,
+    #[pyo3(get)]
+    pub room_ids: Vec<i32>,
+    #[pyo3(get)]
+    pub area: Vec<u32>,
+    #[pyo3(get)]
+    pub agent_present: Vec<bool>,
+    #[pyo3(get)]
+    pub misplaced: Vec<u32>,
+    #[pyo3(get)]
+    pub free_capacity: Vec<u32>,
+    #[pyo3(get)]
+    pub adjacent: Vec<bool>,
+    #[pyo3(get)]
+    pub door_open: Vec<bool>,
+}
+
+impl From<tidy_core::RoomGraphObservation> for PyRoomGraph {
+    fn from(o: tidy_core::RoomGraphObservation) -> Self {
+        PyRoomGraph {
+            max_rooms: o.max_rooms,
+            rooms: o.rooms,
+            room_ids: o.room_ids,
+            area: o.area,
+            agent_present: o.agent_present,
+            misplaced: o.misplaced,
+            free_capacity: o.free_capacity,
+            adjacent: o.adjacent,
+            door_open: o.door_open,
+        }
+    }
+}
+
+/// Cells and objects around the agent; `cells` is a flat row-major `side * side` list
+#[pyclass]
+#[derive(Clone)]
+pub struct PyLocalView {
+    #[pyo3(get)]
+    pub radius: usize,
+    #[pyo3(get)]
+    pub side: usize,
+    #[pyo3(get)]
+    pub cells: Vec<i8>,
+    #[pyo3(get)]
+    pub object_ids: Vec<usize>,
+    /// Object offsets from the agent, parallel to `object_ids`
+    #[pyo3(get)]
+    pub object_dx: Vec<isize>,
+    #[pyo3(get)]
+    pub object_dy: Vec<isize>,
+    #[pyo3(get)]
+    pub holding: bool,
+}
+
+impl From<tidy_core::LocalView> for PyLocalView {
+    fn from(v: tidy_core::LocalView) -> Self {
+        PyLocalView {
+            radius: v.radius,
+            side: v.side(),
+            object_ids: v.objects.iter().map(|o| o.0).collect(),
+            object_dx: v.objects.iter().map(|o| o.1).collect(),
+            object_dy: v.objects.iter().map(|o| o.2).collect(),
+            cells: v.cells,
+            holding: v.holding,
+        }
+    }
+}
+
+/// Frozen, point-in-time view of a simulator; safe to read from other threads
+#[pyclass(frozen)]
+pub struct PyWorldView {
+    view: tidy_core::WorldView,
+}
+
+#[pymethods]
+impl PyWorldView {
+    #[getter]
+    fn width(&self) -> usize {
+        self.view.width
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.view.height
+    }
+
+    #[getter]
+    fn agent_x(&self) -> usize {
+        self.view.agent_x
+    }
+
+    #[getter]
+    fn agent_y(&self) -> usize {
+        self.view.agent_y
+    }
+
+    #[getter]
+    fn holding(&self) -> Option<usize> {
+        self.view.holding
+    }
+
+    #[getter]
+    fn score(&self) -> f32 {
+        self.view.score
+    }
+
+    #[getter]
+    fn steps(&self) -> u32 {
+        self.view.steps
+    }
+
+    #[getter]
+    fn time(&self) -> u32 {
+        self.view.time
+    }
+
+    /// Row-major cells; cell (x, y) is at y * width + x
+    #[getter]
+    fn cells(&self) -> Vec<i8> {
+        self.view.cells.to_vec()
+    }
+
+    /// List of (id, name, x, y, pickable, placed) tuples
+    #[getter]
+    fn objects(&self) -> Vec<(usize, String, usize, usize, bool, bool)> {
+        self.view
+            .objects
+            .iter()
+            .map(|o| (o.id, o.name.to_string(), o.x, o.y, o.pickable, o.placed))
+            .collect()
+    }
+
+    fn door_open(&self, x: usize, y: usize) -> PyResult<Option<bool>> {
+        if x >= self.view.width || y >= self.view.height {
+            return Err(PyValueError::new_err("Coordinates out of bounds"));
         }
+        Ok(self.view.door_open(x, y))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyWorldView(agent=({}, {}), objects={}, score={:.2})",
+            self.view.agent_x,
+            self.view.agent_y,
+            self.view.objects.len(),
+            self.view.score
+        )
+    }
+}
+
+/// Objects that were out of place when the task was read, with an action bound
+#[pyclass(frozen)]
+pub struct PyTidyTask {
+    task: tidy_core::TidyTask,
+}
+
+#[pymethods]
+impl PyTidyTask {
+    /// List of (object id, target description) tuples, e.g. (4, "inside a dishwasher")
+    #[getter]
+    fn goals(&self) -> Vec<(usize, String)> {
+        self.task.goals.iter().map(|g| (g.object_id, g.description.clone())).collect()
+    }
+
+    /// Oracle steps to complete every goal from the state the task was read in; None if it failed
+    #[getter]
+    fn max_actions(&self) -> Option<u32> {
+        self.task.max_actions
+    }
+
+    /// Per goal, whether its object is in place in the simulator's world (a held one is not)
+    fn completed(&self, sim: PyRef<PySimulator>) -> Vec<bool> {
+        self.task.completed(&sim.sim.world)
+    }
+
+    fn is_complete(&self, sim: PyRef<PySimulator>) -> bool {
+        self.task.is_complete(&sim.sim.world)
+    }
+
+    fn __repr__(&self) -> String {
+        let max_actions = self.task.max_actions.map_or_else(|| "None".to_string(), |n| n.to_string());
+        format!("PyTidyTask(goals={}, max_actions={})", self.task.goals.len(), max_actions)
     }
 }
 
+/// (door id, (room a, room b), open, cells as (x, y)) as returned by `get_doors`
+type DoorTuple = (usize, (i8, i8), bool, Vec<(usize, usize)>);
+
 /// Python wrapper for Simulator
 #[pyclass]
 pub struct PySimulator {
     sim: tidy_core::Simulator,
+    /// Scratch view reused by `local_view_into`
+    view: tidy_core::LocalView,
+    /// Second scratch view for its egocentric turn
+    turned: tidy_core::LocalView,
+}
+
+impl From<tidy_core::Simulator> for PySimulator {
+    fn from(sim: tidy_core::Simulator) -> Self {
+        PySimulator { sim, view: Default::default(), turned: Default::default() }
+    }
 }
 
 #[pymethods]
 impl PySimulator {
+    /// `action_costs` maps "move", "door", "pick_up", "drop", "place_into" and "inspect" to clock costs
     #[new]
-    fn new(opts: PyGenOpts) -> PyResult<Self> {
+    #[pyo3(signature = (opts, action_costs=None, max_time=None))]
+    fn new(opts: PyGenOpts, action_costs: Option<HashMap<String, u32>>, max_time: Option<u32>) -> PyResult<Self> {
         let rust_opts = tidy_core::GenOpts::from(opts);
-        let world = tidy_core::generate(&rust_opts);
+        let world = tidy_core::generate(&rust_opts).map_err(|e| PyValueError::new_err(e.to_string()))?;
         
         // Auto-select first room cell for agent start
         let mut start_x = 0;
@@ -183,19 +818,21 @@ impl PySimulator {
             }
         }
         
-        match tidy_core::Simulator::new(world, start_x, start_y) {
-            Ok(sim) => Ok(PySimulator { sim }),
-            Err(e) => Err(PyRuntimeError::new_err(e)),
+        let mut sim = tidy_core::Simulator::new(world, start_x, start_y).map_err(PyRuntimeError::new_err)?;
+        for (action, cost) in action_costs.unwrap_or_default() {
+            sim.costs.set(&action, cost).map_err(PyValueError::new_err)?;
         }
+        sim.max_time = max_time;
+        Ok(PySimulator::from(sim))
     }
 
     #[staticmethod]
     fn from_world_and_position(opts: PyGenOpts, start_x: usize, start_y: usize) -> PyResult<Self> {
         let rust_opts = tidy_core::GenOpts::from(opts);
-        let world = tidy_core::generate(&rust_opts);
+        let world = tidy_core::generate(&rust_opts).map_err(|e| PyValueError::new_err(e.to_string()))?;
         
         match tidy_core::Simulator::new(world, start_x, start_y) {
-            Ok(sim) => Ok(PySimulator { sim }),
+            Ok(sim) => Ok(PySimulator::from(sim)),
             Err(e) => Err(PyRuntimeError::new_err(e)),
         }
     }
@@ -210,29 +847,85 @@ impl PySimulator {
         self.sim.agent.y
     }
 
-    fn move_up(&mut self) -> PyResult<()> {
-        self.sim.up().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+    /// Direction the agent faces: "north", "east", "south" or "west"
+    #[getter]
+    fn agent_facing(&self) -> &'static str {
+        self.sim.agent.facing.name()
     }
 
-    fn move_down(&mut self) -> PyResult<()> {
-        self.sim.down().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+    /// Number of agents; 1 unless created with `with_agents`
+    #[getter]
+    fn num_agents(&self) -> usize {
+        self.sim.num_agents()
     }
 
-    fn move_left(&mut self) -> PyResult<()> {
-        self.sim.left().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+    /// (x, y) of the agent with the given id, None past `num_agents`
+    fn get_agent_position(&self, agent_id: usize) -> Option<(usize, usize)> {
+        self.sim.agent_by_id(agent_id).map(|a| (a.x, a.y))
     }
 
-    fn move_right(&mut self) -> PyResult<()> {
-        self.sim.right().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+    /// Object held by the agent with the given id
+    fn get_agent_holding(&self, agent_id: usize) -> Option<PyObject> {
+        self.sim.holding_by_id(agent_id).map(|o| self.py_object(o))
     }
 
-    fn interact(&mut self, dx: i32, dy: i32) -> PyResult<()> {
+    /// A fresh simulator over the current world with one agent per (x, y) start; agent 0 is
+    /// the one the single-agent methods move. Starts must be distinct and walkable.
+    fn with_agents(&self, starts: Vec<(usize, usize)>) -> PyResult<Self> {
+        tidy_core::Simulator::new_multi(self.sim.world.clone(), &starts)
+            .map(PySimulator::from)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// `step` for the agent with the given id; raises like `step`
+    #[pyo3(signature = (agent_id, action, dx=0, dy=0, object_id=None))]
+    fn step_agent(&mut self, agent_id: usize, action: &str, dx: i8, dy: i8, object_id: Option<usize>) -> PyResult<f32> {
         self.sim
-            .interact(dx as isize, dy as isize)
-            .map_err(|e| PyRuntimeError::new_err(e))
+            .step_agent(agent_id, parse_action(action, dx, dy, object_id)?)
+            .map(|outcome| outcome.reward)
+            .map_err(|e| action_error(e.name(), e))
     }
 
-    fn open_door_up(&mut self) -> PyResult<()> {
+    fn move_up(&mut self) -> PyResult<()> {
+        self.sim.up().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn move_down(&mut self) -> PyResult<()> {
+        self.sim.down().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn move_left(&mut self) -> PyResult<()> {
+        self.sim.left().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn move_right(&mut self) -> PyResult<()> {
+        self.sim.right().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn interact(&mut self, dx: i32, dy: i32) -> PyResult<()> {
+        self.sim
+            .interact(dx as isize, dy as isize)
+            .map_err(|e| action_error(e.name(), e))
+    }
+
+    fn turn_left(&mut self) -> PyResult<()> {
+        self.sim.turn_left().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn turn_right(&mut self) -> PyResult<()> {
+        self.sim.turn_right().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn move_forward(&mut self) -> PyResult<()> {
+        self.sim.move_forward().map_err(|e| action_error(e.name(), e))
+    }
+
+    /// `interact` with the cell the agent faces
+    fn interact_ahead(&mut self) -> PyResult<()> {
+        self.sim.interact_ahead().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn open_door_up(&mut self) -> PyResult<()> {
         self.interact(0, -1)
     }
 
@@ -249,17 +942,238 @@ impl PySimulator {
     }
 
     fn pick_up(&mut self) -> PyResult<()> {
-        self.sim.pick_up().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+        self.sim.pick_up().map_err(|e| action_error(e.name(), e))
+    }
+
+    fn pick_up_by_id(&mut self, object_id: usize) -> PyResult<()> {
+        self.sim.pick_up_by_id(object_id).map_err(|e| action_error(e.name(), e))
     }
 
     fn drop(&mut self) -> PyResult<()> {
-        self.sim.drop().map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+        self.sim.drop().map_err(|e| action_error(e.name(), e))
     }
 
     fn place_into(&mut self, target_id: usize) -> PyResult<()> {
         self.sim
             .place_into(target_id)
-            .map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))
+            .map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Put the held object in a container or on a floor cell satisfying its target, on or next to the agent
+    fn auto_place(&mut self) -> PyResult<()> {
+        self.sim.auto_place().map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Open or close a container on or next to the agent
+    fn toggle_container(&mut self, object_id: usize) -> PyResult<()> {
+        self.sim.toggle_container(object_id).map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Run an appliance on or next to the agent, e.g. a Dishwasher turning DirtyPlates into Plates
+    fn run_appliance(&mut self, object_id: usize) -> PyResult<()> {
+        self.sim.run_appliance(object_id).map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Perform an action by name ("move_up", "move_down", "move_left", "move_right", "pick_up",
+    /// "drop", "interact", "place_into", "auto_place", "noop", "pick_up_by_id",
+    /// "toggle_container" or "run_appliance") and return its reward: the built-in one of
+    /// `set_reward_config`, if set, plus what hooks add
+    #[pyo3(signature = (action, dx=0, dy=0, object_id=None))]
+    fn step(&mut self, action: &str, dx: i8, dy: i8, object_id: Option<usize>) -> PyResult<f32> {
+        self.sim
+            .step(parse_action(action, dx, dy, object_id)?)
+            .map(|outcome| outcome.reward)
+            .map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Perform the action with a discrete id (an index into `action_names()`). Failed actions
+    /// do not raise; returns {"steps", "succeeded", "reward", "tidiness_delta", "error", "done"}
+    /// with `error` naming the failure, or None, and `done` from `is_done()`
+    fn step_id(&mut self, py: Python<'_>, action_id: usize) -> PyResult<Py<PyDict>> {
+        let action = tidy_core::Action::from_id(action_id)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown action id: {}", action_id)))?;
+        let result = self.sim.step(action);
+        let dict = PyDict::new(py);
+        dict.set_item("steps", self.sim.steps)?;
+        dict.set_item("succeeded", result.is_ok())?;
+        dict.set_item("reward", result.as_ref().map_or(self.sim.failed_step_reward(), |o| o.reward))?;
+        dict.set_item("tidiness_delta", result.as_ref().map_or(0.0, |o| o.tidiness_delta))?;
+        dict.set_item("error", result.err().map(|e| e.name()))?;
+        dict.set_item("done", self.sim.is_done())?;
+        Ok(dict.into())
+    }
+
+    /// Parse a command such as "pick up the apple" or "open the door to the east" (see
+    /// `language::parse_command`) and step the action. Returns the `step_id` dict plus "action"
+    /// (the parsed action, None if parsing failed), "message" (the error text, or None) and
+    /// "candidates" (what an ambiguous reference could mean). A parse failure takes no step;
+    /// its `error` is the parse error's name, e.g. "AmbiguousObject" or "UnknownObject"
+    fn parse_and_step(&mut self, py: Python<'_>, text: &str) -> PyResult<Py<PyDict>> {
+        let dict = PyDict::new(py);
+        match tidy_core::language::parse_command(text, &self.sim.world, &self.sim.agent) {
+            Ok(action) => {
+                let result = self.sim.step(action);
+                dict.set_item("action", format!("{:?}", action))?;
+                dict.set_item("succeeded", result.is_ok())?;
+                dict.set_item("reward", result.as_ref().map_or(self.sim.failed_step_reward(), |o| o.reward))?;
+                dict.set_item("tidiness_delta", result.as_ref().map_or(0.0, |o| o.tidiness_delta))?;
+                dict.set_item("error", result.as_ref().err().map(|e| e.name()))?;
+                dict.set_item("message", result.err().map(|e| e.to_string()))?;
+                dict.set_item("candidates", Vec::<String>::new())?;
+            }
+            Err(e) => {
+                dict.set_item("action", py.None())?;
+                dict.set_item("succeeded", false)?;
+                dict.set_item("reward", 0.0)?;
+                dict.set_item("tidiness_delta", 0.0)?;
+                dict.set_item("error", e.name())?;
+                dict.set_item("message", e.to_string())?;
+                let candidates = match e {
+                    tidy_core::language::ParseError::AmbiguousObject { candidates, .. } => candidates,
+                    _ => Vec::new(),
+                };
+                dict.set_item("candidates", candidates)?;
+            }
+        }
+        dict.set_item("steps", self.sim.steps)?;
+        dict.set_item("done", self.sim.is_done())?;
+        Ok(dict.into())
+    }
+
+    /// Fraction of pickable objects in their target placement, counting a held object as misplaced
+    #[getter]
+    fn tidiness(&self) -> f32 {
+        self.sim.tidiness()
+    }
+
+    /// Ids of pickable objects not in their target placement, the held one included, ascending
+    fn misplaced_objects(&self) -> Vec<usize> {
+        self.sim.misplaced_objects()
+    }
+
+    /// Sentence listing a room's name, furniture and the loose items on its floor
+    fn describe_room(&self, room: usize) -> String {
+        tidy_core::language::describe_room(&self.sim.world, room)
+    }
+
+    /// Summary of the rooms, what each contains and how many objects are out of place
+    fn describe_world(&self) -> String {
+        tidy_core::language::describe_world(&self.sim.world)
+    }
+
+    /// Text observation of the agent's cell, held object, neighbouring cells, closed doors and
+    /// the objects within `radius`; the same state always gives the same text
+    #[pyo3(signature = (radius=3))]
+    fn describe(&self, radius: usize) -> String {
+        tidy_core::language::describe_surroundings(&self.sim, radius)
+    }
+
+    /// Action names (see `action_names()`) that walk to (x, y), opening closed doors on the way
+    /// unless `through_closed_doors` is False; None when unreachable
+    #[pyo3(signature = (x, y, through_closed_doors=true))]
+    fn path_to(&self, x: usize, y: usize, through_closed_doors: bool) -> Option<Vec<&'static str>> {
+        self.sim
+            .path_to(x, y, through_closed_doors)
+            .map(|actions| actions.iter().filter_map(|a| a.discrete_name()).collect())
+    }
+
+    /// Moves needed to walk to (x, y), through closed doors; None when unreachable
+    fn distance_to(&self, x: usize, y: usize) -> Option<usize> {
+        self.sim.distance_to(x, y)
+    }
+
+    /// Cells and objects within `radius` of the agent; objects inside containers only
+    /// with `include_contained`. `egocentric` turns the view so the agent faces up.
+    #[pyo3(signature = (radius, include_contained=false, egocentric=false))]
+    fn get_local_view(&self, radius: usize, include_contained: bool, egocentric: bool) -> PyLocalView {
+        let view = self.sim.local_view(radius, include_contained);
+        if egocentric {
+            return view.rotated(self.sim.agent.facing).into();
+        }
+        view.into()
+    }
+
+    /// Write the `get_local_view` cells, row-major, into `cells`: a writable, C-contiguous int8
+    /// buffer of (2 * radius + 1)² elements, e.g. a numpy array allocated once. Reuses its own
+    /// scratch too, so steady-state calls do not allocate. Returns the number of visible objects
+    #[pyo3(signature = (cells, radius, include_contained=false, egocentric=false))]
+    fn local_view_into(
+        &mut self,
+        py: Python<'_>,
+        cells: pyo3::buffer::PyBuffer<i8>,
+        radius: usize,
+        include_contained: bool,
+        egocentric: bool,
+    ) -> PyResult<usize> {
+        self.sim.local_view_into(radius, include_contained, &mut self.view);
+        self.view.pad_to(radius);
+        let view = if egocentric {
+            self.view.rotate_into(self.sim.agent.facing, &mut self.turned);
+            &self.turned
+        } else {
+            &self.view
+        };
+        cells.copy_from_slice(py, &view.cells)?;
+        Ok(view.objects.len())
+    }
+
+    /// List of (door id, (room a, room b), open, [(x, y), ...]) tuples; a side without a room is OUTSIDE (-2)
+    fn get_doors(&self) -> Vec<DoorTuple> {
+        let w = self.sim.world.layout.width;
+        self.sim
+            .world
+            .layout
+            .doors
+            .iter()
+            .map(|d| (d.id, d.rooms, d.open, d.cells.iter().map(|&i| (i % w, i / w)).collect()))
+            .collect()
+    }
+
+    /// (room id, door id) pairs of the rooms one door away from `room_id`
+    fn get_adjacent_rooms(&self, room_id: usize) -> Vec<(usize, usize)> {
+        self.sim.world.layout.adjacent_rooms(room_id)
+    }
+
+    /// Walk to a room, opening doors on the way; returns (hook reward, primitive steps taken)
+    fn go_to_room(&mut self, room: usize) -> PyResult<(f32, u32)> {
+        self.sim.go_to_room(room).map(|o| (o.reward, o.primitive_steps)).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Walk onto an object's cell, opening doors on the way; returns (hook reward, primitive steps taken)
+    fn go_to_object(&mut self, object_id: usize) -> PyResult<(f32, u32)> {
+        self.sim
+            .go_to_object(object_id)
+            .map(|o| (o.reward, o.primitive_steps))
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Run a command such as "go to the kitchen"; returns (hook reward, primitive steps taken)
+    fn command(&mut self, text: &str) -> PyResult<(f32, u32)> {
+        let command = self
+            .sim
+            .parse_command(text)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown command: {}", text)))?;
+        self.sim.run_command(command).map(|o| (o.reward, o.primitive_steps)).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Register a built-in hook: "room_ban" (room=...) or "pickup_tax" (amount=...)
+    #[pyo3(signature = (name, room=None, amount=None))]
+    fn add_hook(&mut self, name: &str, room: Option<String>, amount: Option<f32>) -> PyResult<()> {
+        let hook: Box<dyn tidy_core::SimHook> = match name {
+            "room_ban" => Box::new(tidy_core::RoomBan {
+                room: room.ok_or_else(|| PyValueError::new_err("room_ban requires room"))?,
+            }),
+            "pickup_tax" => Box::new(tidy_core::PickupTax::new(
+                amount.ok_or_else(|| PyValueError::new_err("pickup_tax requires amount"))?,
+            )),
+            other => return Err(PyValueError::new_err(format!("Unknown hook: {}", other))),
+        };
+        self.sim.add_hook(hook);
+        Ok(())
+    }
+
+    fn clear_hooks(&mut self) {
+        self.sim.clear_hooks();
     }
 
     fn get_layout(&self) -> PyLayout {
@@ -267,20 +1181,28 @@ impl PySimulator {
     }
 
     fn get_objects(&self) -> Vec<PyObject> {
-        self.sim.world.objects.iter().map(PyObject::from).collect()
+        self.sim.world.objects.iter().map(|o| self.py_object(o)).collect()
     }
 
     fn get_holding(&self) -> Option<PyObject> {
-        self.sim.holding.as_ref().map(PyObject::from)
+        self.sim.holding.as_ref().map(|o| self.py_object(o))
     }
 
+    /// Objects on a cell, lowest id first
     fn get_objects_at(&self, x: usize, y: usize) -> Vec<PyObject> {
-        self.sim
-            .world
-            .objects
+        self.sim.world.objects_at(x, y).into_iter().map(|o| self.py_object(o)).collect()
+    }
+
+    /// Objects inside a container, in the order they were put in; empty for unknown ids and,
+    /// with `closed_containers` on, for closed containers
+    fn get_contents(&self, container_id: usize) -> Vec<PyObject> {
+        let world = &self.sim.world;
+        let Some(container) = world.object(container_id).filter(|c| self.sim.is_open(c)) else { return Vec::new() };
+        container
+            .contents
             .iter()
-            .filter(|obj| obj.x == x && obj.y == y)
-            .map(PyObject::from)
+            .filter_map(|&id| world.object(id))
+            .map(|o| self.py_object(o))
             .collect()
     }
 
@@ -290,15 +1212,460 @@ impl PySimulator {
             .objects
             .iter()
             .find(|obj| obj.id == id)
-            .map(PyObject::from)
+            .map(|o| self.py_object(o))
     }
 
-    fn check_placement(&self, object_id: usize) -> bool {
-        if let Some(obj) = self.sim.world.objects.iter().find(|o| o.id == object_id) {
-            obj.check_placement(&self.sim.world)
-        } else {
-            false
+    fn inspect(&mut self, object_id: usize) -> PyResult<String> {
+        self.sim
+            .inspect(object_id)
+            .map_err(|e| action_error(e.name(), e))
+    }
+
+    /// Shaping potential: negative step distance to the current goal (0 when nothing is left)
+    fn potential(&mut self) -> f32 {
+        self.sim.potential()
+    }
+
+    /// Have every action pay the built-in reward; unset keywords keep their defaults
+    /// (step_penalty=0.01, correct_place_bonus=1.0, the rest 0 and no shaping). Shaping needs
+    /// both shaping_gamma and shaping_weight
+    #[pyo3(signature = (
+        step_penalty=None,
+        correct_place_bonus=None,
+        incorrect_place_penalty=None,
+        pickup_bonus=None,
+        done_bonus=None,
+        shaping_gamma=None,
+        shaping_weight=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn set_reward_config(
+        &mut self,
+        step_penalty: Option<f32>,
+        correct_place_bonus: Option<f32>,
+        incorrect_place_penalty: Option<f32>,
+        pickup_bonus: Option<f32>,
+        done_bonus: Option<f32>,
+        shaping_gamma: Option<f32>,
+        shaping_weight: Option<f32>,
+    ) -> PyResult<()> {
+        let d = tidy_core::RewardConfig::default();
+        let shaping = match (shaping_gamma, shaping_weight) {
+            (Some(gamma), Some(weight)) => Some(tidy_core::ShapingConfig { gamma, weight }),
+            (None, None) => None,
+            _ => return Err(PyValueError::new_err("shaping needs both shaping_gamma and shaping_weight")),
+        };
+        self.sim.reward_config = Some(tidy_core::RewardConfig {
+            step_penalty: step_penalty.unwrap_or(d.step_penalty),
+            correct_place_bonus: correct_place_bonus.unwrap_or(d.correct_place_bonus),
+            incorrect_place_penalty: incorrect_place_penalty.unwrap_or(d.incorrect_place_penalty),
+            pickup_bonus: pickup_bonus.unwrap_or(d.pickup_bonus),
+            done_bonus: done_bonus.unwrap_or(d.done_bonus),
+            shaping,
+        });
+        Ok(())
+    }
+
+    /// Go back to rewards from hooks only
+    fn clear_reward_config(&mut self) {
+        self.sim.reward_config = None;
+    }
+
+    /// Potential-based shaping term weight * (gamma * phi_after - phi_before)
+    #[staticmethod]
+    fn shaping_term(gamma: f32, weight: f32, phi_before: f32, phi_after: f32) -> f32 {
+        tidy_core::ShapingConfig { gamma, weight }.term(phi_before, phi_after)
+    }
+
+    /// Number of successful actions taken
+    #[getter]
+    fn steps(&self) -> u32 {
+        self.sim.steps
+    }
+
+    /// Elapsed simulator time
+    #[getter]
+    fn time(&self) -> u32 {
+        self.sim.time
+    }
+
+    #[getter]
+    fn get_max_time(&self) -> Option<u32> {
+        self.sim.max_time
+    }
+
+    #[setter]
+    fn set_max_time(&mut self, max_time: Option<u32>) {
+        self.sim.max_time = max_time;
+    }
+
+    fn time_exceeded(&self) -> bool {
+        self.sim.time_exceeded()
+    }
+
+    /// Whether the episode is over: `is_success()` or `is_truncated()`
+    fn is_done(&self) -> bool {
+        self.sim.is_done()
+    }
+
+    /// Whether nothing is held and every pickable is in place, or tidiness has reached
+    /// `min_tidiness` of `set_done_criteria`
+    fn is_success(&self) -> bool {
+        self.sim.is_success()
+    }
+
+    /// Whether the `max_steps` of `set_done_criteria` or `max_time` is used up
+    fn is_truncated(&self) -> bool {
+        self.sim.is_truncated()
+    }
+
+    /// Configure `is_done`: a budget of successful actions and the tidiness that counts as
+    /// success (None: every pickable in place)
+    #[pyo3(signature = (max_steps=None, min_tidiness=None))]
+    fn set_done_criteria(&mut self, max_steps: Option<u32>, min_tidiness: Option<f32>) -> PyResult<()> {
+        if min_tidiness.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+            return Err(PyValueError::new_err("min_tidiness must be between 0 and 1"));
         }
+        self.sim.done_criteria = tidy_core::DoneCriteria { max_steps, min_tidiness };
+        Ok(())
+    }
+
+    fn set_action_cost(&mut self, action: &str, cost: u32) -> PyResult<()> {
+        self.sim.costs.set(action, cost).map_err(PyValueError::new_err)
+    }
+
+    #[getter]
+    fn get_decay_enabled(&self) -> bool {
+        self.sim.decay_enabled
+    }
+
+    #[setter]
+    fn set_decay_enabled(&mut self, enabled: bool) {
+        self.sim.decay_enabled = enabled;
+    }
+
+    /// Whether `place_into`, and picking up or dropping through `interact`, need the target
+    /// on or next to the agent (True by default)
+    #[getter]
+    fn get_strict_reach(&self) -> bool {
+        self.sim.strict_reach
+    }
+
+    #[setter]
+    fn set_strict_reach(&mut self, strict: bool) {
+        self.sim.strict_reach = strict;
+    }
+
+    /// Whether closed containers hide their contents and refuse place_into and pick-ups
+    /// (False by default: every container acts open)
+    #[getter]
+    fn get_closed_containers(&self) -> bool {
+        self.sim.closed_containers
+    }
+
+    #[setter]
+    fn set_closed_containers(&mut self, enabled: bool) {
+        self.sim.closed_containers = enabled;
+    }
+
+    /// Whether large furniture keeps the agent off its cell: moves onto it fail with
+    /// BlockedByObject and go_to routes around it (False by default)
+    #[getter]
+    fn get_blocking_objects(&self) -> bool {
+        self.sim.blocking_objects
+    }
+
+    #[setter]
+    fn set_blocking_objects(&mut self, enabled: bool) {
+        self.sim.blocking_objects = enabled;
+    }
+
+    /// Advance time by one step; returns ("decayed", id, from, into) tuples
+    fn tick(&mut self) -> Vec<(&'static str, usize, String, String)> {
+        self.sim
+            .tick()
+            .into_iter()
+            .filter_map(|event| match event {
+                tidy_core::SimEvent::Decayed { id, from, into } => Some(("decayed", id, from.into_owned(), into.into_owned())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Every state change since the last call, oldest first, as dicts with a "type" key:
+    /// "moved" (agent, from, to), "turned" (agent, facing), "door_opened"/"door_closed" (door,
+    /// x, y; door is None for unregistered doors), "picked_up" (agent, id), "dropped" (agent,
+    /// id, x, y), "placed_into" (agent, id, container), "disposed" (id, name),
+    /// "container_opened"/"container_closed" (id) and "decayed"/"transformed" (id, from, into).
+    /// The latest 4096 are kept; restoring a state clears them.
+    fn drain_events(&mut self, py: Python<'_>) -> PyResult<Vec<Py<PyDict>>> {
+        self.sim.drain_events().iter().map(|event| event_dict(py, event)).collect()
+    }
+
+    /// Override an object's label; None restores the default
+    #[pyo3(signature = (object_id, label=None))]
+    fn set_label(&mut self, object_id: usize, label: Option<String>) -> PyResult<()> {
+        self.sim
+            .set_label(object_id, label)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Room-level observation sorted by room id and padded to `max_rooms` (padding ids are -1)
+    #[pyo3(signature = (max_rooms=16))]
+    fn room_graph_observation(&mut self, max_rooms: usize) -> PyRoomGraph {
+        self.sim.room_graph_observation(max_rooms).into()
+    }
+
+    /// Start an atomic edit of the world: `with sim.transaction() as tx: tx.move_object(...)`.
+    ///
+    /// Edits apply to a copy and replace the world when the block exits without an exception;
+    /// don't step the simulator inside the block.
+    fn transaction(slf: PyRef<'_, Self>) -> PyWorldTx {
+        let tx = tidy_core::WorldTx::new(&slf.sim.world);
+        PyWorldTx { sim: slf.into(), tx: Some(tx) }
+    }
+
+    /// Ids of objects whose name, humanized name or label equals `name`, ignoring case
+    fn find_by_name(&self, name: &str) -> Vec<usize> {
+        self.sim.world.find_by_name(name)
+    }
+
+    /// Up to `limit` (object id, score) pairs most similar to `query`, best first
+    #[pyo3(signature = (query, limit=10))]
+    fn find(&self, query: &str, limit: usize) -> Vec<(usize, f32)> {
+        self.sim.world.find_fuzzy(query, limit)
+    }
+
+    /// Current world as canonical JSON (objects sorted by id), stable for diffing and dedup
+    fn world_json(&self) -> PyResult<String> {
+        self.sim.world.to_json().map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The whole simulator, world included, as JSON for `PySimulator.from_json`
+    fn to_json(&self) -> PyResult<String> {
+        self.sim.to_json().map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Rebuild a simulator saved with `to_json`
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        tidy_core::Simulator::from_json(json).map(PySimulator::from).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Snapshot of the episode state as msgpack bytes, for `load_state` on a simulator over the same world
+    fn save_state(&self, py: Python<'_>) -> PyResult<Py<PyBytes>> {
+        let bytes = self.sim.state().to_msgpack().map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    /// Restore a snapshot from `save_state`
+    fn load_state(&mut self, data: &[u8]) -> PyResult<()> {
+        let state = tidy_core::SimState::from_msgpack(data).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.sim.restore(&state).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Return to the start of the episode: the world as generated, the agent at its start.
+    /// Costs, decay and the time limit are kept.
+    fn reset(&mut self) -> PyResult<()> {
+        self.sim.reset().map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Start recording every attempted action with its result, replacing any earlier recording
+    fn enable_recording(&mut self) {
+        self.sim.enable_recording();
+    }
+
+    /// The recording as JSONL: a format header line, then one action per line
+    fn get_action_log(&self) -> PyResult<String> {
+        tidy_core::recording::to_jsonl(self.sim.actions()).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Replay a `get_action_log` recording on this simulator, which must be in the state the
+    /// recording started from; raises if an action's result differs from the recorded one
+    fn replay_from_log(&mut self, log: &str) -> PyResult<()> {
+        let actions = tidy_core::recording::from_jsonl(log).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.sim.replay_actions(&actions).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+
+    /// Summary statistics of the current world as a JSON string
+    fn world_stats(&self) -> PyResult<String> {
+        let stats = tidy_core::stats::world_stats(&self.sim.world);
+        serde_json::to_string(&stats).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write checkpoints to `path`: a snapshot now, then every change and a fresh snapshot every `every` changes
+    #[pyo3(signature = (path, every=100))]
+    fn checkpoint_to(&mut self, path: &str, every: u32) -> PyResult<()> {
+        self.sim.checkpoint_to(path, every).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Stop checkpointing; raises if a write failed while it was active
+    fn stop_checkpointing(&mut self) -> PyResult<()> {
+        self.sim.stop_checkpointing().map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Resume from a checkpoint file of the same world; returns the number of replayed changes
+    fn resume_from(&mut self, path: &str) -> PyResult<usize> {
+        self.sim.resume_from(path).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Text grid of the current state; `theme` is a built-in theme name or a theme JSON,
+    /// `glyphs` adds room letters and shows object initials (lowercase for pickables) instead of ids
+    #[pyo3(signature = (theme=None, glyphs=false))]
+    fn render_ascii(&self, theme: Option<&str>, glyphs: bool) -> PyResult<String> {
+        let opts = tidy_core::RenderOpts { glyphs, ..Default::default() };
+        Ok(tidy_core::render::render_text(&self.sim, &parse_theme(theme)?, opts))
+    }
+
+    /// Text snapshot of the current state for logs and tests: `#` walls, `+`/`/` closed and
+    /// open doors, `.` floor labelled with room letters, `@` for the agent and objects by
+    /// initial (lowercase for pickables)
+    fn render(&self) -> String {
+        self.sim.render_ascii()
+    }
+
+    /// RGBA pixels as bytes, `height*scale` rows of `width*scale` pixels; `glyphs` adds room
+    /// and object letters (scale 5 or more), `pattern_fill` a fill pattern per room and
+    /// `room_names` each room's name at the center of its bounding box
+    #[pyo3(signature = (theme=None, scale=8, glyphs=false, pattern_fill=false, room_names=false))]
+    fn render_rgba(
+        &self,
+        py: Python<'_>,
+        theme: Option<&str>,
+        scale: usize,
+        glyphs: bool,
+        pattern_fill: bool,
+        room_names: bool,
+    ) -> PyResult<Py<PyBytes>> {
+        let opts = tidy_core::RenderOpts { glyphs, pattern_fill, room_names };
+        let pixels = tidy_core::render::render_rgba(&self.sim, &parse_theme(theme)?, scale.max(1), opts);
+        Ok(PyBytes::new(py, &pixels).into())
+    }
+
+    /// PNG file of the `render_rgba` image as bytes, byte-identical for equal states; see
+    /// `render_rgba` for the options
+    #[pyo3(signature = (theme=None, scale=8, glyphs=false, pattern_fill=false, room_names=false))]
+    fn render_png(
+        &self,
+        py: Python<'_>,
+        theme: Option<&str>,
+        scale: usize,
+        glyphs: bool,
+        pattern_fill: bool,
+        room_names: bool,
+    ) -> PyResult<Py<PyBytes>> {
+        let opts = tidy_core::RenderOpts { glyphs, pattern_fill, room_names };
+        let png = tidy_core::render::render_png(&self.sim, &parse_theme(theme)?, scale.max(1), opts);
+        Ok(PyBytes::new(py, &png).into())
+    }
+
+    /// Animated PNG, or GIF with `format="gif"`, of an episode: plays `actions` (discrete ids,
+    /// see `action_names()`) on this simulator like `step_id` and draws the state before, after
+    /// every `frame_skip`-th action and at the end; `max_frames` raises `frame_skip` to fit,
+    /// `trail` dots the cells walked
+    #[pyo3(signature = (actions, theme=None, scale=8, frame_skip=1, max_frames=None, frame_delay_ms=100, trail=false, format="apng"))]
+    #[allow(clippy::too_many_arguments)]
+    fn render_episode(
+        &mut self,
+        py: Python<'_>,
+        actions: Vec<usize>,
+        theme: Option<&str>,
+        scale: usize,
+        frame_skip: usize,
+        max_frames: Option<usize>,
+        frame_delay_ms: u16,
+        trail: bool,
+        format: &str,
+    ) -> PyResult<Py<PyBytes>> {
+        let format = match format {
+            "apng" => tidy_core::EpisodeFormat::Apng,
+            "gif" => tidy_core::EpisodeFormat::Gif,
+            other => return Err(PyValueError::new_err(format!("Unknown format: {} (expected apng or gif)", other))),
+        };
+        let actions = actions
+            .into_iter()
+            .map(|id| tidy_core::Action::from_id(id).ok_or_else(|| PyValueError::new_err(format!("Unknown action id: {}", id))))
+            .collect::<PyResult<Vec<_>>>()?;
+        let opts = tidy_core::EpisodeRenderOpts {
+            format,
+            theme: parse_theme(theme)?,
+            scale: scale.max(1),
+            frame_skip,
+            max_frames,
+            frame_delay_ms,
+            trail,
+            ..Default::default()
+        };
+        let bytes = tidy_core::export::render_episode(&mut self.sim, &actions, &opts);
+        Ok(PyBytes::new(py, &bytes).into())
+    }
+
+    /// SVG document of the current state, with `aria-label`s on rooms and objects; see `render_rgba` for the options
+    #[pyo3(signature = (theme=None, scale=8, glyphs=false, pattern_fill=false, room_names=false))]
+    fn render_svg(&self, theme: Option<&str>, scale: usize, glyphs: bool, pattern_fill: bool, room_names: bool) -> PyResult<String> {
+        let opts = tidy_core::RenderOpts { glyphs, pattern_fill, room_names };
+        Ok(tidy_core::render::render_svg(&self.sim, &parse_theme(theme)?, scale.max(1), opts))
+    }
+
+    /// Preview drop and place_into for the held object without acting
+    fn drop_preview(&self) -> PyDropPreview {
+        PyDropPreview::from(self.sim.drop_preview())
+    }
+
+    /// Room name signs as (room, x, y, text) tuples, one per room with a door
+    fn room_signs(&self) -> Vec<(usize, usize, usize, String)> {
+        self.sim.world.layout.room_signs().into_iter().map(|s| (s.room, s.x, s.y, s.text.into_owned())).collect()
+    }
+
+    fn decay_remaining(&self, object_id: usize) -> Option<usize> {
+        self.sim.decay_remaining(object_id)
+    }
+
+    fn is_target_known(&self, object_id: usize) -> bool {
+        self.sim.is_target_known(object_id)
+    }
+
+    fn describe_goal(&self, object_id: usize) -> Option<String> {
+        self.sim.describe_goal(object_id)
+    }
+
+    /// The objects out of place now and where they belong, naming targets even before inspection
+    fn get_task(&self) -> PyTidyTask {
+        PyTidyTask { task: tidy_core::TidyTask::from_sim(&self.sim) }
+    }
+
+    /// Return a new simulator over a perturbed copy of this world, keeping the agent position.
+    /// `kind` is one of "move_object" (id), "swap_objects" (a, b), "remove_object" (id)
+    /// or "shuffle_room_names".
+    #[pyo3(signature = (kind, id=None, a=None, b=None, seed=0))]
+    fn perturb(&self, kind: &str, id: Option<usize>, a: Option<usize>, b: Option<usize>, seed: u64) -> PyResult<PySimulator> {
+        let missing = |name: &str| PyValueError::new_err(format!("'{}' requires argument '{}'", kind, name));
+        let perturbation = match kind {
+            "move_object" => tidy_core::Perturbation::MoveObject { id: id.ok_or_else(|| missing("id"))? },
+            "swap_objects" => tidy_core::Perturbation::SwapObjects {
+                a: a.ok_or_else(|| missing("a"))?,
+                b: b.ok_or_else(|| missing("b"))?,
+            },
+            "remove_object" => tidy_core::Perturbation::RemoveObject { id: id.ok_or_else(|| missing("id"))? },
+            "shuffle_room_names" => tidy_core::Perturbation::ShuffleRoomNames,
+            _ => return Err(PyValueError::new_err(format!("Unknown perturbation: {}", kind))),
+        };
+        let world = self
+            .sim
+            .world
+            .perturb(perturbation, seed)
+            .map_err(|e| PyRuntimeError::new_err(format!("{:?}", e)))?;
+        tidy_core::Simulator::new(world, self.sim.agent.x, self.sim.agent.y)
+            .map(PySimulator::from)
+            .map_err(PyRuntimeError::new_err)
+    }
+
+    fn view(&self) -> PyWorldView {
+        PyWorldView { view: self.sim.view() }
+    }
+
+    fn check_placement(&self, object_id: usize) -> bool {
+        self.sim.world.is_in_place(object_id)
     }
 
     fn __repr__(&self) -> String {
@@ -313,14 +1680,685 @@ impl PySimulator {
     }
 }
 
-/// Generate a world without creating a simulator
+impl PySimulator {
+    fn py_object(&self, obj: &tidy_core::Object) -> PyObject {
+        PyObject {
+            decay_remaining: self.sim.decay_remaining(obj.id),
+            is_open: self.sim.is_open(obj),
+            ..PyObject::from(obj)
+        }
+    }
+}
+
+/// Context manager returned by `PySimulator.transaction()`
+#[pyclass]
+pub struct PyWorldTx {
+    sim: Py<PySimulator>,
+    tx: Option<tidy_core::WorldTx>,
+}
+
+impl PyWorldTx {
+    fn tx(&mut self) -> PyResult<&mut tidy_core::WorldTx> {
+        self.tx.as_mut().ok_or_else(|| PyRuntimeError::new_err("Transaction already finished"))
+    }
+}
+
+/// RuntimeError reading "<variant>: <message>" for a failed simulator action
+fn action_error(name: &str, message: impl std::fmt::Display) -> PyErr {
+    PyRuntimeError::new_err(format!("{}: {}", name, message))
+}
+
+/// Dict for a simulator event: "type" is the snake_case variant name, the other keys its fields
+fn event_dict(py: Python<'_>, event: &tidy_core::SimEvent) -> PyResult<Py<PyDict>> {
+    use tidy_core::SimEvent;
+    let dict = PyDict::new(py);
+    let kind = match *event {
+        SimEvent::Decayed { id, ref from, ref into } | SimEvent::Transformed { id, ref from, ref into } => {
+            dict.set_item("id", id)?;
+            dict.set_item("from", from)?;
+            dict.set_item("into", into)?;
+            if matches!(event, SimEvent::Decayed { .. }) { "decayed" } else { "transformed" }
+        }
+        SimEvent::Disposed { id, ref name } => {
+            dict.set_item("id", id)?;
+            dict.set_item("name", name)?;
+            "disposed"
+        }
+        SimEvent::Moved { agent, from, to } => {
+            dict.set_item("agent", agent)?;
+            dict.set_item("from", from)?;
+            dict.set_item("to", to)?;
+            "moved"
+        }
+        SimEvent::Turned { agent, facing } => {
+            dict.set_item("agent", agent)?;
+            dict.set_item("facing", facing.name())?;
+            "turned"
+        }
+        SimEvent::DoorOpened { door, x, y } | SimEvent::DoorClosed { door, x, y } => {
+            dict.set_item("door", door)?;
+            dict.set_item("x", x)?;
+            dict.set_item("y", y)?;
+            if matches!(event, SimEvent::DoorOpened { .. }) { "door_opened" } else { "door_closed" }
+        }
+        SimEvent::PickedUp { agent, id } => {
+            dict.set_item("agent", agent)?;
+            dict.set_item("id", id)?;
+            "picked_up"
+        }
+        SimEvent::Dropped { agent, id, x, y } => {
+            dict.set_item("agent", agent)?;
+            dict.set_item("id", id)?;
+            dict.set_item("x", x)?;
+            dict.set_item("y", y)?;
+            "dropped"
+        }
+        SimEvent::PlacedInto { agent, id, container } => {
+            dict.set_item("agent", agent)?;
+            dict.set_item("id", id)?;
+            dict.set_item("container", container)?;
+            "placed_into"
+        }
+        SimEvent::ContainerOpened { id } => {
+            dict.set_item("id", id)?;
+            "container_opened"
+        }
+        SimEvent::ContainerClosed { id } => {
+            dict.set_item("id", id)?;
+            "container_closed"
+        }
+    };
+    dict.set_item("type", kind)?;
+    Ok(dict.into())
+}
+
+fn tx_error(e: tidy_core::TxError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+#[pymethods]
+impl PyWorldTx {
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Commit when the block succeeded, discard the edits otherwise; never swallows the exception
+    fn __exit__(&mut self, py: Python, exc_type: Option<&PyAny>, _exc: Option<&PyAny>, _tb: Option<&PyAny>) -> PyResult<bool> {
+        let tx = self.tx.take().ok_or_else(|| PyRuntimeError::new_err("Transaction already finished"))?;
+        if exc_type.is_none() {
+            let mut sim = self.sim.borrow_mut(py);
+            sim.sim.world = tx.commit().map_err(tx_error)?;
+            sim.sim.invalidate_caches();
+        }
+        Ok(false)
+    }
+
+    /// Add an object of a catalog schema on a room cell; returns its id
+    fn add_object(&mut self, schema: &str, x: usize, y: usize) -> PyResult<usize> {
+        self.tx()?.add_object(schema, x, y).map_err(tx_error)
+    }
+
+    /// Move an object, with its contents, loose onto a room cell
+    fn move_object(&mut self, object_id: usize, x: usize, y: usize) -> PyResult<()> {
+        self.tx()?.move_object(object_id, x, y).map_err(tx_error)
+    }
+
+    /// Put an object, with its contents, into a container
+    fn place_into(&mut self, object_id: usize, container_id: usize) -> PyResult<()> {
+        self.tx()?.place_into(object_id, container_id).map_err(tx_error)
+    }
+
+    fn remove_object(&mut self, object_id: usize) -> PyResult<()> {
+        self.tx()?.remove_object(object_id).map_err(tx_error)
+    }
+
+    fn set_label(&mut self, object_id: usize, label: Option<String>) -> PyResult<()> {
+        self.tx()?.set_label(object_id, label).map_err(tx_error)
+    }
+}
+
+/// Primitive actions the "random" policy draws from
+const RANDOM_ACTIONS: [tidy_core::Action; 10] = [
+    tidy_core::Action::MoveUp,
+    tidy_core::Action::MoveDown,
+    tidy_core::Action::MoveLeft,
+    tidy_core::Action::MoveRight,
+    tidy_core::Action::PickUp,
+    tidy_core::Action::Drop,
+    tidy_core::Action::Interact { dx: 0, dy: -1 },
+    tidy_core::Action::Interact { dx: 0, dy: 1 },
+    tidy_core::Action::Interact { dx: -1, dy: 0 },
+    tidy_core::Action::Interact { dx: 1, dy: 0 },
+];
+
+/// Episode environment: a simulator over a generated world plus its task.
+///
+/// `with TidyEnv(opts) as env:` closes it on exit, and
+/// `for obs, reward, terminated, truncated, info in env.run("oracle"):` plays an episode.
+#[pyclass(name = "TidyEnv")]
+pub struct PyTidyEnv {
+    sim: Py<PySimulator>,
+    opts: tidy_core::GenOpts,
+    goals: Vec<usize>,
+    delivery: Option<tidy_core::DeliveryTask>,
+    transcript: Vec<String>,
+    closed: bool,
+}
+
+impl PyTidyEnv {
+    /// Generate the world for `opts` and start the agent on the first room cell
+    fn build(opts: &tidy_core::GenOpts) -> PyResult<(tidy_core::Simulator, Vec<usize>, Option<tidy_core::DeliveryTask>)> {
+        let (world, report) = tidy_core::generate_with_report(opts).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let goals = opts.task.goals(&world, report.delivery.as_ref());
+        let start = world.layout.cells.iter().position(|&c| c >= 0).unwrap_or(0);
+        let (x, y) = (start % world.layout.width, start / world.layout.width);
+        let sim = tidy_core::Simulator::new(world, x, y).map_err(PyRuntimeError::new_err)?;
+        Ok((sim, goals, report.delivery))
+    }
+
+    fn check_open(&self) -> PyResult<()> {
+        if self.closed {
+            return Err(PyRuntimeError::new_err("TidyEnv is closed"));
+        }
+        Ok(())
+    }
+}
+
+/// Observation yielded by `TidyEnv.run`: agent position, held object id and goals left
+fn observation(py: Python<'_>, sim: &tidy_core::Simulator, goals: &[usize]) -> PyResult<Py<PyDict>> {
+    let obs = PyDict::new(py);
+    obs.set_item("agent", (sim.agent.x, sim.agent.y))?;
+    obs.set_item("holding", sim.holding.as_ref().map(|o| o.id))?;
+    obs.set_item("remaining", goals.len() - goals_in_place(sim, goals))?;
+    Ok(obs.into())
+}
+
+fn goals_in_place(sim: &tidy_core::Simulator, goals: &[usize]) -> usize {
+    goals.iter().filter(|&&id| sim.world.is_in_place(id)).count()
+}
+
+#[pymethods]
+impl PyTidyEnv {
+    #[new]
+    fn new(py: Python<'_>, opts: PyGenOpts) -> PyResult<Self> {
+        let opts = tidy_core::GenOpts::from(opts);
+        let (sim, goals, delivery) = Self::build(&opts)?;
+        Ok(PyTidyEnv { sim: Py::new(py, PySimulator::from(sim))?, opts, goals, delivery, transcript: Vec::new(), closed: false })
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Close the environment; never swallows the exception
+    fn __exit__(&mut self, py: Python, _exc_type: Option<&PyAny>, _exc: Option<&PyAny>, _tb: Option<&PyAny>) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+
+    /// Stop any checkpointing and refuse further use; raises if a checkpoint write had failed
+    fn close(&mut self, py: Python) -> PyResult<()> {
+        if std::mem::replace(&mut self.closed, true) {
+            return Ok(());
+        }
+        self.sim.borrow_mut(py).stop_checkpointing()
+    }
+
+    /// The underlying simulator, shared with the environment
+    #[getter]
+    fn sim(&self, py: Python) -> Py<PySimulator> {
+        self.sim.clone_ref(py)
+    }
+
+    /// Ids of the objects the task asks to put in place
+    #[getter]
+    fn goals(&self) -> Vec<usize> {
+        self.goals.clone()
+    }
+
+    /// Generate a fresh world (with `seed`, if given) and return its first observation
+    #[pyo3(signature = (seed=None))]
+    fn reset(&mut self, py: Python, seed: Option<u64>) -> PyResult<Py<PyDict>> {
+        self.check_open()?;
+        if let Some(seed) = seed {
+            self.opts.seed = seed;
+        }
+        let (sim, goals, delivery) = Self::build(&self.opts)?;
+        self.sim.borrow_mut(py).sim = sim;
+        (self.goals, self.delivery) = (goals, delivery);
+        self.transcript.clear();
+        observation(py, &self.sim.borrow(py).sim, &self.goals)
+    }
+
+    /// Play up to `max_steps` steps, yielding (obs, reward, terminated, truncated, info) after each.
+    ///
+    /// `policy` is "random", "oracle" (both run in Rust without the GIL; `seed` seeds
+    /// "random") or a callable taking the observation and returning an action name,
+    /// ("interact", dx, dy) or ("place_into", object_id). The reward is the hook reward
+    /// plus the change in goals in place; rejected actions put their error in `info`.
+    #[pyo3(signature = (policy, max_steps=500, seed=0))]
+    fn run(slf: Py<Self>, py: Python, policy: &PyAny, max_steps: u32, seed: u64) -> PyResult<PyEpisode> {
+        let policy = if let Ok(name) = policy.extract::<&str>() {
+            match name {
+                "random" => Policy::Random,
+                "oracle" => Policy::Oracle,
+                other => return Err(PyValueError::new_err(format!("Unknown policy: {} (expected random or oracle)", other))),
+            }
+        } else if policy.is_callable() {
+            Policy::Python(policy.into())
+        } else {
+            return Err(PyTypeError::new_err("policy must be \"random\", \"oracle\" or a callable"));
+        };
+        let obs = {
+            let env = slf.borrow(py);
+            env.check_open()?;
+            let obs = observation(py, &env.sim.borrow(py).sim, &env.goals)?;
+            obs
+        };
+        Ok(PyEpisode { env: slf, policy, rng: StdRng::seed_from_u64(seed), max_steps, taken: 0, obs, done: false })
+    }
+
+    /// Narrated log of the episodes since the last reset, one line per notable event
+    fn play_transcript(&self) -> String {
+        self.transcript.join("\n")
+    }
+}
+
+/// Where `TidyEnv.run` gets its actions
+enum Policy {
+    Random,
+    Oracle,
+    Python(Py<PyAny>),
+}
+
+/// Iterator returned by `TidyEnv.run`
+#[pyclass]
+pub struct PyEpisode {
+    env: Py<PyTidyEnv>,
+    policy: Policy,
+    rng: StdRng,
+    max_steps: u32,
+    taken: u32,
+    obs: Py<PyDict>,
+    done: bool,
+}
+
+/// (obs, reward, terminated, truncated, info) yielded by `TidyEnv.run`
+type Transition = (Py<PyDict>, f32, bool, bool, Py<PyDict>);
+
+/// What one environment step did, computed without the GIL
+struct EpisodeStep {
+    action: tidy_core::Action,
+    reward: f32,
+    error: Option<String>,
+    narration: Vec<String>,
+}
+
+/// Take one action, tick on success and narrate what changed
+fn play_step(sim: &mut tidy_core::Simulator, action: tidy_core::Action, goals: &[usize]) -> EpisodeStep {
+    let placed_before = goals_in_place(sim, goals);
+    let held_before = sim.holding.as_ref().map(|o| (o.id, o.display_label()));
+    let offset = match action {
+        tidy_core::Action::Interact { dx, dy } => Some((dx as isize, dy as isize)),
+        tidy_core::Action::InteractAhead => Some(sim.agent.facing.offset()),
+        _ => None,
+    };
+    let door = offset.and_then(|(dx, dy)| {
+        let (x, y) = (sim.agent.x as isize + dx, sim.agent.y as isize + dy);
+        let inside = x >= 0 && y >= 0 && (x as usize) < sim.world.layout.width && (y as usize) < sim.world.layout.height;
+        inside.then(|| (x as usize, y as usize, sim.world.layout.kind_at(x as usize, y as usize)))
+    });
+    let (reward, error, events) = match sim.step(action) {
+        Ok(outcome) => (outcome.reward, None, outcome.events.into_iter().chain(sim.tick()).collect()),
+        Err(e) => (0.0, Some(e.to_string()), Vec::new()),
+    };
+    let mut narration = Vec::new();
+    match (&held_before, &sim.holding) {
+        (None, Some(held)) => narration.push(format!("Picked up the {}.", held.display_label())),
+        // narrated from its `Disposed` event
+        (Some((id, _)), None) if sim.world.disposed_object(*id).is_some() => {}
+        (Some((id, name)), None) => match sim.world.objects.iter().find(|c| c.contents.contains(id)) {
+            Some(container) => narration.push(format!("Put the {} in the {}.", name, container.display_label())),
+            None => {
+                let location = sim.world.object(*id).map(|o| tidy_core::language::describe_location(&sim.world, o.x, o.y));
+                narration.push(format!("Dropped the {} {}.", name, location.unwrap_or_default()));
+            }
+        },
+        _ => {}
+    }
+    if let Some((x, y, before)) = door {
+        match (before, sim.world.layout.kind_at(x, y)) {
+            (tidy_core::CellKind::ClosedDoor, tidy_core::CellKind::OpenDoor) => narration.push("Opened a door.".into()),
+            (tidy_core::CellKind::OpenDoor, tidy_core::CellKind::ClosedDoor) => narration.push("Closed a door.".into()),
+            _ => {}
+        }
+    }
+    for event in events {
+        match event {
+            tidy_core::SimEvent::Decayed { from, into, .. } => narration.push(format!(
+                "The {} turned into {}.",
+                tidy_core::language::humanize(&from),
+                tidy_core::language::humanize(&into)
+            )),
+            tidy_core::SimEvent::Disposed { name, .. } => {
+                narration.push(format!("Threw away the {}.", tidy_core::language::humanize(&name)))
+            }
+            _ => {}
+        }
+    }
+    let reward = reward + goals_in_place(sim, goals) as f32 - placed_before as f32;
+    EpisodeStep { action, reward, error, narration }
+}
+
+#[pymethods]
+impl PyEpisode {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<Transition>> {
+        if self.done {
+            return Ok(None);
+        }
+        let mut env = self.env.borrow_mut(py);
+        env.check_open()?;
+        let chosen = match &self.policy {
+            Policy::Python(policy) => {
+                // an exception in the policy ends the episode and propagates to the loop
+                let result = policy.call1(py, (self.obs.clone_ref(py),)).and_then(|a| policy_action(a.as_ref(py)));
+                if result.is_err() {
+                    self.done = true;
+                }
+                Some(result?)
+            }
+            _ => None,
+        };
+        let env = &mut *env;
+        let (goals, policy, rng) = (&env.goals, &self.policy, &mut self.rng);
+        let mut sim = env.sim.borrow_mut(py);
+        let sim = &mut sim.sim;
+        let step = py.allow_threads(|| {
+            let action = chosen.unwrap_or_else(|| match policy {
+                Policy::Oracle => tidy_core::solver::next_action(sim, goals).unwrap_or(tidy_core::Action::Noop),
+                _ => *RANDOM_ACTIONS.choose(rng).expect("random policy has actions"),
+            });
+            play_step(sim, action, goals)
+        });
+        self.taken += 1;
+        let terminated = env.opts.task.is_done(sim, env.delivery.as_ref()) || sim.is_success();
+        let truncated = !terminated && (self.taken >= self.max_steps || sim.is_truncated());
+        env.transcript.extend(step.narration);
+        if terminated {
+            env.transcript.push(format!("Finished the task in {} steps.", self.taken));
+        } else if truncated {
+            let remaining = env.goals.len() - goals_in_place(sim, &env.goals);
+            env.transcript.push(format!("Stopped after {} steps with {} goals left.", self.taken, remaining));
+        }
+        self.done = terminated || truncated;
+        self.obs = observation(py, sim, &env.goals)?;
+        let info = PyDict::new(py);
+        info.set_item("action", action_name(step.action))?;
+        info.set_item("error", step.error)?;
+        info.set_item("steps", sim.steps)?;
+        info.set_item("time", sim.time)?;
+        Ok(Some((self.obs.clone_ref(py), step.reward, terminated, truncated, info.into())))
+    }
+}
+
+/// Action returned by a Python policy: a name, ("interact", dx, dy) or ("place_into", object_id)
+fn policy_action(action: &PyAny) -> PyResult<tidy_core::Action> {
+    if let Ok(name) = action.extract::<&str>() {
+        return parse_action(name, 0, 0, None);
+    }
+    if let Ok((name, dx, dy)) = action.extract::<(&str, i8, i8)>() {
+        return parse_action(name, dx, dy, None);
+    }
+    if let Ok((name, object_id)) = action.extract::<(&str, usize)>() {
+        return parse_action(name, 0, 0, Some(object_id));
+    }
+    Err(PyTypeError::new_err(format!("policy returned {}, expected an action name or tuple", action)))
+}
+
+/// Environments stepped in lockstep for vectorized training, one per `GenOpts`.
+///
+/// Observations, rewards and done flags are written into numpy arrays the caller allocates
+/// once: `cells` int8 of num_envs * (2 * radius + 1)² (e.g. shape (num_envs, 2r+1, 2r+1)),
+/// `rewards` float32 and `done` uint8 of num_envs each. Actions are discrete ids
+/// (`action_names`) in an int64 array. Steps reuse internal buffers, so steady-state steps
+/// do not allocate; environments that are done stay done until `reset`.
+#[pyclass(name = "TidyVecEnv")]
+pub struct PyTidyVecEnv {
+    batch: tidy_core::SimBatch,
+    opts: Vec<tidy_core::GenOpts>,
+    radius: usize,
+    action_ids: Vec<i64>,
+    actions: Vec<tidy_core::Action>,
+    cells: Vec<tidy_core::world::Cell>,
+    rewards: Vec<f32>,
+    done: Vec<bool>,
+    done_bytes: Vec<u8>,
+}
+
+impl PyTidyVecEnv {
+    /// Write the current observations into `cells`
+    fn observe_into(&mut self, py: Python<'_>, cells: &pyo3::buffer::PyBuffer<i8>) -> PyResult<()> {
+        self.batch.observe_all(self.radius, &mut self.cells).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        cells.copy_from_slice(py, &self.cells)
+    }
+}
+
+#[pymethods]
+impl PyTidyVecEnv {
+    #[new]
+    #[pyo3(signature = (opts, radius=5))]
+    fn new(opts: Vec<PyGenOpts>, radius: usize) -> PyResult<Self> {
+        let opts: Vec<tidy_core::GenOpts> = opts.into_iter().map(tidy_core::GenOpts::from).collect();
+        let sims = opts.iter().map(|o| PyTidyEnv::build(o).map(|(sim, _, _)| sim)).collect::<PyResult<Vec<_>>>()?;
+        let n = sims.len();
+        Ok(PyTidyVecEnv {
+            batch: tidy_core::SimBatch::new(sims),
+            opts,
+            radius,
+            action_ids: vec![0; n],
+            actions: vec![tidy_core::Action::Noop; n],
+            cells: vec![0; n * tidy_core::SimBatch::observation_len(radius)],
+            rewards: vec![0.0; n],
+            done: vec![false; n],
+            done_bytes: vec![0; n],
+        })
+    }
+
+    #[getter]
+    fn num_envs(&self) -> usize {
+        self.batch.len()
+    }
+
+    /// Cells in one environment's observation: (2 * radius + 1)²
+    #[getter]
+    fn observation_len(&self) -> usize {
+        tidy_core::SimBatch::observation_len(self.radius)
+    }
+
+    /// Regenerate every environment (environment i with `seeds[i]`, if given) and write the
+    /// first observations into `cells`
+    #[pyo3(signature = (cells, seeds=None))]
+    fn reset(&mut self, py: Python<'_>, cells: pyo3::buffer::PyBuffer<i8>, seeds: Option<Vec<u64>>) -> PyResult<()> {
+        if let Some(seeds) = &seeds {
+            if seeds.len() != self.batch.len() {
+                return Err(PyValueError::new_err(format!("expected {} seeds, got {}", self.batch.len(), seeds.len())));
+            }
+        }
+        for (i, opts) in self.opts.iter_mut().enumerate() {
+            if let Some(seeds) = &seeds {
+                opts.seed = seeds[i];
+            }
+            self.batch.sims[i] = PyTidyEnv::build(opts)?.0;
+        }
+        self.observe_into(py, &cells)
+    }
+
+    /// Step environment i with the discrete action `actions[i]` and fill `cells`, `rewards` and
+    /// `done` in place; a rejected action scores 0. Raises ValueError, stepping nothing, for an
+    /// unknown action id or a buffer of the wrong size
+    fn step(
+        &mut self,
+        py: Python<'_>,
+        actions: pyo3::buffer::PyBuffer<i64>,
+        cells: pyo3::buffer::PyBuffer<i8>,
+        rewards: pyo3::buffer::PyBuffer<f32>,
+        done: pyo3::buffer::PyBuffer<u8>,
+    ) -> PyResult<()> {
+        actions.copy_to_slice(py, &mut self.action_ids)?;
+        for (action, &id) in self.actions.iter_mut().zip(&self.action_ids) {
+            *action = usize::try_from(id)
+                .ok()
+                .and_then(tidy_core::Action::from_id)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown action id: {}", id)))?;
+        }
+        for (buffer, expected, found) in [
+            ("cells", self.cells.len(), cells.item_count()),
+            ("rewards", self.rewards.len(), rewards.item_count()),
+            ("done", self.done.len(), done.item_count()),
+        ] {
+            if expected != found {
+                return Err(PyValueError::new_err(format!("expected a {} buffer of {} elements, got {}", buffer, expected, found)));
+            }
+        }
+        self.batch
+            .step_all(&self.actions, self.radius, &mut self.cells, &mut self.rewards, &mut self.done)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        for (byte, &done) in self.done_bytes.iter_mut().zip(&self.done) {
+            *byte = done as u8;
+        }
+        cells.copy_from_slice(py, &self.cells)?;
+        rewards.copy_from_slice(py, &self.rewards)?;
+        done.copy_from_slice(py, &self.done_bytes)
+    }
+}
+
+/// Schemas from a list of schema dicts or the path of a schema JSON file, both in the form
+/// of `tidy_core::schema`; raises ValueError when they are invalid
+fn schemas_from_py(py: Python<'_>, schemas: &PyAny) -> PyResult<Vec<tidy_core::ObjectSchema>> {
+    let json = match schemas.extract::<std::path::PathBuf>() {
+        Ok(path) => std::fs::read_to_string(&path)
+            .map_err(|e| PyValueError::new_err(format!("cannot read {}: {}", path.display(), e)))?,
+        Err(_) => py.import("json")?.call_method1("dumps", (schemas,))?.extract()?,
+    };
+    tidy_core::ObjectSchema::from_json(&json).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Generate a world without creating a simulator; raises ValueError when generation fails.
+/// `schemas`, a list of schema dicts or a schema file path, replaces the built-in object catalog.
+#[pyfunction]
+#[pyo3(signature = (opts, schemas=None))]
+fn generate_world(py: Python<'_>, opts: PyGenOpts, schemas: Option<&PyAny>) -> PyResult<(PyLayout, Vec<PyObject>)> {
+    let rust_opts = tidy_core::GenOpts::from(opts);
+    let world = match schemas {
+        Some(schemas) => tidy_core::generate_with_schemas(&rust_opts, &schemas_from_py(py, schemas)?),
+        None => tidy_core::generate(&rust_opts),
+    }
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let layout = PyLayout::from(&world.layout);
+    let objects = world.objects.iter().map(PyObject::from).collect();
+    Ok((layout, objects))
+}
+
+/// Generate a world and also return the generation report; raises ValueError on invalid options or, in strict mode, failed room kits
 #[pyfunction]
-fn generate_world(opts: PyGenOpts) -> (PyLayout, Vec<PyObject>) {
+fn generate_world_with_report(opts: PyGenOpts) -> PyResult<(PyLayout, Vec<PyObject>, PyGenReport)> {
     let rust_opts = tidy_core::GenOpts::from(opts);
-    let world = tidy_core::generate(&rust_opts);
+    let (world, report) = tidy_core::generate_with_report(&rust_opts).map_err(|e| PyValueError::new_err(e.to_string()))?;
     let layout = PyLayout::from(&world.layout);
     let objects = world.objects.iter().map(PyObject::from).collect();
-    (layout, objects)
+    Ok((layout, objects, PyGenReport::from(&report)))
+}
+
+/// Search seeds upwards from `opts.seed` for a world whose difficulty score lies in
+/// `difficulty`; returns (seed, layout, objects, report)
+#[pyfunction]
+#[pyo3(signature = (opts, difficulty=(0.0, 1.0), max_attempts=1000))]
+fn sample_world(
+    opts: PyGenOpts,
+    difficulty: (f32, f32),
+    max_attempts: usize,
+) -> PyResult<(u64, PyLayout, Vec<PyObject>, PyGenReport)> {
+    let mut sampler = tidy_core::CurriculumSampler::new(tidy_core::GenOpts::from(opts), difficulty);
+    sampler.max_attempts = max_attempts;
+    let (seed, world, report) = sampler.sample().ok_or_else(|| {
+        PyValueError::new_err(format!("no world in difficulty range after {} seeds", max_attempts))
+    })?;
+    let layout = PyLayout::from(&world.layout);
+    let objects = world.objects.iter().map(PyObject::from).collect();
+    Ok((seed, layout, objects, PyGenReport::from(&report)))
+}
+
+/// Row-major index of cell (x, y)
+#[pyfunction]
+fn flat_index(x: usize, y: usize, width: usize) -> usize {
+    tidy_core::flat_index(x, y, width)
+}
+
+/// Inverse of flat_index; returns (x, y)
+#[pyfunction]
+fn coords_from_index(index: usize, width: usize) -> (usize, usize) {
+    let c = tidy_core::coords_from_index(index, width);
+    (c.x, c.y)
+}
+
+/// Reorder a row-major grid to column-major, where cell (x, y) is at x * height + y
+#[pyfunction]
+fn transpose_cells(cells: Vec<i8>, width: usize, height: usize) -> PyResult<Vec<i8>> {
+    if cells.len() != width * height {
+        return Err(PyValueError::new_err("Grid length does not match dimensions"));
+    }
+    Ok(tidy_core::transpose_cells(&cells, width, height))
+}
+
+/// Lint the default object schemas; returns (kind, schema, message) tuples
+#[pyfunction]
+fn lint_schemas() -> Vec<(&'static str, &'static str, String)> {
+    tidy_core::ObjectSchema::lint_defaults()
+        .iter()
+        .map(|l| (l.kind(), l.schema(), l.to_string()))
+        .collect()
+}
+
+/// (name, title, instructions, goal ids, max steps, simulator)
+type TutorialScenario = (&'static str, &'static str, String, Vec<usize>, u32, PySimulator);
+
+/// Tutorial scenarios, easiest first
+#[pyfunction]
+fn tutorial() -> Vec<TutorialScenario> {
+    tidy_core::scenarios::tutorial()
+        .into_iter()
+        .map(|sc| {
+            let sim = PySimulator::from(sc.simulator());
+            (sc.name, sc.title, sc.instructions, sc.goals, sc.max_steps, sim)
+        })
+        .collect()
+}
+
+/// Conformance spec every binding must pass, as JSON; `conformance.py` runs it through `PySimulator`
+#[pyfunction]
+fn conformance_spec() -> PyResult<String> {
+    serde_json::to_string(&tidy_core::conformance::spec()).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Names of the discrete actions `PySimulator.step_id` takes, indexed by action id
+#[pyfunction]
+fn action_names() -> Vec<&'static str> {
+    tidy_core::Action::DISCRETE.iter().map(|&(name, _)| name).collect()
+}
+
+/// Theme from a built-in name ("default", "high_contrast", "dark", "colorblind_safe", "ascii") or a theme JSON object
+fn parse_theme(theme: Option<&str>) -> PyResult<tidy_core::Theme> {
+    match theme {
+        None => Ok(tidy_core::Theme::default()),
+        Some(json) if json.trim_start().starts_with('{') => {
+            serde_json::from_str(json).map_err(|e| PyValueError::new_err(e.to_string()))
+        }
+        Some(name) => tidy_core::Theme::named(name).ok_or_else(|| PyValueError::new_err(format!("Unknown theme: {}", name))),
+    }
 }
 
 /// Constants module
@@ -340,7 +2378,28 @@ fn tidyenv(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PyObject>()?;
     m.add_class::<PyLayout>()?;
     m.add_class::<PySimulator>()?;
+    m.add_class::<PyWorldTx>()?;
+    m.add_class::<PyWorldView>()?;
+    m.add_class::<PyGenReport>()?;
+    m.add_class::<PyDifficultyScore>()?;
+    m.add_class::<PyPlacementProvenance>()?;
+    m.add_class::<PyDropPreview>()?;
+    m.add_class::<PyRoomGraph>()?;
+    m.add_class::<PyLocalView>()?;
+    m.add_class::<PyTidyTask>()?;
+    m.add_class::<PyTidyEnv>()?;
+    m.add_class::<PyEpisode>()?;
+    m.add_class::<PyTidyVecEnv>()?;
     m.add_function(wrap_pyfunction!(generate_world, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_world_with_report, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_world, m)?)?;
+    m.add_function(wrap_pyfunction!(lint_schemas, m)?)?;
+    m.add_function(wrap_pyfunction!(tutorial, m)?)?;
+    m.add_function(wrap_pyfunction!(conformance_spec, m)?)?;
+    m.add_function(wrap_pyfunction!(action_names, m)?)?;
+    m.add_function(wrap_pyfunction!(flat_index, m)?)?;
+    m.add_function(wrap_pyfunction!(coords_from_index, m)?)?;
+    m.add_function(wrap_pyfunction!(transpose_cells, m)?)?;
     
     // Create and add constants submodule
     let constants_module = PyModule::new(_py, "constants")?;
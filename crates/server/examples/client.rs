@@ -0,0 +1,43 @@
+//! Start a server on an ephemeral port and drive one environment for 1000 random steps.
+//!
+//! cargo run -p tidy-env-server --example client
+
+use std::net::{TcpListener, TcpStream};
+use std::time::Instant;
+
+use rand::{Rng, SeedableRng};
+use tidy_core::Action;
+use tidy_server::{Client, ObserveMode, Request};
+
+const ACTIONS: [Action; 6] = [Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight, Action::PickUp, Action::Drop];
+
+fn main() -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    std::thread::spawn(move || tidy_server::serve_tcp(listener, None));
+
+    let mut client = Client::connect(TcpStream::connect(addr)?)?;
+    let created = client.request(&Request::Create { opts: tidy_core::GenOpts { seed: 7, ..Default::default() } })?;
+    let env_id = created["env_id"].as_u64().expect("create returns env_id");
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let (mut ok, mut failed) = (0, 0);
+    let start = Instant::now();
+    for _ in 0..1000 {
+        let action = match rng.gen_range(0..ACTIONS.len() + 1) {
+            i if i < ACTIONS.len() => ACTIONS[i],
+            _ => Action::Interact { dx: rng.gen_range(-1..=1), dy: rng.gen_range(-1..=1) },
+        };
+        match client.request(&Request::Step { env_id, action }) {
+            Ok(_) => ok += 1,
+            Err(_) => failed += 1,
+        }
+    }
+    let elapsed = start.elapsed();
+
+    let observation = client.request(&Request::Observe { env_id, mode: ObserveMode::Ascii, max_rooms: None })?;
+    println!("{}", observation["observation"].as_str().unwrap_or_default());
+    println!("{} steps ok, {} rejected, {:.1} us per request", ok, failed, elapsed.as_micros() as f64 / 1000.0);
+    client.request(&Request::Close { env_id })?;
+    Ok(())
+}
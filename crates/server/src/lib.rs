@@ -0,0 +1,312 @@
+//! Simulator server for clients that cannot use the Python or WASM bindings.
+//!
+//! # Protocol
+//!
+//! Every message, in both directions, is a little-endian `u32` byte length followed by that
+//! many bytes of UTF-8 JSON (the same framing as checkpoint files, with JSON instead of
+//! msgpack). Requests carry a `cmd` field; every response has `ok`, and failed ones add
+//! `code` and `error`. The first request of a connection must be the handshake:
+//!
+//! ```text
+//! -> {"cmd": "hello", "version": 1}
+//! <- {"ok": true, "protocol": 1, "server": "0.1.0"}
+//! -> {"cmd": "create", "opts": {"seed": 7, "max_rooms": 4}}
+//! <- {"ok": true, "env_id": 0}
+//! -> {"cmd": "step", "env_id": 0, "action": "MoveUp"}
+//! <- {"ok": true, "reward": 0.0, "steps": 1, "time": 1}
+//! -> {"cmd": "observe", "env_id": 0, "mode": "ascii"}
+//! <- {"ok": true, "observation": "■■■..."}
+//! -> {"cmd": "reset", "env_id": 0, "seed": 8}
+//! <- {"ok": true}
+//! -> {"cmd": "close", "env_id": 0}
+//! <- {"ok": true}
+//! ```
+//!
+//! `opts` takes any subset of `GenOpts` fields, and neither side may exceed `MAX_SIDE` cells. Actions use the serde form of
+//! `tidy_core::Action`: `"MoveUp"`, `"PickUp"`, `{"Interact": {"dx": 1, "dy": 0}}`,
+//! `{"PlaceInto": 3}`, `{"PickUpById": 3}`, `{"ToggleContainer": 3}`, `{"RunAppliance": 3}`,
+//! `"TurnLeft"`, `"MoveForward"`. Observation modes are `state` (the `SimState` snapshot, the default),
+//! `ascii`, `world` (canonical world JSON) and `rooms` (`RoomGraphObservation`, with an
+//! optional `max_rooms`, default 16). A successful `step` also ticks the clock once; a failed
+//! one changes nothing and leaves the connection open.
+//!
+//! Environments belong to the connection that created them; each connection is served by
+//! its own thread. A server started with `--schemas PATH` generates every environment from
+//! that schema file (see `tidy_core::schema`) instead of the built-in objects.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tidy_core::{Action, GenOpts, ObjectSchema, Simulator};
+
+/// Protocol version checked by the handshake
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Largest accepted frame, to keep a bad length prefix from allocating gigabytes
+pub const MAX_FRAME: usize = 64 << 20;
+
+/// Largest width or height `create` accepts; the core allows up to `gen::MAX_SHELL_SIDE`,
+/// but one server holds the worlds of every client
+pub const MAX_SIDE: usize = 256;
+
+/// Observation returned by `observe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObserveMode {
+    #[default]
+    State,
+    Ascii,
+    World,
+    Rooms,
+}
+
+/// A client request; see the module docs for the wire format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    Hello {
+        version: u32,
+    },
+    Create {
+        #[serde(default)]
+        opts: GenOpts,
+    },
+    /// Run one action, then advance the clock by one `tick`
+    Step {
+        env_id: u64,
+        action: Action,
+    },
+    Observe {
+        env_id: u64,
+        #[serde(default)]
+        mode: ObserveMode,
+        #[serde(default)]
+        max_rooms: Option<usize>,
+    },
+    /// Regenerate the environment, with a new seed if given
+    Reset {
+        env_id: u64,
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+    Close {
+        env_id: u64,
+    },
+}
+
+/// Read one frame; `Ok(None)` on a clean end of stream before the length prefix
+pub fn read_frame(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("frame of {} bytes is too large", len)));
+    }
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(buf))
+}
+
+/// Write one frame with a single `write_all`, so small frames are not split by Nagle's algorithm
+pub fn write_frame(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    if bytes.len() > MAX_FRAME {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("frame of {} bytes is too large", bytes.len())));
+    }
+    let mut frame = Vec::with_capacity(4 + bytes.len());
+    frame.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    frame.extend_from_slice(bytes);
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+fn error(code: &str, message: impl std::fmt::Display) -> Value {
+    json!({ "ok": false, "code": code, "error": message.to_string() })
+}
+
+struct Env {
+    opts: GenOpts,
+    sim: Simulator,
+}
+
+impl Env {
+    fn generate(opts: GenOpts, schemas: Option<&[ObjectSchema]>) -> Result<Env, Value> {
+        if opts.width.max(opts.height) > MAX_SIDE {
+            let message = format!("{}x{} exceeds the server limit of {} cells per side", opts.width, opts.height, MAX_SIDE);
+            return Err(error("shell_too_large", message));
+        }
+        let world = match schemas {
+            Some(schemas) => tidy_core::generate_with_schemas(&opts, schemas),
+            None => tidy_core::generate(&opts),
+        }
+        .map_err(|e| error(e.code(), e))?;
+        let start = world.layout.cells.iter().position(|&c| c >= 0).expect("generate guarantees a room");
+        let width = world.layout.width;
+        let sim = Simulator::new(world, start % width, start / width).map_err(|e| error("no_start_cell", e))?;
+        Ok(Env { opts, sim })
+    }
+}
+
+/// State of one client connection
+#[derive(Default)]
+pub struct Session {
+    greeted: bool,
+    envs: HashMap<u64, Env>,
+    next_id: u64,
+    schemas: Option<Arc<[ObjectSchema]>>,
+}
+
+impl Session {
+    /// Session that generates its environments from `schemas` instead of the built-in catalog
+    pub fn with_schemas(schemas: Option<Arc<[ObjectSchema]>>) -> Self {
+        Session { schemas, ..Default::default() }
+    }
+
+    /// Answer one request
+    pub fn handle(&mut self, request: Request) -> Value {
+        if let Request::Hello { version } = request {
+            if version != PROTOCOL_VERSION {
+                return error("unsupported_version", format!("server speaks protocol {}", PROTOCOL_VERSION));
+            }
+            self.greeted = true;
+            return json!({ "ok": true, "protocol": PROTOCOL_VERSION, "server": env!("CARGO_PKG_VERSION") });
+        }
+        if !self.greeted {
+            return error("handshake_required", "send hello first");
+        }
+        match self.dispatch(request) {
+            Ok(value) => value,
+            Err(e) => e,
+        }
+    }
+
+    fn env(&mut self, env_id: u64) -> Result<&mut Env, Value> {
+        self.envs.get_mut(&env_id).ok_or_else(|| error("unknown_env", format!("no environment {}", env_id)))
+    }
+
+    fn dispatch(&mut self, request: Request) -> Result<Value, Value> {
+        match request {
+            Request::Hello { .. } => unreachable!("handled before dispatch"),
+            Request::Create { opts } => {
+                let env = Env::generate(opts, self.schemas.as_deref())?;
+                let env_id = self.next_id;
+                self.next_id += 1;
+                self.envs.insert(env_id, env);
+                Ok(json!({ "ok": true, "env_id": env_id }))
+            }
+            Request::Step { env_id, action } => {
+                let sim = &mut self.env(env_id)?.sim;
+                let outcome = sim.step(action).map_err(|e| error("action_failed", e))?;
+                sim.tick();
+                Ok(json!({ "ok": true, "reward": outcome.reward, "steps": sim.steps, "time": sim.time }))
+            }
+            Request::Observe { env_id, mode, max_rooms } => {
+                let sim = &mut self.env(env_id)?.sim;
+                let observation = match mode {
+                    ObserveMode::State => serde_json::to_value(sim.state()),
+                    ObserveMode::Ascii => Ok(Value::String(tidy_core::render::render_ascii(sim, &Default::default()))),
+                    ObserveMode::World => {
+                        // canonical order, as in `World::to_json`
+                        let mut world = sim.world.clone();
+                        world.canonicalize();
+                        serde_json::to_value(&world)
+                    }
+                    ObserveMode::Rooms => serde_json::to_value(sim.room_graph_observation(max_rooms.unwrap_or(16))),
+                }
+                .map_err(|e| error("encode_failed", e))?;
+                Ok(json!({ "ok": true, "observation": observation }))
+            }
+            Request::Reset { env_id, seed } => {
+                let schemas = self.schemas.clone();
+                let env = self.env(env_id)?;
+                let opts = GenOpts { seed: seed.unwrap_or(env.opts.seed), ..env.opts.clone() };
+                *env = Env::generate(opts, schemas.as_deref())?;
+                Ok(json!({ "ok": true }))
+            }
+            Request::Close { env_id } => {
+                self.envs.remove(&env_id).ok_or_else(|| error("unknown_env", format!("no environment {}", env_id)))?;
+                Ok(json!({ "ok": true }))
+            }
+        }
+    }
+}
+
+/// Serve one connection until the client hangs up; `schemas` as in `Session::with_schemas`
+pub fn serve_connection(stream: &mut (impl Read + Write), schemas: Option<Arc<[ObjectSchema]>>) -> io::Result<()> {
+    let mut session = Session::with_schemas(schemas);
+    while let Some(frame) = read_frame(stream)? {
+        let response = match serde_json::from_slice::<Request>(&frame) {
+            Ok(request) => session.handle(request),
+            Err(e) => error("invalid_request", e),
+        };
+        write_frame(stream, response.to_string().as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Accept TCP connections forever, one thread per connection; a failed accept is logged and
+/// skipped
+pub fn serve_tcp(listener: TcpListener, schemas: Option<Arc<[ObjectSchema]>>) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let Some(mut stream) = accepted(stream) else { continue };
+        if let Err(e) = stream.set_nodelay(true) {
+            eprintln!("tidy-env-server: dropping connection: {}", e);
+            continue;
+        }
+        let schemas = schemas.clone();
+        thread::spawn(move || serve_connection(&mut stream, schemas));
+    }
+    Ok(())
+}
+
+/// Accept Unix socket connections forever, one thread per connection, like `serve_tcp`
+#[cfg(unix)]
+pub fn serve_unix(listener: std::os::unix::net::UnixListener, schemas: Option<Arc<[ObjectSchema]>>) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let Some(mut stream) = accepted(stream) else { continue };
+        let schemas = schemas.clone();
+        thread::spawn(move || serve_connection(&mut stream, schemas));
+    }
+    Ok(())
+}
+
+/// The accepted stream, or `None` after logging why accepting failed, e.g. when the process
+/// ran out of file descriptors; the listener itself stays usable
+fn accepted<S>(stream: io::Result<S>) -> Option<S> {
+    stream.map_err(|e| eprintln!("tidy-env-server: accept failed: {}", e)).ok()
+}
+
+/// Minimal blocking client, e.g. for tests and as a reference for other languages
+pub struct Client<S: Read + Write> {
+    stream: S,
+}
+
+impl<S: Read + Write> Client<S> {
+    /// Perform the handshake over a connected stream
+    pub fn connect(stream: S) -> io::Result<Self> {
+        let mut client = Client { stream };
+        client.request(&Request::Hello { version: PROTOCOL_VERSION })?;
+        Ok(client)
+    }
+
+    /// Send a request and return the response, turning `ok: false` into an error
+    pub fn request(&mut self, request: &Request) -> io::Result<Value> {
+        let bytes = serde_json::to_vec(request).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_frame(&mut self.stream, &bytes)?;
+        let frame = read_frame(&mut self.stream)?.ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        let response: Value = serde_json::from_slice(&frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if response["ok"] == Value::Bool(true) {
+            Ok(response)
+        } else {
+            Err(io::Error::other(response["error"].as_str().unwrap_or("request failed").to_string()))
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::net::TcpListener;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use tidy_core::ObjectSchema;
+
+const USAGE: &str = "usage: tidy-env-server [--schemas PATH] (--tcp ADDR | --unix PATH)";
+
+/// Read the schema file given with `--schemas`
+fn load_schemas(path: &str) -> Result<Arc<[ObjectSchema]>, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?;
+    ObjectSchema::from_json(&json).map(Arc::from).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut schemas = None;
+    if let Some(i) = args.iter().position(|a| a == "--schemas") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        };
+        match load_schemas(path) {
+            Ok(loaded) => schemas = Some(loaded),
+            Err(e) => {
+                eprintln!("tidy-env-server: {}", e);
+                return ExitCode::from(2);
+            }
+        }
+        args.drain(i..i + 2);
+    }
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["--tcp", addr] => TcpListener::bind(addr).and_then(|listener| {
+            eprintln!("listening on {}", listener.local_addr()?);
+            tidy_server::serve_tcp(listener, schemas)
+        }),
+        #[cfg(unix)]
+        ["--unix", path] => std::os::unix::net::UnixListener::bind(path).and_then(|listener| {
+            eprintln!("listening on {}", path);
+            tidy_server::serve_unix(listener, schemas)
+        }),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("tidy-env-server: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
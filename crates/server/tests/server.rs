@@ -0,0 +1,86 @@
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+use rand::{Rng, SeedableRng};
+use tidy_core::{Action, GenOpts};
+use tidy_server::{Client, ObserveMode, Request};
+
+const ACTIONS: [Action; 6] = [Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight, Action::PickUp, Action::Drop];
+
+/// The server binary on an ephemeral port, killed on drop
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Server {
+    fn spawn() -> Server {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_tidy-env-server"))
+            .args(["--tcp", "127.0.0.1:0"])
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("server starts");
+        let mut line = String::new();
+        BufReader::new(child.stderr.take().unwrap()).read_line(&mut line).unwrap();
+        let addr = line.trim().strip_prefix("listening on ").expect("server reports its address").to_string();
+        Server { child, addr }
+    }
+
+    fn connect(&self) -> Client<TcpStream> {
+        Client::connect(TcpStream::connect(&self.addr).unwrap()).unwrap()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn a_spawned_server_runs_a_thousand_steps() {
+    let server = Server::spawn();
+    let mut client = server.connect();
+    let created = client.request(&Request::Create { opts: GenOpts { seed: 7, ..Default::default() } }).unwrap();
+    let env_id = created["env_id"].as_u64().unwrap();
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let mut ok = 0;
+    for _ in 0..1000 {
+        let action = match rng.gen_range(0..ACTIONS.len() + 1) {
+            i if i < ACTIONS.len() => ACTIONS[i],
+            _ => Action::Interact { dx: rng.gen_range(-1..=1), dy: rng.gen_range(-1..=1) },
+        };
+        if let Ok(response) = client.request(&Request::Step { env_id, action }) {
+            ok += 1;
+            assert_eq!(response["steps"], ok);
+        }
+    }
+    assert!(ok > 0);
+
+    let state = client.request(&Request::Observe { env_id, mode: ObserveMode::State, max_rooms: None }).unwrap();
+    assert_eq!(state["observation"]["steps"], ok);
+    client.request(&Request::Reset { env_id, seed: Some(8) }).unwrap();
+    let state = client.request(&Request::Observe { env_id, mode: ObserveMode::State, max_rooms: None }).unwrap();
+    assert_eq!(state["observation"]["steps"], 0);
+    client.request(&Request::Close { env_id }).unwrap();
+    assert!(client.request(&Request::Step { env_id, action: Action::Noop }).is_err());
+}
+
+#[test]
+fn create_refuses_oversized_shells_and_takes_room_names() {
+    let server = Server::spawn();
+    let mut client = server.connect();
+    let huge = GenOpts { width: 1 << 40, height: 1 << 40, ..Default::default() };
+    let error = client.request(&Request::Create { opts: huge }).unwrap_err();
+    assert!(error.to_string().contains("server limit"), "{}", error);
+
+    // the connection and the server stay usable, and room name lists go through
+    assert!(client.request(&Request::Create { opts: GenOpts::default() }).is_ok());
+    let required = GenOpts { required_rooms: vec!["Kitchen".into()], ..Default::default() };
+    assert!(client.request(&Request::Create { opts: required }).is_ok());
+    let pool = GenOpts { room_name_pool: Some(vec!["Vault".into(), "Armory".into()]), max_rooms: 2, ..Default::default() };
+    assert!(client.request(&Request::Create { opts: pool }).is_ok());
+}
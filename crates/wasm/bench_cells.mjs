@@ -0,0 +1,45 @@
+#!/usr/bin/env node
+// Time the three ways of reading a simulator's cells each frame: the `cells` getter, which
+// allocates a fresh copy, `copy_cells_into` a reused buffer, and the zero-copy `cells_view()`.
+// Build the package for node first: wasm-pack build crates/wasm --target nodejs
+//
+// Usage: node crates/wasm/bench_cells.mjs [pkg/wasm.js] [side] [frames]
+// Exits with status 1 if the three disagree on the cells.
+
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+const wasm = require(process.argv[2] ?? "./pkg/wasm.js");
+const side = Number(process.argv[3] ?? 200);
+const frames = Number(process.argv[4] ?? 1000);
+
+const sim = new wasm.ApartmentSimulator(1, 12, side, side, 40);
+
+const cloned = sim.cells;
+const copied = new Int8Array(cloned.length);
+sim.copy_cells_into(copied);
+const viewed = Int8Array.from(sim.cells_view());
+if (!cloned.every((c, i) => c === copied[i] && c === viewed[i])) {
+  console.log("cells, copy_cells_into and cells_view disagree");
+  process.exit(1);
+}
+
+function time(name, read) {
+  let checksum = 0;
+  const start = process.hrtime.bigint();
+  for (let frame = 0; frame < frames; frame++) {
+    const cells = read();
+    checksum += cells[frame % cells.length];
+  }
+  const ms = Number(process.hrtime.bigint() - start) / 1e6;
+  console.log(`${name.padEnd(16)} ${(ms / frames).toFixed(4)} ms/frame (checksum ${checksum})`);
+}
+
+console.log(`${side}x${side} cells, ${frames} frames`);
+time("cells", () => sim.cells);
+time("copy_cells_into", () => {
+  sim.copy_cells_into(copied);
+  return copied;
+});
+time("cells_view", () => sim.cells_view());
+sim.free();
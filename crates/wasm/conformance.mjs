@@ -0,0 +1,90 @@
+#!/usr/bin/env node
+// Run the core conformance spec (`tidy_core::conformance`) through ApartmentSimulator.
+// Build the package for node first: wasm-pack build crates/wasm --target nodejs
+//
+// Every operation is looked up by its WASM name, methods and getters alike, so a missing
+// binding fails with the operation name just like a diverging result does.
+// Exits with status 1 on any failure.
+
+import { createRequire } from "node:module";
+
+const require = createRequire(import.meta.url);
+const wasm = require(process.argv[2] ?? "./pkg/wasm.js");
+
+class MissingOperation extends Error {}
+
+function normalize(value, returns) {
+  switch (returns) {
+    case "nothing":
+      return { kind: "ok" };
+    case "bool":
+      return { kind: "bool", value: Boolean(value) };
+    case "object":
+      return { kind: "id", value: value == null ? null : value.id };
+    case "objects":
+      return { kind: "ids", value: Array.from(value, (v) => (typeof v === "object" ? v.id : v)) };
+    case "number":
+      return { kind: "number", value: Number(value) };
+    default:
+      throw new Error(`unknown return kind ${returns}`);
+  }
+}
+
+function call(sim, op, args) {
+  if (!(op.wasm in sim)) {
+    throw new MissingOperation(`ApartmentSimulator has no '${op.wasm}'`);
+  }
+  let value;
+  try {
+    const attr = sim[op.wasm];
+    value = typeof attr === "function" ? attr.apply(sim, args) : attr;
+  } catch {
+    return { kind: "failed" };
+  }
+  return normalize(value, op.returns);
+}
+
+function state(sim) {
+  const holding = sim.get_holding();
+  return [[sim.agent_x, sim.agent_y], holding == null ? null : holding.id];
+}
+
+function run(spec) {
+  const operations = new Map(spec.operations.map((op) => [op.name, op]));
+  const failures = [];
+  for (const c of spec.cases) {
+    let sim;
+    try {
+      sim = wasm.ApartmentSimulator.tutorial(c.scenario);
+    } catch (e) {
+      failures.push(`${c.scenario} step 0 (start): ${e}`);
+      continue;
+    }
+    for (const [i, step] of c.steps.entries()) {
+      const where = `${c.scenario} step ${i} (${step.op})`;
+      let outcome;
+      try {
+        outcome = call(sim, operations.get(step.op), step.args);
+      } catch (e) {
+        if (!(e instanceof MissingOperation)) throw e;
+        failures.push(`${where}: ${e.message}`);
+        break;
+      }
+      const found = JSON.stringify([outcome, ...state(sim)]);
+      const expected = JSON.stringify([step.expect, step.agent, step.holding]);
+      if (found !== expected) {
+        failures.push(`${where}: expected ${expected}, got ${found}`);
+        break;
+      }
+    }
+    sim.free();
+  }
+  return failures;
+}
+
+const spec = JSON.parse(wasm.conformance_spec_json());
+const failures = run(spec);
+failures.forEach((f) => console.log(f));
+const steps = spec.cases.reduce((n, c) => n + c.steps.length, 0);
+console.log(`wasm conformance: ${failures.length} failure(s) over ${steps} steps`);
+process.exit(failures.length ? 1 : 0);
@@ -1,73 +1,712 @@
 // wasm/src/lib.rs
 use wasm_bindgen::prelude::*;
-use core as apartment_core;    // assumes your core crate’s Cargo.toml name is “core”
-use js_sys::{Array, Object as JsObject, Reflect};
+use js_sys::{Array, Reflect};
+use js_sys::Object as JsObject;
 use wasm_bindgen::JsValue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Live `ApartmentLayout` and `ApartmentSimulator` instances, see `instances_alive`
+static INSTANCES_ALIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Member of every exported instance type, counting it in `INSTANCES_ALIVE` until dropped
+struct Alive;
+
+impl Alive {
+    fn new() -> Self {
+        INSTANCES_ALIVE.fetch_add(1, Ordering::Relaxed);
+        Alive
+    }
+}
+
+impl Drop for Alive {
+    fn drop(&mut self) {
+        INSTANCES_ALIVE.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[wasm_bindgen]
+/// Number of `ApartmentLayout` and `ApartmentSimulator` instances not yet freed, for leak tests
+pub fn instances_alive() -> usize {
+    INSTANCES_ALIVE.load(Ordering::Relaxed)
+}
 
 #[wasm_bindgen]
+/// Layout grid, with the objects of its world when generated with `with_world`.
+///
+/// Owns wasm memory: call `free()` once done with it. The arrays returned by the getters
+/// are copies owned by JS and stay valid after `free()`.
 pub struct ApartmentLayout {
-    width: usize,
-    height: usize,
-    /// Linear row-major i8 grid: -3=outside, -1=wall, -2=door, 0..=room IDs
-    cells: Vec<i8>,
+    /// Cells are a linear row-major i8 grid (index y*width + x): -1=wall, -2=outside, -3=closed door,
+    /// -4=open door, -5=locked door, -6=window, -7 - n=staircase n, 0..=room IDs (see `tidy_core::CellKind`);
+    /// JS should compare against `cell_constants()` or use `cell_kind_name` rather than these numbers.
+    /// Zones are row-major zone ids, -1=no zone
+    world: tidy_core::World,
+    /// Whether `world` holds the generated objects, so a simulator can be built from it
+    with_world: bool,
+    _alive: Alive,
 }
 
 #[wasm_bindgen]
 impl ApartmentLayout {
+    /// Layout from raw grids, e.g. for the `viewer` build, which cannot generate.
+    /// `zones` defaults to no zones and `room_names` must name every room id in `cells`;
+    /// throws an `Error` with code `invalid_layout` otherwise.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        width: usize,
+        height: usize,
+        cells: Vec<i8>,
+        zones: Option<Vec<i8>>,
+        room_names: Vec<String>,
+    ) -> Result<ApartmentLayout, JsValue> {
+        let invalid = |message: &str| coded_error("invalid_layout", message);
+        if cells.len() != width * height {
+            return Err(invalid("grid length does not match dimensions"));
+        }
+        if cells.iter().any(|&c| c >= 0 && c as usize >= room_names.len()) {
+            return Err(invalid("a room id in cells has no name"));
+        }
+        let mut layout = tidy_core::Layout::new(width, height, cells, room_names);
+        if let Some(zones) = zones {
+            if zones.len() != width * height {
+                return Err(invalid("zone grid length does not match dimensions"));
+            }
+            let count = zones.iter().map(|&z| z + 1).max().unwrap_or(0).max(0) as usize;
+            layout.zone_names = vec!["".into(); count];
+            layout.zones = zones;
+        }
+        Ok(ApartmentLayout { world: tidy_core::World::new(layout, Vec::new()), with_world: false, _alive: Alive::new() })
+    }
     /// Number of columns
     #[wasm_bindgen(getter)]
     pub fn width(&self) -> usize {
-        self.width
+        self.world.layout.width
     }
     /// Number of rows
     #[wasm_bindgen(getter)]
     pub fn height(&self) -> usize {
-        self.height
+        self.world.layout.height
     }
-    /// Flat array of length width*height
+    /// Flat row-major array of length width*height; cell (x, y) is at y*width + x
     #[wasm_bindgen(getter)]
     pub fn cells(&self) -> Vec<i8> {
-        self.cells.clone()
+        self.world.layout.cells.clone()
+    }
+    /// Flat row-major zone-id array of length width*height; cell (x, y) is at y*width + x
+    #[wasm_bindgen(getter)]
+    pub fn zones(&self) -> Vec<i8> {
+        self.world.layout.zones.clone()
+    }
+    /// Names of each room ID (0..rooms.len())
+    #[wasm_bindgen]
+    pub fn get_room_names(&self) -> Array {
+        let arr = Array::new();
+        for name in self.world.layout.room_names.iter() {
+            arr.push(&JsValue::from_str(name));
+        }
+        arr
+    }
+    /// Name of the room at (x, y); null for walls, doors, windows, outside cells and cells off the grid
+    #[wasm_bindgen]
+    pub fn get_room_name_at(&self, x: usize, y: usize) -> Option<String> {
+        self.world.layout.room_name_at(x, y).map(str::to_string)
+    }
+    /// Per room ID, its inclusive bounding box `[min_x, min_y, max_x, max_y]`, or null if the
+    /// room has no cells; e.g. for placing room labels
+    #[wasm_bindgen]
+    pub fn get_room_bboxes(&self) -> Array {
+        let layout = &self.world.layout;
+        (0..layout.room_names.len())
+            .map(|room| match layout.room_bbox(room) {
+                Some((x0, y0, x1, y1)) => [x0, y0, x1, y1].iter().map(|&v| JsValue::from_f64(v as f64)).collect::<Array>().into(),
+                None => JsValue::NULL,
+            })
+            .collect()
+    }
+    /// Stable hash of the layout (`tidy_core::World::fingerprint`); objects are not included
+    #[wasm_bindgen]
+    pub fn fingerprint(&self) -> u64 {
+        self.world.fingerprint()
+    }
+    /// Whether the layout carries its generated objects, as `ApartmentSimulator.from_layout` requires
+    #[wasm_bindgen(getter)]
+    pub fn with_world(&self) -> bool {
+        self.with_world
     }
 }
 
+#[cfg(feature = "render")]
 #[wasm_bindgen]
-/// Generate a new layout with custom width/height
-pub fn generate(seed: u64, max_rooms: usize, width: usize, height: usize, max_objects: usize) -> ApartmentLayout {
-    let opts = apartment_core::GenOpts { seed, max_rooms, width, height, max_objects };
+impl ApartmentLayout {
+    /// RGBA pixels (`width*scale` x `height*scale`, row-major) of the layout and its objects;
+    /// see `ApartmentSimulator.render_rgba` for the options
+    #[wasm_bindgen]
+    pub fn render_rgba(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_rgba(&self.world, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
 
-    // Call into your core crate
-    let world = apartment_core::generate(&opts);
-    let layout = world.layout;
+    /// SVG document of the layout and its objects; see `render_rgba` for the options
+    #[wasm_bindgen]
+    pub fn render_svg(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<String, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_svg(&self.world, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
 
-    // Wrap it up for JS (ignore room_names here; use get_room_names method)
-    ApartmentLayout { width: layout.width, height: layout.height, cells: layout.cells }
+    /// PNG file of the `render_rgba` image; see `render_rgba` for the options
+    #[wasm_bindgen]
+    pub fn render_png(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_png(&self.world, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
+
+    /// Text grid of the layout and its objects; see `ApartmentSimulator.render_ascii` for `glyphs`
+    #[wasm_bindgen]
+    pub fn render_ascii(&self, theme_json: Option<String>, glyphs: Option<bool>) -> Result<String, JsValue> {
+        let opts = tidy_core::RenderOpts { glyphs: glyphs.unwrap_or(false), ..Default::default() };
+        Ok(tidy_core::render::render_text(&self.world, &parse_theme(theme_json)?, opts))
+    }
+
+    /// Text snapshot of the layout and its objects in the `ascii` theme with room letters and
+    /// object initials, like `ApartmentSimulator.render_text`
+    #[wasm_bindgen]
+    pub fn render_text(&self) -> String {
+        let opts = tidy_core::RenderOpts { glyphs: true, ..Default::default() };
+        tidy_core::render::render_text(&self.world, &tidy_core::Theme::ascii(), opts)
+    }
 }
 
-// Expose room names on the layout
+#[cfg(feature = "gen")]
 #[wasm_bindgen]
-impl ApartmentLayout {
-    /// Names of each room ID (0..rooms.len())
-    #[wasm_bindgen]
-    pub fn get_room_names(&self) -> Array {
-        // need to call into core: regenerate? Instead, use simulator get_room_names after instantiation
-        Array::new()
+/// Generate a new layout with custom width/height.
+///
+/// This is the world `new ApartmentSimulator` builds from the same arguments when its first
+/// attempt succeeds (`retries` is 0), so the two have equal `fingerprint()`s. With
+/// `with_world` the layout keeps the generated objects and can seed `ApartmentSimulator.from_layout`.
+/// Throws an `Error` with a `code` property when generation fails.
+pub fn generate(
+    seed: u64,
+    max_rooms: usize,
+    width: usize,
+    height: usize,
+    max_objects: usize,
+    with_world: Option<bool>,
+) -> Result<ApartmentLayout, JsValue> {
+    let opts = tidy_core::GenOpts { seed, max_rooms, width, height, max_objects, ..Default::default() };
+    generate_layout(&opts, with_world.unwrap_or(false))
+}
+
+#[cfg(feature = "gen")]
+#[wasm_bindgen]
+/// `generate` with every option, as JSON in the form `validate_opts` takes (missing fields use the
+/// core defaults), e.g. `{"seed": 3, "min_room_area": 60, "door_min": 3, "door_max": 6}`
+pub fn generate_from_opts(opts_json: &str, with_world: Option<bool>) -> Result<ApartmentLayout, JsValue> {
+    generate_layout(&parse_opts(opts_json)?, with_world.unwrap_or(false))
+}
+
+#[cfg(feature = "gen")]
+fn generate_layout(opts: &tidy_core::GenOpts, with_world: bool) -> Result<ApartmentLayout, JsValue> {
+    let mut world = tidy_core::generate(opts).map_err(|e| gen_error(&e))?;
+    if !with_world {
+        world.objects = Vec::new();
+        world.clear_object_index();
+    }
+    Ok(ApartmentLayout { world, with_world, _alive: Alive::new() })
+}
+
+#[wasm_bindgen]
+/// Row-major index of cell (x, y) in the flat arrays returned by this module
+pub fn flat_index(x: usize, y: usize, width: usize) -> usize {
+    tidy_core::flat_index(x, y, width)
+}
+
+#[wasm_bindgen]
+/// Inverse of `flat_index`: returns `[x, y]`
+pub fn coords_from_index(index: usize, width: usize) -> Vec<usize> {
+    let c = tidy_core::coords_from_index(index, width);
+    vec![c.x, c.y]
+}
+
+#[wasm_bindgen]
+/// Reorder a row-major grid to column-major, where cell (x, y) is at x*height + y
+pub fn transpose_cells(cells: Vec<i8>, width: usize, height: usize) -> Result<Vec<i8>, JsValue> {
+    if cells.len() != width * height {
+        return Err(JsValue::from_str("grid length does not match dimensions"));
     }
+    Ok(tidy_core::transpose_cells(&cells, width, height))
 }
 
+#[cfg(feature = "gen")]
 #[wasm_bindgen]
-/// Simulator wrapper exposing agent movement API
+/// Generate a layout and return the JSON-encoded construction trace (shell, splits, rejections, doors)
+pub fn generate_trace_json(seed: u64, max_rooms: usize, width: usize, height: usize, max_objects: usize) -> Result<String, JsValue> {
+    let opts = tidy_core::GenOpts { seed, max_rooms, width, height, max_objects, ..Default::default() };
+    let (_, trace) = tidy_core::generate_with_trace(&opts).map_err(|e| gen_error(&e))?;
+    serde_json::to_string(&trace).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Generation options as accepted by `validate_opts`; missing fields take the core defaults
+#[cfg(feature = "gen")]
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct OptsJson {
+    seed: u64,
+    max_rooms: usize,
+    width: usize,
+    height: usize,
+    max_objects: usize,
+    zone_min_area: usize,
+    open_plan_prob: f32,
+    strict: bool,
+    task: tidy_core::TaskKind,
+    placement_temperature: f32,
+    min_room_area: usize,
+    min_room_thickness: usize,
+    min_aspect_ratio: f64,
+    max_aspect_ratio: f64,
+    door_min: usize,
+    door_max: usize,
+    required_rooms: Vec<String>,
+    room_name_pool: Option<Vec<String>>,
+    layout_seed: Option<u64>,
+    object_seed: Option<u64>,
+    misplaced_ratio: Option<f32>,
+}
+
+#[cfg(feature = "gen")]
+impl Default for OptsJson {
+    fn default() -> Self {
+        let d = tidy_core::GenOpts::default();
+        OptsJson {
+            seed: d.seed,
+            max_rooms: d.max_rooms,
+            width: d.width,
+            height: d.height,
+            max_objects: d.max_objects,
+            zone_min_area: d.zone_min_area,
+            open_plan_prob: d.open_plan_prob,
+            strict: d.strict,
+            task: d.task,
+            placement_temperature: d.placement_temperature,
+            min_room_area: d.min_room_area,
+            min_room_thickness: d.min_room_thickness,
+            min_aspect_ratio: d.min_aspect_ratio,
+            max_aspect_ratio: d.max_aspect_ratio,
+            door_min: d.door_min,
+            door_max: d.door_max,
+            required_rooms: d.required_rooms,
+            room_name_pool: d.room_name_pool,
+            layout_seed: d.layout_seed,
+            object_seed: d.object_seed,
+            misplaced_ratio: d.misplaced_ratio,
+        }
+    }
+}
+
+#[cfg(feature = "gen")]
+impl From<OptsJson> for tidy_core::GenOpts {
+    fn from(o: OptsJson) -> Self {
+        tidy_core::GenOpts {
+            seed: o.seed,
+            max_rooms: o.max_rooms,
+            width: o.width,
+            height: o.height,
+            max_objects: o.max_objects,
+            zone_min_area: o.zone_min_area,
+            open_plan_prob: o.open_plan_prob,
+            strict: o.strict,
+            task: o.task,
+            placement_temperature: o.placement_temperature,
+            min_room_area: o.min_room_area,
+            min_room_thickness: o.min_room_thickness,
+            min_aspect_ratio: o.min_aspect_ratio,
+            max_aspect_ratio: o.max_aspect_ratio,
+            door_min: o.door_min,
+            door_max: o.door_max,
+            required_rooms: o.required_rooms,
+            room_name_pool: o.room_name_pool,
+            layout_seed: o.layout_seed,
+            object_seed: o.object_seed,
+            misplaced_ratio: o.misplaced_ratio,
+        }
+    }
+}
+
+/// Reward settings as accepted by `set_reward_config`; missing fields take the core defaults
+#[cfg(feature = "sim")]
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct RewardJson {
+    step_penalty: f32,
+    correct_place_bonus: f32,
+    incorrect_place_penalty: f32,
+    pickup_bonus: f32,
+    done_bonus: f32,
+    /// `{ gamma, weight }` for potential-based shaping
+    shaping: Option<ShapingJson>,
+}
+
+#[cfg(feature = "sim")]
+#[derive(serde::Deserialize)]
+struct ShapingJson {
+    gamma: f32,
+    weight: f32,
+}
+
+#[cfg(feature = "sim")]
+impl Default for RewardJson {
+    fn default() -> Self {
+        let d = tidy_core::RewardConfig::default();
+        RewardJson {
+            step_penalty: d.step_penalty,
+            correct_place_bonus: d.correct_place_bonus,
+            incorrect_place_penalty: d.incorrect_place_penalty,
+            pickup_bonus: d.pickup_bonus,
+            done_bonus: d.done_bonus,
+            shaping: None,
+        }
+    }
+}
+
+#[cfg(feature = "sim")]
+impl From<RewardJson> for tidy_core::RewardConfig {
+    fn from(r: RewardJson) -> Self {
+        tidy_core::RewardConfig {
+            step_penalty: r.step_penalty,
+            correct_place_bonus: r.correct_place_bonus,
+            incorrect_place_penalty: r.incorrect_place_penalty,
+            pickup_bonus: r.pickup_bonus,
+            done_bonus: r.done_bonus,
+            shaping: r.shaping.map(|s| tidy_core::ShapingConfig { gamma: s.gamma, weight: s.weight }),
+        }
+    }
+}
+
+/// JS `Error` carrying a machine-readable `code` property
+fn coded_error(code: &str, message: &str) -> JsValue {
+    let err = js_sys::Error::new(message);
+    Reflect::set(&err, &JsValue::from_str("code"), &JsValue::from_str(code)).unwrap();
+    err.into()
+}
+
+#[cfg(feature = "gen")]
+fn gen_error(e: &tidy_core::GenError) -> JsValue {
+    coded_error(e.code(), &e.to_string())
+}
+
+/// Options from JSON; throws with code `invalid_json` when they cannot be parsed
+#[cfg(feature = "gen")]
+fn parse_opts(opts_json: &str) -> Result<tidy_core::GenOpts, JsValue> {
+    serde_json::from_str::<OptsJson>(opts_json)
+        .map(tidy_core::GenOpts::from)
+        .map_err(|e| coded_error("invalid_json", &e.to_string()))
+}
+
+#[cfg(feature = "gen")]
+#[wasm_bindgen]
+/// Check generation options without generating; returns `{ ok, code?, message? }`
+pub fn validate_opts(opts_json: &str) -> JsValue {
+    let result = JsObject::new();
+    let failure = match serde_json::from_str::<OptsJson>(opts_json) {
+        Err(e) => Some(("invalid_json", e.to_string())),
+        Ok(opts) => tidy_core::GenOpts::from(opts).validate().err().map(|e| (e.code(), e.to_string())),
+    };
+    Reflect::set(&result, &JsValue::from_str("ok"), &JsValue::from_bool(failure.is_none())).unwrap();
+    if let Some((code, message)) = failure {
+        Reflect::set(&result, &JsValue::from_str("code"), &JsValue::from_str(code)).unwrap();
+        Reflect::set(&result, &JsValue::from_str("message"), &JsValue::from_str(&message)).unwrap();
+    }
+    result.into()
+}
+
+#[cfg(feature = "render")]
+#[wasm_bindgen]
+/// Built-in theme ("default", "high_contrast", "dark", "colorblind_safe" or "ascii") as JSON, for `render_*` or client-side drawing
+pub fn theme_json(name: &str) -> Result<String, JsValue> {
+    let theme = tidy_core::Theme::named(name).ok_or_else(|| coded_error("unknown_theme", &format!("Unknown theme: {}", name)))?;
+    serde_json::to_string(&theme).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(all(feature = "sim", feature = "default-schemas"))]
+#[wasm_bindgen]
+/// Tutorial scenarios, easiest first, as JSON `{ format, scenarios }` where `scenarios` is an array of
+/// `{ name, title, world, start, goals, max_steps, instructions }`
+pub fn tutorial_scenarios_json() -> Result<String, JsValue> {
+    let scenarios = std::collections::BTreeMap::from([("scenarios", tidy_core::scenarios::tutorial())]);
+    tidy_core::format::to_json(&scenarios).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(all(feature = "sim", feature = "default-schemas"))]
+#[wasm_bindgen]
+/// Binding conformance spec as JSON `{ operations, cases }`; `conformance.mjs` plays it through
+/// `ApartmentSimulator`
+pub fn conformance_spec_json() -> Result<String, JsValue> {
+    serde_json::to_string(&tidy_core::conformance::spec()).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[wasm_bindgen]
+/// Cell values of the non-room kinds as `{WALL, OUTSIDE, CLOSED_DOOR, OPEN_DOOR, LOCKED_DOOR,
+/// WINDOW, STAIRS_BASE}`; staircase `n` is `STAIRS_BASE - n` and rooms are `0..`
+pub fn cell_constants() -> JsValue {
+    let obj = JsObject::new();
+    for (name, value) in [
+        ("WALL", tidy_core::WALL),
+        ("OUTSIDE", tidy_core::OUTSIDE),
+        ("CLOSED_DOOR", tidy_core::CLOSED_DOOR),
+        ("OPEN_DOOR", tidy_core::OPEN_DOOR),
+        ("LOCKED_DOOR", tidy_core::LOCKED_DOOR),
+        ("WINDOW", tidy_core::WINDOW),
+        ("STAIRS_BASE", tidy_core::STAIRS_BASE),
+    ] {
+        Reflect::set(&obj, &JsValue::from_str(name), &JsValue::from_f64(value as f64)).unwrap();
+    }
+    obj.into()
+}
+
+#[wasm_bindgen]
+/// Kind of a cell value: `room`, `wall`, `outside`, `closed_door`, `open_door`, `locked_door`,
+/// `window` or `stairs`
+pub fn cell_kind_name(value: i8) -> String {
+    tidy_core::CellKind::from_i8(value).name().to_string()
+}
+
+#[cfg(feature = "sim")]
+#[wasm_bindgen]
+/// Names of the discrete actions `ApartmentSimulator.step_id` takes, indexed by action id
+pub fn action_names() -> Array {
+    tidy_core::Action::DISCRETE.iter().map(|&(name, _)| JsValue::from_str(name)).collect()
+}
+
+/// Parse a theme JSON; missing fields and `None` fall back to the default theme
+#[cfg(feature = "render")]
+fn parse_theme(theme_json: Option<String>) -> Result<tidy_core::Theme, JsValue> {
+    match theme_json {
+        Some(json) => serde_json::from_str(&json).map_err(|e| coded_error("invalid_theme", &e.to_string())),
+        None => Ok(tidy_core::Theme::default()),
+    }
+}
+
+/// `RenderOpts` from the optional flags of the `render_*` methods, all off by default
+#[cfg(feature = "render")]
+fn render_opts(glyphs: Option<bool>, pattern_fill: Option<bool>, room_names: Option<bool>) -> tidy_core::RenderOpts {
+    tidy_core::RenderOpts {
+        glyphs: glyphs.unwrap_or(false),
+        pattern_fill: pattern_fill.unwrap_or(false),
+        room_names: room_names.unwrap_or(false),
+    }
+}
+
+#[cfg(feature = "sim")]
+fn macro_outcome(outcome: &tidy_core::StepOutcome) -> JsValue {
+    let obj = JsObject::new();
+    Reflect::set(&obj, &JsValue::from_str("reward"), &JsValue::from_f64(outcome.reward as f64)).unwrap();
+    Reflect::set(&obj, &JsValue::from_str("tidiness_delta"), &JsValue::from_f64(outcome.tidiness_delta as f64)).unwrap();
+    Reflect::set(&obj, &JsValue::from_str("primitive_steps"), &JsValue::from_f64(outcome.primitive_steps as f64)).unwrap();
+    Reflect::set(&obj, &JsValue::from_str("steps"), &JsValue::from_f64(outcome.steps as f64)).unwrap();
+    Reflect::set(&obj, &JsValue::from_str("done"), &JsValue::from_bool(outcome.done)).unwrap();
+    obj.into()
+}
+
+/// `{ type, ... }` object for a simulator event, `type` being the `SimEvent` variant name
+#[cfg(feature = "sim")]
+fn event_js(event: &tidy_core::SimEvent) -> JsObject {
+    use tidy_core::SimEvent;
+    let obj = JsObject::new();
+    let set = |key: &str, value: JsValue| {
+        Reflect::set(&obj, &JsValue::from_str(key), &value).unwrap();
+    };
+    let num = |n: usize| JsValue::from_f64(n as f64);
+    let cell = |(x, y): (usize, usize)| JsValue::from(Array::of2(&num(x), &num(y)));
+    let door = |door: Option<usize>| door.map_or(JsValue::NULL, num);
+    let kind = match *event {
+        SimEvent::Decayed { id, ref from, ref into } | SimEvent::Transformed { id, ref from, ref into } => {
+            set("id", num(id));
+            set("from", JsValue::from_str(from));
+            set("into", JsValue::from_str(into));
+            if matches!(event, SimEvent::Decayed { .. }) { "Decayed" } else { "Transformed" }
+        }
+        SimEvent::Disposed { id, ref name } => {
+            set("id", num(id));
+            set("name", JsValue::from_str(name));
+            "Disposed"
+        }
+        SimEvent::Moved { agent, from, to } => {
+            set("agent", num(agent));
+            set("from", cell(from));
+            set("to", cell(to));
+            "Moved"
+        }
+        SimEvent::Turned { agent, facing } => {
+            set("agent", num(agent));
+            set("facing", JsValue::from_str(facing.name()));
+            "Turned"
+        }
+        SimEvent::DoorOpened { door: d, x, y } | SimEvent::DoorClosed { door: d, x, y } => {
+            set("door", door(d));
+            set("x", num(x));
+            set("y", num(y));
+            if matches!(event, SimEvent::DoorOpened { .. }) { "DoorOpened" } else { "DoorClosed" }
+        }
+        SimEvent::PickedUp { agent, id } => {
+            set("agent", num(agent));
+            set("id", num(id));
+            "PickedUp"
+        }
+        SimEvent::Dropped { agent, id, x, y } => {
+            set("agent", num(agent));
+            set("id", num(id));
+            set("x", num(x));
+            set("y", num(y));
+            "Dropped"
+        }
+        SimEvent::PlacedInto { agent, id, container } => {
+            set("agent", num(agent));
+            set("id", num(id));
+            set("container", num(container));
+            "PlacedInto"
+        }
+        SimEvent::ContainerOpened { id } => {
+            set("id", num(id));
+            "ContainerOpened"
+        }
+        SimEvent::ContainerClosed { id } => {
+            set("id", num(id));
+            "ContainerClosed"
+        }
+    };
+    set("type", JsValue::from_str(kind));
+    obj
+}
+
+/// Generation attempts made by the simulator constructor before giving up
+#[cfg(all(feature = "gen", feature = "sim"))]
+const DEFAULT_GEN_ATTEMPTS: u32 = 4;
+
+#[cfg(feature = "sim")]
+#[wasm_bindgen]
+/// Simulator wrapper exposing agent movement API.
+///
+/// Owns wasm memory that the JS garbage collector does not reclaim reliably: call `free()`
+/// when the simulator is no longer needed (e.g. when its tab closes) and do not use it
+/// afterwards. Everything the methods return (arrays, objects, strings) is a copy owned by
+/// JS, so no returned value keeps the simulator alive or becomes invalid after `free()`.
 pub struct ApartmentSimulator {
-    sim: apartment_core::Simulator,
+    sim: tidy_core::Simulator,
+    retries: u32,
+    _alive: Alive,
 }
 
+#[cfg(feature = "sim")]
 #[wasm_bindgen]
 impl ApartmentSimulator {
+    #[cfg(feature = "gen")]
     #[wasm_bindgen(constructor)]
-    /// Create a new simulator with custom width, height (agent start auto-selected)
+    /// Create a new simulator with custom width, height (agent start auto-selected).
+    /// Throws an `Error` with a `code` property when the options are invalid.
     pub fn new(seed: u64, max_rooms: usize, width: usize, height: usize, max_objects: usize) -> Result<ApartmentSimulator, JsValue> {
-        let opts = apartment_core::GenOpts { seed, max_rooms, width, height, max_objects };
-        let world = apartment_core::generate(&opts);
+        ApartmentSimulator::with_attempts(seed, max_rooms, width, height, max_objects, DEFAULT_GEN_ATTEMPTS)
+    }
+
+    #[cfg(feature = "gen")]
+    /// Like the constructor, but retries generation with derived seeds up to `attempts` times
+    #[wasm_bindgen]
+    pub fn with_attempts(
+        seed: u64,
+        max_rooms: usize,
+        width: usize,
+        height: usize,
+        max_objects: usize,
+        attempts: u32,
+    ) -> Result<ApartmentSimulator, JsValue> {
+        let opts = tidy_core::GenOpts { seed, max_rooms, width, height, max_objects, ..Default::default() };
+        ApartmentSimulator::generated(&opts, attempts)
+    }
+
+    #[cfg(feature = "gen")]
+    /// Like the constructor, with every option given as JSON in the form `validate_opts` takes
+    #[wasm_bindgen]
+    pub fn from_opts(opts_json: &str) -> Result<ApartmentSimulator, JsValue> {
+        ApartmentSimulator::generated(&parse_opts(opts_json)?, DEFAULT_GEN_ATTEMPTS)
+    }
+
+    /// Simulator at the start of the tutorial scenario `name` (see `tutorial_scenarios_json`)
+    #[cfg(feature = "default-schemas")]
+    #[wasm_bindgen]
+    pub fn tutorial(name: &str) -> Result<ApartmentSimulator, JsValue> {
+        let scenario = tidy_core::scenarios::tutorial()
+            .into_iter()
+            .find(|sc| sc.name == name)
+            .ok_or_else(|| coded_error("unknown_scenario", &format!("Unknown tutorial scenario: {}", name)))?;
+        Ok(ApartmentSimulator { sim: scenario.simulator(), retries: 0, _alive: Alive::new() })
+    }
+
+    /// Simulator on the world of a layout from `generate(..., true)`; the layout stays usable.
+    /// Throws with code `layout_without_world` when the layout was made without its objects.
+    #[wasm_bindgen]
+    pub fn from_layout(layout: &ApartmentLayout) -> Result<ApartmentSimulator, JsValue> {
+        if !layout.with_world {
+            return Err(coded_error("layout_without_world", "layout has no objects; generate it with with_world set"));
+        }
+        ApartmentSimulator::from_world(layout.world.clone())
+    }
+
+    /// Number of derived seeds tried before generation succeeded
+    #[wasm_bindgen(getter)]
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Stable hash of the layout (`tidy_core::World::fingerprint`), equal to the
+    /// `ApartmentLayout.fingerprint()` of the same world; door states and objects are not included
+    #[wasm_bindgen]
+    pub fn fingerprint(&self) -> u64 {
+        self.sim.world.fingerprint()
+    }
+
+    /// Best-effort release of memory this simulator can rebuild: cached distance fields and
+    /// room aggregates, and spare capacity in its object tables. Linear memory itself never
+    /// shrinks, but the freed space is reused by later allocations.
+    #[wasm_bindgen]
+    pub fn shrink_memory(&mut self) {
+        self.sim.invalidate_caches();
+        self.sim.world.objects.shrink_to_fit();
+        self.sim.decay_remaining.shrink_to_fit();
+        self.sim.known_targets.shrink_to_fit();
+    }
+}
+
+#[cfg(feature = "sim")]
+impl ApartmentSimulator {
+    #[cfg(feature = "gen")]
+    fn generated(opts: &tidy_core::GenOpts, attempts: u32) -> Result<ApartmentSimulator, JsValue> {
+        let (world, retries) = tidy_core::generate_or_retry(opts, attempts).map_err(|e| gen_error(&e))?;
+        let mut sim = ApartmentSimulator::from_world(world)?;
+        sim.retries = retries;
+        Ok(sim)
+    }
+
+    fn from_world(world: tidy_core::World) -> Result<ApartmentSimulator, JsValue> {
         // auto-select first room cell
         let mut start_x = 0;
         let mut start_y = 0;
@@ -78,11 +717,40 @@ impl ApartmentSimulator {
                 break;
             }
         }
-        apartment_core::Simulator::new(world, start_x, start_y)
-            .map(|sim| ApartmentSimulator { sim })
-            .map_err(|e| JsValue::from_str(e))
+        tidy_core::Simulator::new(world, start_x, start_y)
+            .map(|sim| ApartmentSimulator { sim, retries: 0, _alive: Alive::new() })
+            .map_err(|e| coded_error("no_start_cell", e))
     }
 
+    /// An object as the JS object `get_objects` returns
+    fn object_js(&self, o: &tidy_core::Object) -> JsObject {
+        let obj = JsObject::new();
+        Reflect::set(&obj, &JsValue::from_str("id"), &JsValue::from_f64(o.id as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("x"), &JsValue::from_f64(o.x as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("y"), &JsValue::from_f64(o.y as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("pickable"), &JsValue::from_bool(o.pickable)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("blocks_movement"), &JsValue::from_bool(o.blocks_movement)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(&o.name)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("display_name"), &JsValue::from_str(&o.display_name)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("icon"), &JsValue::from_str(&o.icon)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("capacity"), &JsValue::from_f64(o.capacity as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(&o.description)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("label"), &JsValue::from_str(&o.display_label())).unwrap();
+        let decay = self.sim.decay_remaining(o.id).map_or(JsValue::NULL, |d| JsValue::from_f64(d as f64));
+        Reflect::set(&obj, &JsValue::from_str("decay_remaining"), &decay).unwrap();
+        let contents_arr = Array::new();
+        for &c in o.contents.iter() {
+            contents_arr.push(&JsValue::from_f64(c as f64));
+        }
+        Reflect::set(&obj, &JsValue::from_str("contents"), &contents_arr).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("is_open"), &JsValue::from_bool(self.sim.is_open(o))).unwrap();
+        obj
+    }
+}
+
+#[cfg(feature = "sim")]
+#[wasm_bindgen]
+impl ApartmentSimulator {
     /// Current agent X coordinate
     #[wasm_bindgen(getter)]
     pub fn agent_x(&self) -> usize {
@@ -94,25 +762,80 @@ impl ApartmentSimulator {
         self.sim.agent.y
     }
 
+    /// Direction the agent faces: "north", "east", "south" or "west"
+    #[wasm_bindgen(getter)]
+    pub fn agent_facing(&self) -> String {
+        self.sim.agent.facing.name().to_string()
+    }
+
+    /// Number of agents; 1 unless created with `with_agents`
+    #[wasm_bindgen(getter)]
+    pub fn num_agents(&self) -> usize {
+        self.sim.num_agents()
+    }
+
+    /// `[x, y]` of the agent with the given id, undefined past `num_agents`
+    #[wasm_bindgen]
+    pub fn agent_position(&self, agent_id: usize) -> Option<Vec<usize>> {
+        self.sim.agent_by_id(agent_id).map(|a| vec![a.x, a.y])
+    }
+
+    /// Object held by the agent with the given id (or null)
+    #[wasm_bindgen]
+    pub fn agent_holding(&self, agent_id: usize) -> JsValue {
+        self.sim.holding_by_id(agent_id).map_or(JsValue::NULL, |o| self.object_js(o).into())
+    }
+
+    /// A fresh simulator over the current world with one agent per start, given flat as
+    /// `[x0, y0, x1, y1, ...]`; agent 0 is the one the single-agent methods move. Starts must be
+    /// distinct and walkable, otherwise this throws with code `invalid_start`.
+    #[wasm_bindgen]
+    pub fn with_agents(&self, starts: Vec<usize>) -> Result<ApartmentSimulator, JsValue> {
+        let starts: Vec<(usize, usize)> = starts.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+        tidy_core::Simulator::new_multi(self.sim.world.clone(), &starts)
+            .map(|sim| ApartmentSimulator { sim, retries: 0, _alive: Alive::new() })
+            .map_err(|e| coded_error("invalid_start", e))
+    }
+
     /// Move agent up (y-1)
     #[wasm_bindgen]
     pub fn up(&mut self) -> Result<(), JsValue> {
-        self.sim.up().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.up().map_err(|e| coded_error(e.name(), &e.to_string()))
     }
     /// Move agent down (y+1)
     #[wasm_bindgen]
     pub fn down(&mut self) -> Result<(), JsValue> {
-        self.sim.down().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.down().map_err(|e| coded_error(e.name(), &e.to_string()))
     }
     /// Move agent left (x-1)
     #[wasm_bindgen]
     pub fn left(&mut self) -> Result<(), JsValue> {
-        self.sim.left().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.left().map_err(|e| coded_error(e.name(), &e.to_string()))
     }
     /// Move agent right (increasing x)
     #[wasm_bindgen]
     pub fn right(&mut self) -> Result<(), JsValue> {
-        self.sim.right().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.right().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Turn a quarter counter-clockwise in place
+    #[wasm_bindgen]
+    pub fn turn_left(&mut self) -> Result<(), JsValue> {
+        self.sim.turn_left().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Turn a quarter clockwise in place
+    #[wasm_bindgen]
+    pub fn turn_right(&mut self) -> Result<(), JsValue> {
+        self.sim.turn_right().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Move one cell in the facing direction
+    #[wasm_bindgen]
+    pub fn move_forward(&mut self) -> Result<(), JsValue> {
+        self.sim.move_forward().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// `interact` with the cell the agent faces
+    #[wasm_bindgen]
+    pub fn interact_ahead(&mut self) -> Result<(), JsValue> {
+        self.sim.interact_ahead().map_err(|e| coded_error(e.name(), &e.to_string()))
     }
 
     /// Open a closed door in the given direction relative to the agent
@@ -120,7 +843,7 @@ impl ApartmentSimulator {
     pub fn interact(&mut self, dx: i32, dy: i32) -> Result<(), JsValue> {
         self.sim
             .interact(dx as isize, dy as isize)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+            .map_err(|e| coded_error(e.name(), &e.to_string()))
     }
     /// Convenience: open door above
     #[wasm_bindgen]
@@ -134,16 +857,243 @@ impl ApartmentSimulator {
     /// Convenience: open door right
     #[wasm_bindgen]
     pub fn open_right(&mut self) -> Result<(), JsValue> { self.interact(1, 0) }
+    /// Convenience: close door above
+    #[wasm_bindgen]
+    pub fn close_up(&mut self) -> Result<(), JsValue> { self.close(0, -1) }
+    /// Convenience: close door below
+    #[wasm_bindgen]
+    pub fn close_down(&mut self) -> Result<(), JsValue> { self.close(0, 1) }
+    /// Convenience: close door left
+    #[wasm_bindgen]
+    pub fn close_left(&mut self) -> Result<(), JsValue> { self.close(-1, 0) }
+    /// Convenience: close door right
+    #[wasm_bindgen]
+    pub fn close_right(&mut self) -> Result<(), JsValue> { self.close(1, 0) }
+
+    /// Close the open door next to the agent through `use_door`; fails with `DoorwayOccupied`
+    /// while an agent stands in the doorway
+    fn close(&mut self, dx: isize, dy: isize) -> Result<(), JsValue> {
+        let (x, y) = (self.sim.agent.x as isize + dx, self.sim.agent.y as isize + dy);
+        self.sim.use_door(x, y, false).map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+
+    /// Whether (x, y) is a door, open, closed or locked; false off the grid
+    #[wasm_bindgen]
+    pub fn is_door(&self, x: usize, y: usize) -> bool {
+        self.sim.world.layout.kind(x, y).is_some_and(|kind| kind.is_door())
+    }
+
+    /// Whether (x, y) is an open door; false off the grid
+    #[wasm_bindgen]
+    pub fn is_door_open(&self, x: usize, y: usize) -> bool {
+        self.sim.world.layout.kind(x, y) == Some(tidy_core::CellKind::OpenDoor)
+    }
     
-    /// Pick up a pickable object at the agent's location
+    /// Action names (see `action_names()`) that walk to (x, y), opening closed doors on the way
+    /// unless `through_closed_doors` is false (default true); undefined when unreachable
+    #[wasm_bindgen]
+    pub fn path_to(&self, x: usize, y: usize, through_closed_doors: Option<bool>) -> Option<Array> {
+        let actions = self.sim.path_to(x, y, through_closed_doors.unwrap_or(true))?;
+        Some(actions.iter().filter_map(|a| a.discrete_name()).map(JsValue::from_str).collect())
+    }
+
+    /// Moves needed to walk to (x, y), through closed doors; undefined when unreachable
+    #[wasm_bindgen]
+    pub fn distance_to(&self, x: usize, y: usize) -> Option<usize> {
+        self.sim.distance_to(x, y)
+    }
+
+    /// Text observation of the agent's cell, held object, neighbouring cells, closed doors and
+    /// the objects within `radius` (default 3); the same state always gives the same text
+    #[wasm_bindgen]
+    pub fn describe(&self, radius: Option<usize>) -> String {
+        tidy_core::language::describe_surroundings(&self.sim, radius.unwrap_or(3))
+    }
+
+    /// Cells and objects within `radius` of the agent; returns `{ radius, side, cells, object_ids,
+    /// object_dx, object_dy, holding }` with `cells` flat and row-major. Objects inside containers
+    /// are only listed when `include_contained` is true (default false); `egocentric` (default
+    /// false) turns the view so the agent faces up.
+    #[wasm_bindgen]
+    pub fn local_view(&self, radius: usize, include_contained: Option<bool>, egocentric: Option<bool>) -> JsValue {
+        let mut view = self.sim.local_view(radius, include_contained.unwrap_or(false));
+        if egocentric.unwrap_or(false) {
+            view = view.rotated(self.sim.agent.facing);
+        }
+        let column = |f: fn(&(usize, isize, isize)) -> f64| -> Array {
+            view.objects.iter().map(|o| JsValue::from_f64(f(o))).collect()
+        };
+        let obj = JsObject::new();
+        Reflect::set(&obj, &JsValue::from_str("radius"), &JsValue::from_f64(view.radius as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("side"), &JsValue::from_f64(view.side() as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("cells"), &js_sys::Int8Array::from(&view.cells[..])).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("object_ids"), &column(|o| o.0 as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("object_dx"), &column(|o| o.1 as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("object_dy"), &column(|o| o.2 as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("holding"), &JsValue::from_bool(view.holding)).unwrap();
+        obj.into()
+    }
+
+    /// Door segments as `[{ id, rooms: [a, b], open, cells }]` with `cells` flat row-major indices;
+    /// a side without a room is -2 (outside)
+    #[wasm_bindgen]
+    pub fn get_doors(&self) -> Array {
+        self.sim
+            .world
+            .layout
+            .doors
+            .iter()
+            .map(|d| {
+                let door = JsObject::new();
+                let rooms: Array = [d.rooms.0, d.rooms.1].iter().map(|&r| JsValue::from_f64(r as f64)).collect();
+                let cells: Array = d.cells.iter().map(|&i| JsValue::from_f64(i as f64)).collect();
+                Reflect::set(&door, &JsValue::from_str("id"), &JsValue::from_f64(d.id as f64)).unwrap();
+                Reflect::set(&door, &JsValue::from_str("rooms"), &rooms).unwrap();
+                Reflect::set(&door, &JsValue::from_str("open"), &JsValue::from_bool(d.open)).unwrap();
+                Reflect::set(&door, &JsValue::from_str("cells"), &cells).unwrap();
+                JsValue::from(door)
+            })
+            .collect()
+    }
+
+    /// Walk to a room, opening doors on the way; returns `{ reward, tidiness_delta, primitive_steps, steps }`
+    #[wasm_bindgen]
+    pub fn go_to_room(&mut self, room: usize) -> Result<JsValue, JsValue> {
+        let outcome = self.sim.go_to_room(room).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(macro_outcome(&outcome))
+    }
+
+    /// Walk onto an object's cell, opening doors on the way; returns `{ reward, tidiness_delta, primitive_steps, steps }`
+    #[wasm_bindgen]
+    pub fn go_to_object(&mut self, object_id: usize) -> Result<JsValue, JsValue> {
+        let outcome = self.sim.go_to_object(object_id).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(macro_outcome(&outcome))
+    }
+
+    /// Run a command such as "go to the kitchen"; returns `{ reward, tidiness_delta, primitive_steps, steps }`
+    #[wasm_bindgen]
+    pub fn command(&mut self, text: &str) -> Result<JsValue, JsValue> {
+        let command = self
+            .sim
+            .parse_command(text)
+            .ok_or_else(|| coded_error("unknown_command", &format!("Unknown command: {}", text)))?;
+        let outcome = self.sim.run_command(command).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(macro_outcome(&outcome))
+    }
+
+    /// Perform the action with a discrete id (an index into `action_names()`). Failed actions do
+    /// not throw; returns `{ steps, succeeded, reward, tidiness_delta, error, done }` with `error`
+    /// the failure's code, or null, and `done` from `is_done()`
+    #[wasm_bindgen]
+    pub fn step_id(&mut self, action_id: usize) -> Result<JsValue, JsValue> {
+        self.step_agent_id(0, action_id)
+    }
+
+    /// `step_id` for the agent with the given id; see `with_agents`. Unknown agents fail with
+    /// the error `UnknownAgent`.
+    #[wasm_bindgen]
+    pub fn step_agent_id(&mut self, agent_id: usize, action_id: usize) -> Result<JsValue, JsValue> {
+        let action = tidy_core::Action::from_id(action_id)
+            .ok_or_else(|| coded_error("unknown_action", &format!("Unknown action id: {}", action_id)))?;
+        let result = self.sim.step_agent(agent_id, action);
+        let obj = JsObject::new();
+        let failed = (self.sim.failed_step_reward(), 0.0);
+        let (reward, tidiness_delta) = result.as_ref().map_or(failed, |o| (o.reward, o.tidiness_delta));
+        let error = result.as_ref().err().map_or(JsValue::NULL, |e| JsValue::from_str(e.name()));
+        Reflect::set(&obj, &JsValue::from_str("steps"), &JsValue::from_f64(self.sim.steps as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("succeeded"), &JsValue::from_bool(result.is_ok())).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("reward"), &JsValue::from_f64(reward as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("tidiness_delta"), &JsValue::from_f64(tidiness_delta as f64)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("error"), &error).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("done"), &JsValue::from_bool(self.sim.is_done())).unwrap();
+        Ok(obj.into())
+    }
+
+    /// Fraction of pickable objects in their target placement, counting a held object as misplaced
+    #[wasm_bindgen(getter)]
+    pub fn tidiness(&self) -> f32 {
+        self.sim.tidiness()
+    }
+    /// Ids of pickable objects not in their target placement, the held one included, ascending
+    #[wasm_bindgen]
+    pub fn misplaced_objects(&self) -> Vec<usize> {
+        self.sim.misplaced_objects()
+    }
+
+    /// Pick up the pickable object with the lowest id at the agent's location
     #[wasm_bindgen]
     pub fn pick_up(&mut self) -> Result<(), JsValue> {
-        self.sim.pick_up().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.pick_up().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Pick up the given object from the agent's location, for cells holding several
+    #[wasm_bindgen]
+    pub fn pick_up_by_id(&mut self, id: usize) -> Result<(), JsValue> {
+        self.sim.pick_up_by_id(id).map_err(|e| coded_error(e.name(), &e.to_string()))
     }
     /// Drop held object at the agent's location
     #[wasm_bindgen]
     pub fn drop(&mut self) -> Result<(), JsValue> {
-        self.sim.drop().map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        self.sim.drop().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Put the held object into a container on or next to the agent; throws an `Error` with
+    /// code `TooFar` otherwise, unless `strict_reach` is off
+    #[wasm_bindgen]
+    pub fn place_into(&mut self, container_id: usize) -> Result<(), JsValue> {
+        self.sim.place_into(container_id).map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Put the held object in a container or on a floor cell satisfying its target, on or next to
+    /// the agent; throws an `Error` with code `NoValidPlacementNearby` when there is none
+    #[wasm_bindgen]
+    pub fn auto_place(&mut self) -> Result<(), JsValue> {
+        self.sim.auto_place().map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Open or close a container on or next to the agent; see `closed_containers`
+    #[wasm_bindgen]
+    pub fn toggle_container(&mut self, container_id: usize) -> Result<(), JsValue> {
+        self.sim.toggle_container(container_id).map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Run an appliance on or next to the agent, e.g. a Dishwasher turning DirtyPlates into Plates
+    #[wasm_bindgen]
+    pub fn run_appliance(&mut self, appliance_id: usize) -> Result<(), JsValue> {
+        self.sim.run_appliance(appliance_id).map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+
+    /// Inspect an object at or next to the agent, revealing its goal description
+    #[wasm_bindgen]
+    pub fn inspect(&mut self, object_id: u32) -> Result<String, JsValue> {
+        self.sim.inspect(object_id as usize).map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Whether the agent has inspected the object with given ID
+    #[wasm_bindgen]
+    pub fn is_target_known(&self, object_id: u32) -> bool {
+        self.sim.is_target_known(object_id as usize)
+    }
+    /// Goal description of an object ("unknown destination" until inspected)
+    #[wasm_bindgen]
+    pub fn describe_goal(&self, object_id: u32) -> Option<String> {
+        self.sim.describe_goal(object_id as usize)
+    }
+    /// Objects out of place now and where they belong: `{ goals: [{ object_id, description }],
+    /// max_actions }`, with `max_actions` the oracle's step count (null if it fails). Descriptions
+    /// name targets even before inspection; track per-goal completion with `check_placement`.
+    #[wasm_bindgen]
+    pub fn get_task(&self) -> JsValue {
+        let task = tidy_core::TidyTask::from_sim(&self.sim);
+        let goals: Array = task
+            .goals
+            .iter()
+            .map(|g| {
+                let goal = JsObject::new();
+                Reflect::set(&goal, &JsValue::from_str("object_id"), &JsValue::from_f64(g.object_id as f64)).unwrap();
+                Reflect::set(&goal, &JsValue::from_str("description"), &JsValue::from_str(&g.description)).unwrap();
+                JsValue::from(goal)
+            })
+            .collect();
+        let max_actions = task.max_actions.map_or(JsValue::NULL, |n| JsValue::from_f64(n as f64));
+        let obj = JsObject::new();
+        Reflect::set(&obj, &JsValue::from_str("goals"), &goals).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("max_actions"), &max_actions).unwrap();
+        obj.into()
     }
 
     /// Retrieve all objects in the world
@@ -163,23 +1113,356 @@ impl ApartmentSimulator {
             // if contained.contains(&(o.id as u32)) {
             //     continue;
             // }
+            arr.push(&self.object_js(o));
+        }
+        arr
+    }
+    /// The object with this id as in `get_objects`, or null (e.g. once it was thrown away)
+    #[wasm_bindgen]
+    pub fn get_object_by_id(&self, id: usize) -> JsValue {
+        self.sim.world.object(id).map_or(JsValue::NULL, |o| JsValue::from(self.object_js(o)))
+    }
+    /// Objects on cell (x, y) in `World::objects_at` order (by id), as in `get_objects`
+    #[wasm_bindgen]
+    pub fn get_objects_at(&self, x: usize, y: usize) -> Array {
+        self.sim.world.objects_at(x, y).into_iter().map(|o| JsValue::from(self.object_js(o))).collect()
+    }
+    /// Set the clock cost of an action ("move", "door", "pick_up", "drop", "place_into", "inspect")
+    #[wasm_bindgen]
+    pub fn set_action_cost(&mut self, action: &str, cost: u32) -> Result<(), JsValue> {
+        self.sim.costs.set(action, cost).map_err(|e| JsValue::from_str(&e))
+    }
+    /// Number of successful actions taken
+    #[wasm_bindgen(getter)]
+    pub fn steps(&self) -> u32 {
+        self.sim.steps
+    }
+    /// Elapsed simulator time
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> u32 {
+        self.sim.time
+    }
+    /// Optional time limit (undefined for none)
+    #[wasm_bindgen(getter)]
+    pub fn max_time(&self) -> Option<u32> {
+        self.sim.max_time
+    }
+    #[wasm_bindgen(setter)]
+    pub fn set_max_time(&mut self, max_time: Option<u32>) {
+        self.sim.max_time = max_time;
+    }
+    /// Whether the clock has reached `max_time`
+    #[wasm_bindgen]
+    pub fn time_exceeded(&self) -> bool {
+        self.sim.time_exceeded()
+    }
+    /// Whether the episode is over: `is_success()` or `is_truncated()`
+    #[wasm_bindgen]
+    pub fn is_done(&self) -> bool {
+        self.sim.is_done()
+    }
+    /// Whether nothing is held and every pickable is in place, or tidiness has reached the
+    /// `min_tidiness` of `set_done_criteria`
+    #[wasm_bindgen]
+    pub fn is_success(&self) -> bool {
+        self.sim.is_success()
+    }
+    /// Whether the `max_steps` of `set_done_criteria` or `max_time` is used up
+    #[wasm_bindgen]
+    pub fn is_truncated(&self) -> bool {
+        self.sim.is_truncated()
+    }
+    /// Have every action pay the built-in reward, configured from JSON such as
+    /// `{"step_penalty": 0.01, "correct_place_bonus": 1, "incorrect_place_penalty": 0,
+    /// "pickup_bonus": 0, "done_bonus": 0, "shaping": {"gamma": 0.99, "weight": 0.1}}`; missing
+    /// fields take these defaults (shaping off). Throws with code `invalid_json`
+    #[wasm_bindgen]
+    pub fn set_reward_config(&mut self, config_json: &str) -> Result<(), JsValue> {
+        let config = serde_json::from_str::<RewardJson>(config_json).map_err(|e| coded_error("invalid_json", &e.to_string()))?;
+        self.sim.reward_config = Some(config.into());
+        Ok(())
+    }
+    /// Go back to rewards from hooks only
+    #[wasm_bindgen]
+    pub fn clear_reward_config(&mut self) {
+        self.sim.reward_config = None;
+    }
+    /// Configure `is_done`: a budget of successful actions and the tidiness that counts as
+    /// success (undefined: every pickable in place); throws `invalid_criteria` for a
+    /// threshold outside 0..=1
+    #[wasm_bindgen]
+    pub fn set_done_criteria(&mut self, max_steps: Option<u32>, min_tidiness: Option<f32>) -> Result<(), JsValue> {
+        if min_tidiness.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+            return Err(coded_error("invalid_criteria", "min_tidiness must be between 0 and 1"));
+        }
+        self.sim.done_criteria = tidy_core::DoneCriteria { max_steps, min_tidiness };
+        Ok(())
+    }
+    /// Override an object's label; pass undefined to restore the default
+    #[wasm_bindgen]
+    pub fn set_label(&mut self, object_id: u32, label: Option<String>) -> Result<(), JsValue> {
+        self.sim
+            .set_label(object_id as usize, label)
+            .map_err(|e| coded_error(e.name(), &e.to_string()))
+    }
+    /// Room name signs as `{ room, x, y, text }` objects, one per room with a door
+    #[wasm_bindgen]
+    pub fn get_room_signs(&self) -> Array {
+        let arr = Array::new();
+        for sign in self.sim.world.layout.room_signs() {
             let obj = JsObject::new();
-            Reflect::set(&obj, &JsValue::from_str("id"), &JsValue::from_f64(o.id as f64)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("x"), &JsValue::from_f64(o.x as f64)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("y"), &JsValue::from_f64(o.y as f64)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("pickable"), &JsValue::from_bool(o.pickable)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(o.name)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("capacity"), &JsValue::from_f64(o.capacity as f64)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(o.description)).unwrap();
-            let contents_arr = Array::new();
-            for &c in o.contents.iter() {
-                contents_arr.push(&JsValue::from_f64(c as f64));
-            }
-            Reflect::set(&obj, &JsValue::from_str("contents"), &contents_arr).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("room"), &JsValue::from_f64(sign.room as f64)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("x"), &JsValue::from_f64(sign.x as f64)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("y"), &JsValue::from_f64(sign.y as f64)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("text"), &JsValue::from_str(&sign.text)).unwrap();
+            arr.push(&obj);
+        }
+        arr
+    }
+    /// Whether `tick` advances object decay
+    #[wasm_bindgen(getter)]
+    pub fn decay_enabled(&self) -> bool {
+        self.sim.decay_enabled
+    }
+    #[wasm_bindgen(setter)]
+    pub fn set_decay_enabled(&mut self, enabled: bool) {
+        self.sim.decay_enabled = enabled;
+    }
+    /// Whether `place_into`, and picking up or dropping through `interact`, need the target
+    /// on or next to the agent (true by default)
+    #[wasm_bindgen(getter)]
+    pub fn strict_reach(&self) -> bool {
+        self.sim.strict_reach
+    }
+    #[wasm_bindgen(setter)]
+    pub fn set_strict_reach(&mut self, strict: bool) {
+        self.sim.strict_reach = strict;
+    }
+    /// Whether closed containers hide their contents and refuse `place_into` and pick-ups
+    /// (false by default: every container acts open)
+    #[wasm_bindgen(getter)]
+    pub fn closed_containers(&self) -> bool {
+        self.sim.closed_containers
+    }
+    #[wasm_bindgen(setter)]
+    pub fn set_closed_containers(&mut self, enabled: bool) {
+        self.sim.closed_containers = enabled;
+    }
+    /// Whether large furniture keeps the agent off its cell: moves onto it fail with
+    /// `BlockedByObject` and the `go_to_*` macros route around it (false by default)
+    #[wasm_bindgen(getter)]
+    pub fn blocking_objects(&self) -> bool {
+        self.sim.blocking_objects
+    }
+    #[wasm_bindgen(setter)]
+    pub fn set_blocking_objects(&mut self, enabled: bool) {
+        self.sim.blocking_objects = enabled;
+    }
+    /// Advance time by one step; returns events as `{ type: "Decayed", id, from, into }` objects
+    #[wasm_bindgen]
+    pub fn tick(&mut self) -> Array {
+        self.sim.tick().iter().map(event_js).collect()
+    }
+
+    /// Every state change since the last call, oldest first, as `{ type, ... }` objects:
+    /// `Moved { agent, from: [x, y], to }`, `Turned { agent, facing }`, `DoorOpened`/`DoorClosed
+    /// { door, x, y }` (`door` null for unregistered doors), `PickedUp { agent, id }`, `Dropped
+    /// { agent, id, x, y }`, `PlacedInto { agent, id, container }`, `Disposed { id, name }`,
+    /// `ContainerOpened`/`ContainerClosed { id }` and `Decayed`/`Transformed { id, from, into }`.
+    /// The latest 4096 are kept; restoring a state clears them.
+    #[wasm_bindgen]
+    pub fn drain_events(&mut self) -> Array {
+        self.sim.drain_events().iter().map(event_js).collect()
+    }
+    /// Ids of objects whose name, humanized name or label equals `name`, ignoring case
+    #[wasm_bindgen]
+    pub fn find_by_name(&self, name: &str) -> Vec<usize> {
+        self.sim.world.find_by_name(name)
+    }
+
+    /// Up to `limit` objects most similar to `query`, best first, as `[{ id, score }]`
+    #[wasm_bindgen]
+    pub fn find(&self, query: &str, limit: usize) -> Array {
+        let arr = Array::new();
+        for (id, score) in self.sim.world.find_fuzzy(query, limit) {
+            let obj = JsObject::new();
+            Reflect::set(&obj, &JsValue::from_str("id"), &JsValue::from_f64(id as f64)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("score"), &JsValue::from_f64(score as f64)).unwrap();
             arr.push(&obj);
         }
         arr
     }
+
+    /// Current world as canonical JSON (objects sorted by id), stable for diffing and dedup
+    #[wasm_bindgen]
+    pub fn world_json(&self) -> Result<String, JsValue> {
+        self.sim.world.to_json().map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Summary statistics of the current world as JSON (see `tidy_core::stats::WorldStats`)
+    #[wasm_bindgen]
+    pub fn world_stats_json(&self) -> Result<String, JsValue> {
+        let stats = tidy_core::stats::world_stats(&self.sim.world);
+        serde_json::to_string(&stats).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// The whole simulator, world included, as JSON for `ApartmentSimulator.from_json`
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        self.sim.to_json().map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Rebuild a simulator saved with `to_json`; unreadable files throw with a `code` such as
+    /// `format_too_new`
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<ApartmentSimulator, JsValue> {
+        let sim = tidy_core::Simulator::from_json(json).map_err(|e| match e {
+            tidy_core::CheckpointError::Compat(e) => coded_error(e.code(), &e.to_string()),
+            e => JsValue::from_str(&e.to_string()),
+        })?;
+        Ok(ApartmentSimulator { sim, retries: 0, _alive: Alive::new() })
+    }
+
+    /// Snapshot of the episode state as JSON, for the host page to stash (e.g. in IndexedDB)
+    #[wasm_bindgen]
+    pub fn checkpoint_string(&self) -> Result<String, JsValue> {
+        tidy_core::format::to_json(&self.sim.state()).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Restore a snapshot from `checkpoint_string`, upgrading older formats; the simulator must
+    /// hold the same world. Unreadable snapshots throw with a `code` such as `format_too_new`.
+    #[wasm_bindgen]
+    pub fn resume_string(&mut self, checkpoint: &str) -> Result<(), JsValue> {
+        let state: tidy_core::SimState =
+            tidy_core::format::from_json(checkpoint).map_err(|e| coded_error(e.code(), &e.to_string()))?;
+        self.sim.restore(&state).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Snapshot of the episode state as msgpack bytes (`Uint8Array`) with a format header,
+    /// for `load_state`
+    #[wasm_bindgen]
+    pub fn save_state(&self) -> Result<Vec<u8>, JsValue> {
+        self.sim.state().to_msgpack().map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Restore a snapshot from `save_state`; the simulator must hold the same world.
+    /// Unreadable snapshots throw with a `code` such as `format_too_new`.
+    #[wasm_bindgen]
+    pub fn load_state(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        let state = tidy_core::SimState::from_msgpack(bytes).map_err(|e| coded_error(e.code(), &e.to_string()))?;
+        self.sim.restore(&state).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Return to the start of the episode: the world as generated, the agent at its start.
+    /// Costs, decay and the time limit are kept.
+    #[wasm_bindgen]
+    pub fn reset(&mut self) -> Result<(), JsValue> {
+        self.sim.reset().map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[cfg(feature = "render")]
+    /// RGBA pixels (`width*scale` x `height*scale`, row-major) for `ImageData`; `glyphs` adds
+    /// room and object letters (scale 5 or more), `pattern_fill` a fill pattern per room and
+    /// `room_names` each room's name at the center of its bounding box
+    #[wasm_bindgen]
+    pub fn render_rgba(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_rgba(&self.sim, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
+
+    #[cfg(feature = "render")]
+    /// SVG document of the current state, with `aria-label`s on rooms and objects; see `render_rgba` for the options
+    #[wasm_bindgen]
+    pub fn render_svg(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<String, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_svg(&self.sim, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
+
+    #[cfg(feature = "render")]
+    /// PNG file of the `render_rgba` image, byte-identical for equal states; see `render_rgba` for the options
+    #[wasm_bindgen]
+    pub fn render_png(
+        &self,
+        theme_json: Option<String>,
+        scale: usize,
+        glyphs: Option<bool>,
+        pattern_fill: Option<bool>,
+        room_names: Option<bool>,
+    ) -> Result<Vec<u8>, JsValue> {
+        let opts = render_opts(glyphs, pattern_fill, room_names);
+        Ok(tidy_core::render::render_png(&self.sim, &parse_theme(theme_json)?, scale.max(1), opts))
+    }
+
+    #[cfg(feature = "render")]
+    /// `{ id: "#rrggbb" }` for every object, held one included, as drawn by `render_*`
+    #[wasm_bindgen]
+    pub fn object_colors(&self, theme_json: Option<String>) -> Result<JsValue, JsValue> {
+        let theme = parse_theme(theme_json)?;
+        let obj = JsObject::new();
+        for o in self.sim.world.objects.iter().chain(self.sim.holding.iter()) {
+            let color = tidy_core::render::css(theme.object_color(o));
+            Reflect::set(&obj, &JsValue::from_f64(o.id as f64), &JsValue::from_str(&color)).unwrap();
+        }
+        Ok(obj.into())
+    }
+
+    #[cfg(feature = "render")]
+    /// Text grid of the current state; `glyphs` adds room letters and shows object initials
+    /// (lowercase for pickables) instead of object ids
+    #[wasm_bindgen]
+    pub fn render_ascii(&self, theme_json: Option<String>, glyphs: Option<bool>) -> Result<String, JsValue> {
+        let opts = tidy_core::RenderOpts { glyphs: glyphs.unwrap_or(false), ..Default::default() };
+        Ok(tidy_core::render::render_text(&self.sim, &parse_theme(theme_json)?, opts))
+    }
+
+    #[cfg(feature = "render")]
+    /// Text snapshot of the current state for tests: `#` walls, `+`/`/` closed and open doors,
+    /// `.` floor labelled with room letters, `@` for the agent and objects by initial
+    /// (lowercase for pickables)
+    #[wasm_bindgen]
+    pub fn render_text(&self) -> String {
+        self.sim.render_ascii()
+    }
+
+    /// Preview `drop`/`place_into` for the held object:
+    /// `{ holding, can_drop, drop_satisfies_target, containers: [{ id, x, y, remaining_capacity, satisfies_target }], stacked: [id] }`
+    #[wasm_bindgen]
+    pub fn drop_preview(&self) -> JsValue {
+        let preview = self.sim.drop_preview();
+        let obj = JsObject::new();
+        let holding = preview.holding.map_or(JsValue::NULL, |id| JsValue::from_f64(id as f64));
+        Reflect::set(&obj, &JsValue::from_str("holding"), &holding).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("can_drop"), &JsValue::from_bool(preview.can_drop)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("drop_satisfies_target"), &JsValue::from_bool(preview.drop_satisfies_target)).unwrap();
+        let containers = Array::new();
+        for c in &preview.containers {
+            let entry = JsObject::new();
+            Reflect::set(&entry, &JsValue::from_str("id"), &JsValue::from_f64(c.id as f64)).unwrap();
+            Reflect::set(&entry, &JsValue::from_str("x"), &JsValue::from_f64(c.x as f64)).unwrap();
+            Reflect::set(&entry, &JsValue::from_str("y"), &JsValue::from_f64(c.y as f64)).unwrap();
+            Reflect::set(&entry, &JsValue::from_str("remaining_capacity"), &JsValue::from_f64(c.remaining_capacity as f64)).unwrap();
+            Reflect::set(&entry, &JsValue::from_str("satisfies_target"), &JsValue::from_bool(c.satisfies_target)).unwrap();
+            containers.push(&entry);
+        }
+        Reflect::set(&obj, &JsValue::from_str("containers"), &containers).unwrap();
+        let stacked: Array = preview.stacked.iter().map(|&id| JsValue::from_f64(id as f64)).collect();
+        Reflect::set(&obj, &JsValue::from_str("stacked"), &stacked).unwrap();
+        obj.into()
+    }
     /// Get the object currently held by the agent (or null)
     #[wasm_bindgen]
     pub fn get_holding(&self) -> JsValue {
@@ -187,33 +1470,43 @@ impl ApartmentSimulator {
             let obj = JsObject::new();
             Reflect::set(&obj, &JsValue::from_str("id"), &JsValue::from_f64(o.id as f64)).unwrap();
             Reflect::set(&obj, &JsValue::from_str("pickable"), &JsValue::from_bool(o.pickable)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(o.name)).unwrap();
+        Reflect::set(&obj, &JsValue::from_str("blocks_movement"), &JsValue::from_bool(o.blocks_movement)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(&o.name)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("display_name"), &JsValue::from_str(&o.display_name)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("icon"), &JsValue::from_str(&o.icon)).unwrap();
             Reflect::set(&obj, &JsValue::from_str("capacity"), &JsValue::from_f64(o.capacity as f64)).unwrap();
-            Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(o.description)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(&o.description)).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("label"), &JsValue::from_str(&o.display_label())).unwrap();
+            let decay = self.sim.decay_remaining(o.id).map_or(JsValue::NULL, |d| JsValue::from_f64(d as f64));
+            Reflect::set(&obj, &JsValue::from_str("decay_remaining"), &decay).unwrap();
             let contents_arr = Array::new();
             for &c in o.contents.iter() {
                 contents_arr.push(&JsValue::from_f64(c as f64));
             }
             Reflect::set(&obj, &JsValue::from_str("contents"), &contents_arr).unwrap();
+            Reflect::set(&obj, &JsValue::from_str("is_open"), &JsValue::from_bool(self.sim.is_open(o))).unwrap();
             JsValue::from(obj)
         } else {
             JsValue::NULL
         }
     }
-    /// Get contents of a container object by ID
+    /// Get contents of a container object by ID; empty while it is closed and `closed_containers` is on
     #[wasm_bindgen]
     pub fn get_contents(&self, container_id: u32) -> Array {
         let arr = Array::new();
         // find container
-        if let Some(container) = self.sim.world.objects.iter().find(|o| o.id as u32 == container_id) {
+        if let Some(container) = self.sim.world.object(container_id as usize).filter(|c| self.sim.is_open(c)) {
             for &cid in container.contents.iter() {
-                if let Some(inner) = self.sim.world.objects.iter().find(|o| o.id == cid) {
+                if let Some(inner) = self.sim.world.object(cid) {
                     let obj = JsObject::new();
                     Reflect::set(&obj, &JsValue::from_str("id"), &JsValue::from_f64(inner.id as f64)).unwrap();
-                    Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(inner.name)).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("name"), &JsValue::from_str(&inner.name)).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("display_name"), &JsValue::from_str(&inner.display_name)).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("icon"), &JsValue::from_str(&inner.icon)).unwrap();
                     Reflect::set(&obj, &JsValue::from_str("capacity"), &JsValue::from_f64(inner.capacity as f64)).unwrap();
-                    Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(inner.description)).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("description"), &JsValue::from_str(&inner.description)).unwrap();
                     Reflect::set(&obj, &JsValue::from_str("pickable"), &JsValue::from_bool(inner.pickable)).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("blocks_movement"), &JsValue::from_bool(inner.blocks_movement)).unwrap();
                     Reflect::set(&obj, &JsValue::from_str("x"), &JsValue::from_f64(inner.x as f64)).unwrap();
                     Reflect::set(&obj, &JsValue::from_str("y"), &JsValue::from_f64(inner.y as f64)).unwrap();
                     let contents_arr = Array::new();
@@ -221,6 +1514,7 @@ impl ApartmentSimulator {
                         contents_arr.push(&JsValue::from_f64(c as f64));
                     }
                     Reflect::set(&obj, &JsValue::from_str("contents"), &contents_arr).unwrap();
+                    Reflect::set(&obj, &JsValue::from_str("is_open"), &JsValue::from_bool(self.sim.is_open(inner))).unwrap();
                     arr.push(&obj);
                 }
             }
@@ -238,16 +1532,56 @@ impl ApartmentSimulator {
     pub fn height(&self) -> usize {
         self.sim.world.layout.height
     }
-    /// Flat cells array
+    /// Flat row-major cells array; cell (x, y) is at y*width + x
     #[wasm_bindgen(getter)]
     pub fn cells(&self) -> Vec<i8> {
         self.sim.world.layout.cells.clone()
     }
+    /// `cells` without copying: an `Int8Array` over the simulator's own cell buffer.
+    ///
+    /// The view is only valid until the next call into this module. Any call, on this or any
+    /// other instance, may allocate and so grow wasm memory; growing it detaches the old
+    /// `ArrayBuffer`, after which the view reads as empty (length 0) instead of throwing.
+    /// Actions that change cells also write through a live view, and `free()` leaves it over
+    /// released memory. Read it straight away and call `cells_view()` again next time rather
+    /// than keeping it; `copy_cells_into` suits a buffer that must outlive further calls.
+    #[wasm_bindgen]
+    pub fn cells_view(&self) -> js_sys::Int8Array {
+        // SAFETY: the view borrows the cell buffer, which lives as long as `self`; the caveat
+        // above tells JS not to keep it past the next call that could move or free it
+        unsafe { js_sys::Int8Array::view(&self.sim.world.layout.cells) }
+    }
+    /// Copy the cells into `dst`, e.g. an `Int8Array` reused across frames; fails unless
+    /// `dst` has exactly width*height elements
+    #[wasm_bindgen]
+    pub fn copy_cells_into(&self, dst: &mut [i8]) -> Result<(), JsValue> {
+        let cells = &self.sim.world.layout.cells;
+        if dst.len() != cells.len() {
+            let message = format!("expected a buffer of {} cells, got {}", cells.len(), dst.len());
+            return Err(coded_error("invalid_buffer", &message));
+        }
+        dst.copy_from_slice(cells);
+        Ok(())
+    }
     /// Names of each room ID (0..rooms.len())
     #[wasm_bindgen]
     pub fn get_room_names(&self) -> Array {
         let arr = Array::new();
-        for &name in self.sim.world.layout.room_names.iter() {
+        for name in self.sim.world.layout.room_names.iter() {
+            arr.push(&JsValue::from_str(name));
+        }
+        arr
+    }
+    /// Flat row-major zone-id array (-1 for cells outside zoned rooms); cell (x, y) is at y*width + x
+    #[wasm_bindgen(getter)]
+    pub fn zones(&self) -> Vec<i8> {
+        self.sim.world.layout.zones.clone()
+    }
+    /// Names of each zone ID (0..zones.len())
+    #[wasm_bindgen]
+    pub fn get_zone_names(&self) -> Array {
+        let arr = Array::new();
+        for name in self.sim.world.layout.zone_names.iter() {
             arr.push(&JsValue::from_str(name));
         }
         arr
@@ -255,10 +1589,83 @@ impl ApartmentSimulator {
     /// Check if the object with given ID is currently in its correct target location.
     #[wasm_bindgen]
     pub fn check_placement(&self, object_id: u32) -> bool {
-        // delegate to core implementation
-        if let Some(obj) = self.sim.world.objects.iter().find(|o| o.id as u32 == object_id) {
-            return obj.check_placement(&self.sim.world);
+        // delegate to core implementation; thrown away objects count where their target holds
+        self.sim.world.is_in_place(object_id as usize)
+    }
+}
+
+#[cfg(feature = "gen")]
+#[wasm_bindgen]
+/// Incremental generator that can be advanced within a per-frame time budget
+pub struct ApartmentGenJob {
+    job: tidy_core::GenJob,
+    world: Option<tidy_core::World>,
+    progress: f32,
+}
+
+#[cfg(feature = "gen")]
+#[wasm_bindgen]
+impl ApartmentGenJob {
+    #[wasm_bindgen(constructor)]
+    /// Start generating a world; nothing is computed until `tick` is called.
+    /// Throws an `Error` with a `code` property when the options are invalid.
+    pub fn new(seed: u64, max_rooms: usize, width: usize, height: usize, max_objects: usize) -> Result<ApartmentGenJob, JsValue> {
+        let opts = tidy_core::GenOpts { seed, max_rooms, width, height, max_objects, ..Default::default() };
+        ApartmentGenJob::start(&opts)
+    }
+
+    /// Like the constructor, with every option given as JSON in the form `validate_opts` takes
+    #[wasm_bindgen]
+    pub fn from_opts(opts_json: &str) -> Result<ApartmentGenJob, JsValue> {
+        ApartmentGenJob::start(&parse_opts(opts_json)?)
+    }
+
+    /// Advance generation for roughly `budget_ms` milliseconds; returns progress in [0, 1].
+    /// Throws an `Error` with a `code` property when generation fails.
+    #[wasm_bindgen]
+    pub fn tick(&mut self, budget_ms: f64) -> Result<f32, JsValue> {
+        let start = js_sys::Date::now();
+        while self.world.is_none() {
+            match self.job.run_for(1) {
+                tidy_core::GenProgress::InProgress(p) => self.progress = p,
+                tidy_core::GenProgress::Done(world) => {
+                    self.world = Some(world);
+                    self.progress = 1.0;
+                }
+                tidy_core::GenProgress::Failed(e) => return Err(gen_error(&e)),
+            }
+            if js_sys::Date::now() - start >= budget_ms {
+                break;
+            }
         }
-        false
+        Ok(self.progress)
+    }
+
+    /// Completed fraction in [0, 1]
+    #[wasm_bindgen(getter)]
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Whether generation has finished
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.world.is_some()
+    }
+
+    #[cfg(feature = "sim")]
+    /// Build a simulator from the finished world; consumes the job
+    #[wasm_bindgen]
+    pub fn into_simulator(self) -> Result<ApartmentSimulator, JsValue> {
+        let world = self.world.ok_or_else(|| JsValue::from_str("generation has not finished"))?;
+        ApartmentSimulator::from_world(world)
+    }
+}
+
+#[cfg(feature = "gen")]
+impl ApartmentGenJob {
+    fn start(opts: &tidy_core::GenOpts) -> Result<ApartmentGenJob, JsValue> {
+        opts.validate().map_err(|e| gen_error(&e))?;
+        Ok(ApartmentGenJob { job: tidy_core::GenJob::new(opts), world: None, progress: 0.0 })
     }
 }
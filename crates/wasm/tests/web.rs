@@ -0,0 +1,183 @@
+//! Binding tests in a JS host: `wasm-pack test --node crates/wasm`, or `cargo test -p wasm
+//! --target wasm32-unknown-unknown` with `wasm-bindgen-test-runner` as the target runner.
+//! Natively they compile but do not run.
+
+use wasm::{cell_constants, cell_kind_name, generate, validate_opts, ApartmentSimulator};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn simulator() -> ApartmentSimulator {
+    ApartmentSimulator::new(7, 6, 40, 30, 20).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn cells_view_matches_the_copied_cells() {
+    let mut sim = simulator();
+    for _ in 0..2 {
+        let cells = sim.cells();
+        let mut copied = vec![0; cells.len()];
+        sim.copy_cells_into(&mut copied).unwrap();
+        // read the view before any further call into the module
+        let viewed = sim.cells_view().to_vec();
+        assert_eq!(viewed, cells);
+        assert_eq!(copied, cells);
+        // change some cells by walking through doors, then compare again
+        let _ = sim.go_to_room(1);
+    }
+    assert!(sim.copy_cells_into(&mut [0; 3]).is_err());
+}
+
+/// Current size of the module's linear memory in bytes
+fn memory_bytes() -> u32 {
+    use wasm_bindgen::JsCast;
+    let memory: js_sys::WebAssembly::Memory = wasm_bindgen::memory().unchecked_into();
+    memory.buffer().unchecked_into::<js_sys::ArrayBuffer>().byte_length()
+}
+
+#[wasm_bindgen_test]
+fn freed_simulators_leave_nothing_behind() {
+    let before = wasm::instances_alive();
+    let mut sizes = Vec::new();
+    for _ in 0..3 {
+        let mut sims: Vec<ApartmentSimulator> = (0..100).map(|seed| ApartmentSimulator::new(seed, 4, 24, 18, 10).unwrap()).collect();
+        for sim in &mut sims {
+            let _ = sim.go_to_room(1);
+            sim.drain_events();
+            sim.shrink_memory();
+        }
+        assert_eq!(wasm::instances_alive(), before + 100);
+        // what JS `free()` does
+        drop(sims);
+        assert_eq!(wasm::instances_alive(), before);
+        sizes.push(memory_bytes());
+    }
+    // later rounds reuse the memory the first one freed
+    assert_eq!(sizes[2], sizes[0], "{:?}", sizes);
+}
+
+/// The `code` property of a coded error or `validate_opts` result
+fn code(value: &wasm_bindgen::JsValue) -> Option<String> {
+    js_sys::Reflect::get(value, &"code".into()).unwrap().as_string()
+}
+
+#[wasm_bindgen_test]
+fn impossible_shells_report_a_code_instead_of_aborting() {
+    let checked = validate_opts(r#"{"width": 6, "height": 6}"#);
+    assert_eq!(js_sys::Reflect::get(&checked, &"ok".into()).unwrap().as_bool(), Some(false));
+    assert_eq!(code(&checked).as_deref(), Some("shell_too_small"));
+    assert_eq!(js_sys::Reflect::get(&validate_opts("{}"), &"ok".into()).unwrap().as_bool(), Some(true));
+
+    let before = wasm::instances_alive();
+    let error = ApartmentSimulator::new(0, 4, 6, 6, 10).err().expect("a 6x6 shell is refused");
+    assert_eq!(code(&error).as_deref(), Some("shell_too_small"));
+    let error = ApartmentSimulator::from_opts(r#"{"width": 6, "height": 6}"#).err().expect("a 6x6 shell is refused");
+    assert_eq!(code(&error).as_deref(), Some("shell_too_small"));
+    assert_eq!(wasm::instances_alive(), before);
+    // the module is still usable afterwards
+    simulator();
+}
+
+#[wasm_bindgen_test]
+fn generated_layouts_name_their_rooms() {
+    for seed in 0..5 {
+        let layout = generate(seed, 6, 40, 30, 0, None).unwrap();
+        let names: Vec<String> = layout.get_room_names().iter().map(|n| n.as_string().unwrap()).collect();
+        assert!(!names.is_empty() && names.iter().all(|n| !n.is_empty()), "seed {}: {:?}", seed, names);
+        let cells = layout.cells();
+        for (i, &cell) in cells.iter().enumerate() {
+            let (x, y) = (i % layout.width(), i / layout.width());
+            let expected = (cell >= 0).then(|| names[cell as usize].clone());
+            assert_eq!(layout.get_room_name_at(x, y), expected, "seed {} at ({}, {})", seed, x, y);
+        }
+        assert_eq!(layout.get_room_name_at(layout.width(), 0), None);
+    }
+}
+
+#[wasm_bindgen_test]
+fn cell_constants_are_the_core_values() {
+    let constants = cell_constants();
+    for (name, value, kind) in [
+        ("WALL", tidy_core::WALL, "wall"),
+        ("OUTSIDE", tidy_core::OUTSIDE, "outside"),
+        ("CLOSED_DOOR", tidy_core::CLOSED_DOOR, "closed_door"),
+        ("OPEN_DOOR", tidy_core::OPEN_DOOR, "open_door"),
+        ("LOCKED_DOOR", tidy_core::LOCKED_DOOR, "locked_door"),
+        ("WINDOW", tidy_core::WINDOW, "window"),
+        ("STAIRS_BASE", tidy_core::STAIRS_BASE, "stairs"),
+    ] {
+        let exported = js_sys::Reflect::get(&constants, &name.into()).unwrap().as_f64();
+        assert_eq!(exported, Some(value as f64), "{}", name);
+        assert_eq!(cell_kind_name(value), kind);
+    }
+    assert_eq!(cell_kind_name(0), "room");
+    assert_eq!(cell_kind_name(tidy_core::STAIRS_BASE - 2), "stairs");
+}
+
+/// One of the `open_*` or `close_*` conveniences
+type DoorFn = fn(&mut ApartmentSimulator) -> Result<(), wasm_bindgen::JsValue>;
+
+#[wasm_bindgen_test]
+fn doors_open_and_close_next_to_the_agent() {
+    let sim = simulator();
+    let (w, cells) = (sim.width(), sim.cells());
+    // a closed door with free room cells on both sides, agent 0 on one and agent 1 on the other
+    let (door, (dx, dy), mut sim) = (0..cells.len())
+        .filter(|&i| cells[i] == tidy_core::CLOSED_DOOR)
+        .flat_map(|i| [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)].map(|d| (i, d)))
+        .find_map(|(i, (dx, dy))| {
+            let step = dx + dy * w as isize;
+            let (near, far) = ((i as isize - step) as usize, (i as isize + step) as usize);
+            let room = |c: usize| c < cells.len() && cells[c] >= 0 && (c % w).abs_diff(i % w) <= 1;
+            let placed = (room(near) && room(far)).then(|| sim.with_agents(vec![near % w, near / w, far % w, far / w]).ok());
+            placed.flatten().map(|placed| (i, (dx, dy), placed))
+        })
+        .expect("a door between two free room cells");
+    let (x, y) = (door % w, door / w);
+    assert!(sim.is_door(x, y) && !sim.is_door_open(x, y));
+    let agent = sim.agent_position(0).unwrap();
+    assert!(!sim.is_door(agent[0], agent[1]));
+
+    let (open, close): (DoorFn, DoorFn) = match (dx, dy) {
+        (-1, 0) => (ApartmentSimulator::open_left, ApartmentSimulator::close_left),
+        (1, 0) => (ApartmentSimulator::open_right, ApartmentSimulator::close_right),
+        (0, -1) => (ApartmentSimulator::open_up, ApartmentSimulator::close_up),
+        _ => (ApartmentSimulator::open_down, ApartmentSimulator::close_down),
+    };
+    for _ in 0..2 {
+        open(&mut sim).unwrap();
+        assert_eq!(sim.cells()[door], tidy_core::OPEN_DOOR);
+        assert!(sim.is_door_open(x, y));
+        close(&mut sim).unwrap();
+        assert_eq!(sim.cells()[door], tidy_core::CLOSED_DOOR);
+        assert!(!sim.is_door_open(x, y));
+    }
+
+    // agent 1 in the doorway keeps it open
+    open(&mut sim).unwrap();
+    let back = match (dx, dy) {
+        (-1, 0) => "move_right",
+        (1, 0) => "move_left",
+        (0, -1) => "move_down",
+        _ => "move_up",
+    };
+    let back = wasm::action_names().iter().position(|name| name.as_string().as_deref() == Some(back)).unwrap();
+    sim.step_agent_id(1, back).unwrap();
+    assert_eq!(sim.agent_position(1), Some(vec![x, y]));
+    let error = close(&mut sim).unwrap_err();
+    assert_eq!(code(&error).as_deref(), Some("DoorwayOccupied"));
+    assert!(sim.is_door_open(x, y));
+}
+
+#[wasm_bindgen_test]
+fn four_right_turns_restore_the_facing() {
+    let mut sim = simulator();
+    let start = sim.agent_facing();
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        sim.turn_right().unwrap();
+        seen.push(sim.agent_facing());
+    }
+    assert_eq!(seen[3], start);
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 4);
+}
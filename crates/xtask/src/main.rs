@@ -0,0 +1,110 @@
+//! Repository tasks, run through cargo aliases (see `.cargo/config.toml`).
+//!
+//! `cargo wasm-profiles` builds the wasm crate once per feature profile for
+//! `wasm32-unknown-unknown` and prints the size of each bundle, before and after `wasm-opt
+//! -Oz` when binaryen is installed, so a change that pulls generation or the catalog into
+//! the viewer shows up. `cargo wasm-profiles --check` runs
+//! clippy on each profile for the host instead, for machines without the wasm target.
+//!
+//! `cargo conformance` plays `tidy_core::conformance::spec()` through the core simulator;
+//! `crates/ffi_py/conformance.py` and `crates/wasm/conformance.mjs` play the same spec
+//! through the bindings.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+/// Feature profiles of the wasm crate: (name, features)
+const PROFILES: &[(&str, &str)] = &[
+    ("full", "full"),
+    ("play", "sim,render,default-schemas"),
+    ("play-lite", "sim,render"),
+    ("viewer", "viewer"),
+];
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+fn cargo() -> Command {
+    Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+}
+
+/// Workspace target directory, honouring `CARGO_TARGET_DIR`
+fn target_dir() -> PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target"),
+    }
+}
+
+fn run(mut command: Command) -> Result<(), String> {
+    let status = command.status().map_err(|e| format!("could not run cargo: {}", e))?;
+    if status.success() { Ok(()) } else { Err(format!("{:?} failed with {}", command, status)) }
+}
+
+/// Clippy each profile for the host with warnings denied
+fn check_profiles() -> Result<(), String> {
+    for &(name, features) in PROFILES {
+        println!("checking {} ({})", name, features);
+        let mut command = cargo();
+        command.args(["clippy", "-q", "-p", "wasm", "--no-default-features", "--features", features, "--", "-D", "warnings"]);
+        run(command)?;
+    }
+    Ok(())
+}
+
+/// Release-build each profile for the wasm target and print the bundle sizes
+fn build_profiles() -> Result<(), String> {
+    let wasm = target_dir().join(WASM_TARGET).join("release").join("wasm.wasm");
+    let mut sizes = Vec::new();
+    for &(name, features) in PROFILES {
+        let mut command = cargo();
+        command.args(["build", "-q", "--release", "-p", "wasm", "--target", WASM_TARGET, "--no-default-features", "--features", features]);
+        run(command)?;
+        let bytes = std::fs::metadata(&wasm).map_err(|e| format!("{}: {}", wasm.display(), e))?.len();
+        sizes.push((name, features, bytes, optimized_size(&wasm)));
+    }
+    println!("{:<10} {:>10} {:>10}  features", "profile", "size", "wasm-opt");
+    for (name, features, bytes, optimized) in sizes {
+        let optimized = optimized.map_or_else(|| "-".to_string(), |b| format!("{} KB", b / 1024));
+        println!("{:<10} {:>7} KB {:>10}  {}", name, bytes / 1024, optimized, features);
+    }
+    Ok(())
+}
+
+/// Size of `wasm` after `wasm-opt -Oz`, or `None` when binaryen's `wasm-opt` is not on the path
+fn optimized_size(wasm: &Path) -> Option<u64> {
+    let out = wasm.with_extension("opt.wasm");
+    let status = Command::new("wasm-opt").arg("-Oz").arg(wasm).arg("-o").arg(&out).status().ok()?;
+    if !status.success() {
+        return None;
+    }
+    std::fs::metadata(&out).ok().map(|m| m.len())
+}
+
+/// Play the conformance spec through the core simulator and print any failures
+fn conformance() -> Result<(), String> {
+    let spec = tidy_core::conformance::spec();
+    let failures = tidy_core::conformance::run_core(&spec);
+    for failure in &failures {
+        println!("{}", failure);
+    }
+    let steps: usize = spec.cases.iter().map(|c| c.steps.len()).sum();
+    println!("core conformance: {} failure(s) over {} steps", failures.len(), steps);
+    if failures.is_empty() { Ok(()) } else { Err("conformance failures".into()) }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["wasm-profiles"] => build_profiles(),
+        ["wasm-profiles", "--check"] => check_profiles(),
+        ["conformance"] => conformance(),
+        _ => Err("usage: cargo wasm-profiles [--check] | cargo conformance".into()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}